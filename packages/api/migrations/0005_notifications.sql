@@ -0,0 +1,16 @@
+-- In-app notifications (e.g. someone commented on your content)
+
+create table if not exists notifications (
+    id uuid primary key default gen_random_uuid(),
+    user_id uuid not null references users(id) on delete cascade,
+    kind text not null,
+    actor_user_id uuid references users(id) on delete set null,
+    target_type content_target_type not null,
+    target_id uuid not null,
+    comment_id uuid references comments(id) on delete cascade,
+    created_at timestamptz not null default now(),
+    read_at timestamptz
+);
+
+create index if not exists notifications_user_idx on notifications(user_id, created_at desc);
+create index if not exists notifications_unread_idx on notifications(user_id) where read_at is null;
@@ -0,0 +1,3 @@
+-- Bumped on credential changes so previously issued local JWTs stop authenticating
+
+alter table users add column if not exists token_version bigint not null default 0;
@@ -0,0 +1,12 @@
+-- Databases created before the inline unique(user_id, target_type, target_id) may
+-- have collected duplicate votes; keep the most recent one and enforce uniqueness.
+
+delete from votes a
+using votes b
+where a.user_id = b.user_id
+  and a.target_type = b.target_type
+  and a.target_id = b.target_id
+  and (a.updated_at, a.id) < (b.updated_at, b.id);
+
+create unique index if not exists votes_user_target_unique_idx
+    on votes(user_id, target_type, target_id);
@@ -0,0 +1,3 @@
+-- Moderators: set manually (or via seed) to unlock admin-only endpoints
+
+alter table users add column if not exists is_admin boolean not null default false;
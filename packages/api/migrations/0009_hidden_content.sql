@@ -0,0 +1,6 @@
+-- Moderation: hidden content stays in place but is left out of public reads
+
+alter table proposals add column if not exists hidden_at timestamptz;
+alter table programs add column if not exists hidden_at timestamptz;
+alter table comments add column if not exists hidden_at timestamptz;
+alter table videos add column if not exists hidden_at timestamptz;
@@ -0,0 +1,10 @@
+-- Upload intents: storage keys issued to a user, checked and consumed at finalize
+
+create table if not exists upload_intents (
+    id uuid primary key default gen_random_uuid(),
+    user_id uuid not null references users(id) on delete cascade,
+    storage_key text not null unique,
+    created_at timestamptz not null default now()
+);
+
+create index if not exists upload_intents_user_idx on upload_intents(user_id);
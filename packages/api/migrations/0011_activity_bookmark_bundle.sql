@@ -0,0 +1,4 @@
+-- Activity: record bookmarks and program bundling alongside creations
+
+alter type activity_action add value if not exists 'bookmarked';
+alter type activity_action add value if not exists 'bundled';
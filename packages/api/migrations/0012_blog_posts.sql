@@ -0,0 +1,12 @@
+-- Blog: admin-authored posts served at /blog/:id (integer ids keep the route short)
+
+create table if not exists posts (
+    id bigserial primary key,
+    author_user_id uuid not null references users(id) on delete cascade,
+    title text not null,
+    body_markdown text not null default '',
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now()
+);
+
+create index if not exists posts_created_idx on posts(created_at desc);
@@ -0,0 +1,13 @@
+-- Content reports: one per user and target; enough distinct reports auto-hide the target
+
+create table if not exists reports (
+    id uuid primary key default gen_random_uuid(),
+    reporter_user_id uuid not null references users(id) on delete cascade,
+    target_type content_target_type not null,
+    target_id uuid not null,
+    reason text not null default '',
+    created_at timestamptz not null default now(),
+    unique (reporter_user_id, target_type, target_id)
+);
+
+create index if not exists reports_target_idx on reports(target_type, target_id);
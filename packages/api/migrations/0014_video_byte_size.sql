@@ -0,0 +1,3 @@
+-- Uploaded file size, recorded at finalize for quotas and display
+
+alter table videos add column if not exists byte_size bigint;
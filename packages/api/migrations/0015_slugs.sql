@@ -0,0 +1,7 @@
+-- Readable URL slugs for proposals and programs; older rows get one on their next update
+
+alter table proposals add column if not exists slug text;
+alter table programs add column if not exists slug text;
+
+create unique index if not exists proposals_slug_idx on proposals (slug);
+create unique index if not exists programs_slug_idx on programs (slug);
@@ -0,0 +1,19 @@
+-- Bookmarks on any content, not just videos; existing video bookmarks are carried over
+
+create table if not exists content_bookmarks (
+    id uuid primary key default gen_random_uuid(),
+    user_id uuid not null references users(id) on delete cascade,
+    target_type content_target_type not null,
+    target_id uuid not null,
+    created_at timestamptz not null default now(),
+    unique(user_id, target_type, target_id)
+);
+
+insert into content_bookmarks (id, user_id, target_type, target_id, created_at)
+select id, user_id, 'video', video_id, created_at from bookmarks;
+
+drop table bookmarks;
+alter table content_bookmarks rename to bookmarks;
+
+create index if not exists bookmarks_user_idx on bookmarks(user_id, created_at desc);
+create index if not exists bookmarks_target_idx on bookmarks(target_type, target_id);
@@ -0,0 +1,9 @@
+-- Users mentioned (@display_name) in a comment, so the UI can link them
+
+create table if not exists comment_mentions (
+    comment_id uuid not null references comments(id) on delete cascade,
+    user_id uuid not null references users(id) on delete cascade,
+    primary key (comment_id, user_id)
+);
+
+create index if not exists comment_mentions_user_idx on comment_mentions(user_id);
@@ -0,0 +1,9 @@
+-- WebVTT caption tracks for videos, one per language
+
+create table if not exists captions (
+    video_id uuid not null references videos(id) on delete cascade,
+    language text not null,
+    storage_key text not null,
+    created_at timestamptz not null default now(),
+    primary key (video_id, language)
+);
@@ -0,0 +1,3 @@
+-- Opt-in email for comment and reply notifications
+
+alter table profiles add column if not exists notify_email boolean not null default false;
@@ -0,0 +1,10 @@
+-- Users other than the author who may edit a proposal
+
+create table if not exists proposal_contributors (
+    proposal_id uuid not null references proposals(id) on delete cascade,
+    user_id uuid not null references users(id) on delete cascade,
+    created_at timestamptz not null default now(),
+    primary key (proposal_id, user_id)
+);
+
+create index if not exists proposal_contributors_user_idx on proposal_contributors(user_id);
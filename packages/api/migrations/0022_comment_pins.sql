@@ -0,0 +1,6 @@
+-- Authors can pin one top-level comment on their content
+
+alter table comments add column if not exists pinned boolean not null default false;
+
+create unique index if not exists comments_pinned_target_idx
+    on comments(target_type, target_id) where pinned;
@@ -0,0 +1,3 @@
+-- When each user last made an authenticated call, refreshed at most hourly
+
+alter table users add column if not exists last_seen_at timestamptz;
@@ -0,0 +1,6 @@
+-- Trigram index for find_similar_proposals (Postgres only; SQLite compares slugs)
+
+create extension if not exists pg_trgm;
+
+create index if not exists proposals_title_trgm_idx
+    on proposals using gin (lower(title) gin_trgm_ops);
@@ -0,0 +1,12 @@
+-- Audit trail of admin actions (hide/unhide, counter repair); kept when the admin is deleted
+
+create table if not exists admin_audit (
+    id uuid primary key default gen_random_uuid(),
+    actor_user_id uuid references users(id) on delete set null,
+    action text not null,
+    target_type content_target_type,
+    target_id uuid,
+    created_at timestamptz not null default now()
+);
+
+create index if not exists admin_audit_created_idx on admin_audit(created_at desc);
@@ -0,0 +1,3 @@
+-- Activity: record proposal deletions
+
+alter type activity_action add value if not exists 'deleted';
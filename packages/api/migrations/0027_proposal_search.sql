@@ -0,0 +1,7 @@
+-- Full-text index for search_proposals (Postgres only; SQLite uses LIKE)
+
+create index if not exists proposals_search_idx
+    on proposals using gin (
+        (setweight(to_tsvector('simple', title), 'A')
+            || to_tsvector('simple', summary || ' ' || body_markdown))
+    );
@@ -0,0 +1,5 @@
+-- Comment editing and soft deletion: deleted comments keep their row (and replies)
+-- with a blank body
+
+alter table comments add column if not exists updated_at timestamptz;
+alter table comments add column if not exists deleted_at timestamptz;
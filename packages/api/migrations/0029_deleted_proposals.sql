@@ -0,0 +1,8 @@
+-- Tombstones for deleted proposals, so sync clients learn about hard deletes
+
+create table if not exists deleted_proposals (
+    id uuid primary key,
+    deleted_at timestamptz not null default now()
+);
+
+create index if not exists deleted_proposals_deleted_at_idx on deleted_proposals(deleted_at);
@@ -0,0 +1,6 @@
+-- Activity: a single 'voted' action replaces voted_up/voted_down
+--
+-- Postgres can't use a new enum value in the transaction that adds it, so the rows
+-- move over in the next migration.
+
+alter type activity_action add value if not exists 'voted';
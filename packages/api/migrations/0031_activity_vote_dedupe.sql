@@ -0,0 +1,25 @@
+-- Activity: at most one 'voted' row per user and target, kept only while the vote stands
+
+update activity set action = 'voted' where action in ('voted_up', 'voted_down');
+
+delete from activity a
+where a.action = 'voted'
+    and exists (
+        select 1 from activity b
+        where b.action = 'voted'
+            and b.user_id = a.user_id
+            and b.target_type = a.target_type
+            and b.target_id = a.target_id
+            and (b.created_at > a.created_at or (b.created_at = a.created_at and b.id > a.id))
+    );
+
+delete from activity a
+where a.action = 'voted'
+    and not exists (
+        select 1 from votes v
+        where v.user_id = a.user_id and v.target_type = a.target_type and v.target_id = a.target_id
+    );
+
+create unique index if not exists activity_vote_target_idx
+    on activity(user_id, target_type, target_id)
+    where action = 'voted';
@@ -0,0 +1,22 @@
+-- In-app notifications (e.g. someone commented on your content) (SQLite version)
+
+create table if not exists notifications (
+    id text primary key default (
+        lower(hex(randomblob(4))) || '-' ||
+        lower(hex(randomblob(2))) || '-' ||
+        lower(hex(randomblob(2))) || '-' ||
+        lower(hex(randomblob(2))) || '-' ||
+        lower(hex(randomblob(6)))
+    ),
+    user_id text not null references users(id) on delete cascade,
+    kind text not null,
+    actor_user_id text references users(id) on delete set null,
+    target_type text not null,
+    target_id text not null,
+    comment_id text references comments(id) on delete cascade,
+    created_at text not null default current_timestamp,
+    read_at text
+);
+
+create index if not exists notifications_user_idx on notifications(user_id, created_at desc);
+create index if not exists notifications_unread_idx on notifications(user_id) where read_at is null;
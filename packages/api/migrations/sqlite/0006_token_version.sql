@@ -0,0 +1,3 @@
+-- Bumped on credential changes so previously issued local JWTs stop authenticating (SQLite version)
+
+alter table users add column token_version integer not null default 0;
@@ -0,0 +1,10 @@
+-- Databases created before the inline unique(user_id, target_type, target_id) may
+-- have collected duplicate votes; keep the most recent one and enforce uniqueness. (SQLite version)
+
+delete from votes
+where rowid not in (
+    select max(rowid) from votes group by user_id, target_type, target_id
+);
+
+create unique index if not exists votes_user_target_unique_idx
+    on votes(user_id, target_type, target_id);
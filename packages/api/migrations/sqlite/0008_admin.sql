@@ -0,0 +1,3 @@
+-- Moderators: set manually (or via seed) to unlock admin-only endpoints (SQLite version)
+
+alter table users add column is_admin integer not null default 0;
@@ -0,0 +1,6 @@
+-- Moderation: hidden content stays in place but is left out of public reads (SQLite version)
+
+alter table proposals add column hidden_at text;
+alter table programs add column hidden_at text;
+alter table comments add column hidden_at text;
+alter table videos add column hidden_at text;
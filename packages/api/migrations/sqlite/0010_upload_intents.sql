@@ -0,0 +1,16 @@
+-- Upload intents: storage keys issued to a user, checked and consumed at finalize (SQLite version)
+
+create table if not exists upload_intents (
+    id text primary key default (
+        lower(hex(randomblob(4))) || '-' ||
+        lower(hex(randomblob(2))) || '-' ||
+        lower(hex(randomblob(2))) || '-' ||
+        lower(hex(randomblob(2))) || '-' ||
+        lower(hex(randomblob(6)))
+    ),
+    user_id text not null references users(id) on delete cascade,
+    storage_key text not null unique,
+    created_at text not null default current_timestamp
+);
+
+create index if not exists upload_intents_user_idx on upload_intents(user_id);
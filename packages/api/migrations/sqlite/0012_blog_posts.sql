@@ -0,0 +1,12 @@
+-- Blog: admin-authored posts served at /blog/:id (SQLite version)
+
+create table if not exists posts (
+    id integer primary key autoincrement,
+    author_user_id text not null references users(id) on delete cascade,
+    title text not null,
+    body_markdown text not null default '',
+    created_at text not null default current_timestamp,
+    updated_at text not null default current_timestamp
+);
+
+create index if not exists posts_created_idx on posts(created_at desc);
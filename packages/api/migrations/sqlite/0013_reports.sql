@@ -0,0 +1,19 @@
+-- Content reports: one per user and target; enough distinct reports auto-hide the target (SQLite version)
+
+create table if not exists reports (
+    id text primary key default (
+        lower(hex(randomblob(4))) || '-' ||
+        lower(hex(randomblob(2))) || '-' ||
+        lower(hex(randomblob(2))) || '-' ||
+        lower(hex(randomblob(2))) || '-' ||
+        lower(hex(randomblob(6)))
+    ),
+    reporter_user_id text not null references users(id) on delete cascade,
+    target_type text not null,
+    target_id text not null,
+    reason text not null default '',
+    created_at text not null default current_timestamp,
+    unique (reporter_user_id, target_type, target_id)
+);
+
+create index if not exists reports_target_idx on reports(target_type, target_id);
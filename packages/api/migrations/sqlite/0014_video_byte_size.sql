@@ -0,0 +1,3 @@
+-- Uploaded file size, recorded at finalize for quotas and display (SQLite version)
+
+alter table videos add column byte_size integer;
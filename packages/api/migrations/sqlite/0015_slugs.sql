@@ -0,0 +1,7 @@
+-- Readable URL slugs for proposals and programs; older rows get one on their next update (SQLite version)
+
+alter table proposals add column slug text;
+alter table programs add column slug text;
+
+create unique index if not exists proposals_slug_idx on proposals (slug);
+create unique index if not exists programs_slug_idx on programs (slug);
@@ -0,0 +1,19 @@
+-- Bookmarks on any content, not just videos; existing video bookmarks are carried over (SQLite version)
+
+create table if not exists content_bookmarks (
+    id text primary key default (lower(hex(randomblob(16)))),
+    user_id text not null references users(id) on delete cascade,
+    target_type text not null,
+    target_id text not null,
+    created_at text not null default (datetime('now')),
+    unique(user_id, target_type, target_id)
+);
+
+insert into content_bookmarks (id, user_id, target_type, target_id, created_at)
+select id, user_id, 'video', video_id, created_at from bookmarks;
+
+drop table bookmarks;
+alter table content_bookmarks rename to bookmarks;
+
+create index if not exists bookmarks_user_idx on bookmarks(user_id, created_at desc);
+create index if not exists bookmarks_target_idx on bookmarks(target_type, target_id);
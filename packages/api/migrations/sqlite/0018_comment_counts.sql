@@ -0,0 +1,14 @@
+-- Cached visible-comment counts on proposals and programs, shown on detail pages (SQLite version).
+-- Kept current on write; `/api/admin/recompute_counts` repairs drift.
+
+alter table proposals add column comment_count integer not null default 0;
+alter table programs add column comment_count integer not null default 0;
+
+update proposals set comment_count = (
+    select count(*) from comments c
+    where c.target_type = 'proposal' and c.target_id = proposals.id and c.hidden_at is null
+);
+update programs set comment_count = (
+    select count(*) from comments c
+    where c.target_type = 'program' and c.target_id = programs.id and c.hidden_at is null
+);
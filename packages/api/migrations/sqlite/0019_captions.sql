@@ -0,0 +1,9 @@
+-- WebVTT caption tracks for videos, one per language (SQLite version)
+
+create table if not exists captions (
+    video_id text not null references videos(id) on delete cascade,
+    language text not null,
+    storage_key text not null,
+    created_at text not null default (datetime('now')),
+    primary key (video_id, language)
+);
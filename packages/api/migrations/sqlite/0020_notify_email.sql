@@ -0,0 +1,3 @@
+-- Opt-in email for comment and reply notifications (SQLite version)
+
+alter table profiles add column notify_email integer not null default 0;
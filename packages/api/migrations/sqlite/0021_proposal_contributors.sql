@@ -0,0 +1,10 @@
+-- Users other than the author who may edit a proposal (SQLite version)
+
+create table if not exists proposal_contributors (
+    proposal_id text not null references proposals(id) on delete cascade,
+    user_id text not null references users(id) on delete cascade,
+    created_at text not null default (datetime('now')),
+    primary key (proposal_id, user_id)
+);
+
+create index if not exists proposal_contributors_user_idx on proposal_contributors(user_id);
@@ -0,0 +1,6 @@
+-- Authors can pin one top-level comment on their content (SQLite version)
+
+alter table comments add column pinned integer not null default 0;
+
+create unique index if not exists comments_pinned_target_idx
+    on comments(target_type, target_id) where pinned = 1;
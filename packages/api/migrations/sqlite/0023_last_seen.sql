@@ -0,0 +1,3 @@
+-- When each user last made an authenticated call, refreshed at most hourly (SQLite version)
+
+alter table users add column last_seen_at text;
@@ -0,0 +1,18 @@
+-- Audit trail of admin actions (hide/unhide, counter repair); kept when the admin is deleted (SQLite version)
+
+create table if not exists admin_audit (
+    id text primary key default (
+        lower(hex(randomblob(4))) || '-' ||
+        lower(hex(randomblob(2))) || '-' ||
+        lower(hex(randomblob(2))) || '-' ||
+        lower(hex(randomblob(2))) || '-' ||
+        lower(hex(randomblob(6)))
+    ),
+    actor_user_id text references users(id) on delete set null,
+    action text not null,
+    target_type text,
+    target_id text,
+    created_at text not null default current_timestamp
+);
+
+create index if not exists admin_audit_created_idx on admin_audit(created_at desc);
@@ -0,0 +1,6 @@
+-- Activity: record proposal deletions (SQLite version)
+--
+-- SQLite stores activity.action as plain text, so 'deleted' needs no type change;
+-- this keeps the migration numbering in step with Postgres.
+
+select 1;
@@ -0,0 +1,5 @@
+-- Comment editing and soft deletion: deleted comments keep their row (and replies)
+-- with a blank body (SQLite version)
+
+alter table comments add column updated_at text;
+alter table comments add column deleted_at text;
@@ -0,0 +1,8 @@
+-- Tombstones for deleted proposals, so sync clients learn about hard deletes (SQLite version)
+
+create table if not exists deleted_proposals (
+    id text primary key,
+    deleted_at text not null
+);
+
+create index if not exists deleted_proposals_deleted_at_idx on deleted_proposals(deleted_at);
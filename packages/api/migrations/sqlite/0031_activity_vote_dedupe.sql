@@ -0,0 +1,32 @@
+-- Activity: at most one 'voted' row per user and target, kept only while the vote stands (SQLite version)
+--
+-- activity.action is plain text here, so 'voted' needs no type change first.
+
+update activity set action = 'voted' where action in ('voted_up', 'voted_down');
+
+delete from activity
+where action = 'voted'
+    and exists (
+        select 1 from activity b
+        where b.action = 'voted'
+            and b.user_id = activity.user_id
+            and b.target_type = activity.target_type
+            and b.target_id = activity.target_id
+            and (
+                julianday(b.created_at) > julianday(activity.created_at)
+                or (julianday(b.created_at) = julianday(activity.created_at) and b.id > activity.id)
+            )
+    );
+
+delete from activity
+where action = 'voted'
+    and not exists (
+        select 1 from votes v
+        where v.user_id = activity.user_id
+            and v.target_type = activity.target_type
+            and v.target_id = activity.target_id
+    );
+
+create unique index if not exists activity_vote_target_idx
+    on activity(user_id, target_type, target_id)
+    where action = 'voted';
@@ -0,0 +1,333 @@
+//! Account data export: everything stored about the caller in one JSON bundle.
+//!
+//! Each category is read in keyset pages of [`EXPORT_BATCH`] rows, so a large
+//! account never runs one unbounded query.
+
+use crate::types::AccountExport;
+use dioxus::prelude::*;
+#[cfg(feature = "server")]
+use tracing::info;
+
+/// Rows fetched per statement while collecting an export.
+#[cfg(feature = "server")]
+const EXPORT_BATCH: i64 = 500;
+
+/// Every row of `select` for `user_id`, fetched `EXPORT_BATCH` at a time in `key` order.
+///
+/// `select` binds the user as `$1`, ends with its `where` clause, and returns `key`
+/// as an `id` text column.
+#[cfg(feature = "server")]
+async fn collect<T>(
+    pool: &sqlx::Pool<sqlx::Any>,
+    select: &str,
+    key: &str,
+    user_id: uuid::Uuid,
+    map: impl Fn(&sqlx::any::AnyRow) -> Result<T, ServerFnError>,
+) -> Result<Vec<T>, ServerFnError> {
+    use sqlx::Row;
+
+    let mut items = Vec::new();
+    let mut after: Option<String> = None;
+    loop {
+        let after_filter = if after.is_some() {
+            format!("and {key} > $3")
+        } else {
+            String::new()
+        };
+        let sql = format!("{select} {after_filter} order by {key} limit $2");
+        let mut query = sqlx::query(&sql)
+            .bind(crate::db::uuid_to_db(user_id))
+            .bind(EXPORT_BATCH);
+        if let Some(after) = &after {
+            query = query.bind(after);
+        }
+        let rows = query
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        for row in &rows {
+            items.push(map(row)?);
+        }
+        match rows.last() {
+            Some(last) if rows.len() as i64 == EXPORT_BATCH => {
+                after = Some(last.get::<String, _>("id"));
+            }
+            _ => break,
+        }
+    }
+    Ok(items)
+}
+
+/// Download everything about the signed-in user: profile, proposals, programs,
+/// comments, votes, bookmarks and activity, hidden content included.
+#[dioxus::prelude::post("/api/account/export")]
+pub async fn export_account(id_token: String) -> Result<AccountExport, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id_token;
+        Err(ServerFnError::new("export_account is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "account.export_account",
+        use crate::types::{BookmarkedItem, ContentTargetType, Profile, User, VotedItem};
+        use sqlx::Row;
+
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        let row = sqlx::query(
+            "select CAST(id as TEXT) as id, CAST(created_at as TEXT) as created_at from users where id = $1",
+        )
+        .bind(crate::db::uuid_to_db(user_id))
+        .fetch_one(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        let user = User {
+            id: crate::db::uuid_from_db(&row.get::<String, _>("id"))?,
+            created_at: crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?,
+        };
+
+        let profile = sqlx::query(
+            r#"
+            select
+                CAST(user_id as TEXT) as user_id,
+                display_name,
+                bio,
+                avatar_url,
+                location,
+                notify_email,
+                CAST(updated_at as TEXT) as updated_at
+            from profiles
+            where user_id = $1
+            "#,
+        )
+        .bind(crate::db::uuid_to_db(user_id))
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .map(|row| {
+            Ok::<_, ServerFnError>(Profile {
+                user_id: crate::db::uuid_from_db(&row.get::<String, _>("user_id"))?,
+                display_name: row.get("display_name"),
+                bio: row.get("bio"),
+                avatar_url: row.get("avatar_url"),
+                location: row.get("location"),
+                notify_email: crate::db::bool_from_row(&row, "notify_email"),
+                updated_at: crate::db::datetime_from_db(&row.get::<String, _>("updated_at"))?,
+            })
+        })
+        .transpose()?;
+
+        let tags = if crate::db::is_sqlite() {
+            "p.tags"
+        } else {
+            "to_json(p.tags)::text"
+        };
+        let proposals = collect(
+            pool,
+            &format!(
+                r#"
+                select
+                    CAST(p.id as TEXT) as id,
+                    CAST(p.author_user_id as TEXT) as author_user_id,
+                    p.title,
+                    coalesce(p.slug, CAST(p.id as TEXT)) as slug,
+                    p.summary,
+                    p.body_markdown,
+                    {tags} as tags,
+                    CAST(p.created_at as TEXT) as created_at,
+                    CAST(p.updated_at as TEXT) as updated_at,
+                    (select coalesce(sum(v.value), 0) from votes v
+                        where v.target_type = 'proposal' and v.target_id = p.id) as vote_score,
+                    (select display_name from profiles where user_id = p.author_user_id)
+                        as author_display_name
+                from proposals p
+                where p.author_user_id = $1
+                "#
+            ),
+            "p.id",
+            user_id,
+            crate::proposals::proposal_from_row,
+        )
+        .await?;
+
+        let programs = collect(
+            pool,
+            r#"
+            select
+                CAST(p.id as TEXT) as id,
+                CAST(p.author_user_id as TEXT) as author_user_id,
+                p.title,
+                coalesce(p.slug, CAST(p.id as TEXT)) as slug,
+                p.summary,
+                p.body_markdown,
+                CAST(p.created_at as TEXT) as created_at,
+                CAST(p.updated_at as TEXT) as updated_at,
+                (select coalesce(sum(v.value), 0) from votes v
+                    where v.target_type = 'program' and v.target_id = p.id) as vote_score,
+                (select display_name from profiles where user_id = p.author_user_id)
+                    as author_display_name
+            from programs p
+            where p.author_user_id = $1
+            "#,
+            "p.id",
+            user_id,
+            crate::programs::program_from_row,
+        )
+        .await?;
+
+        let mut comments = collect(
+            pool,
+            r#"
+            select
+                CAST(c.id as TEXT) as id,
+                CAST(c.author_user_id as TEXT) as author_user_id,
+                c.target_type,
+                CAST(c.target_id as TEXT) as target_id,
+                CAST(c.parent_comment_id as TEXT) as parent_comment_id,
+                c.body_markdown,
+                CAST(c.created_at as TEXT) as created_at,
+                CAST(c.updated_at as TEXT) as updated_at,
+                CAST(c.deleted_at as TEXT) as deleted_at,
+                c.pinned,
+                (select coalesce(sum(v.value), 0) from votes v
+                    where v.target_type = 'comment' and v.target_id = c.id) as vote_score,
+                (select display_name from profiles where user_id = c.author_user_id)
+                    as author_display_name
+            from comments c
+            where c.author_user_id = $1
+            "#,
+            "c.id",
+            user_id,
+            crate::comments::comment_from_row,
+        )
+        .await?;
+        crate::mentions::attach(pool, comments.iter_mut()).await?;
+
+        let title_expr = if crate::db::is_sqlite() {
+            "substr(body_markdown, 1, 80)"
+        } else {
+            "left(body_markdown, 80)"
+        };
+        let votes = collect(
+            pool,
+            &format!(
+                r#"
+                select
+                    CAST(v.id as TEXT) as id,
+                    CAST(v.target_type as TEXT) as target_type,
+                    CAST(v.target_id as TEXT) as target_id,
+                    v.value,
+                    CAST(v.updated_at as TEXT) as voted_at,
+                    case
+                        when v.target_type = 'proposal' then (select title from proposals where id = v.target_id)
+                        when v.target_type = 'program' then (select title from programs where id = v.target_id)
+                        when v.target_type = 'comment' then (select {title_expr} from comments where id = v.target_id)
+                        when v.target_type = 'video' then (select storage_key from videos where id = v.target_id)
+                        else null
+                    end as title
+                from votes v
+                where v.user_id = $1
+                "#
+            ),
+            "v.id",
+            user_id,
+            |row| {
+                Ok(VotedItem {
+                    target_type: ContentTargetType::from_db(&row.get::<String, _>("target_type"))
+                        .ok_or_else(|| ServerFnError::new("invalid target_type"))?,
+                    target_id: crate::db::uuid_from_db(&row.get::<String, _>("target_id"))?,
+                    value: row.get::<i16, _>("value"),
+                    voted_at: crate::db::datetime_from_db(&row.get::<String, _>("voted_at"))?,
+                    title: row.get("title"),
+                })
+            },
+        )
+        .await?;
+
+        let bookmarks = collect(
+            pool,
+            &format!(
+                r#"
+                select
+                    CAST(b.id as TEXT) as id,
+                    CAST(b.target_type as TEXT) as target_type,
+                    CAST(b.target_id as TEXT) as target_id,
+                    CAST(b.created_at as TEXT) as bookmarked_at,
+                    case
+                        when b.target_type = 'proposal' then (select title from proposals where id = b.target_id)
+                        when b.target_type = 'program' then (select title from programs where id = b.target_id)
+                        when b.target_type = 'comment' then (select {title_expr} from comments where id = b.target_id)
+                        when b.target_type = 'video' then (select storage_key from videos where id = b.target_id)
+                        else null
+                    end as title
+                from bookmarks b
+                where b.user_id = $1
+                "#
+            ),
+            "b.id",
+            user_id,
+            |row| {
+                Ok(BookmarkedItem {
+                    target_type: ContentTargetType::from_db(&row.get::<String, _>("target_type"))
+                        .ok_or_else(|| ServerFnError::new("invalid target_type"))?,
+                    target_id: crate::db::uuid_from_db(&row.get::<String, _>("target_id"))?,
+                    bookmarked_at: crate::db::datetime_from_db(
+                        &row.get::<String, _>("bookmarked_at"),
+                    )?,
+                    title: row.get("title"),
+                })
+            },
+        )
+        .await?;
+
+        let activity = collect(
+            pool,
+            &format!(
+                r#"
+                select
+                    CAST(a.id as TEXT) as id,
+                    CAST(a.user_id as TEXT) as user_id,
+                    a.action,
+                    a.target_type,
+                    CAST(a.target_id as TEXT) as target_id,
+                    CAST(a.created_at as TEXT) as created_at,
+                    {} as title,
+                    (select display_name from profiles where user_id = a.user_id)
+                        as actor_display_name
+                from activity a
+                where a.user_id = $1
+                "#,
+                crate::activity::title_sql(false)
+            ),
+            "a.id",
+            user_id,
+            crate::activity::activity_from_row,
+        )
+        .await?;
+
+        info!(
+            "account.export_account: user_id={} proposals={} programs={} comments={} votes={} bookmarks={} activity={}",
+            user_id,
+            proposals.len(),
+            programs.len(),
+            comments.len(),
+            votes.len(),
+            bookmarks.len(),
+            activity.len()
+        );
+        Ok(AccountExport {
+            user,
+            profile,
+            proposals,
+            programs,
+            comments,
+            votes,
+            bookmarks,
+            activity,
+            exported_at: time::OffsetDateTime::now_utc(),
+        })
+    }
+}
@@ -1,32 +1,103 @@
-use crate::types::ActivityItem;
+use crate::types::{ActivityCursor, ActivityItem};
 use dioxus::prelude::*;
 #[cfg(feature = "server")]
 use tracing::debug;
 
+/// Best-effort title for an activity row's target.
+///
+/// With `visible_only`, hidden or deleted targets yield `NULL` so callers can drop them.
+#[cfg(feature = "server")]
+pub(crate) fn title_sql(visible_only: bool) -> String {
+    let comment_title = if crate::db::is_sqlite() {
+        "substr(body_markdown, 1, 80)"
+    } else {
+        "left(body_markdown, 80)"
+    };
+    let visible = if visible_only {
+        " and hidden_at is null"
+    } else {
+        ""
+    };
+    format!(
+        r#"case
+                    when a.target_type = 'proposal' then (select title from proposals where id = a.target_id{visible})
+                    when a.target_type = 'program' then (select title from programs where id = a.target_id{visible})
+                    when a.target_type = 'comment' then (select {comment_title} from comments where id = a.target_id{visible})
+                    when a.target_type = 'video' then (select storage_key from videos where id = a.target_id{visible})
+                    else null
+                end"#
+    )
+}
+
+#[cfg(feature = "server")]
+pub(crate) fn activity_from_row(row: &sqlx::any::AnyRow) -> Result<ActivityItem, ServerFnError> {
+    use crate::types::{ActivityAction, ContentTargetType};
+    use sqlx::Row;
+
+    Ok(ActivityItem {
+        id: crate::db::uuid_from_db(&row.get::<String, _>("id"))?,
+        user_id: crate::db::uuid_from_db(&row.get::<String, _>("user_id"))?,
+        action: match row.get::<String, _>("action").as_str() {
+            "created" => ActivityAction::Created,
+            "voted" => ActivityAction::Voted,
+            "commented" => ActivityAction::Commented,
+            "bookmarked" => ActivityAction::Bookmarked,
+            "bundled" => ActivityAction::Bundled,
+            "deleted" => ActivityAction::Deleted,
+            _ => ActivityAction::Created,
+        },
+        target_type: match row.get::<String, _>("target_type").as_str() {
+            "proposal" => ContentTargetType::Proposal,
+            "program" => ContentTargetType::Program,
+            "video" => ContentTargetType::Video,
+            "comment" => ContentTargetType::Comment,
+            _ => ContentTargetType::Proposal,
+        },
+        target_id: crate::db::uuid_from_db(&row.get::<String, _>("target_id"))?,
+        created_at: crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?,
+        title: row.get("title"),
+        actor_display_name: row.get("actor_display_name"),
+    })
+}
+
+/// The signed-in user's activity, newest first.
+///
+/// Pass the last item of the previous page as `before` to get the next one.
 #[dioxus::prelude::post("/api/activity/me")]
 pub async fn list_my_activity(
     id_token: String,
     limit: i64,
+    before: Option<ActivityCursor>,
 ) -> Result<Vec<ActivityItem>, ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = (id_token, limit);
+        let _ = (id_token, limit, before);
         Err(ServerFnError::new("list_my_activity is server-only"))
     }
 
     #[cfg(feature = "server")]
-    {
-        use crate::types::{ActivityAction, ContentTargetType};
-        use sqlx::Row;
-        debug!("activity.list_my_activity: limit={}", limit);
+    crate::telemetry::traced! { "activity.list_my_activity",
+        debug!(
+            "activity.list_my_activity: limit={} before={:?}",
+            limit, before
+        );
         let user_id = crate::auth::require_user_id(id_token).await?;
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
+        let limit = state.clamp_limit(limit);
         let pool = state.db.pool().await;
 
-        let title_expr = if crate::db::is_sqlite() {
-            "substr(body_markdown, 1, 80)"
-        } else {
-            "left(body_markdown, 80)"
+        // SQLite timestamps are text with varying fractional digits, so compare them
+        // as julian days rather than as strings.
+        let before_filter = match before {
+            None => "",
+            Some(_) if crate::db::is_sqlite() => {
+                "and (julianday(a.created_at) < julianday($3)
+                    or (julianday(a.created_at) = julianday($3) and a.id < $4))"
+            }
+            Some(_) => {
+                "and (a.created_at < $3::timestamptz
+                    or (a.created_at = $3::timestamptz and a.id < $4))"
+            }
         };
         let sql = format!(
             r#"
@@ -37,57 +108,100 @@ pub async fn list_my_activity(
                 a.target_type,
                 CAST(a.target_id as TEXT) as target_id,
                 CAST(a.created_at as TEXT) as created_at,
-                case
-                    when a.target_type = 'proposal' then (select title from proposals where id = a.target_id)
-                    when a.target_type = 'program' then (select title from programs where id = a.target_id)
-                    when a.target_type = 'comment' then (select {} from comments where id = a.target_id)
-                    when a.target_type = 'video' then (select storage_key from videos where id = a.target_id)
-                    else null
-                end as title
+                {} as title,
+                pr.display_name as actor_display_name
             from activity a
-            where a.user_id = $1
-            order by a.created_at desc
+            left join profiles pr on pr.user_id = a.user_id
+            where a.user_id = $1 {before_filter}
+            order by a.created_at desc, a.id desc
             limit $2
             "#,
-            title_expr
+            title_sql(false)
         );
-        let rows = sqlx::query(&sql)
+        let mut query = sqlx::query(&sql)
             .bind(crate::db::uuid_to_db(user_id))
-            .bind(limit)
+            .bind(limit);
+        if let Some(cursor) = before {
+            query = query
+                .bind(crate::db::datetime_to_db(cursor.created_at)?)
+                .bind(crate::db::uuid_to_db(cursor.id));
+        }
+        let rows = query
             .fetch_all(pool)
             .await
             .map_err(|e| ServerFnError::new(e.to_string()))?;
 
-        let mut items = Vec::with_capacity(rows.len());
-        for row in rows {
-            let id = crate::db::uuid_from_db(&row.get::<String, _>("id"))?;
-            let user_id = crate::db::uuid_from_db(&row.get::<String, _>("user_id"))?;
-            let target_id = crate::db::uuid_from_db(&row.get::<String, _>("target_id"))?;
-            let created_at = crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?;
-            items.push(ActivityItem {
-                id,
-                user_id,
-                action: match row.get::<String, _>("action").as_str() {
-                    "created" => ActivityAction::Created,
-                    "voted_up" => ActivityAction::VotedUp,
-                    "voted_down" => ActivityAction::VotedDown,
-                    "commented" => ActivityAction::Commented,
-                    _ => ActivityAction::Created,
-                },
-                target_type: match row.get::<String, _>("target_type").as_str() {
-                    "proposal" => ContentTargetType::Proposal,
-                    "program" => ContentTargetType::Program,
-                    "video" => ContentTargetType::Video,
-                    "comment" => ContentTargetType::Comment,
-                    _ => ContentTargetType::Proposal,
-                },
-                target_id,
-                created_at,
-                title: row.get("title"),
-            });
-        }
-
+        let items = rows
+            .iter()
+            .map(activity_from_row)
+            .collect::<Result<Vec<_>, _>>()?;
         debug!("activity.list_my_activity: count={}", items.len());
         Ok(items)
     }
 }
+
+/// Recent public activity across all users (home feed), newest first.
+///
+/// Only creations and comments are public; votes stay private, and activity on
+/// hidden or deleted content is dropped.
+#[dioxus::prelude::post("/api/activity/global")]
+pub async fn list_global_activity(
+    limit: i64,
+    offset: i64,
+    id_token: Option<String>,
+) -> Result<Vec<ActivityItem>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (limit, offset, id_token);
+        Err(ServerFnError::new("list_global_activity is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "activity.list_global_activity",
+        debug!(
+            "activity.list_global_activity: limit={} offset={}",
+            limit, offset
+        );
+        crate::auth::reader_id(id_token).await?;
+        let state = crate::state::AppState::current()?;
+        let (limit, offset) = state.page(limit, offset)?;
+        let pool = state.db.pool().await;
+
+        let sql = format!(
+            r#"
+            select * from (
+                select
+                    CAST(a.id as TEXT) as id,
+                    CAST(a.user_id as TEXT) as user_id,
+                    a.action,
+                    a.target_type,
+                    CAST(a.target_id as TEXT) as target_id,
+                    CAST(a.created_at as TEXT) as created_at,
+                    a.created_at as sort_at,
+                    {} as title,
+                    pr.display_name as actor_display_name
+                from activity a
+                left join profiles pr on pr.user_id = a.user_id
+                where a.action in ('created', 'commented')
+            ) feed
+            where feed.title is not null
+            order by feed.sort_at desc, feed.id desc
+            limit $1 offset $2
+            "#,
+            title_sql(true)
+        );
+        let rows = sqlx::query(&sql)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let items = rows
+            .iter()
+            .map(activity_from_row)
+            .collect::<Result<Vec<_>, _>>()?;
+        debug!("activity.list_global_activity: count={}", items.len());
+        Ok(items)
+    }
+}
@@ -0,0 +1,108 @@
+//! Audit log of admin actions.
+//!
+//! Admin handlers call [`record`] in the same transaction as the change they audit,
+//! so an action is logged exactly when it takes effect. Admins read the log back
+//! with `list_admin_audit`.
+
+use crate::types::AuditEntry;
+use dioxus::prelude::*;
+#[cfg(feature = "server")]
+use tracing::debug;
+
+/// Log `action` by `actor`, on `target` when the action has one.
+#[cfg(feature = "server")]
+pub(crate) async fn record<'e>(
+    executor: impl sqlx::Executor<'e, Database = sqlx::Any>,
+    actor: uuid::Uuid,
+    action: crate::types::AdminAction,
+    target: Option<(crate::types::ContentTargetType, uuid::Uuid)>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        insert into admin_audit (actor_user_id, action, target_type, target_id)
+        values ($1, $2, $3, $4)
+        "#,
+    )
+    .bind(crate::db::uuid_to_db(actor))
+    .bind(action.as_db())
+    .bind(target.map(|(target_type, _)| target_type.as_db()))
+    .bind(target.map(|(_, target_id)| crate::db::uuid_to_db(target_id)))
+    .execute(executor)
+    .await?;
+    debug!("audit.record: actor={} action={:?}", actor, action);
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+fn entry_from_row(row: &sqlx::any::AnyRow) -> Result<AuditEntry, ServerFnError> {
+    use crate::types::{AdminAction, ContentTargetType};
+    use sqlx::Row;
+
+    let opt_uuid = |col: &str| -> Result<Option<uuid::Uuid>, ServerFnError> {
+        row.get::<Option<String>, _>(col)
+            .as_deref()
+            .map(crate::db::uuid_from_db)
+            .transpose()
+    };
+    let target_type = row
+        .get::<Option<String>, _>("target_type")
+        .map(|t| {
+            ContentTargetType::from_db(&t).ok_or_else(|| ServerFnError::new("invalid target_type"))
+        })
+        .transpose()?;
+
+    Ok(AuditEntry {
+        id: crate::db::uuid_from_db(&row.get::<String, _>("id"))?,
+        actor_user_id: opt_uuid("actor_user_id")?,
+        action: AdminAction::from_db(&row.get::<String, _>("action"))
+            .ok_or_else(|| ServerFnError::new("invalid audit action"))?,
+        target_type,
+        target_id: opt_uuid("target_id")?,
+        created_at: crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?,
+    })
+}
+
+/// The admin audit log, newest first (admin only).
+#[dioxus::prelude::post("/api/admin/audit")]
+pub async fn list_admin_audit(
+    id_token: String,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<AuditEntry>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, limit, offset);
+        Err(ServerFnError::new("list_admin_audit is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "audit.list_admin_audit",
+        debug!("audit.list_admin_audit: limit={} offset={}", limit, offset);
+        crate::auth::require_admin(id_token).await?;
+        let state = crate::state::AppState::current()?;
+        let (limit, offset) = state.page(limit, offset)?;
+        let pool = state.db.pool().await;
+
+        let rows = sqlx::query(
+            r#"
+            select
+                CAST(id as TEXT) as id,
+                CAST(actor_user_id as TEXT) as actor_user_id,
+                action,
+                CAST(target_type as TEXT) as target_type,
+                CAST(target_id as TEXT) as target_id,
+                CAST(created_at as TEXT) as created_at
+            from admin_audit
+            order by created_at desc, id desc
+            limit $1 offset $2
+            "#,
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        rows.iter().map(entry_from_row).collect()
+    }
+}
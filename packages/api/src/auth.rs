@@ -2,6 +2,8 @@ use crate::types::{Profile, User};
 use dioxus::prelude::ServerFnError;
 use uuid::Uuid;
 
+#[cfg(feature = "server")]
+use crate::types::ErrorCode;
 #[cfg(feature = "server")]
 use sqlx::Row;
 
@@ -24,7 +26,8 @@ mod server {
     struct Claims {
         sub: String,
         iss: String,
-        aud: Option<String>,
+        // A single string or an array, depending on the provider.
+        aud: Option<serde_json::Value>,
         exp: usize,
     }
 
@@ -50,12 +53,35 @@ mod server {
         Ok(JWK_SET.get().expect("jwk set initialized"))
     }
 
-    fn expected_issuer() -> Result<String, anyhow::Error> {
-        std::env::var("AUTH_ISSUER").context("AUTH_ISSUER must be set")
+    /// Comma-separated `list_key`, falling back to the single-value `single_key`.
+    fn env_list(list_key: &str, single_key: &str) -> Result<Vec<String>, anyhow::Error> {
+        let values: Vec<String> = std::env::var(list_key)
+            .or_else(|_| std::env::var(single_key))
+            .unwrap_or_default()
+            .split(',')
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect();
+        if values.is_empty() {
+            return Err(anyhow!("{list_key} or {single_key} must be set"));
+        }
+        Ok(values)
     }
 
-    fn expected_audience() -> Result<String, anyhow::Error> {
-        std::env::var("AUTH_CLIENT_ID").context("AUTH_CLIENT_ID must be set")
+    fn expected_issuers() -> Result<Vec<String>, anyhow::Error> {
+        env_list("AUTH_ISSUERS", "AUTH_ISSUER")
+    }
+
+    fn expected_audiences() -> Result<Vec<String>, anyhow::Error> {
+        env_list("AUTH_CLIENT_IDS", "AUTH_CLIENT_ID")
+    }
+
+    /// Issuer/audience checks for OAuth id tokens; any configured value is accepted.
+    fn oauth_validation(alg: Algorithm) -> Result<Validation, anyhow::Error> {
+        let mut validation = Validation::new(alg);
+        validation.set_issuer(&expected_issuers()?);
+        validation.set_audience(&expected_audiences()?);
+        Ok(validation)
     }
 
     pub async fn verify_id_token(id_token: &str) -> Result<String, anyhow::Error> {
@@ -81,10 +107,7 @@ mod server {
 
                 let key = DecodingKey::from_rsa_components(&n, &e).context("bad rsa components")?;
 
-                let mut validation = Validation::new(Algorithm::RS256);
-                validation.set_issuer(&[expected_issuer()?]);
-                validation.set_audience(&[expected_audience()?]);
-
+                let validation = oauth_validation(Algorithm::RS256)?;
                 let token =
                     decode::<Claims>(id_token, &key, &validation).context("jwt verify failed")?;
                 Ok(token.claims.sub)
@@ -92,20 +115,25 @@ mod server {
             Algorithm::HS256 => {
                 debug!("auth.verify_id_token: alg=HS256");
                 // Local email/password flow - new verification
-                let user_id = verify_local_jwt(id_token)?;
+                let (user_id, version) = verify_local_jwt(id_token)?;
+                ensure_token_version(user_id, version).await?;
                 Ok(user_id.to_string())
             }
             _ => Err(anyhow!("unsupported jwt algorithm: {:?}", header.alg)),
         }
     }
 
+    /// The user behind an auth subject, created on first sight, with its
+    /// `last_seen_at` refreshed once it is older than `LAST_SEEN_THROTTLE`.
+    ///
+    /// The freshness check rides on the lookup, so most requests skip the write.
     pub async fn ensure_user_for_subject(subject: &str) -> Result<User, ServerFnError> {
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
         let pool = state.db.pool().await;
 
         // Try fetch existing
         if let Some(row) = sqlx::query(
-            "select CAST(id as TEXT) as id, CAST(created_at as TEXT) as created_at from users where auth_subject = $1",
+            "select CAST(id as TEXT) as id, CAST(created_at as TEXT) as created_at, CAST(last_seen_at as TEXT) as last_seen_at from users where auth_subject = $1",
         )
             .bind(subject)
             .fetch_optional(pool)
@@ -114,7 +142,15 @@ mod server {
         {
             let id = crate::db::uuid_from_db(&row.get::<String, _>("id"))?;
             let created_at = crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?;
+            let last_seen_at = row
+                .get::<Option<String>, _>("last_seen_at")
+                .map(|at| crate::db::datetime_from_db(&at))
+                .transpose()?;
             debug!("auth.ensure_user_for_subject: existing user_id={}", id);
+            let now = time::OffsetDateTime::now_utc();
+            if last_seen_at.is_none_or(|at| now - at >= super::LAST_SEEN_THROTTLE) {
+                touch_last_seen(id).await;
+            }
             return Ok(User { id, created_at });
         }
 
@@ -129,18 +165,55 @@ mod server {
 
         let id = crate::db::uuid_from_db(&row.get::<String, _>("id"))?;
         info!("auth.ensure_user_for_subject: created user_id={}", id);
+        touch_last_seen(id).await;
         Ok(User {
             id,
             created_at: crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?,
         })
     }
 
+    /// Stamp `users.last_seen_at`, unless it was stamped within `LAST_SEEN_THROTTLE`.
+    ///
+    /// The condition guards concurrent requests that all saw a stale value. Best-effort:
+    /// it is skipped in read-only mode and failures are only logged, so it never fails
+    /// the caller.
+    async fn touch_last_seen(user_id: Uuid) {
+        let Ok(state) = crate::state::AppState::current() else {
+            return;
+        };
+        if state.maintenance_mode() {
+            return;
+        }
+        let pool = state.db.pool().await;
+        let secs = super::LAST_SEEN_THROTTLE.whole_seconds();
+        let stale_before = if crate::db::is_sqlite() {
+            format!("strftime('%Y-%m-%d %H:%M:%f', 'now', '-{secs} seconds')")
+        } else {
+            format!("now() - interval '{secs} seconds'")
+        };
+        let sql = format!(
+            "update users set last_seen_at = {now} where id = $1 and (last_seen_at is null or last_seen_at < {stale_before})",
+            now = crate::db::now_sql(),
+        );
+        match sqlx::query(&sql)
+            .bind(crate::db::uuid_to_db(user_id))
+            .execute(pool)
+            .await
+        {
+            Ok(done) if done.rows_affected() > 0 => {
+                debug!("auth.touch_last_seen: user_id={}", user_id);
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("auth.touch_last_seen: user_id={} error={}", user_id, e),
+        }
+    }
+
     pub async fn get_profile_for_user(user_id: Uuid) -> Result<Option<Profile>, ServerFnError> {
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
         let pool = state.db.pool().await;
 
         let row = sqlx::query(
-            "select CAST(user_id as TEXT) as user_id, display_name, bio, avatar_url, location, CAST(updated_at as TEXT) as updated_at from profiles where user_id = $1",
+            "select CAST(user_id as TEXT) as user_id, display_name, bio, avatar_url, location, notify_email, CAST(updated_at as TEXT) as updated_at from profiles where user_id = $1",
         )
         .bind(crate::db::uuid_to_db(user_id))
         .fetch_optional(pool)
@@ -154,6 +227,7 @@ mod server {
                 bio: row.get("bio"),
                 avatar_url: row.get("avatar_url"),
                 location: row.get("location"),
+                notify_email: crate::db::bool_from_row(&row, "notify_email"),
                 updated_at: crate::db::datetime_from_db(&row.get::<String, _>("updated_at"))?,
             };
             debug!("auth.get_profile_for_user: hit user_id={}", user_id);
@@ -164,26 +238,27 @@ mod server {
         }
     }
 
-    pub fn validate_password(password: &str) -> Result<(), anyhow::Error> {
+    pub fn validate_password(password: &str) -> Result<(), ErrorCode> {
         if password.len() < 8 {
-            return Err(anyhow::anyhow!("Password must be at least 8 characters"));
+            return Err(ErrorCode::PasswordTooShort);
         }
         if !password.chars().any(|c| c.is_uppercase()) {
-            return Err(anyhow::anyhow!(
-                "Password must contain at least one uppercase letter"
-            ));
+            return Err(ErrorCode::PasswordNoUppercase);
         }
         if !password.chars().any(|c| c.is_lowercase()) {
-            return Err(anyhow::anyhow!(
-                "Password must contain at least one lowercase letter"
-            ));
+            return Err(ErrorCode::PasswordNoLowercase);
         }
         if !password.chars().any(|c| c.is_numeric()) {
-            return Err(anyhow::anyhow!("Password must contain at least one number"));
+            return Err(ErrorCode::PasswordNoNumber);
         }
         Ok(())
     }
 
+    /// Validation failure carrying its stable code, localized for `lang`.
+    pub fn rejected(code: ErrorCode, lang: &Option<String>) -> ServerFnError {
+        ServerFnError::new(code.error_text(lang.as_deref()))
+    }
+
     use jsonwebtoken::{encode, EncodingKey, Header};
 
     #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -192,10 +267,25 @@ mod server {
         iss: String,
         exp: usize,
         iat: usize,
+        /// `users.token_version` at issue time; bumping the column revokes the token.
+        #[serde(default)]
+        ver: i64,
     }
 
-    pub fn generate_local_jwt(user_id: Uuid) -> Result<String, anyhow::Error> {
+    /// `JWT_SECRET`, refusing one too short to sign with even if config validation
+    /// was bypassed.
+    fn jwt_secret() -> Result<String, anyhow::Error> {
         let secret = std::env::var("JWT_SECRET").context("JWT_SECRET must be set")?;
+        checked_jwt_secret(secret)
+    }
+
+    fn checked_jwt_secret(secret: String) -> Result<String, anyhow::Error> {
+        crate::config::validate_jwt_secret(&secret).map_err(anyhow::Error::msg)?;
+        Ok(secret)
+    }
+
+    pub fn generate_local_jwt(user_id: Uuid, token_version: i64) -> Result<String, anyhow::Error> {
+        let secret = jwt_secret()?;
 
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)?
@@ -208,6 +298,7 @@ mod server {
             iss: "alelysee".to_string(),
             exp,
             iat: now,
+            ver: token_version,
         };
 
         let token = encode(
@@ -220,8 +311,9 @@ mod server {
         Ok(token)
     }
 
-    pub fn verify_local_jwt(token: &str) -> Result<Uuid, anyhow::Error> {
-        let secret = std::env::var("JWT_SECRET").context("JWT_SECRET must be set")?;
+    /// Check the signature and return `(user_id, token_version)`.
+    pub fn verify_local_jwt(token: &str) -> Result<(Uuid, i64), anyhow::Error> {
+        let secret = jwt_secret()?;
 
         let mut validation = jsonwebtoken::Validation::new(Algorithm::HS256);
         validation.set_issuer(&["alelysee"]);
@@ -234,7 +326,80 @@ mod server {
 
         let user_id = Uuid::parse_str(&token_data.claims.sub)?;
         debug!("auth.verify_local_jwt: user_id={}", user_id);
-        Ok(user_id)
+        Ok((user_id, token_data.claims.ver))
+    }
+
+    /// Reject local tokens issued before the user's last credential change.
+    async fn ensure_token_version(user_id: Uuid, version: i64) -> Result<(), anyhow::Error> {
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        let current: Option<i64> =
+            sqlx::query_scalar("select token_version from users where id = $1")
+                .bind(crate::db::uuid_to_db(user_id))
+                .fetch_optional(pool)
+                .await?;
+        match current {
+            Some(current) if current == version => Ok(()),
+            _ => {
+                info!("auth.ensure_token_version: revoked user_id={}", user_id);
+                Err(anyhow!("token revoked"))
+            }
+        }
+    }
+
+    /// Minimum delay between two verification or reset emails to the same user.
+    pub const EMAIL_COOLDOWN_SECS: i64 = 60;
+
+    /// Whether a token was already issued to `user_id` in `table` within the cooldown.
+    ///
+    /// `table` is one of the fixed token tables (`email_verifications`, `password_resets`).
+    pub async fn email_sent_recently(
+        pool: &sqlx::Pool<sqlx::Any>,
+        table: &str,
+        user_id: &str,
+    ) -> Result<bool, ServerFnError> {
+        let last: Option<String> = sqlx::query_scalar(&format!(
+            "select CAST(max(created_at) as TEXT) from {table} where user_id = $1"
+        ))
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let Some(last) = last else {
+            return Ok(false);
+        };
+        let last = crate::db::datetime_from_db(&last)?;
+        Ok(time::OffsetDateTime::now_utc() - last < time::Duration::seconds(EMAIL_COOLDOWN_SECS))
+    }
+
+    /// Signup attempts allowed per email and per client address in `AUTH_RATE_WINDOW_SECS`.
+    pub const SIGNUP_RATE_LIMIT: usize = 5;
+    /// Password reset requests allowed per email and per client address in the window.
+    pub const RESET_RATE_LIMIT: usize = 3;
+    pub const AUTH_RATE_WINDOW_SECS: u64 = 60 * 60;
+
+    /// Record an `action` attempt for `email` and the requesting address; `false` once
+    /// either has used up its `max` attempts for the window.
+    pub fn allow_attempt(
+        state: &crate::state::AppState,
+        action: &str,
+        email: &str,
+        max: usize,
+    ) -> bool {
+        let window = std::time::Duration::from_secs(AUTH_RATE_WINDOW_SECS);
+        let email = email.trim().to_lowercase();
+        let by_email = state
+            .rate_limiter
+            .check(&format!("{action}:email:{email}"), max, window);
+        let by_ip = match crate::client_ip::current() {
+            Some(ip) => state
+                .rate_limiter
+                .check(&format!("{action}:ip:{ip}"), max, window),
+            None => true,
+        };
+        by_email && by_ip
     }
 
     pub fn email_domain(email: &str) -> &str {
@@ -293,12 +458,72 @@ mod server {
             std::env::set_var("JWT_SECRET", "test-secret-key-for-testing-32chars");
 
             let user_id = Uuid::new_v4();
-            let token = generate_local_jwt(user_id).unwrap();
+            let token = generate_local_jwt(user_id, 3).unwrap();
 
             assert!(!token.is_empty());
 
-            let verified_id = verify_local_jwt(&token).unwrap();
+            let (verified_id, version) = verify_local_jwt(&token).unwrap();
             assert_eq!(verified_id, user_id);
+            assert_eq!(version, 3);
+        }
+
+        #[test]
+        fn test_oauth_validation_accepts_any_listed_audience() {
+            std::env::set_var(
+                "AUTH_ISSUERS",
+                "https://a.example.com/, https://b.example.com/",
+            );
+            std::env::set_var("AUTH_CLIENT_IDS", "web-client,mobile-client");
+            let validation = oauth_validation(Algorithm::HS256).unwrap();
+            std::env::remove_var("AUTH_ISSUERS");
+            std::env::remove_var("AUTH_CLIENT_IDS");
+
+            let secret = b"oauth-validation-test";
+            let sign = |aud: &str| {
+                let exp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs()
+                    + 60;
+                let claims = serde_json::json!({
+                    "sub": "subject",
+                    "iss": "https://b.example.com/",
+                    "aud": aud,
+                    "exp": exp,
+                });
+                encode(
+                    &Header::new(Algorithm::HS256),
+                    &claims,
+                    &EncodingKey::from_secret(secret),
+                )
+                .unwrap()
+            };
+            let key = DecodingKey::from_secret(secret);
+
+            let token = decode::<Claims>(&sign("mobile-client"), &key, &validation)
+                .expect("second audience should verify");
+            assert_eq!(token.claims.sub, "subject");
+            assert!(decode::<Claims>(&sign("other-client"), &key, &validation).is_err());
+        }
+
+        #[test]
+        fn test_env_list_falls_back_to_single_value() {
+            std::env::set_var("TEST_SINGLE_AUD", "only-client");
+            assert_eq!(
+                env_list("TEST_MISSING_AUDS", "TEST_SINGLE_AUD").unwrap(),
+                vec!["only-client"]
+            );
+            assert!(env_list("TEST_MISSING_AUDS", "TEST_MISSING_AUD").is_err());
+        }
+
+        #[test]
+        fn test_checked_jwt_secret_rejects_short_secret() {
+            let err = checked_jwt_secret("short".to_string()).unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("JWT_SECRET must be at least 32 bytes"));
+            let ok = checked_jwt_secret("test-secret-key-for-testing-32chars".to_string());
+            assert!(ok.is_ok());
         }
 
         #[tokio::test]
@@ -317,32 +542,117 @@ pub struct PublicConfig {
     pub auth_client_id: String,
     pub auth_redirect_uri: String,
     pub media_base_url: Option<String>,
+    pub uploads_enabled: bool,
+    pub allowed_video_types: Vec<String>,
+    /// Widget to show on signup and password reset, when CAPTCHA is enabled.
+    pub captcha: Option<CaptchaWidget>,
+}
+
+/// Public half of the CAPTCHA setup: which provider and its site key.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CaptchaWidget {
+    pub provider: crate::config::CaptchaProvider,
+    pub site_key: String,
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Me {
     pub user: User,
     pub profile: Option<Profile>,
+    /// Whether a display name is set; see `profile_completeness` for the rest.
     pub profile_complete: bool,
+    pub profile_completeness: ProfileCompleteness,
+}
+
+/// Which optional profile fields are filled in, to nudge users toward the rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ProfileCompleteness {
+    pub display_name: bool,
+    pub bio: bool,
+    pub avatar: bool,
+    pub location: bool,
+    /// Share of the fields above that are filled, 0-100.
+    pub percent: u8,
+}
+
+impl ProfileCompleteness {
+    pub fn of(profile: Option<&Profile>) -> Self {
+        let filled = |v: Option<&str>| v.is_some_and(|v| !v.trim().is_empty());
+        let display_name = filled(profile.map(|p| p.display_name.as_str()));
+        let bio = filled(profile.map(|p| p.bio.as_str()));
+        let avatar = filled(profile.and_then(|p| p.avatar_url.as_deref()));
+        let location = filled(profile.and_then(|p| p.location.as_deref()));
+        let count = [display_name, bio, avatar, location]
+            .iter()
+            .filter(|f| **f)
+            .count();
+        ProfileCompleteness {
+            display_name,
+            bio,
+            avatar,
+            location,
+            percent: (count * 100 / 4) as u8,
+        }
+    }
 }
 
+static PUBLIC_CONFIG: std::sync::OnceLock<PublicConfig> = std::sync::OnceLock::new();
+
+/// Process-wide `PublicConfig`, read from env on first success and then reused.
+///
+/// Errors (missing vars) are not cached, so a later call can still succeed.
+/// `uploads_enabled` is re-read on every call: it depends on the global `AppState`,
+/// which isn't installed yet when `AppState::from_config` warms this cache.
 pub async fn public_config() -> Result<PublicConfig, ServerFnError> {
-    #[cfg(feature = "server")]
-    tracing::debug!("auth.public_config");
-    let auth_authorize_url = std::env::var("AUTH_AUTHORIZE_URL")
-        .map_err(|_| ServerFnError::new("AUTH_AUTHORIZE_URL not set"))?;
-    let auth_client_id = std::env::var("AUTH_CLIENT_ID")
-        .map_err(|_| ServerFnError::new("AUTH_CLIENT_ID not set"))?;
-    let auth_redirect_uri = std::env::var("AUTH_REDIRECT_URI")
-        .map_err(|_| ServerFnError::new("AUTH_REDIRECT_URI not set"))?;
-    let media_base_url = std::env::var("MEDIA_BASE_URL").ok();
-
-    Ok(PublicConfig {
-        auth_authorize_url,
-        auth_client_id,
-        auth_redirect_uri,
-        media_base_url,
-    })
+    let mut cfg = match PUBLIC_CONFIG.get() {
+        Some(cfg) => cfg.clone(),
+        None => {
+            let cfg = PublicConfig::from_env()?;
+            PUBLIC_CONFIG.get_or_init(|| cfg).clone()
+        }
+    };
+    cfg.uploads_enabled = crate::uploads::uploads_configured();
+    Ok(cfg)
+}
+
+impl PublicConfig {
+    /// Build the config from the environment, bypassing the cache.
+    pub fn from_env() -> Result<Self, ServerFnError> {
+        #[cfg(feature = "server")]
+        tracing::debug!("auth.public_config: reading env");
+        let auth_authorize_url = std::env::var("AUTH_AUTHORIZE_URL")
+            .map_err(|_| ServerFnError::new("AUTH_AUTHORIZE_URL not set"))?;
+        let auth_client_id = std::env::var("AUTH_CLIENT_ID")
+            .map_err(|_| ServerFnError::new("AUTH_CLIENT_ID not set"))?;
+        let auth_redirect_uri = std::env::var("AUTH_REDIRECT_URI")
+            .map_err(|_| ServerFnError::new("AUTH_REDIRECT_URI not set"))?;
+        let media_base_url = std::env::var("MEDIA_BASE_URL").ok();
+        let uploads_enabled = crate::uploads::uploads_configured();
+        let allowed_video_types = crate::uploads::ALLOWED_VIDEO_TYPES
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        let captcha = std::env::var("CAPTCHA_SITE_KEY")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .map(|site_key| CaptchaWidget {
+                provider: std::env::var("CAPTCHA_PROVIDER")
+                    .ok()
+                    .and_then(|v| crate::config::CaptchaProvider::parse(&v).ok())
+                    .unwrap_or(crate::config::CaptchaProvider::Turnstile),
+                site_key,
+            });
+
+        Ok(PublicConfig {
+            auth_authorize_url,
+            auth_client_id,
+            auth_redirect_uri,
+            media_base_url,
+            uploads_enabled,
+            allowed_video_types,
+            captcha,
+        })
+    }
 }
 
 pub async fn me_from_id_token(id_token: String) -> Result<Me, ServerFnError> {
@@ -361,21 +671,24 @@ pub async fn me_from_id_token(id_token: String) -> Result<Me, ServerFnError> {
 
         let user = server::ensure_user_for_subject(&sub).await?;
         let profile = server::get_profile_for_user(user.id).await?;
-        let profile_complete = profile
-            .as_ref()
-            .is_some_and(|p| !p.display_name.trim().is_empty());
+        let profile_completeness = ProfileCompleteness::of(profile.as_ref());
 
         Ok(Me {
             user,
             profile,
-            profile_complete,
+            profile_complete: profile_completeness.display_name,
+            profile_completeness,
         })
     }
 }
 
+/// How often an active user's `last_seen_at` is rewritten, at most.
+pub const LAST_SEEN_THROTTLE: time::Duration = time::Duration::hours(1);
+
 /// Resolve an authenticated user id from an id_token.
 ///
-/// This will also upsert the `users` record for the auth subject.
+/// This will also upsert the `users` record for the auth subject and refresh its
+/// `last_seen_at` (see [`LAST_SEEN_THROTTLE`]).
 pub async fn require_user_id(id_token: String) -> Result<Uuid, ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
@@ -391,36 +704,114 @@ pub async fn require_user_id(id_token: String) -> Result<Uuid, ServerFnError> {
             .map_err(|e| ServerFnError::new(format!("auth: {e:#}")))?;
         let user = server::ensure_user_for_subject(&sub).await?;
         tracing::debug!("auth.require_user_id: user_id={}", user.id);
+        crate::telemetry::record_user(user.id);
         Ok(user.id)
     }
 }
 
+/// The caller's id for endpoints that also serve anonymous readers.
+///
+/// A missing or invalid token reads as anonymous rather than failing the request.
+#[cfg(feature = "server")]
+pub(crate) async fn optional_user_id(id_token: Option<String>) -> Option<Uuid> {
+    require_user_id(id_token?).await.ok()
+}
+
+/// The caller's id for content reads (proposals, programs, comments, videos), which
+/// honour `public_read`.
+///
+/// With `public_read` on this is [`optional_user_id`]; with it off, a missing or
+/// invalid token is an `Unauthorized` error.
+#[cfg(feature = "server")]
+pub(crate) async fn reader_id(id_token: Option<String>) -> Result<Option<Uuid>, ServerFnError> {
+    if crate::state::AppState::current()?.config.public_read {
+        return Ok(optional_user_id(id_token).await);
+    }
+    let unauthorized = || ServerFnError::new(ErrorCode::Unauthorized.to_string());
+    let token = id_token
+        .filter(|t| !t.is_empty())
+        .ok_or_else(unauthorized)?;
+    match require_user_id(token).await {
+        Ok(user_id) => Ok(Some(user_id)),
+        Err(e) => {
+            tracing::debug!("auth.reader_id: rejected anonymous read: {}", e);
+            Err(unauthorized())
+        }
+    }
+}
+
+/// Resolve the caller like `require_user_id`, but only for users with `is_admin` set.
+pub async fn require_admin(id_token: String) -> Result<Uuid, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id_token;
+        Err(ServerFnError::new("require_admin is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    {
+        let user_id = require_user_id(id_token).await?;
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        let row = sqlx::query("select is_admin from users where id = $1")
+            .bind(crate::db::uuid_to_db(user_id))
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        // SQLite stores booleans as integers (0 = false, 1 = true)
+        let is_admin = row.is_some_and(|row| match row.try_get::<bool, _>("is_admin") {
+            Ok(v) => v,
+            Err(_) => row.get::<i64, _>("is_admin") != 0,
+        });
+        if !is_admin {
+            tracing::info!("auth.require_admin: denied user_id={}", user_id);
+            return Err(ServerFnError::new("forbidden"));
+        }
+        tracing::debug!("auth.require_admin: user_id={}", user_id);
+        Ok(user_id)
+    }
+}
+
 /// Sign up a new user with email and password
+///
+/// `lang` (a language code or `Accept-Language` value) localizes validation messages.
+/// `captcha_token` is required when `CAPTCHA_SECRET` is configured. At most
+/// `SIGNUP_RATE_LIMIT` attempts per email and per client address are allowed each hour.
 #[dioxus::prelude::post("/api/auth/signup")]
-pub async fn signup(email: String, password: String) -> Result<(), ServerFnError> {
+pub async fn signup(
+    email: String,
+    password: String,
+    lang: Option<String>,
+    captcha_token: Option<String>,
+) -> Result<(), ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = (email, password);
+        let _ = (email, password, lang, captcha_token);
         Err(ServerFnError::new("signup is server-only"))
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "auth.signup",
         // Get AppState
-        let state = crate::state::AppState::global();
-        tracing::info!(
-            "auth.request_password_reset: email={}",
-            server::email_label(&email)
-        );
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
         tracing::info!("auth.signup: email={}", server::email_label(&email));
+        if !server::allow_attempt(&state, "signup", &email, server::SIGNUP_RATE_LIMIT) {
+            tracing::info!("auth.signup: rate limited email={}", server::email_label(&email));
+            return Err(server::rejected(ErrorCode::TooManyAttempts, &lang));
+        }
 
         // Validate email format (basic check)
         if !email.contains('@') || email.len() < 3 {
-            return Err(ServerFnError::new("Invalid email address"));
+            return Err(server::rejected(ErrorCode::InvalidEmail, &lang));
         }
 
         // Validate password
-        server::validate_password(&password).map_err(|e| ServerFnError::new(e.to_string()))?;
+        server::validate_password(&password).map_err(|code| server::rejected(code, &lang))?;
+
+        crate::captcha::check(state.captcha.as_deref(), captcha_token.as_deref(), lang.as_deref())
+            .await?;
 
         // Get database pool from state
         let pool = state.db.pool().await;
@@ -434,7 +825,7 @@ pub async fn signup(email: String, password: String) -> Result<(), ServerFnError
 
         if existing.is_some() {
             tracing::info!("auth.signup: email already registered");
-            return Err(ServerFnError::new("Email already registered"));
+            return Err(server::rejected(ErrorCode::EmailTaken, &lang));
         }
 
         // Hash password
@@ -474,7 +865,8 @@ pub async fn signup(email: String, password: String) -> Result<(), ServerFnError
         let token_hash = crate::email::hash_token(&token);
 
         // Calculate expiration; use native timestamptz on Postgres, RFC3339 text on SQLite.
-        let expires_at = time::OffsetDateTime::now_utc() + time::Duration::hours(24);
+        let expires_at = time::OffsetDateTime::now_utc()
+            + time::Duration::hours(state.config.verify_token_ttl_hours.into());
         let expires_at_str = expires_at
             .format(&time::format_description::well_known::Rfc3339)
             .map_err(|e| ServerFnError::new(format!("Failed to format timestamp: {}", e)))?;
@@ -516,18 +908,19 @@ pub async fn signup(email: String, password: String) -> Result<(), ServerFnError
 
 /// Verify email address with token
 #[dioxus::prelude::post("/api/auth/verify-email")]
-pub async fn verify_email(token: String) -> Result<(), ServerFnError> {
+pub async fn verify_email(token: String, lang: Option<String>) -> Result<(), ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = token;
+        let _ = (token, lang);
         Err(ServerFnError::new("verify_email is server-only"))
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "auth.verify_email",
         tracing::info!("auth.verify_email: token_len={}", token.len());
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
         let token_hash = crate::email::hash_token(&token);
-        let state = crate::state::AppState::global();
         let pool = state.db.pool().await;
 
         // Look up verification token
@@ -540,7 +933,7 @@ pub async fn verify_email(token: String) -> Result<(), ServerFnError> {
         .map_err(|e| ServerFnError::new(e.to_string()))?;
 
         let verification = verification
-            .ok_or_else(|| ServerFnError::new("Verification link is invalid or has expired"))?;
+            .ok_or_else(|| server::rejected(ErrorCode::VerificationInvalid, &lang))?;
 
         let user_id = crate::db::uuid_from_db(&verification.get::<String, _>("user_id"))?;
         let expires_at = crate::db::datetime_from_db(&verification.get::<String, _>("expires_at"))?;
@@ -548,7 +941,7 @@ pub async fn verify_email(token: String) -> Result<(), ServerFnError> {
         // Check expiration
         if time::OffsetDateTime::now_utc() > expires_at {
             tracing::info!("auth.verify_email: token expired");
-            return Err(ServerFnError::new("Verification link has expired"));
+            return Err(server::rejected(ErrorCode::VerificationExpired, &lang));
         }
 
         // Mark email as verified
@@ -572,32 +965,37 @@ pub async fn verify_email(token: String) -> Result<(), ServerFnError> {
 
 /// Sign in with email and password
 #[dioxus::prelude::post("/api/auth/signin")]
-pub async fn signin(email: String, password: String) -> Result<String, ServerFnError> {
+pub async fn signin(
+    email: String,
+    password: String,
+    lang: Option<String>,
+) -> Result<String, ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = (email, password);
+        let _ = (email, password, lang);
         Err(ServerFnError::new("signin is server-only"))
     }
 
     #[cfg(feature = "server")]
-    {
-        let state = crate::state::AppState::global();
+    crate::telemetry::traced! { "auth.signin",
+        let state = crate::state::AppState::current()?;
         let pool = state.db.pool().await;
         tracing::info!("auth.signin: email={}", server::email_label(&email));
 
         // Look up user by email
         let user = sqlx::query(
-            "select CAST(id as TEXT) as id, password_hash, email_verified from users where email = $1",
+            "select CAST(id as TEXT) as id, password_hash, email_verified, token_version from users where email = $1",
         )
         .bind(&email)
         .fetch_optional(pool)
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
 
-        let user = user.ok_or_else(|| ServerFnError::new("Invalid email or password"))?;
+        let user = user.ok_or_else(|| server::rejected(ErrorCode::InvalidCredentials, &lang))?;
 
         let user_id = crate::db::uuid_from_db(&user.get::<String, _>("id"))?;
         let password_hash: Option<String> = user.get("password_hash");
+        let token_version: i64 = user.get("token_version");
         // SQLite stores booleans as integers (0 = false, 1 = true)
         let email_verified: bool = match user.try_get::<bool, _>("email_verified") {
             Ok(v) => v,
@@ -609,9 +1007,8 @@ pub async fn signin(email: String, password: String) -> Result<String, ServerFnE
         };
 
         // Check if user has password (not OAuth-only)
-        let password_hash = password_hash.ok_or_else(|| {
-            ServerFnError::new("This account uses OAuth. Please sign in with your provider.")
-        })?;
+        let password_hash =
+            password_hash.ok_or_else(|| server::rejected(ErrorCode::OauthAccount, &lang))?;
 
         // Verify password
         use argon2::{Argon2, PasswordHash, PasswordVerifier};
@@ -621,18 +1018,16 @@ pub async fn signin(email: String, password: String) -> Result<String, ServerFnE
 
         Argon2::default()
             .verify_password(password.as_bytes(), &parsed_hash)
-            .map_err(|_| ServerFnError::new("Invalid email or password"))?;
+            .map_err(|_| server::rejected(ErrorCode::InvalidCredentials, &lang))?;
 
         // Check email verified
         if !email_verified {
             tracing::info!("auth.signin: email not verified");
-            return Err(ServerFnError::new(
-                "Please verify your email before signing in",
-            ));
+            return Err(server::rejected(ErrorCode::EmailNotVerified, &lang));
         }
 
         // Generate JWT
-        let token = server::generate_local_jwt(user_id)
+        let token = server::generate_local_jwt(user_id, token_version)
             .map_err(|e| ServerFnError::new(format!("Failed to generate token: {}", e)))?;
 
         tracing::info!("auth.signin: success user_id={}", user_id);
@@ -652,8 +1047,9 @@ pub async fn resend_verification_email(email: String) -> Result<(), ServerFnErro
     }
 
     #[cfg(feature = "server")]
-    {
-        let state = crate::state::AppState::global();
+    crate::telemetry::traced! { "auth.resend_verification_email",
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
         let pool = state.db.pool().await;
         tracing::info!(
             "auth.resend_verification_email: email={}",
@@ -679,10 +1075,21 @@ pub async fn resend_verification_email(email: String) -> Result<(), ServerFnErro
                 }
             };
 
-            if !email_verified && password_hash.is_some() {
+            if !email_verified
+                && password_hash.is_some()
+                && server::email_sent_recently(pool, "email_verifications", &user_id.to_string())
+                    .await?
+            {
+                // Still report success so callers can't probe account state.
+                tracing::info!(
+                    "auth.resend_verification_email: cooldown user_id={}",
+                    user_id
+                );
+            } else if !email_verified && password_hash.is_some() {
                 let token = crate::email::generate_token();
                 let token_hash = crate::email::hash_token(&token);
-                let expires_at = time::OffsetDateTime::now_utc() + time::Duration::hours(24);
+                let expires_at = time::OffsetDateTime::now_utc()
+                    + time::Duration::hours(state.config.verify_token_ttl_hours.into());
                 let expires_at_str = expires_at
                     .format(&time::format_description::well_known::Rfc3339)
                     .map_err(|e| {
@@ -725,18 +1132,38 @@ pub async fn resend_verification_email(email: String) -> Result<(), ServerFnErro
 }
 
 /// Request password reset (always returns success for security)
+///
+/// `lang` (a language code or `Accept-Language` value) localizes refusals.
+/// `captcha_token` is required when `CAPTCHA_SECRET` is configured. After
+/// `RESET_RATE_LIMIT` requests in an hour for one email or client address, further
+/// requests fail with `TooManyAttempts`, whether or not the email is registered.
 #[dioxus::prelude::post("/api/auth/request-password-reset")]
-pub async fn request_password_reset(email: String) -> Result<(), ServerFnError> {
+pub async fn request_password_reset(
+    email: String,
+    lang: Option<String>,
+    captcha_token: Option<String>,
+) -> Result<(), ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = email;
+        let _ = (email, lang, captcha_token);
         Err(ServerFnError::new("request_password_reset is server-only"))
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "auth.request_password_reset",
         // Get AppState
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        // Checked before the lookup so the reply is the same for unknown addresses.
+        if !server::allow_attempt(&state, "password_reset", &email, server::RESET_RATE_LIMIT) {
+            tracing::info!(
+                "auth.request_password_reset: rate limited email={}",
+                server::email_label(&email)
+            );
+            return Err(server::rejected(ErrorCode::TooManyAttempts, &lang));
+        }
+        crate::captcha::check(state.captcha.as_deref(), captcha_token.as_deref(), lang.as_deref())
+            .await?;
 
         // Get database pool from state
         let pool = state.db.pool().await;
@@ -761,12 +1188,20 @@ pub async fn request_password_reset(email: String) -> Result<(), ServerFnError>
             let password_hash: Option<String> = user.get("password_hash");
 
             // Only send if user has a password (not OAuth-only)
-            if password_hash.is_some() {
+            if password_hash.is_some()
+                && server::email_sent_recently(pool, "password_resets", &user_id_str).await?
+            {
+                tracing::info!(
+                    "auth.request_password_reset: cooldown user_id={}",
+                    user_id_str
+                );
+            } else if password_hash.is_some() {
                 // Generate reset token
                 let token = crate::email::generate_token();
                 let token_hash = crate::email::hash_token(&token);
 
-                let expires_at = time::OffsetDateTime::now_utc() + time::Duration::hours(1);
+                let expires_at = time::OffsetDateTime::now_utc()
+                    + time::Duration::minutes(state.config.reset_token_ttl_minutes.into());
                 let expires_at_str = expires_at
                     .format(&time::format_description::well_known::Rfc3339)
                     .map_err(|e| {
@@ -806,13 +1241,19 @@ pub async fn request_password_reset(email: String) -> Result<(), ServerFnError>
                     .await
                     {
                         tracing::warn!("auth.request_password_reset: send email failed: {}", e);
+                    } else {
+                        tracing::info!(
+                            "auth.request_password_reset: dispatched user_id={}",
+                            user_id_str
+                        );
                     }
                 }
+            } else {
+                tracing::debug!(
+                    "auth.request_password_reset: no password user_id={}",
+                    user_id_str
+                );
             }
-            tracing::info!(
-                "auth.request_password_reset: dispatched user_id={}",
-                user_id_str
-            );
         } else {
             tracing::debug!("auth.request_password_reset: user not found");
         }
@@ -824,21 +1265,26 @@ pub async fn request_password_reset(email: String) -> Result<(), ServerFnError>
 
 /// Reset password with token
 #[dioxus::prelude::post("/api/auth/reset-password")]
-pub async fn reset_password(token: String, new_password: String) -> Result<(), ServerFnError> {
+pub async fn reset_password(
+    token: String,
+    new_password: String,
+    lang: Option<String>,
+) -> Result<(), ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = (token, new_password);
+        let _ = (token, new_password, lang);
         Err(ServerFnError::new("reset_password is server-only"))
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "auth.reset_password",
         tracing::info!("auth.reset_password: token_len={}", token.len());
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
         // Validate new password
-        server::validate_password(&new_password).map_err(|e| ServerFnError::new(e.to_string()))?;
+        server::validate_password(&new_password).map_err(|code| server::rejected(code, &lang))?;
 
         let token_hash = crate::email::hash_token(&token);
-        let state = crate::state::AppState::global();
         let pool = state.db.pool().await;
 
         // Look up reset token
@@ -851,7 +1297,7 @@ pub async fn reset_password(token: String, new_password: String) -> Result<(), S
         .map_err(|e| ServerFnError::new(e.to_string()))?;
 
         let reset =
-            reset.ok_or_else(|| ServerFnError::new("Reset link is invalid or has expired"))?;
+            reset.ok_or_else(|| server::rejected(ErrorCode::ResetInvalid, &lang))?;
 
         let user_id = crate::db::uuid_from_db(&reset.get::<String, _>("user_id"))?;
         let expires_at = crate::db::datetime_from_db(&reset.get::<String, _>("expires_at"))?;
@@ -859,7 +1305,7 @@ pub async fn reset_password(token: String, new_password: String) -> Result<(), S
         // Check expiration
         if time::OffsetDateTime::now_utc() > expires_at {
             tracing::info!("auth.reset_password: token expired");
-            return Err(ServerFnError::new("Reset link has expired"));
+            return Err(server::rejected(ErrorCode::ResetExpired, &lang));
         }
 
         // Hash new password
@@ -873,13 +1319,15 @@ pub async fn reset_password(token: String, new_password: String) -> Result<(), S
             .map_err(|e| ServerFnError::new(format!("Password hashing failed: {}", e)))?
             .to_string();
 
-        // Update password
-        sqlx::query("update users set password_hash = $1 where id = $2")
-            .bind(&password_hash)
-            .bind(crate::db::uuid_to_db(user_id))
-            .execute(pool)
-            .await
-            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        // Update password and revoke previously issued sessions
+        sqlx::query(
+            "update users set password_hash = $1, token_version = token_version + 1 where id = $2",
+        )
+        .bind(&password_hash)
+        .bind(crate::db::uuid_to_db(user_id))
+        .execute(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
 
         // Delete reset token
         sqlx::query("delete from password_resets where token_hash = $1")
@@ -0,0 +1,137 @@
+use crate::types::Post;
+use dioxus::prelude::*;
+#[cfg(feature = "server")]
+use tracing::{debug, info};
+
+#[cfg(feature = "server")]
+const POST_COLUMNS: &str = r#"
+    id,
+    CAST(author_user_id as TEXT) as author_user_id,
+    title,
+    body_markdown,
+    CAST(created_at as TEXT) as created_at,
+    CAST(updated_at as TEXT) as updated_at
+"#;
+
+#[cfg(feature = "server")]
+fn post_from_row(row: &sqlx::any::AnyRow) -> Result<Post, ServerFnError> {
+    use sqlx::Row;
+
+    Ok(Post {
+        id: row.get("id"),
+        author_user_id: crate::db::uuid_from_db(&row.get::<String, _>("author_user_id"))?,
+        title: row.get("title"),
+        body_markdown: row.get("body_markdown"),
+        created_at: crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?,
+        updated_at: crate::db::datetime_from_db(&row.get::<String, _>("updated_at"))?,
+    })
+}
+
+/// Publish a blog post (admin only).
+#[dioxus::prelude::post("/api/blog/create")]
+pub async fn create_post(
+    id_token: String,
+    title: String,
+    body_markdown: String,
+) -> Result<Post, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, title, body_markdown);
+        Err(ServerFnError::new("create_post is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "blog.create_post",
+        crate::state::AppState::current()?.ensure_writable()?;
+
+        let author_user_id = crate::auth::require_admin(id_token).await?;
+        let title = title.trim().to_string();
+        if title.is_empty() {
+            return Err(ServerFnError::new("title is empty"));
+        }
+        info!(
+            "blog.create_post: author_user_id={} title_len={} body_len={}",
+            author_user_id,
+            title.len(),
+            body_markdown.len()
+        );
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        let row = sqlx::query(&format!(
+            r#"
+            insert into posts (author_user_id, title, body_markdown)
+            values ($1, $2, $3)
+            returning {POST_COLUMNS}
+            "#
+        ))
+        .bind(crate::db::uuid_to_db(author_user_id))
+        .bind(&title)
+        .bind(&body_markdown)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let post = post_from_row(&row)?;
+        info!("blog.create_post: id={}", post.id);
+        Ok(post)
+    }
+}
+
+#[dioxus::prelude::get("/api/blog/:id")]
+pub async fn get_post(id: i64) -> Result<Post, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Err(ServerFnError::new("get_post is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "blog.get_post",
+        debug!("blog.get_post: id={}", id);
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        let row = sqlx::query(&format!("select {POST_COLUMNS} from posts where id = $1"))
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?
+            .ok_or_else(|| ServerFnError::new(crate::types::ErrorCode::NotFound.to_string()))?;
+        post_from_row(&row)
+    }
+}
+
+/// Blog posts, newest first.
+#[dioxus::prelude::get("/api/blog?limit&offset")]
+pub async fn list_posts(limit: i64, offset: i64) -> Result<Vec<Post>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (limit, offset);
+        Err(ServerFnError::new("list_posts is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "blog.list_posts",
+        debug!("blog.list_posts: limit={} offset={}", limit, offset);
+        let state = crate::state::AppState::current()?;
+        let (limit, offset) = state.page(limit, offset)?;
+        let pool = state.db.pool().await;
+
+        let rows = sqlx::query(&format!(
+            r#"
+            select {POST_COLUMNS}
+            from posts
+            order by created_at desc, id desc
+            limit $1 offset $2
+            "#
+        ))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        rows.iter().map(post_from_row).collect()
+    }
+}
@@ -0,0 +1,152 @@
+//! Request body size limits for the server router.
+//!
+//! Server fns buffer and deserialize the whole body before they run, so an
+//! oversized body costs memory before any validation. [`apply`] rejects bodies over
+//! `max_request_body_bytes` with `413 Payload Too Large`, except on upload chunk
+//! routes, which only get the [`MAX_UPLOAD_BODY_BYTES`] ceiling.
+
+use crate::types::ErrorCode;
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use tower_http::limit::RequestBodyLimitLayer;
+
+/// Routes carrying binary upload chunks rather than text.
+const UPLOAD_ROUTES: [&str; 1] = ["/api/uploads/multipart/part"];
+
+/// Ceiling for every body, upload chunks included: a `MAX_PART_BYTES` chunk sent as
+/// a JSON array of numbers (up to four bytes per byte), plus the other arguments.
+pub const MAX_UPLOAD_BODY_BYTES: usize = 4 * crate::uploads::MAX_PART_BYTES + 64 * 1024;
+
+/// Limit request bodies on `router` to `max_body_bytes`, except upload chunks.
+pub fn apply(router: Router, max_body_bytes: usize) -> Router {
+    router
+        .layer(axum::middleware::from_fn_with_state(
+            max_body_bytes,
+            limit_text_body,
+        ))
+        .layer(RequestBodyLimitLayer::new(MAX_UPLOAD_BODY_BYTES))
+}
+
+/// Refuse a declared `Content-Length` over the limit outright, and cap the
+/// body stream for requests that don't declare one.
+async fn limit_text_body(State(limit): State<usize>, req: Request, next: Next) -> Response {
+    if UPLOAD_ROUTES.contains(&req.uri().path()) {
+        return next.run(req).await;
+    }
+    let declared = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+    if declared.is_some_and(|len| len > limit as u64) {
+        let lang = req
+            .headers()
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|v| v.to_str().ok());
+        tracing::debug!(
+            "body_limit: rejected path={} content_length={:?}",
+            req.uri().path(),
+            declared
+        );
+        return (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            ErrorCode::PayloadTooLarge.error_text(lang),
+        )
+            .into_response();
+    }
+    next.run(req.map(|body| Body::new(http_body_util::Limited::new(body, limit))))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::post;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    const LIMIT: usize = 1024;
+
+    /// Router with text and upload routes that record whether they ran.
+    fn router(ran: Arc<AtomicBool>) -> Router {
+        let handler = move |body: axum::body::Bytes| {
+            let ran = ran.clone();
+            async move {
+                ran.store(true, Ordering::SeqCst);
+                body.len().to_string()
+            }
+        };
+        apply(
+            Router::new()
+                .route("/api/comments/create", post(handler.clone()))
+                .route(UPLOAD_ROUTES[0], post(handler)),
+            LIMIT,
+        )
+    }
+
+    fn request(path: &str, body: Vec<u8>, content_length: bool) -> Request {
+        let mut builder = Request::post(path).header(header::ACCEPT_LANGUAGE, "fr");
+        if content_length {
+            builder = builder.header(header::CONTENT_LENGTH, body.len());
+        }
+        builder.body(Body::from(body)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_over_limit_body_is_rejected_before_the_handler() {
+        let ran = Arc::new(AtomicBool::new(false));
+        let res = router(ran.clone())
+            .oneshot(request("/api/comments/create", vec![b'x'; LIMIT + 1], true))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(!ran.load(Ordering::SeqCst));
+
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            ErrorCode::from_error(&String::from_utf8_lossy(&body)),
+            Some(ErrorCode::PayloadTooLarge)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_undeclared_over_limit_body_is_rejected_before_the_handler() {
+        let ran = Arc::new(AtomicBool::new(false));
+        let res = router(ran.clone())
+            .oneshot(request(
+                "/api/comments/create",
+                vec![b'x'; LIMIT + 1],
+                false,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(!ran.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_bodies_within_the_limit_and_upload_chunks_pass() {
+        let ran = Arc::new(AtomicBool::new(false));
+        let res = router(ran.clone())
+            .oneshot(request("/api/comments/create", vec![b'x'; LIMIT], true))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(ran.load(Ordering::SeqCst));
+
+        let ran = Arc::new(AtomicBool::new(false));
+        let res = router(ran.clone())
+            .oneshot(request(UPLOAD_ROUTES[0], vec![b'x'; LIMIT * 4], true))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(ran.load(Ordering::SeqCst));
+    }
+}
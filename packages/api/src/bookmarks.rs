@@ -0,0 +1,189 @@
+use crate::types::{BookmarkedItem, ContentTargetType};
+use dioxus::prelude::*;
+#[cfg(feature = "server")]
+use tracing::{debug, info};
+
+/// Bookmark or un-bookmark any content; returns whether it is now bookmarked.
+#[dioxus::prelude::post("/api/bookmarks/toggle")]
+pub async fn toggle_bookmark(
+    id_token: String,
+    target_type: ContentTargetType,
+    target_id: String,
+) -> Result<bool, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, target_type, target_id);
+        Err(ServerFnError::new("toggle_bookmark is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "bookmarks.toggle_bookmark",
+        use uuid::Uuid;
+
+        crate::state::AppState::current()?.ensure_writable()?;
+
+        debug!(
+            "bookmarks.toggle_bookmark: target_type={:?} target_id={}",
+            target_type, target_id
+        );
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let tid =
+            Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        toggle(pool, user_id, target_type, tid).await
+    }
+}
+
+/// Flip the caller's bookmark on a target and keep the activity feed in step.
+#[cfg(feature = "server")]
+pub(crate) async fn toggle(
+    pool: &sqlx::Pool<sqlx::Any>,
+    user_id: uuid::Uuid,
+    target_type: ContentTargetType,
+    target_id: uuid::Uuid,
+) -> Result<bool, ServerFnError> {
+    if !crate::db::target_exists(pool, target_type, target_id).await? {
+        return Err(ServerFnError::new("target not found"));
+    }
+
+    let removed = sqlx::query(
+        "delete from bookmarks where user_id = $1 and target_type = $2 and target_id = $3",
+    )
+    .bind(crate::db::uuid_to_db(user_id))
+    .bind(target_type.as_db())
+    .bind(crate::db::uuid_to_db(target_id))
+    .execute(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?
+    .rows_affected()
+        > 0;
+
+    if removed {
+        // Activity log (best-effort): an un-bookmark leaves nothing in the feed.
+        let _ = sqlx::query(
+            "delete from activity where user_id = $1 and action = 'bookmarked' and target_type = $2 and target_id = $3",
+        )
+        .bind(crate::db::uuid_to_db(user_id))
+        .bind(target_type.as_db())
+        .bind(crate::db::uuid_to_db(target_id))
+        .execute(pool)
+        .await;
+        info!(
+            "bookmarks.toggle: removed user_id={} target_type={:?} target_id={}",
+            user_id, target_type, target_id
+        );
+        return Ok(false);
+    }
+
+    // Stamped explicitly so SQLite orders bookmarks made within the same second.
+    sqlx::query(&format!(
+        "insert into bookmarks (user_id, target_type, target_id, created_at) values ($1, $2, $3, {})",
+        crate::db::now_sql()
+    ))
+    .bind(crate::db::uuid_to_db(user_id))
+    .bind(target_type.as_db())
+    .bind(crate::db::uuid_to_db(target_id))
+    .execute(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let _ = sqlx::query(
+        "insert into activity (user_id, action, target_type, target_id) values ($1, 'bookmarked', $2, $3)",
+    )
+    .bind(crate::db::uuid_to_db(user_id))
+    .bind(target_type.as_db())
+    .bind(crate::db::uuid_to_db(target_id))
+    .execute(pool)
+    .await;
+    info!(
+        "bookmarks.toggle: added user_id={} target_type={:?} target_id={}",
+        user_id, target_type, target_id
+    );
+    Ok(true)
+}
+
+/// The caller's bookmarks, most recent first, optionally of one kind only.
+#[dioxus::prelude::post("/api/bookmarks/list")]
+pub async fn list_bookmarks(
+    id_token: String,
+    target_type: Option<ContentTargetType>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<BookmarkedItem>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, target_type, limit, offset);
+        Err(ServerFnError::new("list_bookmarks is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "bookmarks.list_bookmarks",
+        use sqlx::Row;
+
+        debug!(
+            "bookmarks.list_bookmarks: target_type={:?} limit={} offset={}",
+            target_type, limit, offset
+        );
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let state = crate::state::AppState::current()?;
+        let (limit, offset) = state.page(limit, offset)?;
+        let pool = state.db.pool().await;
+
+        let title_expr = if crate::db::is_sqlite() {
+            "substr(body_markdown, 1, 80)"
+        } else {
+            "left(body_markdown, 80)"
+        };
+        let type_filter = if target_type.is_some() {
+            "and b.target_type = $4"
+        } else {
+            ""
+        };
+        let sql = format!(
+            r#"
+            select
+                CAST(b.target_type as TEXT) as target_type,
+                CAST(b.target_id as TEXT) as target_id,
+                CAST(b.created_at as TEXT) as bookmarked_at,
+                case
+                    when b.target_type = 'proposal' then (select title from proposals where id = b.target_id and hidden_at is null)
+                    when b.target_type = 'program' then (select title from programs where id = b.target_id and hidden_at is null)
+                    when b.target_type = 'comment' then (select {title_expr} from comments where id = b.target_id and hidden_at is null)
+                    when b.target_type = 'video' then (select storage_key from videos where id = b.target_id and hidden_at is null)
+                    else null
+                end as title
+            from bookmarks b
+            where b.user_id = $1 {type_filter}
+            order by b.created_at desc, b.id desc
+            limit $2 offset $3
+            "#
+        );
+        let mut query = sqlx::query(&sql)
+            .bind(crate::db::uuid_to_db(user_id))
+            .bind(limit)
+            .bind(offset);
+        if let Some(t) = target_type {
+            query = query.bind(t.as_db());
+        }
+        let rows = query
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let mut items = Vec::with_capacity(rows.len());
+        for row in rows {
+            items.push(BookmarkedItem {
+                target_type: ContentTargetType::from_db(&row.get::<String, _>("target_type"))
+                    .ok_or_else(|| ServerFnError::new("invalid target_type"))?,
+                target_id: crate::db::uuid_from_db(&row.get::<String, _>("target_id"))?,
+                bookmarked_at: crate::db::datetime_from_db(&row.get::<String, _>("bookmarked_at"))?,
+                title: row.get("title"),
+            });
+        }
+
+        debug!("bookmarks.list_bookmarks: count={}", items.len());
+        Ok(items)
+    }
+}
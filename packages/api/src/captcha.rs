@@ -0,0 +1,116 @@
+//! CAPTCHA checks on signup and password reset requests.
+//!
+//! The client solves the provider's widget (Turnstile or hCaptcha) and sends the
+//! resulting token along. With `CAPTCHA_SECRET` set, `AppState::captcha` verifies it
+//! with the provider; without it there is no verifier and the check is skipped.
+
+use crate::config::{AppConfig, CaptchaProvider};
+use crate::types::ErrorCode;
+use anyhow::Result;
+use async_trait::async_trait;
+use dioxus::prelude::ServerFnError;
+
+/// Trait for CAPTCHA verifier implementations
+#[async_trait]
+pub trait CaptchaVerifier: Send + Sync {
+    /// Whether the provider accepts `token`; `Err` means it couldn't be asked.
+    async fn verify(&self, token: &str) -> Result<bool>;
+}
+
+/// Verifier calling the provider's `siteverify` endpoint.
+pub struct SiteverifyCaptcha {
+    provider: CaptchaProvider,
+    secret: String,
+    client: reqwest::Client,
+}
+
+impl SiteverifyCaptcha {
+    pub fn new(provider: CaptchaProvider, secret: String) -> Self {
+        Self {
+            provider,
+            secret,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// A verifier for the configured provider, or `None` when `CAPTCHA_SECRET` is unset.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        let secret = config.captcha_secret.clone()?;
+        Some(Self::new(config.captcha_provider, secret))
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct SiteverifyResponse {
+    success: bool,
+}
+
+#[async_trait]
+impl CaptchaVerifier for SiteverifyCaptcha {
+    async fn verify(&self, token: &str) -> Result<bool> {
+        let res: SiteverifyResponse = self
+            .client
+            .post(self.provider.verify_url())
+            .form(&[("secret", self.secret.as_str()), ("response", token)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(res.success)
+    }
+}
+
+/// Require a valid `token` when a verifier is configured.
+///
+/// A missing or refused token is a `CaptchaFailed` error in `lang`; a provider
+/// outage fails closed with a generic error.
+pub(crate) async fn check(
+    verifier: Option<&dyn CaptchaVerifier>,
+    token: Option<&str>,
+    lang: Option<&str>,
+) -> Result<(), ServerFnError> {
+    let Some(verifier) = verifier else {
+        return Ok(());
+    };
+    let rejected = || ServerFnError::new(ErrorCode::CaptchaFailed.error_text(lang));
+    let token = token.map(str::trim).filter(|t| !t.is_empty());
+    let Some(token) = token else {
+        return Err(rejected());
+    };
+    match verifier.verify(token).await {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            tracing::info!("captcha.check: token refused");
+            Err(rejected())
+        }
+        Err(e) => {
+            tracing::warn!("captcha.check: verification failed: {e:#}");
+            Err(ServerFnError::new("captcha verification unavailable"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifier whose provider is down.
+    struct Unreachable;
+
+    #[async_trait]
+    impl CaptchaVerifier for Unreachable {
+        async fn verify(&self, _token: &str) -> Result<bool> {
+            Err(anyhow::anyhow!("connection refused"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_provider_outage_fails_closed() {
+        let err = check(Some(&Unreachable), Some("token"), None)
+            .await
+            .unwrap_err();
+        assert_eq!(ErrorCode::from_error(&err.to_string()), None);
+        assert!(check(None, None, None).await.is_ok());
+    }
+}
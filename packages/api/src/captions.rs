@@ -0,0 +1,298 @@
+//! WebVTT captions for videos.
+//!
+//! Caption files are uploaded like videos: `create_caption_intent` presigns a PUT
+//! under the video's storage prefix, then `finalize_caption` records the track.
+//! A video has at most one track per language; finalizing again replaces it.
+
+use crate::types::{CaptionTrack, UploadIntent};
+use dioxus::prelude::*;
+#[cfg(feature = "server")]
+use tracing::{debug, info};
+
+/// Content type caption files are uploaded with.
+#[cfg(feature = "server")]
+const CAPTION_CONTENT_TYPE: &str = "text/vtt";
+
+/// Largest accepted caption file, in bytes.
+#[cfg(feature = "server")]
+const MAX_CAPTION_BYTES: i64 = 1024 * 1024;
+
+/// Whether `tag` looks like a BCP 47 language tag (`fr`, `en-GB`, `zh-Hant`).
+///
+/// Only the shape is checked: a 2–3 letter primary language followed by
+/// alphanumeric subtags of 1–8 characters.
+#[cfg(feature = "server")]
+fn valid_language(tag: &str) -> bool {
+    let mut subtags = tag.split('-');
+    let primary = subtags.next().unwrap_or_default();
+    (2..=3).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags
+            .all(|s| (1..=8).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// Where caption files for `video_id` live, under the video's own storage prefix.
+#[cfg(feature = "server")]
+fn caption_key_prefix(
+    target_type: crate::types::ContentTargetType,
+    target_id: uuid::Uuid,
+    video_id: uuid::Uuid,
+) -> String {
+    format!(
+        "{}captions/{}/",
+        crate::uploads::storage_key_prefix(target_type, target_id),
+        video_id
+    )
+}
+
+/// Storage prefix for captions of a visible video owned by `user_id`.
+#[cfg(feature = "server")]
+async fn owned_video_prefix(
+    pool: &sqlx::Pool<sqlx::Any>,
+    user_id: uuid::Uuid,
+    video_id: uuid::Uuid,
+) -> Result<String, ServerFnError> {
+    use sqlx::Row;
+
+    let row = sqlx::query(
+        "select CAST(owner_user_id as TEXT) as owner_user_id, CAST(target_type as TEXT) as target_type, CAST(target_id as TEXT) as target_id from videos where id = $1 and hidden_at is null",
+    )
+    .bind(crate::db::uuid_to_db(video_id))
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?
+    .ok_or_else(|| ServerFnError::new(crate::types::ErrorCode::NotFound.to_string()))?;
+    if crate::db::uuid_from_db(&row.get::<String, _>("owner_user_id"))? != user_id {
+        return Err(ServerFnError::new("forbidden"));
+    }
+    let target_type =
+        crate::types::ContentTargetType::from_db(&row.get::<String, _>("target_type"))
+            .ok_or_else(|| ServerFnError::new("invalid target_type"))?;
+    let target_id = crate::db::uuid_from_db(&row.get::<String, _>("target_id"))?;
+    Ok(caption_key_prefix(target_type, target_id, video_id))
+}
+
+/// Presign an upload for a `language` caption file on one of your videos.
+#[dioxus::prelude::post("/api/captions/intent")]
+pub async fn create_caption_intent(
+    id_token: String,
+    video_id: String,
+    language: String,
+) -> Result<UploadIntent, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, video_id, language);
+        Err(ServerFnError::new("create_caption_intent is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "captions.create_caption_intent",
+        use aws_sdk_s3::types::ObjectCannedAcl;
+
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        if !valid_language(&language) {
+            return Err(ServerFnError::new("invalid language"));
+        }
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let vid = uuid::Uuid::parse_str(&video_id)
+            .map_err(|_| ServerFnError::new("invalid video_id"))?;
+        let pool = state.db.pool().await;
+        let prefix = owned_video_prefix(pool, user_id, vid).await?;
+
+        let key = format!("{prefix}{language}-{}.vtt", uuid::Uuid::new_v4());
+        debug!("captions.create_caption_intent: storage_key={}", key);
+
+        let (client, bucket) = crate::uploads::s3_client().await?;
+        let presigned = client
+            .put_object()
+            .bucket(&bucket)
+            .key(&key)
+            .content_type(CAPTION_CONTENT_TYPE)
+            .acl(ObjectCannedAcl::Private)
+            .presigned(crate::uploads::presigning_config()?)
+            .await
+            .map_err(|e| ServerFnError::new(format!("presign error: {e}")))?;
+        let mut headers: std::collections::BTreeMap<String, String> = presigned
+            .headers()
+            .map(|(name, value)| (name.to_ascii_lowercase(), value.to_string()))
+            .collect();
+        headers
+            .entry("content-type".to_string())
+            .or_insert_with(|| CAPTION_CONTENT_TYPE.to_string());
+
+        crate::uploads::record_upload_intent(pool, user_id, &key).await?;
+
+        info!(
+            "captions.create_caption_intent: video_id={} language={}",
+            vid, language
+        );
+        Ok(UploadIntent {
+            presigned_put_url: presigned.uri().to_string(),
+            storage_key: key,
+            bucket,
+            headers,
+        })
+    }
+}
+
+/// Attach an uploaded caption file to a video, replacing any track in `language`.
+#[dioxus::prelude::post("/api/captions/finalize")]
+pub async fn finalize_caption(
+    id_token: String,
+    video_id: String,
+    language: String,
+    storage_key: String,
+) -> Result<CaptionTrack, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, video_id, language, storage_key);
+        Err(ServerFnError::new("finalize_caption is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "captions.finalize_caption",
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        if !valid_language(&language) {
+            return Err(ServerFnError::new("invalid language"));
+        }
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let vid = uuid::Uuid::parse_str(&video_id)
+            .map_err(|_| ServerFnError::new("invalid video_id"))?;
+        let pool = state.db.pool().await;
+        let prefix = owned_video_prefix(pool, user_id, vid).await?;
+        if !storage_key.starts_with(&format!("{prefix}{language}-")) {
+            return Err(ServerFnError::new("invalid storage key"));
+        }
+        crate::uploads::ensure_upload_intent(pool, user_id, &storage_key).await?;
+
+        if crate::uploads::filesystem_storage() {
+            let found = state
+                .storage
+                .exists(&storage_key)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?;
+            if !found {
+                return Err(ServerFnError::new("upload not found"));
+            }
+        } else {
+            let (client, bucket) = crate::uploads::s3_client().await?;
+            let head = client
+                .head_object()
+                .bucket(&bucket)
+                .key(&storage_key)
+                .send()
+                .await
+                .map_err(|e| {
+                    if e.as_service_error().is_some_and(|se| se.is_not_found()) {
+                        ServerFnError::new("upload not found")
+                    } else {
+                        ServerFnError::new(format!("head_object failed: {e}"))
+                    }
+                })?;
+            if head.content_length().is_some_and(|len| len > MAX_CAPTION_BYTES) {
+                return Err(ServerFnError::new("invalid file size"));
+            }
+        }
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        crate::uploads::consume_upload_intent(&mut *tx, user_id, &storage_key).await?;
+        sqlx::query(
+            r#"
+            insert into captions (video_id, language, storage_key)
+            values ($1, $2, $3)
+            on conflict (video_id, language) do update set storage_key = excluded.storage_key
+            "#,
+        )
+        .bind(crate::db::uuid_to_db(vid))
+        .bind(&language)
+        .bind(&storage_key)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        tx.commit()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        info!(
+            "captions.finalize_caption: video_id={} language={}",
+            vid, language
+        );
+        Ok(CaptionTrack {
+            language,
+            storage_key,
+        })
+    }
+}
+
+/// Fill in `captions` for a page of listed videos.
+#[cfg(feature = "server")]
+pub(crate) async fn attach<'a>(
+    pool: &sqlx::Pool<sqlx::Any>,
+    videos: impl IntoIterator<Item = &'a mut crate::types::Video>,
+) -> Result<(), ServerFnError> {
+    use sqlx::Row;
+
+    let mut videos: Vec<_> = videos.into_iter().collect();
+    if videos.is_empty() {
+        return Ok(());
+    }
+    let placeholders = (1..=videos.len())
+        .map(|n| format!("${n}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(
+        "select CAST(video_id as TEXT) as video_id, language, storage_key \
+         from captions where video_id in ({placeholders}) order by language"
+    );
+    let mut query = sqlx::query(&sql);
+    for video in &videos {
+        query = query.bind(crate::db::uuid_to_db(video.id));
+    }
+    let rows = query
+        .fetch_all(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    for row in rows {
+        let video_id = crate::db::uuid_from_db(&row.get::<String, _>("video_id"))?;
+        if let Some(video) = videos.iter_mut().find(|v| v.id == video_id) {
+            video.captions.push(CaptionTrack {
+                language: row.get("language"),
+                storage_key: row.get("storage_key"),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_language_accepts_bcp47_shapes() {
+        for tag in ["fr", "en", "en-GB", "zh-Hant", "es-419", "fil"] {
+            assert!(valid_language(tag), "{tag}");
+        }
+    }
+
+    #[test]
+    fn test_valid_language_rejects_paths_and_junk() {
+        for tag in [
+            "",
+            "f",
+            "french",
+            "en_GB",
+            "en-",
+            "../fr",
+            "fr/x",
+            "en-toolongtag",
+        ] {
+            assert!(!valid_language(tag), "{tag}");
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Client address of the request being served, for per-IP throttling in server fns.
+//!
+//! Server fns only see their arguments, so [`apply`] records the address in a
+//! task-local around each request and [`current`] reads it back. The address is the
+//! socket peer, which [`serve`] exposes; with `TRUST_PROXY` set it is instead the
+//! last `X-Forwarded-For` entry, the one the reverse proxy itself appended.
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use axum::Router;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+tokio::task_local! {
+    static CLIENT_IP: IpAddr;
+}
+
+/// Make the client address of every request on `router` available to [`current`].
+///
+/// `trust_proxy` reads `X-Forwarded-For`; only set it when a proxy overwrites or
+/// appends that header, or clients can pick their own address.
+pub fn apply(router: Router, trust_proxy: bool) -> Router {
+    router.layer(axum::middleware::from_fn_with_state(
+        trust_proxy,
+        scope_client_ip,
+    ))
+}
+
+/// Serve `router` on the configured address, exposing each connection's peer to
+/// [`apply`].
+pub async fn serve(router: Router) -> std::io::Result<()> {
+    let address = dioxus::cli_config::fullstack_address_or_localhost();
+    let listener = tokio::net::TcpListener::bind(address).await?;
+    tracing::info!("client_ip.serve: listening on {}", address);
+    axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+}
+
+async fn scope_client_ip(State(trust_proxy): State<bool>, req: Request, next: Next) -> Response {
+    let peer = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let ip = client_ip(trust_proxy, req.headers(), peer);
+    CLIENT_IP.scope(ip, next.run(req)).await
+}
+
+/// The forwarded address when the proxy is trusted, else the socket peer.
+fn client_ip(trust_proxy: bool, headers: &axum::http::HeaderMap, peer: Option<IpAddr>) -> IpAddr {
+    let forwarded = if trust_proxy {
+        forwarded_for(headers)
+    } else {
+        None
+    };
+    forwarded.or(peer).unwrap_or_else(|| {
+        // Not served through `serve`: every such request shares one bucket rather
+        // than escaping per-address limits.
+        tracing::warn!("client_ip: no peer address, falling back to a shared bucket");
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    })
+}
+
+/// Last entry of `X-Forwarded-For`, if it is an address. Earlier entries come from
+/// the client and can't be trusted.
+fn forwarded_for(headers: &axum::http::HeaderMap) -> Option<IpAddr> {
+    headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .last()
+        .and_then(|entry| entry.trim().parse().ok())
+}
+
+/// Address of the client behind the current request; `None` only outside a request
+/// (e.g. server fns called directly in tests).
+pub fn current() -> Option<IpAddr> {
+    CLIENT_IP.try_with(|ip| *ip).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{HeaderMap, HeaderValue};
+
+    #[test]
+    fn test_forwarded_for_takes_the_entry_the_proxy_appended() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(forwarded_for(&headers), None);
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("203.0.113.7, 198.51.100.2"),
+        );
+        assert_eq!(forwarded_for(&headers), "198.51.100.2".parse().ok());
+        headers.append("x-forwarded-for", HeaderValue::from_static("2001:db8::1"));
+        assert_eq!(forwarded_for(&headers), "2001:db8::1".parse().ok());
+        headers.append(
+            "x-forwarded-for",
+            HeaderValue::from_static("203.0.113.7, junk"),
+        );
+        assert_eq!(forwarded_for(&headers), None);
+    }
+
+    #[test]
+    fn test_forwarded_for_is_ignored_unless_the_proxy_is_trusted() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", HeaderValue::from_static("203.0.113.7"));
+        let peer: IpAddr = "192.0.2.1".parse().unwrap();
+        assert_eq!(client_ip(false, &headers, Some(peer)), peer);
+        assert_eq!(
+            client_ip(true, &headers, Some(peer)),
+            "203.0.113.7".parse::<IpAddr>().unwrap()
+        );
+        assert_eq!(client_ip(true, &HeaderMap::new(), Some(peer)), peer);
+        assert_eq!(
+            client_ip(false, &headers, None),
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_current_is_scoped_to_the_request() {
+        assert_eq!(current(), None);
+        let ip: IpAddr = "192.0.2.1".parse().unwrap();
+        let seen = CLIENT_IP.scope(ip, async { current() }).await;
+        assert_eq!(seen, Some(ip));
+    }
+}
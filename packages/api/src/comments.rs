@@ -1,8 +1,99 @@
-use crate::types::{Comment, ContentTargetType};
+use crate::types::{AuthoredComment, Comment, ContentTargetType};
 use dioxus::prelude::*;
 #[cfg(feature = "server")]
 use tracing::{debug, info};
 
+/// Max comments a single user may post per `COMMENT_RATE_WINDOW_SECS`.
+const COMMENT_RATE_LIMIT: usize = 5;
+const COMMENT_RATE_WINDOW_SECS: u64 = 60;
+/// Most comments [`list_top_comments`] returns, whatever the caller asks for.
+const TOP_COMMENTS_MAX: i64 = 10;
+/// Deepest `depth` [`list_comments`] reports; deeper replies are shown at this level.
+#[cfg(feature = "server")]
+const MAX_COMMENT_DEPTH: u32 = 5;
+
+/// `target_title` column for a comment `c`: the title of what it's on, null if hidden.
+#[cfg(feature = "server")]
+const TARGET_TITLE_SQL: &str = "case \
+    when c.target_type = 'proposal' then (select title from proposals where id = c.target_id and hidden_at is null) \
+    when c.target_type = 'program' then (select title from programs where id = c.target_id and hidden_at is null) \
+    when c.target_type = 'video' then (select storage_key from videos where id = c.target_id and hidden_at is null) \
+    else null \
+    end as target_title";
+
+/// Parse a comment row selected with the column aliases used by the list queries,
+/// `updated_at` and `deleted_at` included.
+#[cfg(feature = "server")]
+pub(crate) fn comment_from_row(row: &sqlx::any::AnyRow) -> Result<Comment, ServerFnError> {
+    use sqlx::Row;
+
+    let parent_comment_id = match row.get::<Option<String>, _>("parent_comment_id") {
+        Some(value) => Some(crate::db::uuid_from_db(&value)?),
+        None => None,
+    };
+    let updated_at = row
+        .get::<Option<String>, _>("updated_at")
+        .as_deref()
+        .map(crate::db::datetime_from_db)
+        .transpose()?;
+    let deleted = row.get::<Option<String>, _>("deleted_at").is_some();
+    Ok(Comment {
+        id: crate::db::uuid_from_db(&row.get::<String, _>("id"))?,
+        author_user_id: crate::db::uuid_from_db(&row.get::<String, _>("author_user_id"))?,
+        target_type: ContentTargetType::from_db(&row.get::<String, _>("target_type"))
+            .ok_or_else(|| ServerFnError::new("invalid target_type"))?,
+        target_id: crate::db::uuid_from_db(&row.get::<String, _>("target_id"))?,
+        parent_comment_id,
+        body_markdown: row.get("body_markdown"),
+        created_at: crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?,
+        vote_score: row.get::<i64, _>("vote_score"),
+        // A tombstone no longer names its author.
+        author_display_name: row
+            .get::<Option<String>, _>("author_display_name")
+            .filter(|_| !deleted),
+        mentioned_user_ids: Vec::new(),
+        depth: 0,
+        pinned: crate::db::bool_from_row(row, "pinned"),
+        updated_at,
+        deleted,
+    })
+}
+
+/// Order `comments` as a thread: each comment followed by its replies, depth-first,
+/// siblings kept in their input order, with `depth` set (capped at `MAX_COMMENT_DEPTH`).
+///
+/// Replies whose parent isn't in `comments` (hidden, or outside the page) are
+/// treated as top-level.
+#[cfg(feature = "server")]
+fn thread(comments: Vec<Comment>) -> Vec<Comment> {
+    use std::collections::HashMap;
+
+    let ids: std::collections::HashSet<_> = comments.iter().map(|c| c.id).collect();
+    let mut roots = Vec::new();
+    let mut replies: HashMap<uuid::Uuid, Vec<Comment>> = HashMap::new();
+    for comment in comments {
+        match comment
+            .parent_comment_id
+            .filter(|parent| ids.contains(parent))
+        {
+            Some(parent) => replies.entry(parent).or_default().push(comment),
+            None => roots.push(comment),
+        }
+    }
+
+    let mut ordered = Vec::with_capacity(ids.len());
+    // Stack of (comment, depth), pushed in reverse so siblings pop in order.
+    let mut stack: Vec<(Comment, u32)> = roots.into_iter().rev().map(|c| (c, 0)).collect();
+    while let Some((mut comment, depth)) = stack.pop() {
+        if let Some(children) = replies.remove(&comment.id) {
+            stack.extend(children.into_iter().rev().map(|c| (c, depth + 1)));
+        }
+        comment.depth = depth.min(MAX_COMMENT_DEPTH);
+        ordered.push(comment);
+    }
+    ordered
+}
+
 #[dioxus::prelude::post("/api/comments/create")]
 pub async fn create_comment(
     id_token: String,
@@ -24,109 +115,166 @@ pub async fn create_comment(
     }
 
     #[cfg(feature = "server")]
+    crate::telemetry::traced! { "comments.create_comment",
+        insert_comment(
+            id_token,
+            target_type,
+            target_id,
+            parent_comment_id,
+            body_markdown,
+        )
+        .await
+    }
+}
+
+/// Reply to `parent_comment_id`, which must be a visible, undeleted comment on the
+/// same target.
+///
+/// Same as [`create_comment`] with a parent; `list_comments` then returns the reply
+/// right after its parent, one `depth` deeper.
+#[dioxus::prelude::post("/api/comments/reply")]
+pub async fn create_comment_reply(
+    id_token: String,
+    target_type: ContentTargetType,
+    target_id: String,
+    parent_comment_id: String,
+    body_markdown: String,
+) -> Result<Comment, ServerFnError> {
+    #[cfg(not(feature = "server"))]
     {
-        use sqlx::Row;
-        use uuid::Uuid;
+        let _ = (
+            id_token,
+            target_type,
+            target_id,
+            parent_comment_id,
+            body_markdown,
+        );
+        Err(ServerFnError::new("create_comment_reply is server-only"))
+    }
 
-        info!(
-            "comments.create_comment: target_type={:?} body_len={}",
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "comments.create_comment_reply",
+        if parent_comment_id.trim().is_empty() {
+            return Err(ServerFnError::new("invalid parent_comment_id"));
+        }
+        insert_comment(
+            id_token,
             target_type,
-            body_markdown.len()
+            target_id,
+            Some(parent_comment_id),
+            body_markdown,
+        )
+        .await
+    }
+}
+
+/// Visible comments on a target (the oldest `limit`), in thread order with `depth`
+/// set, so clients can indent without rebuilding the tree.
+///
+/// A pinned comment and its replies come first. Deleted comments stay in place,
+/// flagged `deleted` with a blank body, so their replies keep their parent.
+#[dioxus::prelude::post("/api/comments/list")]
+pub async fn list_comments(
+    target_type: ContentTargetType,
+    target_id: String,
+    limit: i64,
+    id_token: Option<String>,
+) -> Result<Vec<Comment>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (target_type, target_id, limit, id_token);
+        Err(ServerFnError::new("list_comments is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "comments.list_comments",
+        use uuid::Uuid;
+
+        debug!(
+            "comments.list_comments: target_type={:?} target_id={} limit={}",
+            target_type, target_id, limit
         );
-        let author_user_id = crate::auth::require_user_id(id_token).await?;
         let tid =
             Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
-        let parent_id = match parent_comment_id {
-            None => None,
-            Some(s) if s.trim().is_empty() => None,
-            Some(s) => Some(
-                Uuid::parse_str(&s).map_err(|_| ServerFnError::new("invalid parent_comment_id"))?,
-            ),
-        };
-
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let limit = state.clamp_limit(limit);
         let pool = state.db.pool().await;
 
-        let parent_id_db = parent_id.map(crate::db::uuid_to_db);
-        let row = sqlx::query(
+        let rows = sqlx::query(
             r#"
-            insert into comments (author_user_id, target_type, target_id, parent_comment_id, body_markdown)
-            values ($1, $2, $3, $4, $5)
-            returning
-                CAST(id as TEXT) as id,
-                CAST(author_user_id as TEXT) as author_user_id,
-                target_type,
-                CAST(target_id as TEXT) as target_id,
-                CAST(parent_comment_id as TEXT) as parent_comment_id,
-                body_markdown,
-                CAST(created_at as TEXT) as created_at
+            select
+                CAST(c.id as TEXT) as id,
+                CAST(c.author_user_id as TEXT) as author_user_id,
+                c.target_type,
+                CAST(c.target_id as TEXT) as target_id,
+                CAST(c.parent_comment_id as TEXT) as parent_comment_id,
+                c.body_markdown,
+                CAST(c.created_at as TEXT) as created_at,
+                CAST(c.updated_at as TEXT) as updated_at,
+                CAST(c.deleted_at as TEXT) as deleted_at,
+                c.pinned,
+                coalesce(sum(v.value), 0) as vote_score,
+                pr.display_name as author_display_name
+            from comments c
+            left join votes v
+                on v.target_type = 'comment' and v.target_id = c.id
+            left join profiles pr on pr.user_id = c.author_user_id
+            where c.target_type = $1 and c.target_id = $2 and c.hidden_at is null
+            group by c.id, pr.display_name
+            order by c.pinned desc, c.created_at asc, c.id asc
+            limit $3
             "#,
         )
-        .bind(crate::db::uuid_to_db(author_user_id))
         .bind(target_type.as_db())
         .bind(crate::db::uuid_to_db(tid))
-        .bind(parent_id_db)
-        .bind(&body_markdown)
-        .fetch_one(pool)
+        .bind(limit)
+        .fetch_all(pool)
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
 
-        let cid = crate::db::uuid_from_db(&row.get::<String, _>("id"))?;
-        info!("comments.create_comment: comment_id={}", cid);
-
-        let _ = sqlx::query(
-            "insert into activity (user_id, action, target_type, target_id) values ($1, 'commented', $2, $3)",
-        )
-        .bind(crate::db::uuid_to_db(author_user_id))
-        .bind(target_type.as_db())
-        .bind(crate::db::uuid_to_db(tid))
-        .execute(pool)
-        .await;
+        let mut comments = rows
+            .iter()
+            .map(comment_from_row)
+            .collect::<Result<Vec<_>, _>>()?;
+        crate::mentions::attach(pool, &mut comments).await?;
+        let comments = thread(comments);
 
-        let author_user_id = crate::db::uuid_from_db(&row.get::<String, _>("author_user_id"))?;
-        let parent_comment_id = match row.get::<Option<String>, _>("parent_comment_id") {
-            Some(value) => Some(crate::db::uuid_from_db(&value)?),
-            None => None,
-        };
-        let created_at = crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?;
-
-        Ok(Comment {
-            id: cid,
-            author_user_id,
-            target_type,
-            target_id: tid,
-            parent_comment_id,
-            body_markdown: row.get("body_markdown"),
-            created_at,
-            vote_score: 0,
-        })
+        debug!("comments.list_comments: count={}", comments.len());
+        Ok(comments)
     }
 }
 
-#[dioxus::prelude::post("/api/comments/list")]
-pub async fn list_comments(
+/// A short preview of a thread: the highest-voted top-level comments, newest first on ties.
+///
+/// `limit` is capped at `TOP_COMMENTS_MAX`; replies, hidden and deleted comments are
+/// skipped.
+#[dioxus::prelude::post("/api/comments/top")]
+pub async fn list_top_comments(
     target_type: ContentTargetType,
     target_id: String,
     limit: i64,
+    id_token: Option<String>,
 ) -> Result<Vec<Comment>, ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = (target_type, target_id, limit);
-        Err(ServerFnError::new("list_comments is server-only"))
+        let _ = (target_type, target_id, limit, id_token);
+        Err(ServerFnError::new("list_top_comments is server-only"))
     }
 
     #[cfg(feature = "server")]
-    {
-        use sqlx::Row;
+    crate::telemetry::traced! { "comments.list_top_comments",
         use uuid::Uuid;
 
         debug!(
-            "comments.list_comments: target_type={:?} target_id={} limit={}",
+            "comments.list_top_comments: target_type={:?} target_id={} limit={}",
             target_type, target_id, limit
         );
         let tid =
             Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
-        let state = crate::state::AppState::global();
+        let limit = limit.clamp(0, TOP_COMMENTS_MAX);
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
         let pool = state.db.pool().await;
 
         let rows = sqlx::query(
@@ -134,16 +282,27 @@ pub async fn list_comments(
             select
                 CAST(c.id as TEXT) as id,
                 CAST(c.author_user_id as TEXT) as author_user_id,
+                c.target_type,
+                CAST(c.target_id as TEXT) as target_id,
                 CAST(c.parent_comment_id as TEXT) as parent_comment_id,
                 c.body_markdown,
                 CAST(c.created_at as TEXT) as created_at,
-                coalesce(sum(v.value), 0) as vote_score
+                CAST(c.updated_at as TEXT) as updated_at,
+                CAST(c.deleted_at as TEXT) as deleted_at,
+                c.pinned,
+                coalesce(sum(v.value), 0) as vote_score,
+                pr.display_name as author_display_name
             from comments c
             left join votes v
                 on v.target_type = 'comment' and v.target_id = c.id
-            where c.target_type = $1 and c.target_id = $2
-            group by c.id
-            order by c.created_at asc
+            left join profiles pr on pr.user_id = c.author_user_id
+            where c.target_type = $1
+                and c.target_id = $2
+                and c.parent_comment_id is null
+                and c.hidden_at is null
+                and c.deleted_at is null
+            group by c.id, pr.display_name
+            order by vote_score desc, c.created_at desc, c.id desc
             limit $3
             "#,
         )
@@ -154,28 +313,736 @@ pub async fn list_comments(
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
 
-        let mut comments = Vec::with_capacity(rows.len());
-        for row in rows {
-            let id = crate::db::uuid_from_db(&row.get::<String, _>("id"))?;
-            let author_user_id = crate::db::uuid_from_db(&row.get::<String, _>("author_user_id"))?;
-            let parent_comment_id = match row.get::<Option<String>, _>("parent_comment_id") {
-                Some(value) => Some(crate::db::uuid_from_db(&value)?),
-                None => None,
-            };
-            let created_at = crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?;
-            comments.push(Comment {
-                id,
+        let mut comments = rows
+            .iter()
+            .map(comment_from_row)
+            .collect::<Result<Vec<_>, _>>()?;
+        crate::mentions::attach(pool, &mut comments).await?;
+
+        debug!("comments.list_top_comments: count={}", comments.len());
+        Ok(comments)
+    }
+}
+
+/// A user's comment history, newest first, with the title of what each comment is on.
+///
+/// Hidden and deleted comments are skipped; comments on hidden content keep
+/// `target_title: None`.
+#[dioxus::prelude::post("/api/comments/by_author/:user_id")]
+pub async fn list_comments_by_author(
+    user_id: String,
+    limit: i64,
+    offset: i64,
+    id_token: Option<String>,
+) -> Result<Vec<AuthoredComment>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (user_id, limit, offset, id_token);
+        Err(ServerFnError::new("list_comments_by_author is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "comments.list_comments_by_author",
+        use sqlx::Row;
+        use uuid::Uuid;
+
+        debug!(
+            "comments.list_comments_by_author: user_id={} limit={} offset={}",
+            user_id, limit, offset
+        );
+        let uid = Uuid::parse_str(&user_id).map_err(|_| ServerFnError::new("invalid user_id"))?;
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let (limit, offset) = state.page(limit, offset)?;
+        let pool = state.db.pool().await;
+
+        let rows = sqlx::query(&format!(
+            r#"
+            select
+                CAST(c.id as TEXT) as id,
+                CAST(c.author_user_id as TEXT) as author_user_id,
+                c.target_type,
+                CAST(c.target_id as TEXT) as target_id,
+                CAST(c.parent_comment_id as TEXT) as parent_comment_id,
+                c.body_markdown,
+                CAST(c.created_at as TEXT) as created_at,
+                CAST(c.updated_at as TEXT) as updated_at,
+                CAST(c.deleted_at as TEXT) as deleted_at,
+                c.pinned,
+                coalesce(sum(v.value), 0) as vote_score,
+                pr.display_name as author_display_name,
+                {TARGET_TITLE_SQL}
+            from comments c
+            left join votes v
+                on v.target_type = 'comment' and v.target_id = c.id
+            left join profiles pr on pr.user_id = c.author_user_id
+            where c.author_user_id = $1 and c.hidden_at is null and c.deleted_at is null
+            group by c.id, pr.display_name
+            order by c.created_at desc, c.id desc
+            limit $2 offset $3
+            "#,
+        ))
+        .bind(crate::db::uuid_to_db(uid))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let mut items = rows
+            .iter()
+            .map(|row| {
+                Ok(AuthoredComment {
+                    comment: comment_from_row(row)?,
+                    target_title: row.get("target_title"),
+                })
+            })
+            .collect::<Result<Vec<_>, ServerFnError>>()?;
+        crate::mentions::attach(pool, items.iter_mut().map(|item| &mut item.comment)).await?;
+        debug!("comments.list_comments_by_author: count={}", items.len());
+        Ok(items)
+    }
+}
+
+/// One visible comment with what it was posted on, for deep links (e.g. from a
+/// notification); the thread is then loaded with `list_comments`.
+#[dioxus::prelude::post("/api/comments/:id")]
+pub async fn get_comment(
+    id: String,
+    id_token: Option<String>,
+) -> Result<AuthoredComment, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, id_token);
+        Err(ServerFnError::new("get_comment is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "comments.get_comment",
+        use uuid::Uuid;
+
+        debug!("comments.get_comment: id={}", id);
+        let cid = Uuid::parse_str(&id).map_err(|_| ServerFnError::new("invalid id"))?;
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let pool = state.db.pool().await;
+        load_comment(pool, cid).await
+    }
+}
+
+/// A visible comment (deleted ones included) with what it was posted on.
+#[cfg(feature = "server")]
+async fn load_comment(
+    pool: &sqlx::Pool<sqlx::Any>,
+    cid: uuid::Uuid,
+) -> Result<AuthoredComment, ServerFnError> {
+    use sqlx::Row;
+
+    let row = sqlx::query(&format!(
+        r#"
+        select
+            CAST(c.id as TEXT) as id,
+            CAST(c.author_user_id as TEXT) as author_user_id,
+            c.target_type,
+            CAST(c.target_id as TEXT) as target_id,
+            CAST(c.parent_comment_id as TEXT) as parent_comment_id,
+            c.body_markdown,
+            CAST(c.created_at as TEXT) as created_at,
+            CAST(c.updated_at as TEXT) as updated_at,
+            CAST(c.deleted_at as TEXT) as deleted_at,
+            c.pinned,
+            coalesce(sum(v.value), 0) as vote_score,
+            pr.display_name as author_display_name,
+            {TARGET_TITLE_SQL}
+        from comments c
+        left join votes v
+            on v.target_type = 'comment' and v.target_id = c.id
+        left join profiles pr on pr.user_id = c.author_user_id
+        where c.id = $1 and c.hidden_at is null
+        group by c.id, pr.display_name
+        "#,
+    ))
+    .bind(crate::db::uuid_to_db(cid))
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?
+    .ok_or_else(|| ServerFnError::new(crate::types::ErrorCode::NotFound.to_string()))?;
+
+    let mut item = AuthoredComment {
+        comment: comment_from_row(&row)?,
+        target_title: row.get("target_title"),
+    };
+    crate::mentions::attach(pool, std::iter::once(&mut item.comment)).await?;
+    Ok(item)
+}
+
+/// Edit the body of one of your comments. Mentions are kept as they were resolved
+/// when the comment was posted.
+#[dioxus::prelude::post("/api/comments/update")]
+pub async fn update_comment(
+    id_token: String,
+    comment_id: String,
+    body_markdown: String,
+) -> Result<Comment, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, comment_id, body_markdown);
+        Err(ServerFnError::new("update_comment is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "comments.update_comment",
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        info!(
+            "comments.update_comment: comment_id={} body_len={}",
+            comment_id,
+            body_markdown.len()
+        );
+        let user_id = crate::auth::require_user_id(id_token).await?;
+
+        let (body_markdown, flagged) = validate_comment_body(&state, &body_markdown)?;
+
+        let pool = state.db.pool().await;
+        let cid = own_comment(pool, user_id, &comment_id).await?;
+        sqlx::query(&format!(
+            "update comments set body_markdown = $2, updated_at = {} where id = $1",
+            crate::db::now_sql()
+        ))
+        .bind(crate::db::uuid_to_db(cid))
+        .bind(&body_markdown)
+        .execute(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        if let Some(reason) = flagged {
+            info!("comments.update_comment: flagged comment_id={}", cid);
+            crate::content_filter::report_flagged(
+                pool,
+                user_id,
+                ContentTargetType::Comment,
+                cid,
+                &reason,
+            )
+            .await?;
+        }
+
+        info!("comments.update_comment: ok comment_id={} user_id={}", cid, user_id);
+        Ok(load_comment(pool, cid).await?.comment)
+    }
+}
+
+/// Delete one of your comments, leaving a tombstone: the body and author name are
+/// blanked, the comment unpinned and the notifications it sent dropped, but it stays
+/// in its thread so replies keep their parent.
+#[dioxus::prelude::post("/api/comments/delete")]
+pub async fn delete_comment(id_token: String, comment_id: String) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, comment_id);
+        Err(ServerFnError::new("delete_comment is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "comments.delete_comment",
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let pool = state.db.pool().await;
+        let cid = own_comment(pool, user_id, &comment_id).await?;
+
+        // Any early return drops the transaction, which rolls it back.
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        sqlx::query(&format!(
+            "update comments set body_markdown = '', pinned = false, deleted_at = {} where id = $1",
+            crate::db::now_sql()
+        ))
+        .bind(crate::db::uuid_to_db(cid))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        for sql in [
+            "delete from comment_mentions where comment_id = $1",
+            "delete from notifications where comment_id = $1",
+        ] {
+            sqlx::query(sql)
+                .bind(crate::db::uuid_to_db(cid))
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        crate::counts::refresh_for_comment(pool, cid).await?;
+
+        info!("comments.delete_comment: comment_id={} user_id={}", cid, user_id);
+        Ok(())
+    }
+}
+
+/// Pin a top-level comment on your own proposal, program or video, replacing any
+/// comment pinned there before.
+#[dioxus::prelude::post("/api/comments/pin")]
+pub async fn pin_comment(id_token: String, comment_id: String) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, comment_id);
+        Err(ServerFnError::new("pin_comment is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "comments.pin_comment",
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let pool = state.db.pool().await;
+        let (cid, target_type, tid) = pinnable_comment(pool, user_id, &comment_id).await?;
+
+        // Any early return drops the transaction, which rolls it back.
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        sqlx::query(
+            "update comments set pinned = false where target_type = $1 and target_id = $2 and pinned = true",
+        )
+        .bind(target_type.as_db())
+        .bind(crate::db::uuid_to_db(tid))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        sqlx::query("update comments set pinned = true where id = $1")
+            .bind(crate::db::uuid_to_db(cid))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        tx.commit()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        info!("comments.pin_comment: comment_id={} user_id={}", cid, user_id);
+        Ok(())
+    }
+}
+
+/// Unpin a comment on your own proposal, program or video.
+#[dioxus::prelude::post("/api/comments/unpin")]
+pub async fn unpin_comment(id_token: String, comment_id: String) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, comment_id);
+        Err(ServerFnError::new("unpin_comment is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "comments.unpin_comment",
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let pool = state.db.pool().await;
+        let (cid, _, _) = pinnable_comment(pool, user_id, &comment_id).await?;
+
+        sqlx::query("update comments set pinned = false where id = $1")
+            .bind(crate::db::uuid_to_db(cid))
+            .execute(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        info!("comments.unpin_comment: comment_id={} user_id={}", cid, user_id);
+        Ok(())
+    }
+}
+
+/// Body of [`create_comment`] and [`create_comment_reply`], kept out of either
+/// endpoint's trace so a reply is traced and counted once.
+#[cfg(feature = "server")]
+async fn insert_comment(
+    id_token: String,
+    target_type: ContentTargetType,
+    target_id: String,
+    parent_comment_id: Option<String>,
+    body_markdown: String,
+) -> Result<Comment, ServerFnError> {
+    use crate::types::NotificationKind;
+    use sqlx::Row;
+    use uuid::Uuid;
+
+    crate::state::AppState::current()?.ensure_writable()?;
+
+    info!(
+        "comments.insert_comment: target_type={:?} body_len={}",
+        target_type,
+        body_markdown.len()
+    );
+    let author_user_id = crate::auth::require_user_id(id_token).await?;
+    let state = crate::state::AppState::current()?;
+    if !state.rate_limiter.check(
+        &format!("comment:{author_user_id}"),
+        COMMENT_RATE_LIMIT,
+        std::time::Duration::from_secs(COMMENT_RATE_WINDOW_SECS),
+    ) {
+        info!(
+            "comments.insert_comment: rate limited user_id={}",
+            author_user_id
+        );
+        return Err(ServerFnError::new(
+            crate::types::ErrorCode::CommentTooFast.to_string(),
+        ));
+    }
+    let tid = Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
+    let parent_id = match parent_comment_id {
+        None => None,
+        Some(s) if s.trim().is_empty() => None,
+        Some(s) => {
+            Some(Uuid::parse_str(&s).map_err(|_| ServerFnError::new("invalid parent_comment_id"))?)
+        }
+    };
+
+    let (body_markdown, flagged) = validate_comment_body(&state, &body_markdown)?;
+
+    let pool = state.db.pool().await;
+    if !crate::db::target_exists(pool, target_type, tid).await? {
+        return Err(ServerFnError::new("target not found"));
+    }
+    // Hidden and deleted comments can't be replied to; replies posted before stay.
+    if let Some(parent) = parent_id {
+        let parent_target = sqlx::query(
+            "select target_type, CAST(target_id as TEXT) as target_id from comments where id = $1 and hidden_at is null and deleted_at is null",
+        )
+        .bind(crate::db::uuid_to_db(parent))
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .ok_or_else(|| ServerFnError::new("parent comment not found"))?;
+        let same_target = parent_target.get::<String, _>("target_type") == target_type.as_db()
+            && crate::db::uuid_from_db(&parent_target.get::<String, _>("target_id"))? == tid;
+        if !same_target {
+            info!(
+                "comments.insert_comment: parent on another target parent_comment_id={}",
+                parent
+            );
+            return Err(ServerFnError::new(
+                "parent comment is on a different target",
+            ));
+        }
+    }
+
+    let parent_id_db = parent_id.map(crate::db::uuid_to_db);
+    let row = sqlx::query(
+        r#"
+        insert into comments (author_user_id, target_type, target_id, parent_comment_id, body_markdown)
+        values ($1, $2, $3, $4, $5)
+        returning
+            CAST(id as TEXT) as id,
+            CAST(author_user_id as TEXT) as author_user_id,
+            target_type,
+            CAST(target_id as TEXT) as target_id,
+            CAST(parent_comment_id as TEXT) as parent_comment_id,
+            body_markdown,
+            CAST(created_at as TEXT) as created_at
+        "#,
+    )
+    .bind(crate::db::uuid_to_db(author_user_id))
+    .bind(target_type.as_db())
+    .bind(crate::db::uuid_to_db(tid))
+    .bind(parent_id_db)
+    .bind(&body_markdown)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let cid = crate::db::uuid_from_db(&row.get::<String, _>("id"))?;
+    info!("comments.insert_comment: comment_id={}", cid);
+    if let Some(reason) = flagged {
+        info!("comments.insert_comment: flagged comment_id={}", cid);
+        crate::content_filter::report_flagged(
+            pool,
+            author_user_id,
+            ContentTargetType::Comment,
+            cid,
+            &reason,
+        )
+        .await?;
+    }
+    crate::counts::refresh_comment_count(pool, target_type, tid).await?;
+
+    let _ = sqlx::query(
+        "insert into activity (user_id, action, target_type, target_id) values ($1, 'commented', $2, $3)",
+    )
+    .bind(crate::db::uuid_to_db(author_user_id))
+    .bind(target_type.as_db())
+    .bind(crate::db::uuid_to_db(tid))
+    .execute(pool)
+    .await;
+
+    // Notifications (best-effort): target author, then parent comment author on replies,
+    // each also emailed when they opted in.
+    let target_owner = crate::db::target_owner(pool, target_type, tid)
+        .await
+        .ok()
+        .flatten();
+    if let Some(owner) = target_owner {
+        let _ = crate::notifications::notify(
+            pool,
+            owner,
+            NotificationKind::Comment,
+            author_user_id,
+            target_type,
+            tid,
+            Some(cid),
+        )
+        .await;
+        let _ = crate::notifications::email(
+            pool,
+            owner,
+            NotificationKind::Comment,
+            author_user_id,
+            target_type,
+            tid,
+        )
+        .await;
+    }
+    if let Some(parent) = parent_id {
+        let parent_author = crate::db::target_owner(pool, ContentTargetType::Comment, parent)
+            .await
+            .ok()
+            .flatten();
+        if let Some(parent_author) = parent_author.filter(|a| Some(*a) != target_owner) {
+            let _ = crate::notifications::notify(
+                pool,
+                parent_author,
+                NotificationKind::Reply,
+                author_user_id,
+                target_type,
+                tid,
+                Some(cid),
+            )
+            .await;
+            let _ = crate::notifications::email(
+                pool,
+                parent_author,
+                NotificationKind::Reply,
                 author_user_id,
                 target_type,
-                target_id: tid,
-                parent_comment_id,
-                body_markdown: row.get("body_markdown"),
-                created_at,
-                vote_score: row.get::<i64, _>("vote_score"),
-            });
+                tid,
+            )
+            .await;
         }
+    }
 
-        debug!("comments.list_comments: count={}", comments.len());
-        Ok(comments)
+    // Mentions (best-effort): unknown names and self-mentions are dropped.
+    let mentioned_user_ids = match crate::mentions::resolve(
+        pool,
+        &crate::mentions::parse_mentions(&body_markdown),
+    )
+    .await
+    {
+        Ok(ids) => ids
+            .into_iter()
+            .filter(|id| *id != author_user_id)
+            .collect::<Vec<_>>(),
+        Err(_) => Vec::new(),
+    };
+    if crate::mentions::store(pool, cid, &mentioned_user_ids)
+        .await
+        .is_ok()
+    {
+        for mentioned in &mentioned_user_ids {
+            let _ = crate::notifications::notify(
+                pool,
+                *mentioned,
+                NotificationKind::Mention,
+                author_user_id,
+                target_type,
+                tid,
+                Some(cid),
+            )
+            .await;
+        }
+    }
+
+    let author_user_id = crate::db::uuid_from_db(&row.get::<String, _>("author_user_id"))?;
+    let parent_comment_id = match row.get::<Option<String>, _>("parent_comment_id") {
+        Some(value) => Some(crate::db::uuid_from_db(&value)?),
+        None => None,
+    };
+    let created_at = crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?;
+    let author_display_name: Option<String> =
+        sqlx::query_scalar("select display_name from profiles where user_id = $1")
+            .bind(crate::db::uuid_to_db(author_user_id))
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(Comment {
+        id: cid,
+        author_user_id,
+        target_type,
+        target_id: tid,
+        parent_comment_id,
+        body_markdown: row.get("body_markdown"),
+        created_at,
+        vote_score: 0,
+        author_display_name,
+        mentioned_user_ids,
+        depth: 0,
+        pinned: false,
+        updated_at: None,
+        deleted: false,
+    })
+}
+
+/// Trim a comment body and check it is non-empty, within `comment_max_length` and
+/// accepted by the content filter; also returns the filter's reason when it flags it.
+#[cfg(feature = "server")]
+fn validate_comment_body(
+    state: &crate::state::AppState,
+    body_markdown: &str,
+) -> Result<(String, Option<String>), ServerFnError> {
+    let body_markdown = body_markdown.trim().to_string();
+    if body_markdown.is_empty() {
+        return Err(ServerFnError::new(
+            crate::types::ErrorCode::CommentEmpty.to_string(),
+        ));
+    }
+    let max_len = state.config.comment_max_length;
+    if body_markdown.chars().count() > max_len {
+        return Err(ServerFnError::new(format!(
+            "comment body exceeds {max_len} characters"
+        )));
+    }
+    let flagged = crate::content_filter::screen(state.content_filter.as_ref(), &[&body_markdown])?;
+    Ok((body_markdown, flagged))
+}
+
+/// A visible, undeleted comment written by `user_id`.
+#[cfg(feature = "server")]
+async fn own_comment(
+    pool: &sqlx::Pool<sqlx::Any>,
+    user_id: uuid::Uuid,
+    comment_id: &str,
+) -> Result<uuid::Uuid, ServerFnError> {
+    use crate::types::ErrorCode;
+
+    let cid =
+        uuid::Uuid::parse_str(comment_id).map_err(|_| ServerFnError::new("invalid comment_id"))?;
+    let author = sqlx::query_scalar::<_, String>(
+        "select CAST(author_user_id as TEXT) from comments where id = $1 and hidden_at is null and deleted_at is null",
+    )
+    .bind(crate::db::uuid_to_db(cid))
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?
+    .ok_or_else(|| ServerFnError::new(ErrorCode::NotFound.to_string()))?;
+    if crate::db::uuid_from_db(&author)? != user_id {
+        info!(
+            "comments.own_comment: forbidden comment_id={} user_id={}",
+            cid, user_id
+        );
+        return Err(ServerFnError::new(ErrorCode::NotAllowed.to_string()));
+    }
+    Ok(cid)
+}
+
+/// A visible top-level comment on content authored by `user_id`, with its target.
+#[cfg(feature = "server")]
+async fn pinnable_comment(
+    pool: &sqlx::Pool<sqlx::Any>,
+    user_id: uuid::Uuid,
+    comment_id: &str,
+) -> Result<(uuid::Uuid, ContentTargetType, uuid::Uuid), ServerFnError> {
+    use crate::types::ErrorCode;
+    use sqlx::Row;
+
+    let cid =
+        uuid::Uuid::parse_str(comment_id).map_err(|_| ServerFnError::new("invalid comment_id"))?;
+    let row = sqlx::query(
+        "select target_type, CAST(target_id as TEXT) as target_id, CAST(parent_comment_id as TEXT) as parent_comment_id from comments where id = $1 and hidden_at is null and deleted_at is null",
+    )
+    .bind(crate::db::uuid_to_db(cid))
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?
+    .ok_or_else(|| ServerFnError::new(ErrorCode::NotFound.to_string()))?;
+    let target_type = ContentTargetType::from_db(&row.get::<String, _>("target_type"))
+        .ok_or_else(|| ServerFnError::new("invalid target_type"))?;
+    let tid = crate::db::uuid_from_db(&row.get::<String, _>("target_id"))?;
+
+    if crate::db::target_owner(pool, target_type, tid).await? != Some(user_id) {
+        info!(
+            "comments.pinnable_comment: forbidden comment_id={} user_id={}",
+            cid, user_id
+        );
+        return Err(ServerFnError::new(ErrorCode::NotAllowed.to_string()));
+    }
+    if row.get::<Option<String>, _>("parent_comment_id").is_some() {
+        return Err(ServerFnError::new("only top-level comments can be pinned"));
+    }
+    Ok((cid, target_type, tid))
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn comment(id: u128, parent: Option<u128>) -> Comment {
+        Comment {
+            id: Uuid::from_u128(id),
+            author_user_id: Uuid::nil(),
+            target_type: ContentTargetType::Proposal,
+            target_id: Uuid::nil(),
+            parent_comment_id: parent.map(Uuid::from_u128),
+            body_markdown: String::new(),
+            created_at: time::OffsetDateTime::UNIX_EPOCH,
+            vote_score: 0,
+            author_display_name: None,
+            mentioned_user_ids: Vec::new(),
+            depth: 0,
+            pinned: false,
+            updated_at: None,
+            deleted: false,
+        }
+    }
+
+    fn shape(comments: &[Comment]) -> Vec<(u128, u32)> {
+        comments.iter().map(|c| (c.id.as_u128(), c.depth)).collect()
+    }
+
+    #[test]
+    fn test_thread_puts_replies_under_their_parent() {
+        let threaded = thread(vec![
+            comment(1, None),
+            comment(2, None),
+            comment(3, Some(1)),
+            comment(4, Some(3)),
+            comment(5, Some(1)),
+            comment(6, Some(2)),
+        ]);
+        assert_eq!(
+            shape(&threaded),
+            vec![(1, 0), (3, 1), (4, 2), (5, 1), (2, 0), (6, 1)]
+        );
+    }
+
+    #[test]
+    fn test_thread_treats_orphans_as_top_level() {
+        let threaded = thread(vec![
+            comment(1, None),
+            comment(2, Some(99)),
+            comment(3, Some(2)),
+        ]);
+        assert_eq!(shape(&threaded), vec![(1, 0), (2, 0), (3, 1)]);
+    }
+
+    #[test]
+    fn test_thread_caps_depth() {
+        let chain: Vec<_> = (1..=8)
+            .map(|id| comment(id, (id > 1).then(|| id - 1)))
+            .collect();
+        let depths: Vec<u32> = thread(chain).iter().map(|c| c.depth).collect();
+        assert_eq!(depths, vec![0, 1, 2, 3, 4, 5, 5, 5]);
     }
 }
@@ -0,0 +1,164 @@
+//! Screening of user-written text before it is stored.
+//!
+//! `AppState::content_filter` runs on new comments and proposals. The default
+//! [`BlocklistFilter`] matches whole words from `CONTENT_BLOCKLIST`; with an empty
+//! list (the default) it allows everything.
+
+use crate::config::{AppConfig, ContentFilterAction};
+use crate::types::{ContentTargetType, ErrorCode};
+use dioxus::prelude::ServerFnError;
+use uuid::Uuid;
+
+/// Outcome of screening a piece of text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Allow,
+    /// Store it, but report it for moderator review with this reason.
+    Flag(String),
+    /// Refuse to store it.
+    Reject,
+}
+
+/// Trait for content filter implementations
+pub trait ContentFilter: Send + Sync {
+    fn check(&self, text: &str) -> Verdict;
+}
+
+/// Case-insensitive whole-word blocklist; a term may span several words.
+pub struct BlocklistFilter {
+    terms: Vec<Vec<String>>,
+    action: ContentFilterAction,
+}
+
+impl BlocklistFilter {
+    pub fn new(terms: &[String], action: ContentFilterAction) -> Self {
+        let terms = terms
+            .iter()
+            .map(|term| words(term))
+            .filter(|words| !words.is_empty())
+            .collect();
+        Self { terms, action }
+    }
+
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self::new(&config.content_blocklist, config.content_filter_action)
+    }
+
+    /// First blocklisted term found in `text`.
+    fn matched(&self, text: &str) -> Option<String> {
+        let text = words(text);
+        self.terms
+            .iter()
+            .find(|term| {
+                text.windows(term.len())
+                    .any(|window| window == term.as_slice())
+            })
+            .map(|term| term.join(" "))
+    }
+}
+
+impl ContentFilter for BlocklistFilter {
+    fn check(&self, text: &str) -> Verdict {
+        match (self.matched(text), self.action) {
+            (None, _) => Verdict::Allow,
+            (Some(_), ContentFilterAction::Reject) => Verdict::Reject,
+            (Some(term), ContentFilterAction::Flag) => {
+                Verdict::Flag(format!("content filter: {term}"))
+            }
+        }
+    }
+}
+
+/// Lowercased runs of letters and digits.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Run `filter` over the fields of one submission.
+///
+/// Rejections become a `ContentRejected` error; a flag returns the report reason.
+pub(crate) fn screen(
+    filter: &dyn ContentFilter,
+    fields: &[&str],
+) -> Result<Option<String>, ServerFnError> {
+    match filter.check(&fields.join("\n")) {
+        Verdict::Allow => Ok(None),
+        Verdict::Flag(reason) => Ok(Some(reason)),
+        Verdict::Reject => Err(ServerFnError::new(ErrorCode::ContentRejected.to_string())),
+    }
+}
+
+/// File a report on flagged content in its author's name, so it reaches the
+/// moderation queue like any user report.
+pub(crate) async fn report_flagged(
+    pool: &sqlx::Pool<sqlx::Any>,
+    author_user_id: Uuid,
+    target_type: ContentTargetType,
+    target_id: Uuid,
+    reason: &str,
+) -> Result<(), ServerFnError> {
+    sqlx::query(
+        r#"
+        insert into reports (reporter_user_id, target_type, target_id, reason)
+        values ($1, $2, $3, $4)
+        on conflict (reporter_user_id, target_type, target_id) do nothing
+        "#,
+    )
+    .bind(crate::db::uuid_to_db(author_user_id))
+    .bind(target_type.as_db())
+    .bind(crate::db::uuid_to_db(target_id))
+    .bind(reason)
+    .execute(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(action: ContentFilterAction) -> BlocklistFilter {
+        BlocklistFilter::new(&["Crétin".to_string(), "sale type".to_string()], action)
+    }
+
+    #[test]
+    fn test_clean_text_passes() {
+        let filter = filter(ContentFilterAction::Reject);
+        assert_eq!(filter.check("Une proposition raisonnable."), Verdict::Allow);
+        // Whole words only: a blocklisted term inside another word is fine.
+        assert_eq!(filter.check("crétinisme"), Verdict::Allow);
+        assert_eq!(filter.check("sale, typé"), Verdict::Allow);
+    }
+
+    #[test]
+    fn test_blocklisted_text_is_rejected() {
+        let filter = filter(ContentFilterAction::Reject);
+        assert_eq!(filter.check("Quel CRÉTIN !"), Verdict::Reject);
+        assert_eq!(filter.check("espèce de sale  type"), Verdict::Reject);
+        let err = screen(&filter, &["titre", "quel crétin"]).unwrap_err();
+        assert_eq!(
+            ErrorCode::from_error(&err.to_string()),
+            Some(ErrorCode::ContentRejected)
+        );
+    }
+
+    #[test]
+    fn test_blocklisted_text_is_flagged() {
+        let filter = filter(ContentFilterAction::Flag);
+        assert_eq!(
+            filter.check("Quel crétin"),
+            Verdict::Flag("content filter: crétin".to_string())
+        );
+        assert_eq!(screen(&filter, &["ok"]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_empty_blocklist_allows_everything() {
+        let filter = BlocklistFilter::new(&[" ".to_string()], ContentFilterAction::Reject);
+        assert_eq!(filter.check("Quel crétin"), Verdict::Allow);
+    }
+}
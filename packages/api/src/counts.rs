@@ -0,0 +1,175 @@
+//! Denormalized counters cached on content rows.
+//!
+//! `comment_count` on proposals and programs caches how many visible comments they
+//! have. Writes that change that refresh it; `recompute_counts` repairs any drift.
+
+#[cfg(feature = "server")]
+use crate::types::ContentTargetType;
+use crate::types::CountsRecomputed;
+use dioxus::prelude::*;
+#[cfg(feature = "server")]
+use tracing::{debug, info};
+
+/// Targets whose rows cache a `comment_count`.
+#[cfg(feature = "server")]
+const COMMENT_COUNT_TARGETS: [ContentTargetType; 2] =
+    [ContentTargetType::Proposal, ContentTargetType::Program];
+
+/// Rows compared per statement while recomputing.
+#[cfg(feature = "server")]
+const RECOMPUTE_BATCH: i64 = 500;
+
+/// Correlated subquery counting the visible, undeleted comments on the current
+/// `target_type` row.
+#[cfg(feature = "server")]
+fn comment_count_sql(target_type: ContentTargetType) -> String {
+    format!(
+        "(select count(*) from comments c where c.target_type = '{kind}' and c.target_id = {table}.id and c.hidden_at is null and c.deleted_at is null)",
+        kind = target_type.as_db(),
+        table = crate::db::target_table(target_type),
+    )
+}
+
+/// Cached visible-comment count of a proposal or program; `0` for other targets.
+#[cfg(feature = "server")]
+pub(crate) async fn comment_count(
+    pool: &sqlx::Pool<sqlx::Any>,
+    target_type: ContentTargetType,
+    target_id: uuid::Uuid,
+) -> Result<i64, ServerFnError> {
+    if !COMMENT_COUNT_TARGETS.contains(&target_type) {
+        return Ok(0);
+    }
+    let sql = format!(
+        "select comment_count from {} where id = $1",
+        crate::db::target_table(target_type)
+    );
+    sqlx::query_scalar::<_, i64>(&sql)
+        .bind(crate::db::uuid_to_db(target_id))
+        .fetch_optional(pool)
+        .await
+        .map(Option::unwrap_or_default)
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Recount the comments on one target after a comment was added, hidden or unhidden.
+#[cfg(feature = "server")]
+pub(crate) async fn refresh_comment_count(
+    pool: &sqlx::Pool<sqlx::Any>,
+    target_type: ContentTargetType,
+    target_id: uuid::Uuid,
+) -> Result<(), ServerFnError> {
+    if !COMMENT_COUNT_TARGETS.contains(&target_type) {
+        return Ok(());
+    }
+    let sql = format!(
+        "update {table} set comment_count = {count} where id = $1",
+        table = crate::db::target_table(target_type),
+        count = comment_count_sql(target_type),
+    );
+    sqlx::query(&sql)
+        .bind(crate::db::uuid_to_db(target_id))
+        .execute(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    Ok(())
+}
+
+/// `refresh_comment_count` for whatever `comment_id` was posted on.
+#[cfg(feature = "server")]
+pub(crate) async fn refresh_for_comment(
+    pool: &sqlx::Pool<sqlx::Any>,
+    comment_id: uuid::Uuid,
+) -> Result<(), ServerFnError> {
+    use sqlx::Row;
+
+    let Some(row) = sqlx::query(
+        "select CAST(target_type as TEXT) as target_type, CAST(target_id as TEXT) as target_id from comments where id = $1",
+    )
+    .bind(crate::db::uuid_to_db(comment_id))
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?
+    else {
+        return Ok(());
+    };
+    let Some(target_type) = ContentTargetType::from_db(&row.get::<String, _>("target_type")) else {
+        return Ok(());
+    };
+    let target_id = crate::db::uuid_from_db(&row.get::<String, _>("target_id"))?;
+    refresh_comment_count(pool, target_type, target_id).await
+}
+
+/// Recompute every cached counter from its source table (admin only).
+///
+/// Walks each table in id order, `RECOMPUTE_BATCH` rows per statement, and only
+/// writes rows whose cached value is wrong.
+#[dioxus::prelude::post("/api/admin/recompute_counts")]
+pub async fn recompute_counts(id_token: String) -> Result<CountsRecomputed, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id_token;
+        Err(ServerFnError::new("recompute_counts is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "counts.recompute_counts",
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        let admin_id = crate::auth::require_admin(id_token).await?;
+        let pool = state.db.pool().await;
+
+        let mut report = CountsRecomputed::default();
+        for target_type in COMMENT_COUNT_TARGETS {
+            let table = crate::db::target_table(target_type);
+            let count = comment_count_sql(target_type);
+            let mut after: Option<String> = None;
+            loop {
+                let after_filter = if after.is_some() { "where id > $2" } else { "" };
+                let batch_sql = format!(
+                    "select CAST(id as TEXT) from {table} {after_filter} order by id limit $1"
+                );
+                let mut batch = sqlx::query_scalar(&batch_sql).bind(RECOMPUTE_BATCH);
+                if let Some(after) = &after {
+                    batch = batch.bind(after);
+                }
+                let ids: Vec<String> = batch
+                    .fetch_all(pool)
+                    .await
+                    .map_err(|e| ServerFnError::new(e.to_string()))?;
+                let (Some(first), Some(last)) = (ids.first(), ids.last()) else {
+                    break;
+                };
+
+                let corrected = sqlx::query(&format!(
+                    "update {table} set comment_count = {count} \
+                     where id >= $1 and id <= $2 and comment_count <> {count}"
+                ))
+                .bind(first)
+                .bind(last)
+                .execute(pool)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?
+                .rows_affected();
+                debug!(
+                    "counts.recompute_counts: table={} rows={} corrected={}",
+                    table,
+                    ids.len(),
+                    corrected
+                );
+                report.scanned += ids.len() as i64;
+                report.corrected += corrected as i64;
+                after = Some(last.clone());
+            }
+        }
+
+        crate::audit::record(pool, admin_id, crate::types::AdminAction::RecomputeCounts, None)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        info!(
+            "counts.recompute_counts: admin_id={} scanned={} corrected={}",
+            admin_id, report.scanned, report.corrected
+        );
+        Ok(report)
+    }
+}
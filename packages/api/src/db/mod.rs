@@ -13,6 +13,9 @@ pub trait Database: Send + Sync {
     async fn pool(&self) -> &Pool<Any>;
 
     async fn run_migrations(&self) -> Result<()>;
+
+    /// Versions of the migrations embedded for this backend.
+    fn expected_migrations(&self) -> Vec<i64>;
 }
 
 // Re-export implementations
@@ -43,20 +46,23 @@ pub fn datetime_from_db(
     }
 
     let fmt_with_offset = format_description!(
-        "[year]-[month]-[day] [hour]:[minute]:[second][offset_hour sign:mandatory][offset_minute]"
+        "[year]-[month]-[day] [hour]:[minute]:[second][optional [.[subsecond]]][offset_hour sign:mandatory][offset_minute]"
     );
     if let Ok(dt) = time::OffsetDateTime::parse(value, &fmt_with_offset) {
         return Ok(dt);
     }
 
+    // Postgres prints `+00` for whole-hour offsets and `+05:30` otherwise.
     let fmt_with_offset_colon = format_description!(
-        "[year]-[month]-[day] [hour]:[minute]:[second][offset_hour sign:mandatory]:[offset_minute]"
+        "[year]-[month]-[day] [hour]:[minute]:[second][optional [.[subsecond]]][offset_hour sign:mandatory][optional [:[offset_minute]]]"
     );
     if let Ok(dt) = time::OffsetDateTime::parse(value, &fmt_with_offset_colon) {
         return Ok(dt);
     }
 
-    let fmt_no_offset = format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
+    let fmt_no_offset = format_description!(
+        "[year]-[month]-[day] [hour]:[minute]:[second][optional [.[subsecond]]]"
+    );
     if let Ok(dt) = time::PrimitiveDateTime::parse(value, &fmt_no_offset) {
         return Ok(dt.assume_utc());
     }
@@ -66,6 +72,52 @@ pub fn datetime_from_db(
     ))
 }
 
+/// Bind value for comparing a timestamp column against `value`.
+///
+/// SQLite stores UTC text (`YYYY-MM-DD HH:MM:SS[.fff]`), so the value is spelled the
+/// same way for text comparison; Postgres gets RFC 3339 to cast with `::timestamptz`.
+#[cfg(feature = "server")]
+pub fn datetime_to_db(
+    value: time::OffsetDateTime,
+) -> Result<String, dioxus::prelude::ServerFnError> {
+    use time::macros::format_description;
+
+    let utc = value.to_offset(time::UtcOffset::UTC);
+    let formatted = if is_sqlite() {
+        utc.format(format_description!(
+            "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond digits:6]"
+        ))
+    } else {
+        utc.format(&time::format_description::well_known::Rfc3339)
+    };
+    formatted.map_err(|_| dioxus::prelude::ServerFnError::new("invalid timestamp"))
+}
+
+/// SQL for "now" when stamping `updated_at` (or any other timestamp) in an update.
+///
+/// SQLite has no `now()`, and its `CURRENT_TIMESTAMP` only has second precision, which is
+/// too coarse for the optimistic-concurrency checks on updates, so it gets millisecond
+/// precision instead. Use this rather than spelling either backend's form inline.
+#[cfg(feature = "server")]
+pub fn now_sql() -> &'static str {
+    if is_sqlite() {
+        "strftime('%Y-%m-%d %H:%M:%f', 'now')"
+    } else {
+        "now()"
+    }
+}
+
+/// Read a boolean column; SQLite stores booleans as integers (0 = false, 1 = true).
+#[cfg(feature = "server")]
+pub fn bool_from_row(row: &sqlx::any::AnyRow, column: &str) -> bool {
+    use sqlx::Row;
+
+    match row.try_get::<bool, _>(column) {
+        Ok(v) => v,
+        Err(_) => row.get::<i64, _>(column) != 0,
+    }
+}
+
 #[cfg(feature = "server")]
 pub fn tags_to_db(tags: &[String]) -> Result<String, dioxus::prelude::ServerFnError> {
     serde_json::to_string(tags)
@@ -91,12 +143,81 @@ pub fn tags_from_db(value: &str) -> Result<Vec<String>, dioxus::prelude::ServerF
         .collect())
 }
 
+/// Compare the migrations recorded in `_sqlx_migrations` with the embedded set.
+pub async fn migration_status(db: &dyn Database) -> Result<crate::types::MigrationStatus> {
+    let applied: Vec<i64> = sqlx::query_scalar(
+        "select version from _sqlx_migrations where success = true order by version",
+    )
+    .fetch_all(db.pool().await)
+    .await?;
+    let expected = db.expected_migrations();
+    let up_to_date = applied == expected;
+    Ok(crate::types::MigrationStatus {
+        applied,
+        expected,
+        up_to_date,
+    })
+}
+
+/// Table holding rows for a given content target type.
+#[cfg(feature = "server")]
+pub fn target_table(target_type: crate::types::ContentTargetType) -> &'static str {
+    use crate::types::ContentTargetType;
+    match target_type {
+        ContentTargetType::Proposal => "proposals",
+        ContentTargetType::Program => "programs",
+        ContentTargetType::Video => "videos",
+        ContentTargetType::Comment => "comments",
+    }
+}
+
+/// Whether `target_id` references an existing row of `target_type`.
+#[cfg(feature = "server")]
+pub async fn target_exists(
+    pool: &Pool<Any>,
+    target_type: crate::types::ContentTargetType,
+    target_id: Uuid,
+) -> Result<bool, dioxus::prelude::ServerFnError> {
+    let sql = format!(
+        "select count(*) from {} where id = $1",
+        target_table(target_type)
+    );
+    let count: i64 = sqlx::query_scalar(&sql)
+        .bind(uuid_to_db(target_id))
+        .fetch_one(pool)
+        .await
+        .map_err(|e| dioxus::prelude::ServerFnError::new(e.to_string()))?;
+    Ok(count > 0)
+}
+
+/// Author/owner of a content target, if the row exists.
+#[cfg(feature = "server")]
+pub async fn target_owner(
+    pool: &Pool<Any>,
+    target_type: crate::types::ContentTargetType,
+    target_id: Uuid,
+) -> Result<Option<Uuid>, dioxus::prelude::ServerFnError> {
+    use crate::types::ContentTargetType;
+    let owner_col = match target_type {
+        ContentTargetType::Video => "owner_user_id",
+        _ => "author_user_id",
+    };
+    let sql = format!(
+        "select CAST({owner_col} as TEXT) from {} where id = $1",
+        target_table(target_type)
+    );
+    let owner: Option<String> = sqlx::query_scalar(&sql)
+        .bind(uuid_to_db(target_id))
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| dioxus::prelude::ServerFnError::new(e.to_string()))?;
+    owner.as_deref().map(uuid_from_db).transpose()
+}
+
 #[cfg(feature = "server")]
 pub fn is_sqlite() -> bool {
-    matches!(
-        crate::state::AppState::global().config.database,
-        DatabaseConfig::SQLite { .. }
-    )
+    crate::state::AppState::try_global()
+        .is_some_and(|state| matches!(state.config.database, DatabaseConfig::SQLite { .. }))
 }
 
 /// Backward compatibility function for existing server functions
@@ -108,3 +229,31 @@ pub fn is_sqlite() -> bool {
 pub async fn pool() -> Result<&'static Pool<Postgres>, sqlx::Error> {
     compat::pool().await
 }
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::datetime_from_db;
+
+    #[test]
+    fn datetime_from_db_accepts_backend_formats() {
+        let expected = time::macros::datetime!(2024-01-02 03:04:05 UTC);
+        for value in [
+            "2024-01-02T03:04:05Z",
+            "2024-01-02 03:04:05",
+            "2024-01-02 03:04:05+00",
+            "2024-01-02 03:04:05+00:00",
+            "2024-01-02 03:04:05+0000",
+        ] {
+            assert_eq!(datetime_from_db(value).unwrap(), expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn datetime_from_db_keeps_subseconds() {
+        let millis = datetime_from_db("2024-01-02 03:04:05.250").unwrap();
+        assert_eq!(millis.millisecond(), 250);
+        let micros = datetime_from_db("2024-01-02 03:04:05.123456+00").unwrap();
+        assert_eq!(micros.microsecond(), 123456);
+        assert_ne!(millis, datetime_from_db("2024-01-02 03:04:05").unwrap());
+    }
+}
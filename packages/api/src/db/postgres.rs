@@ -31,4 +31,11 @@ impl Database for PostgresDatabase {
             .context("Failed to run migrations")?;
         Ok(())
     }
+
+    fn expected_migrations(&self) -> Vec<i64> {
+        sqlx::migrate!("./migrations")
+            .iter()
+            .map(|m| m.version)
+            .collect()
+    }
 }
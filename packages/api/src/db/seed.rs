@@ -30,8 +30,8 @@ pub async fn seed_database(pool: &Pool<Any>) -> Result<()> {
         let auth_subject = user_id.clone();
         sqlx::query(
             r#"
-            INSERT INTO users (id, auth_subject, email, password_hash, email_verified)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO users (id, auth_subject, email, password_hash, email_verified, is_admin)
+            VALUES ($1, $2, $3, $4, $5, $6)
             "#,
         )
         .bind(&user_id)
@@ -39,6 +39,8 @@ pub async fn seed_database(pool: &Pool<Any>) -> Result<()> {
         .bind(email)
         .bind(&password_hash)
         .bind(true)
+        // The first seeded account doubles as the local moderator.
+        .bind(email == "user1@local.dev")
         .execute(pool)
         .await
         .with_context(|| format!("Failed to create user {email}"))?;
@@ -165,8 +167,8 @@ async fn create_proposals(
         )?;
         let id = sqlx::query_scalar::<_, String>(
             r#"
-            INSERT INTO proposals (author_user_id, title, summary, body_markdown, tags)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO proposals (author_user_id, title, summary, body_markdown, tags, slug)
+            VALUES ($1, $2, $3, $4, $5, $6)
             RETURNING CAST(id as TEXT)
             "#,
         )
@@ -175,6 +177,7 @@ async fn create_proposals(
         .bind(description)
         .bind(description)
         .bind(tags_json)
+        .bind(crate::slug::slugify(title))
         .fetch_one(pool)
         .await
         .context("Failed to create proposal")?;
@@ -189,8 +192,8 @@ async fn create_programs(pool: &Pool<Any>, user_id: &str, proposal_ids: &[String
     // Create program 1: Progressive platform
     let program1_id = sqlx::query_scalar::<_, String>(
         r#"
-        INSERT INTO programs (author_user_id, title, summary, body_markdown)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO programs (author_user_id, title, summary, body_markdown, slug)
+        VALUES ($1, $2, $3, $4, $5)
         RETURNING CAST(id as TEXT)
         "#,
     )
@@ -198,6 +201,7 @@ async fn create_programs(pool: &Pool<Any>, user_id: &str, proposal_ids: &[String
     .bind("Programme Progressiste 2027")
     .bind("Un programme ambitieux pour une société plus juste, écologique et démocratique.")
     .bind("Un programme ambitieux pour une société plus juste, écologique et démocratique.")
+    .bind(crate::slug::slugify("Programme Progressiste 2027"))
     .fetch_one(pool)
     .await
     .context("Failed to create program 1")?;
@@ -221,8 +225,8 @@ async fn create_programs(pool: &Pool<Any>, user_id: &str, proposal_ids: &[String
     // Create program 2: Ecological transition
     let program2_id = sqlx::query_scalar::<_, String>(
         r#"
-        INSERT INTO programs (author_user_id, title, summary, body_markdown)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO programs (author_user_id, title, summary, body_markdown, slug)
+        VALUES ($1, $2, $3, $4, $5)
         RETURNING CAST(id as TEXT)
         "#,
     )
@@ -230,6 +234,7 @@ async fn create_programs(pool: &Pool<Any>, user_id: &str, proposal_ids: &[String
     .bind("Transition Écologique Maintenant")
     .bind("Placer l'urgence climatique au cœur de l'action politique.")
     .bind("Placer l'urgence climatique au cœur de l'action politique.")
+    .bind(crate::slug::slugify("Transition Écologique Maintenant"))
     .fetch_one(pool)
     .await
     .context("Failed to create program 2")?;
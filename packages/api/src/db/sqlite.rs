@@ -76,4 +76,11 @@ impl Database for SqliteDatabase {
             .context("Failed to run migrations")?;
         Ok(())
     }
+
+    fn expected_migrations(&self) -> Vec<i64> {
+        sqlx::migrate!("./migrations/sqlite")
+            .iter()
+            .map(|m| m.version)
+            .collect()
+    }
 }
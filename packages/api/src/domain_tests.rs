@@ -129,3 +129,130 @@ async fn comments_and_activity_insert() {
 
     assert_eq!(count, 1);
 }
+
+#[tokio::test]
+async fn program_proposal_tags_decode_from_json_text() {
+    let pool = match crate::test_support::pool().await {
+        Some(p) => p,
+        None => return,
+    };
+    crate::test_support::reset_db().await.expect("reset db");
+
+    let sub = format!("test-sub-{}", Uuid::new_v4());
+    let user_id: Uuid =
+        sqlx::query_scalar("insert into users (auth_subject) values ($1) returning id")
+            .bind(sub)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+    let proposal_id: Uuid = sqlx::query_scalar(
+        "insert into proposals (author_user_id, title, summary, body_markdown, tags) values ($1, 'T', '', '', '{housing,\"rent cap\"}'::text[]) returning id",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    let program_id: Uuid = sqlx::query_scalar(
+        "insert into programs (author_user_id, title, summary, body_markdown) values ($1, 'P', '', '') returning id",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    sqlx::query("insert into program_items (program_id, proposal_id, position) values ($1, $2, 0)")
+        .bind(program_id)
+        .bind(proposal_id)
+        .execute(pool)
+        .await
+        .unwrap();
+
+    // Same projection `get_program` uses on Postgres.
+    let tags: String = sqlx::query_scalar(
+        "select to_json(pr.tags)::text from program_items pi join proposals pr on pr.id = pi.proposal_id where pi.program_id = $1",
+    )
+    .bind(program_id)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        crate::db::tags_from_db(&tags).expect("tags decode"),
+        vec!["housing", "rent cap"]
+    );
+}
+
+#[tokio::test]
+async fn sqlite_proposal_create_get_round_trip() {
+    let ctx = crate::test_support::sqlite_state().await;
+    let token = crate::test_support::signed_in_user(&ctx, "author@sqlite.test").await;
+
+    let created = crate::create_proposal(
+        token.clone(),
+        "Bike lanes".to_string(),
+        "Safer streets".to_string(),
+        "# Plan".to_string(),
+        "mobility, city".to_string(),
+    )
+    .await
+    .expect("create proposal");
+
+    let fetched = crate::get_proposal(created.id.to_string(), None)
+        .await
+        .expect("get proposal")
+        .proposal;
+    assert_eq!(fetched.id, created.id);
+    assert_eq!(fetched.title, "Bike lanes");
+    assert_eq!(fetched.body_markdown, "# Plan");
+    assert_eq!(fetched.tags, vec!["mobility", "city"]);
+
+    let vote = crate::set_vote(
+        token,
+        ContentTargetType::Proposal,
+        created.id.to_string(),
+        1,
+    )
+    .await
+    .expect("vote");
+    assert_eq!(vote.score, 1);
+    let fetched = crate::get_proposal(created.id.to_string(), None)
+        .await
+        .expect("get proposal")
+        .proposal;
+    assert_eq!(fetched.vote_score, 1);
+}
+
+#[tokio::test]
+async fn sqlite_comment_create_list_round_trip() {
+    let ctx = crate::test_support::sqlite_state().await;
+    let token = crate::test_support::signed_in_user(&ctx, "commenter@sqlite.test").await;
+    let proposal = crate::create_proposal(
+        token.clone(),
+        "Parks".to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("create proposal");
+
+    let comment = crate::create_comment(
+        token,
+        ContentTargetType::Proposal,
+        proposal.id.to_string(),
+        None,
+        "Love it".to_string(),
+    )
+    .await
+    .expect("create comment");
+
+    let listed = crate::list_comments(
+        ContentTargetType::Proposal,
+        proposal.id.to_string(),
+        10,
+        None,
+    )
+    .await
+    .expect("list comments");
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, comment.id);
+    assert_eq!(listed[0].body_markdown, "Love it");
+}
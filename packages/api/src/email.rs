@@ -1,5 +1,6 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use hmac::{Hmac, Mac};
 use rand::Rng;
 use sha2::{Digest, Sha256};
 use tracing::{debug, info, warn};
@@ -25,6 +26,28 @@ pub fn hash_token(token: &str) -> String {
     hex::encode(hasher.finalize())
 }
 
+/// HMAC-SHA256 keyed by `secret` over the purpose-tagged user id.
+fn unsubscribe_mac(secret: &str, user_id: uuid::Uuid) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(b"unsubscribe:");
+    mac.update(user_id.as_bytes());
+    mac
+}
+
+/// Token for the unsubscribe link in notification emails.
+///
+/// An HMAC of the user id rather than a stored value, so it stays valid until
+/// `JWT_SECRET` is rotated.
+pub fn unsubscribe_token(secret: &str, user_id: uuid::Uuid) -> String {
+    hex::encode(unsubscribe_mac(secret, user_id).finalize().into_bytes())
+}
+
+/// Whether `token` is `user_id`'s unsubscribe token, compared in constant time.
+pub fn verify_unsubscribe_token(secret: &str, user_id: uuid::Uuid, token: &str) -> bool {
+    hex::decode(token).is_ok_and(|tag| unsubscribe_mac(secret, user_id).verify_slice(&tag).is_ok())
+}
+
 use lettre::{
     message::{MultiPart, SinglePart},
     transport::smtp::authentication::Credentials,
@@ -233,6 +256,45 @@ pub async fn send_password_reset_email(
         .await
 }
 
+/// Send a comment or reply notification, with a link to the content and one to
+/// turn these emails off.
+pub async fn send_notification_email(
+    email_service: &dyn EmailService,
+    to: &str,
+    subject: &str,
+    content_url: &str,
+    unsubscribe_url: &str,
+) -> Result<()> {
+    info!(
+        "email.send_notification_email: to={} subject_len={}",
+        email_label(to),
+        subject.len()
+    );
+
+    let html = format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><meta charset="UTF-8"></head>
+<body style="font-family: sans-serif; max-width: 600px; margin: 0 auto; padding: 20px;">
+  <h1 style="color: #333;">{}</h1>
+  <p style="margin: 30px 0;">
+    <a href="{}" style="background-color: #007bff; color: white; padding: 12px 24px; text-decoration: none; border-radius: 4px; display: inline-block;">View the discussion</a>
+  </p>
+  <p style="color: #666; font-size: 14px;">Or copy this link: {}</p>
+  <p style="color: #666; font-size: 14px;">Don't want these emails? <a href="{}">Unsubscribe</a>.</p>
+</body>
+</html>"#,
+        subject, content_url, content_url, unsubscribe_url
+    );
+
+    let text = format!(
+        "{}\n\nView the discussion:\n\n{}\n\nTo stop these emails, visit:\n\n{}",
+        subject, content_url, unsubscribe_url
+    );
+
+    email_service.send_email(to, subject, &html, &text).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -259,6 +321,28 @@ mod tests {
         assert_eq!(hash1, hash2);
     }
 
+    #[test]
+    fn test_unsubscribe_token_is_per_user_and_secret() {
+        let alice = uuid::Uuid::from_u128(1);
+        let bob = uuid::Uuid::from_u128(2);
+        let token = unsubscribe_token("secret", alice);
+        assert_eq!(token, unsubscribe_token("secret", alice));
+        assert_ne!(token, unsubscribe_token("secret", bob));
+        assert_ne!(token, unsubscribe_token("other-secret", alice));
+    }
+
+    #[test]
+    fn test_verify_unsubscribe_token_checks_user_and_secret() {
+        let alice = uuid::Uuid::from_u128(1);
+        let bob = uuid::Uuid::from_u128(2);
+        let token = unsubscribe_token("secret", alice);
+        assert!(verify_unsubscribe_token("secret", alice, &token));
+        assert!(!verify_unsubscribe_token("secret", bob, &token));
+        assert!(!verify_unsubscribe_token("other-secret", alice, &token));
+        assert!(!verify_unsubscribe_token("secret", alice, &token[..32]));
+        assert!(!verify_unsubscribe_token("secret", alice, "not hex"));
+    }
+
     #[test]
     fn test_hash_token_produces_64_hex_chars() {
         let token = "test_token";
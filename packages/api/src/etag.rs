@@ -0,0 +1,173 @@
+//! Weak ETags for detail endpoints, so clients can skip re-rendering unchanged content.
+
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// A value a response's ETag depends on.
+///
+/// Each part is fed with a length or presence prefix, so adjacent parts can't run
+/// together (`("ab", "c")` and `("a", "bc")` hash differently).
+pub trait EtagPart {
+    fn feed(&self, hasher: &mut Sha256);
+}
+
+impl EtagPart for Uuid {
+    fn feed(&self, hasher: &mut Sha256) {
+        hasher.update(self.as_bytes());
+    }
+}
+
+impl EtagPart for OffsetDateTime {
+    fn feed(&self, hasher: &mut Sha256) {
+        hasher.update(self.unix_timestamp_nanos().to_be_bytes());
+    }
+}
+
+impl EtagPart for i64 {
+    fn feed(&self, hasher: &mut Sha256) {
+        hasher.update(self.to_be_bytes());
+    }
+}
+
+impl EtagPart for bool {
+    fn feed(&self, hasher: &mut Sha256) {
+        hasher.update([u8::from(*self)]);
+    }
+}
+
+impl EtagPart for String {
+    fn feed(&self, hasher: &mut Sha256) {
+        hasher.update((self.len() as u64).to_be_bytes());
+        hasher.update(self.as_bytes());
+    }
+}
+
+impl<T: EtagPart> EtagPart for Option<T> {
+    fn feed(&self, hasher: &mut Sha256) {
+        match self {
+            None => hasher.update([0]),
+            Some(value) => {
+                hasher.update([1]);
+                value.feed(hasher);
+            }
+        }
+    }
+}
+
+impl<T: EtagPart> EtagPart for Vec<T> {
+    fn feed(&self, hasher: &mut Sha256) {
+        hasher.update((self.len() as u64).to_be_bytes());
+        for item in self {
+            item.feed(hasher);
+        }
+    }
+}
+
+/// Weak ETag (`W/"…"`) over every part a response shows that can change, in display
+/// order: row ids and `updated_at`s, derived numbers such as vote scores and comment
+/// counts, joined fields such as display names, and caller-dependent flags.
+///
+/// Anything that changes one of those (an edit, a vote, a renamed author, reordering a
+/// program, a different caller) changes the tag; it doesn't identify a byte-exact body,
+/// hence weak.
+pub fn weak_etag<'a>(parts: impl IntoIterator<Item = &'a dyn EtagPart>) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        part.feed(&mut hasher);
+    }
+    format!("W/\"{}\"", &hex::encode(hasher.finalize())[..16])
+}
+
+/// Whether a client's `If-None-Match` value matches `etag`.
+///
+/// Accepts a comma-separated list and `*`, and compares weakly (ignoring `W/`).
+pub fn matches(if_none_match: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    if_none_match
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == opaque(etag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(unix: i64) -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(unix).unwrap()
+    }
+
+    #[test]
+    fn test_weak_etag_changes_with_any_part() {
+        let id = Uuid::from_u128(1);
+        let name = Some("Ada".to_string());
+        let base = weak_etag([&id as &dyn EtagPart, &at(100), &3i64, &name, &true]);
+        assert!(base.starts_with("W/\"") && base.ends_with('"'));
+        assert_eq!(
+            base,
+            weak_etag([&id as &dyn EtagPart, &at(100), &3i64, &name, &true])
+        );
+        assert_ne!(
+            base,
+            weak_etag([&id as &dyn EtagPart, &at(101), &3i64, &name, &true])
+        );
+        assert_ne!(
+            base,
+            weak_etag([&id as &dyn EtagPart, &at(100), &4i64, &name, &true])
+        );
+        assert_ne!(
+            base,
+            weak_etag([
+                &id as &dyn EtagPart,
+                &at(100),
+                &3i64,
+                &None::<String>,
+                &true
+            ])
+        );
+        assert_ne!(
+            base,
+            weak_etag([&id as &dyn EtagPart, &at(100), &3i64, &name, &false])
+        );
+        assert_ne!(
+            base,
+            weak_etag([
+                &Uuid::from_u128(2) as &dyn EtagPart,
+                &at(100),
+                &3i64,
+                &name,
+                &true
+            ])
+        );
+    }
+
+    #[test]
+    fn test_weak_etag_depends_on_order_and_boundaries() {
+        let (a, b) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        assert_ne!(
+            weak_etag([&a as &dyn EtagPart, &b]),
+            weak_etag([&b as &dyn EtagPart, &a])
+        );
+        let text = |s: &str| s.to_string();
+        assert_ne!(
+            weak_etag([&text("ab") as &dyn EtagPart, &text("c")]),
+            weak_etag([&text("a") as &dyn EtagPart, &text("bc")])
+        );
+        assert_ne!(
+            weak_etag([&vec![a] as &dyn EtagPart, &vec![b]]),
+            weak_etag([&vec![a, b] as &dyn EtagPart, &Vec::<Uuid>::new()])
+        );
+    }
+
+    #[test]
+    fn test_matches_compares_weakly() {
+        let etag = weak_etag([&Uuid::from_u128(1) as &dyn EtagPart, &at(100)]);
+        let strong = etag.trim_start_matches("W/");
+        assert!(matches(&etag, &etag));
+        assert!(matches(strong, &etag));
+        assert!(matches(&format!("\"other\", {etag}"), &etag));
+        assert!(matches("*", &etag));
+        assert!(!matches("\"other\"", &etag));
+        assert!(!matches("", &etag));
+    }
+}
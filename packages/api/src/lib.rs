@@ -4,9 +4,21 @@ use dioxus::prelude::*;
 pub mod config;
 pub mod types;
 
+#[cfg(feature = "server")]
+pub mod body_limit;
+
+#[cfg(feature = "server")]
+pub mod captcha;
+
+#[cfg(feature = "server")]
+pub mod client_ip;
+
 #[cfg(feature = "server")]
 pub mod db;
 
+#[cfg(feature = "server")]
+pub mod content_filter;
+
 #[cfg(feature = "server")]
 pub mod email;
 
@@ -16,12 +28,46 @@ pub mod storage;
 #[cfg(feature = "server")]
 pub mod state;
 
+#[cfg(feature = "server")]
+pub mod rate_limit;
+
+#[cfg(feature = "server")]
+pub mod sitemap;
+
+#[cfg(feature = "server")]
+mod etag;
+
+#[cfg(feature = "server")]
+mod mentions;
+
+#[cfg(feature = "server")]
+mod reading;
+
+#[cfg(feature = "server")]
+mod slug;
+
+#[cfg(feature = "server")]
+mod telemetry;
+
+#[cfg(feature = "server")]
+pub mod metrics;
+
+mod account;
 mod activity;
+mod audit;
 mod auth;
+mod blog;
+mod bookmarks;
+mod captions;
 mod comments;
+mod counts;
+mod moderation;
+mod notifications;
 mod profile;
 mod programs;
 mod proposals;
+pub mod schema;
+mod sync;
 mod uploads;
 mod video_feed;
 mod votes;
@@ -69,27 +115,58 @@ pub async fn detailed_health_check() -> Result<serde_json::Value, ServerFnError>
     Ok(health)
 }
 
-/// Metrics endpoint for monitoring
-#[get("/api/metrics")]
-pub async fn metrics_endpoint() -> Result<String, ServerFnError> {
-    #[cfg(feature = "server")]
-    tracing::debug!("metrics_endpoint");
+/// Applied vs embedded migration versions (admin only).
+#[post("/api/health/migrations")]
+pub async fn migration_health(id_token: String) -> Result<types::MigrationStatus, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id_token;
+        Err(ServerFnError::new("migration_health is server-only"))
+    }
 
-    // Simple metrics in Prometheus format
-    let metrics = r#"# HELP alelysee_requests_total Total number of requests
-# TYPE alelysee_requests_total counter
-alelysee_requests_total 0
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "migration_health",
+        tracing::debug!("migration_health");
+        auth::require_admin(id_token).await?;
+        let state = state::AppState::current()?;
+        db::migration_status(state.db.as_ref())
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))
+    }
+}
 
-# HELP alelysee_health_status Health check status (1=healthy, 0=unhealthy)
-# TYPE alelysee_health_status gauge
-alelysee_health_status 1
+/// Prometheus metrics; requires `?token=` when `METRICS_TOKEN` is configured.
+#[get("/metrics?token")]
+pub async fn metrics_endpoint(token: Option<String>) -> Result<String, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = token;
+        Err(ServerFnError::new("metrics_endpoint is server-only"))
+    }
 
-# HELP alelysee_uptime_seconds Time since application started
-# TYPE alelysee_uptime_seconds gauge
-alelysee_uptime_seconds 0
-"#;
+    #[cfg(feature = "server")]
+    {
+        tracing::debug!("metrics_endpoint");
+        let state = state::AppState::current()?;
+        if let Some(expected) = &state.config.metrics_token {
+            if !metrics::token_matches(token.as_deref(), expected) {
+                return Err(ServerFnError::new("forbidden"));
+            }
+        }
+        let pool = state.db.pool().await;
+        Ok(metrics::global().render(metrics::PoolStats {
+            size: pool.size(),
+            idle: pool.num_idle(),
+        }))
+    }
+}
 
-    Ok(metrics.to_string())
+/// Machine-readable description of the API surface.
+#[get("/api/_schema")]
+pub async fn api_schema() -> Result<serde_json::Value, ServerFnError> {
+    #[cfg(feature = "server")]
+    tracing::debug!("api_schema");
+    Ok(schema::schema_json())
 }
 
 /// Echo the user input on the server.
@@ -114,18 +191,52 @@ pub async fn auth_me(id_token: String) -> Result<auth::Me, ServerFnError> {
     auth::me_from_id_token(id_token).await
 }
 
-pub use activity::list_my_activity;
+pub use account::export_account;
+pub use activity::{list_global_activity, list_my_activity};
+pub use audit::list_admin_audit;
 pub use auth::{
     request_password_reset, resend_verification_email, reset_password, signin, signup, verify_email,
 };
-pub use comments::{create_comment, list_comments};
-pub use profile::upsert_profile;
-pub use programs::ProgramDetail;
-pub use programs::{add_program_item, create_program, get_program, list_programs, update_program};
-pub use proposals::{create_proposal, get_proposal, list_proposals, update_proposal};
-pub use uploads::{create_video_upload_intent, finalize_video_upload, list_videos};
+pub use auth::{CaptchaWidget, ProfileCompleteness, PublicConfig};
+pub use blog::{create_post, get_post, list_posts};
+pub use bookmarks::{list_bookmarks, toggle_bookmark};
+pub use captions::{create_caption_intent, finalize_caption};
+pub use comments::{
+    create_comment, create_comment_reply, delete_comment, get_comment, list_comments,
+    list_comments_by_author, list_top_comments, pin_comment, unpin_comment, update_comment,
+};
+pub use counts::recompute_counts;
+pub use moderation::{hide_content, moderation_get_proposal, report_content, unhide_content};
+pub use notifications::{
+    list_notifications, mark_all_notifications_read, mark_notification_read,
+    set_email_notifications, unread_notification_count, unsubscribe_email,
+};
+pub use profile::{get_public_profile, upsert_profile};
+pub use programs::{
+    add_program_item, count_programs, create_program, get_program, get_program_if_changed,
+    get_program_rank, list_programs, list_programs_by_author, list_proposal_programs,
+    remove_program_item, reorder_program_items, set_program_items, update_program,
+};
+pub use programs::{ProgramDetail, ProgramMembership};
+pub use proposals::ProposalDetail;
+pub use proposals::{
+    add_proposal_contributor, count_proposals, create_proposal, delete_proposal,
+    discover_proposals, find_similar_proposals, get_proposal, get_proposal_if_changed,
+    get_proposal_rank, list_proposals, list_proposals_by_author, list_proposals_by_tag,
+    list_related_proposals, list_trending_proposals, remove_proposal_contributor, search_proposals,
+    update_proposal,
+};
+pub use sync::sync_proposals;
+pub use uploads::VideoDetail;
+pub use uploads::{
+    complete_multipart_upload, create_video_upload_intent, finalize_video_upload, get_video,
+    get_video_playback_url, list_videos, list_videos_by_author, start_multipart_upload,
+    upload_multipart_part,
+};
 pub use video_feed::{
-    bookmark_video, list_bookmarked_videos, list_feed_videos, list_single_content_videos,
-    mark_video_viewed,
+    bookmark_video, list_bookmarked_videos, list_feed_videos, list_my_videos,
+    list_single_content_videos, mark_video_viewed,
+};
+pub use votes::{
+    clear_vote, get_vote_breakdown, get_vote_score, get_vote_state, list_my_votes, set_vote,
 };
-pub use votes::{get_vote_state, set_vote};
@@ -0,0 +1,158 @@
+//! `@name` mentions in comment bodies.
+//!
+//! A mention is `@` followed by a display name, with `_` standing in for spaces
+//! (`@Ada_L` mentions "Ada L."). Matching is case-insensitive and ignores trailing
+//! periods; names that match no profile are ignored.
+
+use dioxus::prelude::ServerFnError;
+use uuid::Uuid;
+
+/// Most distinct names resolved per comment, so one comment can't ping everyone.
+const MAX_MENTIONS: usize = 10;
+
+/// Lowercased, de-duplicated names mentioned in `body`, in order of appearance.
+///
+/// `@` only starts a mention at the beginning of the text or after a character
+/// that can't be part of a name, so e-mail addresses aren't mentions.
+pub fn parse_mentions(body: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut chars = body.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let starts_mention = c == '@' && !prev.is_some_and(is_name_char);
+        prev = Some(c);
+        if !starts_mention {
+            continue;
+        }
+        let rest = &body[i + 1..];
+        let len = rest
+            .char_indices()
+            .find(|(_, c)| !is_name_char(*c))
+            .map_or(rest.len(), |(end, _)| end);
+        // A sentence-ending period isn't part of the name.
+        let name = rest[..len].trim_end_matches('.').to_lowercase();
+        if !name.is_empty() && !names.contains(&name) {
+            names.push(name);
+        }
+        if names.len() == MAX_MENTIONS {
+            break;
+        }
+        while chars.peek().is_some_and(|(j, _)| *j <= i + len) {
+            prev = chars.next().map(|(_, c)| c);
+        }
+    }
+    names
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.')
+}
+
+/// Users whose display name matches one of the mentioned `names`.
+pub(crate) async fn resolve(
+    pool: &sqlx::Pool<sqlx::Any>,
+    names: &[String],
+) -> Result<Vec<Uuid>, ServerFnError> {
+    let mut user_ids = Vec::new();
+    for name in names {
+        let ids: Vec<String> = sqlx::query_scalar(
+            "select CAST(user_id as TEXT) from profiles where rtrim(lower(replace(display_name, ' ', '_')), '.') = $1",
+        )
+        .bind(name)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        for id in ids {
+            let id = crate::db::uuid_from_db(&id)?;
+            if !user_ids.contains(&id) {
+                user_ids.push(id);
+            }
+        }
+    }
+    Ok(user_ids)
+}
+
+/// Record who `comment_id` mentions.
+pub(crate) async fn store(
+    pool: &sqlx::Pool<sqlx::Any>,
+    comment_id: Uuid,
+    user_ids: &[Uuid],
+) -> Result<(), ServerFnError> {
+    for user_id in user_ids {
+        sqlx::query("insert into comment_mentions (comment_id, user_id) values ($1, $2)")
+            .bind(crate::db::uuid_to_db(comment_id))
+            .bind(crate::db::uuid_to_db(*user_id))
+            .execute(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Fill in `mentioned_user_ids` for a page of listed comments.
+pub(crate) async fn attach<'a>(
+    pool: &sqlx::Pool<sqlx::Any>,
+    comments: impl IntoIterator<Item = &'a mut crate::types::Comment>,
+) -> Result<(), ServerFnError> {
+    use sqlx::Row;
+
+    let mut comments: Vec<_> = comments.into_iter().collect();
+    if comments.is_empty() {
+        return Ok(());
+    }
+    let placeholders = (1..=comments.len())
+        .map(|n| format!("${n}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(
+        "select CAST(comment_id as TEXT) as comment_id, CAST(user_id as TEXT) as user_id \
+         from comment_mentions where comment_id in ({placeholders})"
+    );
+    let mut query = sqlx::query(&sql);
+    for comment in &comments {
+        query = query.bind(crate::db::uuid_to_db(comment.id));
+    }
+    let rows = query
+        .fetch_all(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    for row in rows {
+        let comment_id = crate::db::uuid_from_db(&row.get::<String, _>("comment_id"))?;
+        let user_id = crate::db::uuid_from_db(&row.get::<String, _>("user_id"))?;
+        if let Some(comment) = comments.iter_mut().find(|c| c.id == comment_id) {
+            comment.mentioned_user_ids.push(user_id);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mentions_finds_names() {
+        assert_eq!(parse_mentions("Thanks @marianne!"), vec!["marianne"]);
+        assert_eq!(
+            parse_mentions("@Ada_L. and @grace-h, see above."),
+            vec!["ada_l", "grace-h"]
+        );
+    }
+
+    #[test]
+    fn test_parse_mentions_dedupes_case_insensitively() {
+        assert_eq!(parse_mentions("@Bob @bob @BOB"), vec!["bob"]);
+    }
+
+    #[test]
+    fn test_parse_mentions_ignores_emails_and_bare_ats() {
+        assert!(parse_mentions("write to contact@example.org").is_empty());
+        assert!(parse_mentions("meet @ noon, @@").is_empty());
+    }
+
+    #[test]
+    fn test_parse_mentions_caps_count() {
+        let body = (0..20).map(|n| format!("@user{n}")).collect::<Vec<_>>();
+        assert_eq!(parse_mentions(&body.join(" ")).len(), MAX_MENTIONS);
+    }
+}
@@ -0,0 +1,197 @@
+//! Process-wide counters rendered in Prometheus text format at `/metrics`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use crate::types::ErrorCode;
+
+#[derive(Debug)]
+pub struct Metrics {
+    started: Instant,
+    requests: Mutex<BTreeMap<&'static str, u64>>,
+    /// Failures keyed by [`ErrorCode`] label, or [`UNKNOWN_ERROR`] when none parses.
+    errors: Mutex<BTreeMap<&'static str, u64>>,
+    uploads: AtomicU64,
+}
+
+/// Connection counts sampled from the DB pool at scrape time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PoolStats {
+    pub size: u32,
+    pub idle: usize,
+}
+
+/// `code` label for failures whose text carries no [`ErrorCode`] (database errors,
+/// bad tokens, ...).
+pub const UNKNOWN_ERROR: &str = "unknown";
+
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+/// Whether a scrape's `token` matches the configured `METRICS_TOKEN`.
+///
+/// Both sides are HMACed and compared with `verify_slice`, so the time taken says
+/// nothing about how much of the token, or its length, was right.
+pub fn token_matches(given: Option<&str>, expected: &str) -> bool {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    let mac = |key: &str| {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts any key length");
+        mac.update(b"metrics");
+        mac
+    };
+    given.is_some_and(|given| {
+        mac(expected)
+            .verify_slice(&mac(given).finalize().into_bytes())
+            .is_ok()
+    })
+}
+
+/// Shared instance; the first call starts the uptime clock.
+pub fn global() -> &'static Metrics {
+    METRICS.get_or_init(Metrics::new)
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            requests: Mutex::default(),
+            errors: Mutex::default(),
+            uploads: AtomicU64::new(0),
+        }
+    }
+
+    /// Count one server-function call, and its failure by error code when `error` holds
+    /// the failure's text.
+    pub fn record_request(&self, name: &'static str, error: Option<&str>) {
+        let mut requests = self.requests.lock().unwrap_or_else(|e| e.into_inner());
+        *requests.entry(name).or_default() += 1;
+        drop(requests);
+        if let Some(error) = error {
+            let code = ErrorCode::from_error(error).map_or(UNKNOWN_ERROR, |code| code.as_str());
+            let mut errors = self.errors.lock().unwrap_or_else(|e| e.into_inner());
+            *errors.entry(code).or_default() += 1;
+        }
+    }
+
+    pub fn record_upload(&self) {
+        self.uploads.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn requests(&self, name: &str) -> u64 {
+        let requests = self.requests.lock().unwrap_or_else(|e| e.into_inner());
+        requests.get(name).copied().unwrap_or(0)
+    }
+
+    /// Failures recorded under `code`, an [`ErrorCode`] label or [`UNKNOWN_ERROR`].
+    pub fn errors(&self, code: &str) -> u64 {
+        let errors = self.errors.lock().unwrap_or_else(|e| e.into_inner());
+        errors.get(code).copied().unwrap_or(0)
+    }
+
+    pub fn uploads(&self) -> u64 {
+        self.uploads.load(Ordering::Relaxed)
+    }
+
+    pub fn render(&self, pool: PoolStats) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP alelysee_requests_total Server function calls by function\n");
+        out.push_str("# TYPE alelysee_requests_total counter\n");
+        for (name, count) in self
+            .requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+        {
+            let _ = writeln!(out, "alelysee_requests_total{{fn=\"{name}\"}} {count}");
+        }
+
+        out.push_str(
+            "\n# HELP alelysee_request_errors_total Failed server function calls by error code\n",
+        );
+        out.push_str("# TYPE alelysee_request_errors_total counter\n");
+        for (code, count) in self.errors.lock().unwrap_or_else(|e| e.into_inner()).iter() {
+            let _ = writeln!(
+                out,
+                "alelysee_request_errors_total{{code=\"{code}\"}} {count}"
+            );
+        }
+
+        out.push_str("\n# HELP alelysee_uploads_total Finalized video uploads\n");
+        out.push_str("# TYPE alelysee_uploads_total counter\n");
+        let _ = writeln!(out, "alelysee_uploads_total {}", self.uploads());
+
+        let in_use = (pool.size as usize).saturating_sub(pool.idle);
+        out.push_str("\n# HELP alelysee_db_connections Database pool connections by state\n");
+        out.push_str("# TYPE alelysee_db_connections gauge\n");
+        let _ = writeln!(out, "alelysee_db_connections{{state=\"in_use\"}} {in_use}");
+        let _ = writeln!(
+            out,
+            "alelysee_db_connections{{state=\"idle\"}} {}",
+            pool.idle
+        );
+
+        out.push_str(
+            "\n# HELP alelysee_health_status Health check status (1=healthy, 0=unhealthy)\n",
+        );
+        out.push_str("# TYPE alelysee_health_status gauge\n");
+        out.push_str("alelysee_health_status 1\n");
+
+        out.push_str("\n# HELP alelysee_uptime_seconds Time since application started\n");
+        out.push_str("# TYPE alelysee_uptime_seconds gauge\n");
+        let _ = writeln!(
+            out,
+            "alelysee_uptime_seconds {}",
+            self.started.elapsed().as_secs()
+        );
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counts_requests_and_errors() {
+        let metrics = Metrics::new();
+        let not_found = ErrorCode::NotFound.to_string();
+        metrics.record_request("votes.set_vote", None);
+        metrics.record_request("votes.set_vote", Some(&not_found));
+        metrics.record_request("comments.list_comments", None);
+        metrics.record_request("comments.create_comment", Some("database is locked"));
+        metrics.record_upload();
+
+        let text = metrics.render(PoolStats { size: 4, idle: 1 });
+        assert!(text.contains("alelysee_requests_total{fn=\"votes.set_vote\"} 2\n"));
+        assert!(text.contains("alelysee_requests_total{fn=\"comments.list_comments\"} 1\n"));
+        assert!(text.contains("alelysee_request_errors_total{code=\"not_found\"} 1\n"));
+        assert!(text.contains("alelysee_request_errors_total{code=\"unknown\"} 1\n"));
+        assert!(!text.contains("alelysee_request_errors_total{fn="));
+        assert!(text.contains("alelysee_uploads_total 1\n"));
+        assert!(text.contains("alelysee_db_connections{state=\"in_use\"} 3\n"));
+        assert!(text.contains("alelysee_db_connections{state=\"idle\"} 1\n"));
+    }
+
+    #[test]
+    fn test_token_matches_only_the_exact_token() {
+        assert!(token_matches(Some("scrape-me"), "scrape-me"));
+        assert!(!token_matches(Some("scrape-m"), "scrape-me"));
+        assert!(!token_matches(Some("scrape-me!"), "scrape-me"));
+        assert!(!token_matches(Some(""), "scrape-me"));
+        assert!(!token_matches(None, "scrape-me"));
+    }
+}
@@ -0,0 +1,260 @@
+use crate::types::{ContentTargetType, ModeratedProposal};
+use dioxus::prelude::*;
+#[cfg(feature = "server")]
+use tracing::info;
+
+/// Set or clear `hidden_at` on a target; errors when the target doesn't exist.
+///
+/// Unhiding a proposal also bumps its `updated_at`, so `sync_proposals` resends it.
+#[cfg(feature = "server")]
+async fn set_hidden(
+    id_token: String,
+    target_type: ContentTargetType,
+    target_id: String,
+    hidden: bool,
+) -> Result<(), ServerFnError> {
+    use crate::types::AdminAction;
+    use uuid::Uuid;
+
+    crate::state::AppState::current()?.ensure_writable()?;
+
+    let admin_id = crate::auth::require_admin(id_token).await?;
+    let tid = Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
+    info!(
+        "moderation.set_hidden: admin_id={} target_type={:?} target_id={} hidden={}",
+        admin_id, target_type, tid, hidden
+    );
+    let state = crate::state::AppState::current()?;
+    let pool = state.db.pool().await;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let table = crate::db::target_table(target_type);
+    let value = if hidden { crate::db::now_sql() } else { "null" };
+    // Sync already told clients a hidden proposal was removed; moving `updated_at`
+    // past their cursor is what sends it to them again once it is back.
+    let reappear = if !hidden && target_type == ContentTargetType::Proposal {
+        format!(", updated_at = {}", crate::db::now_sql())
+    } else {
+        String::new()
+    };
+    let result = sqlx::query(&format!(
+        "update {table} set hidden_at = {value}{reappear} where id = $1"
+    ))
+    .bind(crate::db::uuid_to_db(tid))
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+    if result.rows_affected() == 0 {
+        return Err(ServerFnError::new("target not found"));
+    }
+    let action = if hidden {
+        AdminAction::Hide
+    } else {
+        AdminAction::Unhide
+    };
+    crate::audit::record(&mut *tx, admin_id, action, Some((target_type, tid)))
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    tx.commit()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    if target_type == ContentTargetType::Comment {
+        crate::counts::refresh_for_comment(pool, tid).await?;
+    }
+    Ok(())
+}
+
+/// Hide a proposal/program/comment/video from public listings (reversible).
+#[dioxus::prelude::post("/api/moderation/hide")]
+pub async fn hide_content(
+    id_token: String,
+    target_type: ContentTargetType,
+    target_id: String,
+) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, target_type, target_id);
+        Err(ServerFnError::new("hide_content is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "moderation.hide_content",
+        set_hidden(id_token, target_type, target_id, true).await
+    }
+}
+
+#[dioxus::prelude::post("/api/moderation/unhide")]
+pub async fn unhide_content(
+    id_token: String,
+    target_type: ContentTargetType,
+    target_id: String,
+) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, target_type, target_id);
+        Err(ServerFnError::new("unhide_content is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "moderation.unhide_content",
+        set_hidden(id_token, target_type, target_id, false).await
+    }
+}
+
+/// Report a target as abusive; one report per user and target.
+///
+/// The report that brings a target to `report_auto_hide_threshold` distinct reporters
+/// hides it pending review and notifies admins, in the same transaction.
+#[dioxus::prelude::post("/api/moderation/report")]
+pub async fn report_content(
+    id_token: String,
+    target_type: ContentTargetType,
+    target_id: String,
+    reason: String,
+) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, target_type, target_id, reason);
+        Err(ServerFnError::new("report_content is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "moderation.report_content",
+        use crate::types::NotificationKind;
+        use uuid::Uuid;
+
+        crate::state::AppState::current()?.ensure_writable()?;
+
+        let reporter = crate::auth::require_user_id(id_token).await?;
+        let tid = Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+        if !crate::db::target_exists(pool, target_type, tid).await? {
+            return Err(ServerFnError::new("target not found"));
+        }
+
+        // Any early return drops the transaction, which rolls it back.
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let inserted = sqlx::query(
+            r#"
+            insert into reports (reporter_user_id, target_type, target_id, reason)
+            values ($1, $2, $3, $4)
+            on conflict (reporter_user_id, target_type, target_id) do nothing
+            "#,
+        )
+        .bind(crate::db::uuid_to_db(reporter))
+        .bind(target_type.as_db())
+        .bind(crate::db::uuid_to_db(tid))
+        .bind(reason.trim())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .rows_affected();
+        if inserted == 0 {
+            info!(
+                "moderation.report_content: duplicate reporter={} target_id={}",
+                reporter, tid
+            );
+            return Ok(());
+        }
+
+        let reporters: i64 = sqlx::query_scalar(
+            "select count(distinct reporter_user_id) from reports where target_type = $1 and target_id = $2",
+        )
+        .bind(target_type.as_db())
+        .bind(crate::db::uuid_to_db(tid))
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        info!(
+            "moderation.report_content: target_type={:?} target_id={} reporters={}",
+            target_type, tid, reporters
+        );
+
+        // Only the report that reaches the threshold hides, so an admin's unhide sticks.
+        let threshold = i64::from(state.config.report_auto_hide_threshold);
+        if threshold > 0 && reporters == threshold {
+            let hidden = sqlx::query(&format!(
+                "update {table} set hidden_at = {now} where id = $1 and hidden_at is null",
+                table = crate::db::target_table(target_type),
+                now = crate::db::now_sql(),
+            ))
+            .bind(crate::db::uuid_to_db(tid))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?
+            .rows_affected();
+
+            if hidden > 0 {
+                let admins: Vec<String> =
+                    sqlx::query_scalar("select CAST(id as TEXT) from users where is_admin = true")
+                        .fetch_all(&mut *tx)
+                        .await
+                        .map_err(|e| ServerFnError::new(e.to_string()))?;
+                for admin in admins {
+                    crate::notifications::notify(
+                        &mut *tx,
+                        crate::db::uuid_from_db(&admin)?,
+                        NotificationKind::AutoHidden,
+                        reporter,
+                        target_type,
+                        tid,
+                        None,
+                    )
+                    .await
+                    .map_err(|e| ServerFnError::new(e.to_string()))?;
+                }
+                info!(
+                    "moderation.report_content: auto-hidden target_type={:?} target_id={}",
+                    target_type, tid
+                );
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        // An auto-hidden comment no longer counts towards its target.
+        if target_type == ContentTargetType::Comment {
+            crate::counts::refresh_for_comment(pool, tid).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Fetch a proposal regardless of visibility, flagged with `hidden_at`.
+#[dioxus::prelude::post("/api/moderation/proposal")]
+pub async fn moderation_get_proposal(
+    id_token: String,
+    id: String,
+) -> Result<ModeratedProposal, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, id);
+        Err(ServerFnError::new("moderation_get_proposal is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "moderation.moderation_get_proposal",
+        use uuid::Uuid;
+
+        crate::auth::require_admin(id_token).await?;
+        let pid = Uuid::parse_str(&id).map_err(|_| ServerFnError::new("invalid id"))?;
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        let (proposal, hidden_at) = crate::proposals::fetch_proposal(pool, pid, true).await?;
+        Ok(ModeratedProposal {
+            proposal,
+            hidden_at,
+        })
+    }
+}
@@ -0,0 +1,386 @@
+use crate::types::Notification;
+use dioxus::prelude::*;
+#[cfg(feature = "server")]
+use tracing::{debug, info};
+
+/// Insert a notification for `recipient` (best-effort callers ignore the error).
+///
+/// Self-notifications are skipped.
+#[cfg(feature = "server")]
+pub(crate) async fn notify<'e>(
+    executor: impl sqlx::Executor<'e, Database = sqlx::Any>,
+    recipient: uuid::Uuid,
+    kind: crate::types::NotificationKind,
+    actor: uuid::Uuid,
+    target_type: crate::types::ContentTargetType,
+    target_id: uuid::Uuid,
+    comment_id: Option<uuid::Uuid>,
+) -> Result<(), sqlx::Error> {
+    if recipient == actor {
+        return Ok(());
+    }
+    sqlx::query(
+        r#"
+        insert into notifications (user_id, kind, actor_user_id, target_type, target_id, comment_id)
+        values ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(crate::db::uuid_to_db(recipient))
+    .bind(kind.as_db())
+    .bind(crate::db::uuid_to_db(actor))
+    .bind(target_type.as_db())
+    .bind(crate::db::uuid_to_db(target_id))
+    .bind(comment_id.map(crate::db::uuid_to_db))
+    .execute(executor)
+    .await?;
+    debug!(
+        "notifications.notify: recipient={} kind={:?}",
+        recipient, kind
+    );
+    Ok(())
+}
+
+/// Email `recipient` about a comment or reply if their profile opted in; returns
+/// whether an email went out (best-effort callers ignore the error).
+///
+/// Other kinds stay in-app only.
+#[cfg(feature = "server")]
+pub(crate) async fn email(
+    pool: &sqlx::Pool<sqlx::Any>,
+    recipient: uuid::Uuid,
+    kind: crate::types::NotificationKind,
+    actor: uuid::Uuid,
+    target_type: crate::types::ContentTargetType,
+    target_id: uuid::Uuid,
+) -> Result<bool, ServerFnError> {
+    use crate::types::{ContentTargetType, NotificationKind};
+    use sqlx::Row;
+
+    let subject = match kind {
+        NotificationKind::Comment => "New comment on your content",
+        NotificationKind::Reply => "New reply to your comment",
+        NotificationKind::AutoHidden | NotificationKind::Mention => return Ok(false),
+    };
+    if recipient == actor {
+        return Ok(false);
+    }
+    let row = sqlx::query(
+        "select u.email, p.notify_email from users u join profiles p on p.user_id = u.id where u.id = $1",
+    )
+    .bind(crate::db::uuid_to_db(recipient))
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let Some(row) = row.filter(|row| crate::db::bool_from_row(row, "notify_email")) else {
+        return Ok(false);
+    };
+    let Some(to) = row.get::<Option<String>, _>("email") else {
+        return Ok(false);
+    };
+
+    let state = crate::state::AppState::current()?;
+    let base_url = state.config.app_base_url.trim_end_matches('/');
+    let content_url = match target_type {
+        ContentTargetType::Proposal => format!("{base_url}/proposals/{target_id}"),
+        ContentTargetType::Program => format!("{base_url}/programs/{target_id}"),
+        ContentTargetType::Video => format!("{base_url}/videos/{target_id}"),
+        ContentTargetType::Comment => format!("{base_url}/"),
+    };
+    let unsubscribe_url = format!(
+        "{base_url}/notifications/unsubscribe?user_id={recipient}&token={}",
+        crate::email::unsubscribe_token(&state.config.jwt_secret, recipient)
+    );
+    crate::email::send_notification_email(
+        state.email.as_ref(),
+        &to,
+        subject,
+        &content_url,
+        &unsubscribe_url,
+    )
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+    debug!(
+        "notifications.email: recipient={} kind={:?}",
+        recipient, kind
+    );
+    Ok(true)
+}
+
+#[cfg(feature = "server")]
+fn notification_from_row(row: &sqlx::any::AnyRow) -> Result<Notification, ServerFnError> {
+    use crate::types::{ContentTargetType, NotificationKind};
+    use sqlx::Row;
+
+    let opt_uuid = |col: &str| -> Result<Option<uuid::Uuid>, ServerFnError> {
+        row.get::<Option<String>, _>(col)
+            .as_deref()
+            .map(crate::db::uuid_from_db)
+            .transpose()
+    };
+    let read_at = row
+        .get::<Option<String>, _>("read_at")
+        .as_deref()
+        .map(crate::db::datetime_from_db)
+        .transpose()?;
+
+    Ok(Notification {
+        id: crate::db::uuid_from_db(&row.get::<String, _>("id"))?,
+        user_id: crate::db::uuid_from_db(&row.get::<String, _>("user_id"))?,
+        kind: NotificationKind::from_db(&row.get::<String, _>("kind"))
+            .ok_or_else(|| ServerFnError::new("invalid notification kind"))?,
+        actor_user_id: opt_uuid("actor_user_id")?,
+        target_type: ContentTargetType::from_db(&row.get::<String, _>("target_type"))
+            .ok_or_else(|| ServerFnError::new("invalid target_type"))?,
+        target_id: crate::db::uuid_from_db(&row.get::<String, _>("target_id"))?,
+        comment_id: opt_uuid("comment_id")?,
+        created_at: crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?,
+        read_at,
+    })
+}
+
+/// The caller's notifications, newest first; `kind` narrows to one type.
+#[dioxus::prelude::post("/api/notifications/list")]
+pub async fn list_notifications(
+    id_token: String,
+    limit: i64,
+    kind: Option<crate::types::NotificationKind>,
+) -> Result<Vec<Notification>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, limit, kind);
+        Err(ServerFnError::new("list_notifications is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "notifications.list_notifications",
+        debug!(
+            "notifications.list_notifications: limit={} kind={:?}",
+            limit, kind
+        );
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let state = crate::state::AppState::current()?;
+        let limit = state.clamp_limit(limit);
+        let pool = state.db.pool().await;
+
+        let kind_filter = if kind.is_some() { "and kind = $3" } else { "" };
+        let sql = format!(
+            r#"
+            select
+                CAST(id as TEXT) as id,
+                CAST(user_id as TEXT) as user_id,
+                kind,
+                CAST(actor_user_id as TEXT) as actor_user_id,
+                CAST(target_type as TEXT) as target_type,
+                CAST(target_id as TEXT) as target_id,
+                CAST(comment_id as TEXT) as comment_id,
+                CAST(created_at as TEXT) as created_at,
+                CAST(read_at as TEXT) as read_at
+            from notifications
+            where user_id = $1 {kind_filter}
+            order by created_at desc
+            limit $2
+            "#
+        );
+        let mut query = sqlx::query(&sql)
+            .bind(crate::db::uuid_to_db(user_id))
+            .bind(limit);
+        if let Some(kind) = kind {
+            query = query.bind(kind.as_db());
+        }
+        let rows = query
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let items = rows
+            .iter()
+            .map(notification_from_row)
+            .collect::<Result<Vec<_>, _>>()?;
+        debug!("notifications.list_notifications: count={}", items.len());
+        Ok(items)
+    }
+}
+
+/// Mark one of the caller's notifications as read.
+#[dioxus::prelude::post("/api/notifications/mark_read")]
+pub async fn mark_notification_read(
+    id_token: String,
+    notification_id: String,
+) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, notification_id);
+        Err(ServerFnError::new("mark_notification_read is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "notifications.mark_notification_read",
+        use uuid::Uuid;
+
+        crate::state::AppState::current()?.ensure_writable()?;
+
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let nid =
+            Uuid::parse_str(&notification_id).map_err(|_| ServerFnError::new("invalid id"))?;
+        info!(
+            "notifications.mark_notification_read: user_id={} id={}",
+            user_id, nid
+        );
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        let sql = format!(
+            "update notifications set read_at = {} where id = $1 and user_id = $2 and read_at is null",
+            crate::db::now_sql()
+        );
+        sqlx::query(&sql)
+            .bind(crate::db::uuid_to_db(nid))
+            .bind(crate::db::uuid_to_db(user_id))
+            .execute(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Mark every unread notification of the caller as read.
+#[dioxus::prelude::post("/api/notifications/mark_all_read")]
+pub async fn mark_all_notifications_read(id_token: String) -> Result<u64, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id_token;
+        Err(ServerFnError::new(
+            "mark_all_notifications_read is server-only",
+        ))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "notifications.mark_all_notifications_read",
+        crate::state::AppState::current()?.ensure_writable()?;
+
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        let marked = sqlx::query(&format!(
+            "update notifications set read_at = {} where user_id = $1 and read_at is null",
+            crate::db::now_sql()
+        ))
+        .bind(crate::db::uuid_to_db(user_id))
+        .execute(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .rows_affected();
+        info!(
+            "notifications.mark_all_notifications_read: user_id={} marked={}",
+            user_id, marked
+        );
+        Ok(marked)
+    }
+}
+
+/// Number of unread notifications for the caller (navbar badge).
+#[dioxus::prelude::post("/api/notifications/unread_count")]
+pub async fn unread_notification_count(id_token: String) -> Result<i64, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id_token;
+        Err(ServerFnError::new(
+            "unread_notification_count is server-only",
+        ))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "notifications.unread_notification_count",
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        let count: i64 = sqlx::query_scalar(
+            "select count(*) from notifications where user_id = $1 and read_at is null",
+        )
+        .bind(crate::db::uuid_to_db(user_id))
+        .fetch_one(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        debug!(
+            "notifications.unread_notification_count: user_id={} count={}",
+            user_id, count
+        );
+        Ok(count)
+    }
+}
+
+/// Turn emails for comments on your content and replies to you on or off.
+///
+/// Requires a profile; returns the new setting.
+#[dioxus::prelude::post("/api/notifications/email_preference")]
+pub async fn set_email_notifications(
+    id_token: String,
+    enabled: bool,
+) -> Result<bool, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, enabled);
+        Err(ServerFnError::new("set_email_notifications is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "notifications.set_email_notifications",
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let pool = state.db.pool().await;
+
+        let updated = sqlx::query("update profiles set notify_email = $1 where user_id = $2")
+            .bind(enabled)
+            .bind(crate::db::uuid_to_db(user_id))
+            .execute(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?
+            .rows_affected();
+        if updated == 0 {
+            return Err(ServerFnError::new("profile not found"));
+        }
+        info!(
+            "notifications.set_email_notifications: user_id={} enabled={}",
+            user_id, enabled
+        );
+        Ok(enabled)
+    }
+}
+
+/// Turn notification emails off without signing in, with the `user_id` and `token`
+/// from an email's unsubscribe link.
+///
+/// The link opens a confirmation page that posts here, so mail scanners prefetching
+/// it can't unsubscribe anyone.
+#[dioxus::prelude::post("/api/notifications/unsubscribe")]
+pub async fn unsubscribe_email(user_id: String, token: String) -> Result<String, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (user_id, token);
+        Err(ServerFnError::new("unsubscribe_email is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "notifications.unsubscribe_email",
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        let uid = uuid::Uuid::parse_str(&user_id)
+            .map_err(|_| ServerFnError::new("invalid unsubscribe link"))?;
+        if !crate::email::verify_unsubscribe_token(&state.config.jwt_secret, uid, &token) {
+            return Err(ServerFnError::new("invalid unsubscribe link"));
+        }
+        let pool = state.db.pool().await;
+
+        sqlx::query("update profiles set notify_email = $1 where user_id = $2")
+            .bind(false)
+            .bind(crate::db::uuid_to_db(uid))
+            .execute(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        info!("notifications.unsubscribe_email: user_id={}", uid);
+        Ok("You will no longer receive notification emails.".to_string())
+    }
+}
@@ -1,7 +1,7 @@
-use crate::types::Profile;
+use crate::types::{Profile, PublicProfile};
 use dioxus::prelude::*;
 #[cfg(feature = "server")]
-use tracing::info;
+use tracing::{debug, info};
 
 #[dioxus::prelude::post("/api/profile/upsert")]
 pub async fn upsert_profile(
@@ -10,53 +10,88 @@ pub async fn upsert_profile(
     bio: String,
     avatar_url: Option<String>,
     location: Option<String>,
+    expected_updated_at: Option<time::OffsetDateTime>,
 ) -> Result<Profile, ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = (id_token, display_name, bio, avatar_url, location);
+        let _ = (
+            id_token,
+            display_name,
+            bio,
+            avatar_url,
+            location,
+            expected_updated_at,
+        );
         Err(ServerFnError::new("upsert_profile is server-only"))
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "profile.upsert_profile",
         use sqlx::Row;
+
+        crate::state::AppState::current()?.ensure_writable()?;
+
         info!(
             "profile.upsert_profile: display_name_len={} bio_len={}",
             display_name.len(),
             bio.len()
         );
         let user_id = crate::auth::require_user_id(id_token).await?;
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
         let pool = state.db.pool().await;
 
-        let row = sqlx::query(
+        // `expected_updated_at` is `None` when the client saw no profile yet.
+        let current: Option<String> = sqlx::query_scalar(
+            "select CAST(updated_at as TEXT) from profiles where user_id = $1",
+        )
+        .bind(crate::db::uuid_to_db(user_id))
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        let unchanged = match (current.as_deref(), expected_updated_at) {
+            (None, None) => true,
+            (Some(current), Some(expected)) => crate::db::datetime_from_db(current)? == expected,
+            _ => false,
+        };
+        if !unchanged {
+            info!("profile.upsert_profile: conflict user_id={}", user_id);
+            return Err(ServerFnError::new(crate::types::CONFLICT_ERROR));
+        }
+
+        // The `updated_at` guard makes the check above atomic with the write.
+        let row = sqlx::query(&format!(
             r#"
             insert into profiles (user_id, display_name, bio, avatar_url, location, updated_at)
-            values ($1, $2, $3, $4, $5, now())
+            values ($1, $2, $3, $4, $5, {now})
             on conflict (user_id)
             do update set
                 display_name = excluded.display_name,
                 bio = excluded.bio,
                 avatar_url = excluded.avatar_url,
                 location = excluded.location,
-                updated_at = now()
+                updated_at = {now}
+            where CAST(profiles.updated_at as TEXT) = $6
             returning
                 CAST(user_id as TEXT) as user_id,
                 display_name,
                 bio,
                 avatar_url,
                 location,
+                notify_email,
                 CAST(updated_at as TEXT) as updated_at
             "#,
-        )
+            now = crate::db::now_sql(),
+        ))
         .bind(crate::db::uuid_to_db(user_id))
         .bind(&display_name)
         .bind(&bio)
         .bind(&avatar_url)
         .bind(&location)
-        .fetch_one(pool)
+        .bind(&current)
+        .fetch_optional(pool)
         .await
-        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .ok_or_else(|| ServerFnError::new(crate::types::CONFLICT_ERROR))?;
 
         info!("profile.upsert_profile: user_id={}", user_id);
         Ok(Profile {
@@ -65,7 +100,65 @@ pub async fn upsert_profile(
             bio: row.get("bio"),
             avatar_url: row.get("avatar_url"),
             location: row.get("location"),
+            notify_email: crate::db::bool_from_row(&row, "notify_email"),
             updated_at: crate::db::datetime_from_db(&row.get::<String, _>("updated_at"))?,
         })
     }
 }
+
+/// A user's public profile and last-active time; `NotFound` for unknown users.
+#[dioxus::prelude::post("/api/profile/:user_id")]
+pub async fn get_public_profile(
+    user_id: String,
+    id_token: Option<String>,
+) -> Result<PublicProfile, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (user_id, id_token);
+        Err(ServerFnError::new("get_public_profile is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "profile.get_public_profile",
+        use sqlx::Row;
+
+        debug!("profile.get_public_profile: user_id={}", user_id);
+        crate::auth::reader_id(id_token).await?;
+        let uid =
+            uuid::Uuid::parse_str(&user_id).map_err(|_| ServerFnError::new("invalid user_id"))?;
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        let row = sqlx::query(
+            r#"
+            select
+                p.display_name,
+                coalesce(p.bio, '') as bio,
+                p.avatar_url,
+                p.location,
+                CAST(u.last_seen_at as TEXT) as last_seen_at
+            from users u
+            left join profiles p on p.user_id = u.id
+            where u.id = $1
+            "#,
+        )
+        .bind(crate::db::uuid_to_db(uid))
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .ok_or_else(|| ServerFnError::new(crate::types::ErrorCode::NotFound.to_string()))?;
+
+        let last_seen_at = match row.get::<Option<String>, _>("last_seen_at") {
+            Some(value) => Some(crate::db::datetime_from_db(&value)?),
+            None => None,
+        };
+        Ok(PublicProfile {
+            user_id: uid,
+            display_name: row.get("display_name"),
+            bio: row.get("bio"),
+            avatar_url: row.get("avatar_url"),
+            location: row.get("location"),
+            last_seen_at,
+        })
+    }
+}
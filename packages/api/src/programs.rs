@@ -1,4 +1,4 @@
-use crate::types::{Program, Proposal};
+use crate::types::{Conditional, Program, Proposal};
 use dioxus::prelude::*;
 #[cfg(feature = "server")]
 use tracing::{debug, info};
@@ -7,6 +7,69 @@ use tracing::{debug, info};
 pub struct ProgramDetail {
     pub program: Program,
     pub proposals: Vec<Proposal>,
+    /// Whether the caller wrote the program; always false without a token.
+    pub is_author: bool,
+    /// Visible comments on the program.
+    pub comment_count: i64,
+    /// Net score from voters with a verified email. `program.vote_score` equals it
+    /// under the `verified_only` vote policy.
+    pub verified_vote_score: i64,
+    /// Weak ETag of the program, its scores, comment count and author name, the caller's
+    /// `is_author`, and its listed proposals, for `get_program_if_changed`.
+    pub etag: String,
+}
+
+/// A program bundling a given proposal, with the proposal's position in it.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProgramMembership {
+    pub program: Program,
+    pub position: i32,
+}
+
+#[cfg(feature = "server")]
+pub(crate) fn program_from_row(row: &sqlx::any::AnyRow) -> Result<Program, ServerFnError> {
+    use sqlx::Row;
+
+    Ok(Program {
+        id: crate::db::uuid_from_db(&row.get::<String, _>("id"))?,
+        author_user_id: crate::db::uuid_from_db(&row.get::<String, _>("author_user_id"))?,
+        title: row.get("title"),
+        slug: row.get("slug"),
+        summary: row.get("summary"),
+        body_markdown: row.get("body_markdown"),
+        created_at: crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?,
+        updated_at: crate::db::datetime_from_db(&row.get::<String, _>("updated_at"))?,
+        vote_score: row.get::<i64, _>("vote_score"),
+        author_display_name: row.get("author_display_name"),
+    })
+}
+
+/// Check `user_id` wrote program `pid`: `NotFound` when there is no such program,
+/// `NotAllowed` when someone else wrote it.
+#[cfg(feature = "server")]
+async fn ensure_program_owner(
+    pool: &sqlx::Pool<sqlx::Any>,
+    pid: uuid::Uuid,
+    user_id: uuid::Uuid,
+) -> Result<(), ServerFnError> {
+    use crate::types::ErrorCode;
+
+    let owner = sqlx::query_scalar::<_, String>(
+        "select CAST(author_user_id as TEXT) from programs where id = $1",
+    )
+    .bind(crate::db::uuid_to_db(pid))
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?
+    .ok_or_else(|| ServerFnError::new(ErrorCode::NotFound.to_string()))?;
+    if crate::db::uuid_from_db(&owner)? != user_id {
+        info!(
+            "programs.ensure_program_owner: forbidden program_id={} user_id={}",
+            pid, user_id
+        );
+        return Err(ServerFnError::new(ErrorCode::NotAllowed.to_string()));
+    }
+    Ok(())
 }
 
 #[dioxus::prelude::post("/api/programs/create")]
@@ -23,22 +86,26 @@ pub async fn create_program(
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "programs.create_program",
         use sqlx::Row;
 
+        crate::state::AppState::current()?.ensure_writable()?;
+
         info!("programs.create_program: title_len={}", title.len());
         let author_user_id = crate::auth::require_user_id(id_token).await?;
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
         let pool = state.db.pool().await;
+        let slug = crate::slug::unique_slug(pool, "programs", &title, None).await?;
 
         let row = sqlx::query(
             r#"
-            insert into programs (author_user_id, title, summary, body_markdown)
-            values ($1, $2, $3, $4)
+            insert into programs (author_user_id, title, summary, body_markdown, slug)
+            values ($1, $2, $3, $4, $5)
             returning
                 CAST(id as TEXT) as id,
                 CAST(author_user_id as TEXT) as author_user_id,
                 title,
+                slug,
                 summary,
                 body_markdown,
                 CAST(created_at as TEXT) as created_at,
@@ -49,6 +116,7 @@ pub async fn create_program(
         .bind(&title)
         .bind(&summary)
         .bind(&body_markdown)
+        .bind(&slug)
         .fetch_one(pool)
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
@@ -73,11 +141,13 @@ pub async fn create_program(
             id,
             author_user_id,
             title: row.get("title"),
+            slug: row.get("slug"),
             summary: row.get("summary"),
             body_markdown: row.get("body_markdown"),
             created_at,
             updated_at,
             vote_score: 0,
+            author_display_name: None,
         })
     }
 }
@@ -96,8 +166,11 @@ pub async fn add_program_item(
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "programs.add_program_item",
         use uuid::Uuid;
+
+        crate::state::AppState::current()?.ensure_writable()?;
+
         debug!(
             "programs.add_program_item: program_id={} proposal_id={} position={}",
             program_id, proposal_id, position
@@ -108,22 +181,20 @@ pub async fn add_program_item(
         let prop_id =
             Uuid::parse_str(&proposal_id).map_err(|_| ServerFnError::new("invalid proposal_id"))?;
 
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
         let pool = state.db.pool().await;
 
-        // Ownership check (program author)
-        let owner = sqlx::query_scalar::<_, String>(
-            "select CAST(author_user_id as TEXT) from programs where id = $1",
+        ensure_program_owner(pool, pid, user_id).await?;
+
+        let already_bundled = sqlx::query(
+            "select 1 from program_items where program_id = $1 and proposal_id = $2",
         )
         .bind(crate::db::uuid_to_db(pid))
-        .fetch_one(pool)
+        .bind(crate::db::uuid_to_db(prop_id))
+        .fetch_optional(pool)
         .await
-        .map_err(|e| ServerFnError::new(e.to_string()))?;
-        let owner = crate::db::uuid_from_db(&owner)?;
-        if owner != user_id {
-            info!("programs.add_program_item: forbidden user_id={}", user_id);
-            return Err(ServerFnError::new("not allowed"));
-        }
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .is_some();
 
         sqlx::query(
             "insert into program_items (program_id, proposal_id, position) values ($1, $2, $3) on conflict (program_id, proposal_id) do update set position = excluded.position",
@@ -135,6 +206,17 @@ pub async fn add_program_item(
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
 
+        // Activity log (best-effort): only a new bundling is recorded, not a reposition.
+        if !already_bundled {
+            let _ = sqlx::query(
+                "insert into activity (user_id, action, target_type, target_id) values ($1, 'bundled', 'proposal', $2)",
+            )
+            .bind(crate::db::uuid_to_db(user_id))
+            .bind(crate::db::uuid_to_db(prop_id))
+            .execute(pool)
+            .await;
+        }
+
         info!(
             "programs.add_program_item: ok program_id={} proposal_id={}",
             program_id, proposal_id
@@ -143,197 +225,674 @@ pub async fn add_program_item(
     }
 }
 
+/// Replace a program's bundle with the given ordered proposal ids in one transaction.
+#[dioxus::prelude::post("/api/programs/set_items")]
+pub async fn set_program_items(
+    id_token: String,
+    program_id: String,
+    proposal_ids: Vec<String>,
+) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, program_id, proposal_ids);
+        Err(ServerFnError::new("set_program_items is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "programs.set_program_items",
+        use uuid::Uuid;
+
+        crate::state::AppState::current()?.ensure_writable()?;
+
+        debug!(
+            "programs.set_program_items: program_id={} count={}",
+            program_id,
+            proposal_ids.len()
+        );
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let pid =
+            Uuid::parse_str(&program_id).map_err(|_| ServerFnError::new("invalid program_id"))?;
+        let mut ids = Vec::with_capacity(proposal_ids.len());
+        for raw in &proposal_ids {
+            let id = Uuid::parse_str(raw).map_err(|_| ServerFnError::new("invalid proposal_id"))?;
+            if ids.contains(&id) {
+                return Err(ServerFnError::new("duplicate proposal_id"));
+            }
+            ids.push(id);
+        }
+
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        ensure_program_owner(pool, pid, user_id).await?;
+
+        // Any early return drops the transaction, which rolls it back.
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        for id in &ids {
+            let exists: i64 = sqlx::query_scalar("select count(*) from proposals where id = $1")
+                .bind(crate::db::uuid_to_db(*id))
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?;
+            if exists == 0 {
+                info!("programs.set_program_items: unknown proposal_id={}", id);
+                return Err(ServerFnError::new(format!("proposal not found: {id}")));
+            }
+        }
+
+        sqlx::query("delete from program_items where program_id = $1")
+            .bind(crate::db::uuid_to_db(pid))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        for (position, id) in ids.iter().enumerate() {
+            sqlx::query(
+                "insert into program_items (program_id, proposal_id, position) values ($1, $2, $3)",
+            )
+            .bind(crate::db::uuid_to_db(pid))
+            .bind(crate::db::uuid_to_db(*id))
+            .bind(position as i32)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        info!(
+            "programs.set_program_items: ok program_id={} count={}",
+            program_id,
+            ids.len()
+        );
+        Ok(())
+    }
+}
+
+/// Take one proposal out of a program's bundle; the program author only.
+#[dioxus::prelude::post("/api/programs/remove_item")]
+pub async fn remove_program_item(
+    id_token: String,
+    program_id: String,
+    proposal_id: String,
+) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, program_id, proposal_id);
+        Err(ServerFnError::new("remove_program_item is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "programs.remove_program_item",
+        use uuid::Uuid;
+
+        crate::state::AppState::current()?.ensure_writable()?;
+
+        debug!(
+            "programs.remove_program_item: program_id={} proposal_id={}",
+            program_id, proposal_id
+        );
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let pid =
+            Uuid::parse_str(&program_id).map_err(|_| ServerFnError::new("invalid program_id"))?;
+        let prop_id =
+            Uuid::parse_str(&proposal_id).map_err(|_| ServerFnError::new("invalid proposal_id"))?;
+
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        ensure_program_owner(pool, pid, user_id).await?;
+
+        let removed = sqlx::query(
+            "delete from program_items where program_id = $1 and proposal_id = $2",
+        )
+        .bind(crate::db::uuid_to_db(pid))
+        .bind(crate::db::uuid_to_db(prop_id))
+        .execute(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .rows_affected();
+        if removed == 0 {
+            return Err(ServerFnError::new(crate::types::ErrorCode::NotFound.to_string()));
+        }
+
+        info!(
+            "programs.remove_program_item: ok program_id={} proposal_id={}",
+            program_id, proposal_id
+        );
+        Ok(())
+    }
+}
+
+/// Rewrite a program's item positions to follow `ordered_proposal_ids`, in one
+/// transaction. Every id must already be in the program; members left out keep
+/// their relative order after the listed ones.
+#[dioxus::prelude::post("/api/programs/reorder_items")]
+pub async fn reorder_program_items(
+    id_token: String,
+    program_id: String,
+    ordered_proposal_ids: Vec<String>,
+) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, program_id, ordered_proposal_ids);
+        Err(ServerFnError::new("reorder_program_items is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "programs.reorder_program_items",
+        use uuid::Uuid;
+
+        crate::state::AppState::current()?.ensure_writable()?;
+
+        debug!(
+            "programs.reorder_program_items: program_id={} count={}",
+            program_id,
+            ordered_proposal_ids.len()
+        );
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let pid =
+            Uuid::parse_str(&program_id).map_err(|_| ServerFnError::new("invalid program_id"))?;
+        let mut ids = Vec::with_capacity(ordered_proposal_ids.len());
+        for raw in &ordered_proposal_ids {
+            let id = Uuid::parse_str(raw).map_err(|_| ServerFnError::new("invalid proposal_id"))?;
+            if ids.contains(&id) {
+                return Err(ServerFnError::new("duplicate proposal_id"));
+            }
+            ids.push(id);
+        }
+
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        ensure_program_owner(pool, pid, user_id).await?;
+
+        // Any early return drops the transaction, which rolls it back.
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let members = sqlx::query_scalar::<_, String>(
+            "select CAST(proposal_id as TEXT) from program_items where program_id = $1 order by position, proposal_id",
+        )
+        .bind(crate::db::uuid_to_db(pid))
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .iter()
+        .map(|id| crate::db::uuid_from_db(id))
+        .collect::<Result<Vec<_>, _>>()?;
+        if let Some(id) = ids.iter().find(|id| !members.contains(id)) {
+            info!("programs.reorder_program_items: not a member proposal_id={}", id);
+            return Err(ServerFnError::new(format!("proposal not in program: {id}")));
+        }
+        let rest: Vec<Uuid> = members.into_iter().filter(|id| !ids.contains(id)).collect();
+
+        for (position, id) in ids.iter().chain(&rest).enumerate() {
+            sqlx::query(
+                "update program_items set position = $3 where program_id = $1 and proposal_id = $2",
+            )
+            .bind(crate::db::uuid_to_db(pid))
+            .bind(crate::db::uuid_to_db(*id))
+            .bind(position as i32)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        info!(
+            "programs.reorder_program_items: ok program_id={} count={}",
+            program_id,
+            ids.len()
+        );
+        Ok(())
+    }
+}
+
+/// Which programs `list_programs` shows, over programs `p`; `count_programs`
+/// counts the same rows.
+#[cfg(feature = "server")]
+const LISTED_PROGRAMS: &str = "p.hidden_at is null";
+
 #[dioxus::prelude::post("/api/programs/list")]
-pub async fn list_programs(limit: i64) -> Result<Vec<Program>, ServerFnError> {
+pub async fn list_programs(
+    limit: i64,
+    id_token: Option<String>,
+) -> Result<Vec<Program>, ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = limit;
+        let _ = (limit, id_token);
         Err(ServerFnError::new("list_programs is server-only"))
     }
 
     #[cfg(feature = "server")]
-    {
-        use sqlx::Row;
+    crate::telemetry::traced! { "programs.list_programs",
         debug!("programs.list_programs: limit={}", limit);
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let limit = state.clamp_limit(limit);
         let pool = state.db.pool().await;
-        let rows = sqlx::query(
+        let sql = format!(
             r#"
             select
                 CAST(p.id as TEXT) as id,
                 CAST(p.author_user_id as TEXT) as author_user_id,
                 p.title,
+                coalesce(p.slug, CAST(p.id as TEXT)) as slug,
                 p.summary,
                 p.body_markdown,
                 CAST(p.created_at as TEXT) as created_at,
                 CAST(p.updated_at as TEXT) as updated_at,
-                coalesce(sum(v.value), 0) as vote_score
+                coalesce(sum(v.value), 0) as vote_score,
+                ap.display_name as author_display_name
             from programs p
             left join votes v
                 on v.target_type = 'program' and v.target_id = p.id
-            group by p.id
+            left join profiles ap on ap.user_id = p.author_user_id
+            where {LISTED_PROGRAMS}
+            group by p.id, ap.display_name
             order by p.created_at desc
             limit $1
-            "#,
-        )
-        .bind(limit)
-        .fetch_all(pool)
-        .await
-        .map_err(|e| ServerFnError::new(e.to_string()))?;
+            "#
+        );
+        let rows = sqlx::query(&sql)
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
 
-        let mut programs = Vec::with_capacity(rows.len());
-        for row in rows {
-            let id = crate::db::uuid_from_db(&row.get::<String, _>("id"))?;
-            let author_user_id = crate::db::uuid_from_db(&row.get::<String, _>("author_user_id"))?;
-            let created_at = crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?;
-            let updated_at = crate::db::datetime_from_db(&row.get::<String, _>("updated_at"))?;
-            programs.push(Program {
-                id,
-                author_user_id,
-                title: row.get("title"),
-                summary: row.get("summary"),
-                body_markdown: row.get("body_markdown"),
-                created_at,
-                updated_at,
-                vote_score: row.get::<i64, _>("vote_score"),
-            });
-        }
+        let programs = rows
+            .iter()
+            .map(program_from_row)
+            .collect::<Result<Vec<_>, _>>()?;
 
         debug!("programs.list_programs: count={}", programs.len());
         Ok(programs)
     }
 }
 
-#[dioxus::prelude::get("/api/programs/get/:id")]
-pub async fn get_program(id: String) -> Result<ProgramDetail, ServerFnError> {
+/// Number of programs `list_programs` pages over (hidden ones excluded); programs
+/// have no search or tag filters to honour.
+#[dioxus::prelude::post("/api/programs/count")]
+pub async fn count_programs(id_token: Option<String>) -> Result<i64, ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = id;
-        Err(ServerFnError::new("get_program is server-only"))
+        let _ = id_token;
+        Err(ServerFnError::new("count_programs is server-only"))
     }
 
     #[cfg(feature = "server")]
+    crate::telemetry::traced! { "programs.count_programs",
+        crate::auth::reader_id(id_token).await?;
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+        let sql = format!("select count(*) from programs p where {LISTED_PROGRAMS}");
+        let count: i64 = sqlx::query_scalar(&sql)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        debug!("programs.count_programs: count={}", count);
+        Ok(count)
+    }
+}
+
+/// A user's programs with vote scores, newest first; hidden ones are skipped.
+#[dioxus::prelude::post("/api/programs/by_author/:user_id")]
+pub async fn list_programs_by_author(
+    user_id: String,
+    limit: i64,
+    offset: i64,
+    id_token: Option<String>,
+) -> Result<Vec<Program>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
     {
-        use sqlx::Row;
+        let _ = (user_id, limit, offset, id_token);
+        Err(ServerFnError::new("list_programs_by_author is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "programs.list_programs_by_author",
         use uuid::Uuid;
 
-        debug!("programs.get_program: id={}", id);
-        let program_id = Uuid::parse_str(&id).map_err(|_| ServerFnError::new("invalid id"))?;
-        let state = crate::state::AppState::global();
+        debug!(
+            "programs.list_programs_by_author: user_id={} limit={} offset={}",
+            user_id, limit, offset
+        );
+        let uid = Uuid::parse_str(&user_id).map_err(|_| ServerFnError::new("invalid user_id"))?;
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let (limit, offset) = state.page(limit, offset)?;
         let pool = state.db.pool().await;
-
-        let row = sqlx::query(
+        let rows = sqlx::query(
             r#"
             select
                 CAST(p.id as TEXT) as id,
                 CAST(p.author_user_id as TEXT) as author_user_id,
                 p.title,
+                coalesce(p.slug, CAST(p.id as TEXT)) as slug,
                 p.summary,
                 p.body_markdown,
                 CAST(p.created_at as TEXT) as created_at,
                 CAST(p.updated_at as TEXT) as updated_at,
-                coalesce(sum(v.value), 0) as vote_score
+                coalesce(sum(v.value), 0) as vote_score,
+                ap.display_name as author_display_name
             from programs p
             left join votes v
                 on v.target_type = 'program' and v.target_id = p.id
-            where p.id = $1
-            group by p.id
+            left join profiles ap on ap.user_id = p.author_user_id
+            where p.author_user_id = $1 and p.hidden_at is null
+            group by p.id, ap.display_name
+            order by p.created_at desc, p.id desc
+            limit $2 offset $3
             "#,
         )
+        .bind(crate::db::uuid_to_db(uid))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let programs = rows
+            .iter()
+            .map(program_from_row)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        debug!("programs.list_programs_by_author: count={}", programs.len());
+        Ok(programs)
+    }
+}
+
+/// A program and its proposals; `id` is either the UUID or the slug.
+#[dioxus::prelude::post("/api/programs/get/:id")]
+pub async fn get_program(
+    id: String,
+    id_token: Option<String>,
+) -> Result<ProgramDetail, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, id_token);
+        Err(ServerFnError::new("get_program is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "programs.get_program",
+        debug!("programs.get_program: id={}", id);
+        load_detail(&id, id_token).await
+    }
+}
+
+/// `get_program`, unless `etag` (the client's `If-None-Match`) still matches.
+#[dioxus::prelude::post("/api/programs/get/:id/if_changed")]
+pub async fn get_program_if_changed(
+    id: String,
+    etag: String,
+    id_token: Option<String>,
+) -> Result<Conditional<ProgramDetail>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, etag, id_token);
+        Err(ServerFnError::new("get_program_if_changed is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "programs.get_program_if_changed",
+        debug!("programs.get_program_if_changed: id={} etag={}", id, etag);
+        let detail = load_detail(&id, id_token).await?;
+        if crate::etag::matches(&etag, &detail.etag) {
+            return Ok(Conditional::NotModified);
+        }
+        Ok(Conditional::Modified(detail))
+    }
+}
+
+#[cfg(feature = "server")]
+async fn load_detail(id: &str, id_token: Option<String>) -> Result<ProgramDetail, ServerFnError> {
+    use sqlx::Row;
+
+    // As for proposals: check the caller before the lookup can answer `NotFound`.
+    let caller = crate::auth::reader_id(id_token).await?;
+    let state = crate::state::AppState::current()?;
+    let pool = state.db.pool().await;
+    let program_id = crate::slug::resolve_id(pool, "programs", id).await?;
+
+    let row = sqlx::query(
+        r#"
+        select
+            CAST(p.id as TEXT) as id,
+            CAST(p.author_user_id as TEXT) as author_user_id,
+            p.title,
+            coalesce(p.slug, CAST(p.id as TEXT)) as slug,
+            p.summary,
+            p.body_markdown,
+            CAST(p.created_at as TEXT) as created_at,
+            CAST(p.updated_at as TEXT) as updated_at,
+            coalesce(sum(v.value), 0) as vote_score,
+            ap.display_name as author_display_name
+        from programs p
+        left join votes v
+            on v.target_type = 'program' and v.target_id = p.id
+        left join profiles ap on ap.user_id = p.author_user_id
+        where p.id = $1 and p.hidden_at is null
+        group by p.id, ap.display_name
+        "#,
+    )
+    .bind(crate::db::uuid_to_db(program_id))
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?
+    .ok_or_else(|| ServerFnError::new(crate::types::ErrorCode::NotFound.to_string()))?;
+
+    let id = crate::db::uuid_from_db(&row.get::<String, _>("id"))?;
+    let author_user_id = crate::db::uuid_from_db(&row.get::<String, _>("author_user_id"))?;
+    let created_at = crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?;
+    let updated_at = crate::db::datetime_from_db(&row.get::<String, _>("updated_at"))?;
+
+    let verified_vote_score =
+        crate::votes::verified_score(pool, crate::types::ContentTargetType::Program, program_id)
+            .await?;
+    let vote_score = match state.config.vote_policy {
+        crate::config::VotePolicy::All => row.get::<i64, _>("vote_score"),
+        crate::config::VotePolicy::VerifiedOnly => verified_vote_score,
+    };
+    let program = Program {
+        id,
+        author_user_id,
+        title: row.get("title"),
+        slug: row.get("slug"),
+        summary: row.get("summary"),
+        body_markdown: row.get("body_markdown"),
+        created_at,
+        updated_at,
+        vote_score,
+        author_display_name: row.get("author_display_name"),
+    };
+
+    let sql = if crate::db::is_sqlite() {
+        r#"
+        select
+            CAST(pr.id as TEXT) as id,
+            CAST(pr.author_user_id as TEXT) as author_user_id,
+            pr.title,
+            coalesce(pr.slug, CAST(pr.id as TEXT)) as slug,
+            pr.summary,
+            pr.body_markdown,
+            pr.tags,
+            CAST(pr.created_at as TEXT) as created_at,
+            CAST(pr.updated_at as TEXT) as updated_at,
+            coalesce(sum(v.value), 0) as vote_score,
+            ap.display_name as author_display_name
+        from program_items pi
+        join proposals pr on pr.id = pi.proposal_id
+        left join votes v
+            on v.target_type = 'proposal' and v.target_id = pr.id
+        left join profiles ap on ap.user_id = pr.author_user_id
+        where pi.program_id = $1 and pr.hidden_at is null
+        group by pr.id, pi.position, ap.display_name
+        order by pi.position asc
+        "#
+    } else {
+        r#"
+        select
+            CAST(pr.id as TEXT) as id,
+            CAST(pr.author_user_id as TEXT) as author_user_id,
+            pr.title,
+            coalesce(pr.slug, CAST(pr.id as TEXT)) as slug,
+            pr.summary,
+            pr.body_markdown,
+            to_json(pr.tags)::text as tags,
+            CAST(pr.created_at as TEXT) as created_at,
+            CAST(pr.updated_at as TEXT) as updated_at,
+            coalesce(sum(v.value), 0) as vote_score,
+            ap.display_name as author_display_name
+        from program_items pi
+        join proposals pr on pr.id = pi.proposal_id
+        left join votes v
+            on v.target_type = 'proposal' and v.target_id = pr.id
+        left join profiles ap on ap.user_id = pr.author_user_id
+        where pi.program_id = $1 and pr.hidden_at is null
+        group by pr.id, pi.position, ap.display_name
+        order by pi.position asc
+        "#
+    };
+
+    let proposal_rows = sqlx::query(sql)
         .bind(crate::db::uuid_to_db(program_id))
-        .fetch_one(pool)
+        .fetch_all(pool)
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
 
-        let id = crate::db::uuid_from_db(&row.get::<String, _>("id"))?;
-        let author_user_id = crate::db::uuid_from_db(&row.get::<String, _>("author_user_id"))?;
-        let created_at = crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?;
-        let updated_at = crate::db::datetime_from_db(&row.get::<String, _>("updated_at"))?;
+    let mut proposals = Vec::with_capacity(proposal_rows.len());
+    for row in proposal_rows {
+        proposals.push(crate::proposals::proposal_from_row(&row)?);
+    }
 
-        let program = Program {
-            id,
-            author_user_id,
-            title: row.get("title"),
-            summary: row.get("summary"),
-            body_markdown: row.get("body_markdown"),
-            created_at,
-            updated_at,
-            vote_score: row.get::<i64, _>("vote_score"),
-        };
+    debug!(
+        "programs.get_program: program_id={} proposals={}",
+        program_id,
+        proposals.len()
+    );
+    let is_author = caller == Some(program.author_user_id);
+    let comment_count =
+        crate::counts::comment_count(pool, crate::types::ContentTargetType::Program, program_id)
+            .await?;
+    let mut parts = vec![
+        &program.id as &dyn crate::etag::EtagPart,
+        &program.updated_at,
+        &program.vote_score,
+        &verified_vote_score,
+        &comment_count,
+        &program.author_display_name,
+        &is_author,
+    ];
+    for p in &proposals {
+        parts.extend([
+            &p.id as &dyn crate::etag::EtagPart,
+            &p.updated_at,
+            &p.vote_score,
+            &p.author_display_name,
+        ]);
+    }
+    let etag = crate::etag::weak_etag(parts);
+    Ok(ProgramDetail {
+        program,
+        proposals,
+        is_author,
+        comment_count,
+        verified_vote_score,
+        etag,
+    })
+}
 
-        let sql = if crate::db::is_sqlite() {
-            r#"
-            select
-                CAST(pr.id as TEXT) as id,
-                CAST(pr.author_user_id as TEXT) as author_user_id,
-                pr.title,
-                pr.summary,
-                pr.body_markdown,
-                pr.tags,
-                CAST(pr.created_at as TEXT) as created_at,
-                CAST(pr.updated_at as TEXT) as updated_at,
-                coalesce(sum(v.value), 0) as vote_score
-            from program_items pi
-            join proposals pr on pr.id = pi.proposal_id
-            left join votes v
-                on v.target_type = 'proposal' and v.target_id = pr.id
-            where pi.program_id = $1
-            group by pr.id, pi.position
-            order by pi.position asc
-            "#
-        } else {
+/// Programs that include the proposal `id`, highest-scored first.
+#[dioxus::prelude::post("/api/proposals/:id/programs")]
+pub async fn list_proposal_programs(
+    id: String,
+    id_token: Option<String>,
+) -> Result<Vec<ProgramMembership>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, id_token);
+        Err(ServerFnError::new("list_proposal_programs is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "programs.list_proposal_programs",
+        use sqlx::Row;
+
+        debug!("programs.list_proposal_programs: id={}", id);
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let pool = state.db.pool().await;
+        let proposal_id = crate::slug::resolve_id(pool, "proposals", &id).await?;
+
+        let rows = sqlx::query(
             r#"
             select
-                CAST(pr.id as TEXT) as id,
-                CAST(pr.author_user_id as TEXT) as author_user_id,
-                pr.title,
-                pr.summary,
-                pr.body_markdown,
-                to_json(pr.tags)::text as tags,
-                CAST(pr.created_at as TEXT) as created_at,
-                CAST(pr.updated_at as TEXT) as updated_at,
+                CAST(p.id as TEXT) as id,
+                CAST(p.author_user_id as TEXT) as author_user_id,
+                p.title,
+                coalesce(p.slug, CAST(p.id as TEXT)) as slug,
+                p.summary,
+                p.body_markdown,
+                CAST(p.created_at as TEXT) as created_at,
+                CAST(p.updated_at as TEXT) as updated_at,
+                CAST(pi.position as BIGINT) as position,
                 coalesce(sum(v.value), 0) as vote_score
             from program_items pi
-            join proposals pr on pr.id = pi.proposal_id
+            join programs p on p.id = pi.program_id
             left join votes v
-                on v.target_type = 'proposal' and v.target_id = pr.id
-            where pi.program_id = $1
-            group by pr.id, pi.position
-            order by pi.position asc
-            "#
-        };
-
-        let proposal_rows = sqlx::query(sql)
-            .bind(crate::db::uuid_to_db(program_id))
-            .fetch_all(pool)
-            .await
-            .map_err(|e| ServerFnError::new(e.to_string()))?;
+                on v.target_type = 'program' and v.target_id = p.id
+            where pi.proposal_id = $1 and p.hidden_at is null
+            group by p.id, pi.position
+            order by vote_score desc, p.created_at desc
+            "#,
+        )
+        .bind(crate::db::uuid_to_db(proposal_id))
+        .fetch_all(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
 
-        let mut proposals = Vec::with_capacity(proposal_rows.len());
-        for row in proposal_rows {
+        let mut memberships = Vec::with_capacity(rows.len());
+        for row in rows {
             let id = crate::db::uuid_from_db(&row.get::<String, _>("id"))?;
             let author_user_id = crate::db::uuid_from_db(&row.get::<String, _>("author_user_id"))?;
             let created_at = crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?;
             let updated_at = crate::db::datetime_from_db(&row.get::<String, _>("updated_at"))?;
-            proposals.push(Proposal {
-                id,
-                author_user_id,
-                title: row.get("title"),
-                summary: row.get("summary"),
-                body_markdown: row.get("body_markdown"),
-                tags: crate::db::tags_from_db(&row.get::<String, _>("tags"))?,
-                created_at,
-                updated_at,
-                vote_score: row.get::<i64, _>("vote_score"),
+            memberships.push(ProgramMembership {
+                program: Program {
+                    id,
+                    author_user_id,
+                    title: row.get("title"),
+                    slug: row.get("slug"),
+                    summary: row.get("summary"),
+                    body_markdown: row.get("body_markdown"),
+                    created_at,
+                    updated_at,
+                    vote_score: row.get::<i64, _>("vote_score"),
+                    author_display_name: None,
+                },
+                position: row.get::<i64, _>("position") as i32,
             });
         }
 
         debug!(
-            "programs.get_program: program_id={} proposals={}",
-            program_id,
-            proposals.len()
+            "programs.list_proposal_programs: proposal_id={} count={}",
+            proposal_id,
+            memberships.len()
         );
-        Ok(ProgramDetail { program, proposals })
+        Ok(memberships)
     }
 }
 
@@ -344,62 +903,86 @@ pub async fn update_program(
     title: String,
     summary: String,
     body_markdown: String,
+    expected_updated_at: time::OffsetDateTime,
 ) -> Result<Program, ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = (id_token, id, title, summary, body_markdown);
+        let _ = (
+            id_token,
+            id,
+            title,
+            summary,
+            body_markdown,
+            expected_updated_at,
+        );
         Err(ServerFnError::new("update_program is server-only"))
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "programs.update_program",
         use sqlx::Row;
         use uuid::Uuid;
 
+        crate::state::AppState::current()?.ensure_writable()?;
+
         info!("programs.update_program: id={}", id);
         let user_id = crate::auth::require_user_id(id_token).await?;
         let program_id = Uuid::parse_str(&id).map_err(|_| ServerFnError::new("invalid id"))?;
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
         let pool = state.db.pool().await;
 
-        let owner = sqlx::query_scalar::<_, String>(
-            "select CAST(author_user_id as TEXT) from programs where id = $1",
+        let current = sqlx::query(
+            "select CAST(author_user_id as TEXT) as author_user_id, CAST(updated_at as TEXT) as updated_at from programs where id = $1",
         )
         .bind(crate::db::uuid_to_db(program_id))
         .fetch_one(pool)
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
-        let owner = crate::db::uuid_from_db(&owner)?;
+        let owner = crate::db::uuid_from_db(&current.get::<String, _>("author_user_id"))?;
         if owner != user_id {
             info!("programs.update_program: forbidden user_id={}", user_id);
-            return Err(ServerFnError::new("not allowed"));
+            return Err(ServerFnError::new(crate::types::ErrorCode::NotAllowed.to_string()));
+        }
+        let current_updated_at: String = current.get("updated_at");
+        if crate::db::datetime_from_db(&current_updated_at)? != expected_updated_at {
+            info!("programs.update_program: conflict id={}", program_id);
+            return Err(ServerFnError::new(crate::types::CONFLICT_ERROR));
         }
 
-        let row = sqlx::query(
+        let slug = crate::slug::unique_slug(pool, "programs", &title, Some(program_id)).await?;
+
+        // The `updated_at` guard makes the check above atomic with the write.
+        let row = sqlx::query(&format!(
             r#"
             update programs
             set title = $2,
                 summary = $3,
                 body_markdown = $4,
-                updated_at = now()
-            where id = $1
+                slug = $6,
+                updated_at = {now}
+            where id = $1 and CAST(updated_at as TEXT) = $5
             returning
                 CAST(id as TEXT) as id,
                 CAST(author_user_id as TEXT) as author_user_id,
                 title,
+                slug,
                 summary,
                 body_markdown,
                 CAST(created_at as TEXT) as created_at,
                 CAST(updated_at as TEXT) as updated_at
             "#,
-        )
+            now = crate::db::now_sql(),
+        ))
         .bind(crate::db::uuid_to_db(program_id))
         .bind(&title)
         .bind(&summary)
         .bind(&body_markdown)
-        .fetch_one(pool)
+        .bind(&current_updated_at)
+        .bind(&slug)
+        .fetch_optional(pool)
         .await
-        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .ok_or_else(|| ServerFnError::new(crate::types::CONFLICT_ERROR))?;
 
         let score = sqlx::query_scalar::<_, i64>(
             "select coalesce(sum(value), 0) from votes where target_type = 'program' and target_id = $1",
@@ -418,11 +1001,45 @@ pub async fn update_program(
             id,
             author_user_id,
             title: row.get("title"),
+            slug: row.get("slug"),
             summary: row.get("summary"),
             body_markdown: row.get("body_markdown"),
             created_at,
             updated_at,
             vote_score: score,
+            author_display_name: None,
         })
     }
 }
+
+/// Position of a program in the vote-score ordering ("#3 of 120").
+#[dioxus::prelude::post("/api/programs/:id/rank")]
+pub async fn get_program_rank(
+    id: String,
+    id_token: Option<String>,
+) -> Result<crate::types::VoteRank, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, id_token);
+        Err(ServerFnError::new("get_program_rank is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "programs.get_program_rank",
+        use uuid::Uuid;
+
+        debug!("programs.get_program_rank: id={}", id);
+        let target_id = Uuid::parse_str(&id).map_err(|_| ServerFnError::new("invalid id"))?;
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let pool = state.db.pool().await;
+        let rank =
+            crate::votes::vote_rank(pool, crate::types::ContentTargetType::Program, target_id)
+                .await?;
+        debug!(
+            "programs.get_program_rank: rank={} total={}",
+            rank.rank, rank.total
+        );
+        Ok(rank)
+    }
+}
@@ -1,7 +1,64 @@
-use crate::types::Proposal;
+use crate::types::{Conditional, DiscoverOptions, Proposal, ProposalCursor, ProposalPage};
 use dioxus::prelude::*;
 #[cfg(feature = "server")]
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProposalDetail {
+    pub proposal: Proposal,
+    /// Whether the caller wrote it; always false without a token.
+    pub is_author: bool,
+    /// Whether the caller may edit it: the author or a contributor.
+    pub can_edit: bool,
+    /// Words in `body_markdown`, ignoring markdown syntax.
+    pub word_count: i64,
+    /// Reading time at ~200 words per minute, rounded up.
+    pub estimated_read_minutes: i64,
+    /// Visible comments on the proposal.
+    pub comment_count: i64,
+    /// Net score from voters with a verified email. `proposal.vote_score` equals it
+    /// under the `verified_only` vote policy.
+    pub verified_vote_score: i64,
+    /// Weak ETag of everything above that can change (`updated_at`, scores, comment
+    /// count, author name, contributors and the caller's flags), for
+    /// `get_proposal_if_changed`.
+    pub etag: String,
+}
+
+/// `user_id`'s profile display name, for write responses that can't join `profiles`
+/// into their `returning` clause.
+#[cfg(feature = "server")]
+async fn author_display_name(
+    pool: &sqlx::Pool<sqlx::Any>,
+    user_id: uuid::Uuid,
+) -> Result<Option<String>, ServerFnError> {
+    sqlx::query_scalar("select display_name from profiles where user_id = $1")
+        .bind(crate::db::uuid_to_db(user_id))
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Parse a proposal row selected with the column aliases used by the list queries,
+/// `vote_score` and `author_display_name` included.
+#[cfg(feature = "server")]
+pub(crate) fn proposal_from_row(row: &sqlx::any::AnyRow) -> Result<Proposal, ServerFnError> {
+    use sqlx::Row;
+
+    Ok(Proposal {
+        id: crate::db::uuid_from_db(&row.get::<String, _>("id"))?,
+        author_user_id: crate::db::uuid_from_db(&row.get::<String, _>("author_user_id"))?,
+        title: row.get("title"),
+        slug: row.get("slug"),
+        summary: row.get("summary"),
+        body_markdown: row.get("body_markdown"),
+        tags: crate::db::tags_from_db(&row.get::<String, _>("tags"))?,
+        created_at: crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?,
+        updated_at: crate::db::datetime_from_db(&row.get::<String, _>("updated_at"))?,
+        vote_score: row.get::<i64, _>("vote_score"),
+        author_display_name: row.get("author_display_name"),
+    })
+}
 
 #[dioxus::prelude::post("/api/proposals/create")]
 pub async fn create_proposal(
@@ -18,16 +75,22 @@ pub async fn create_proposal(
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "proposals.create_proposal",
         use sqlx::Row;
 
+        crate::state::AppState::current()?.ensure_writable()?;
+
         info!(
             "proposals.create_proposal: title_len={} tags_len={}",
             title.len(),
             tags_csv.len()
         );
         let author_user_id = crate::auth::require_user_id(id_token).await?;
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
+        let flagged = crate::content_filter::screen(
+            state.content_filter.as_ref(),
+            &[&title, &summary, &body_markdown],
+        )?;
         let pool = state.db.pool().await;
 
         let tags: Vec<String> = tags_csv
@@ -37,15 +100,17 @@ pub async fn create_proposal(
             .map(|s| s.to_string())
             .collect();
         let tags_json = crate::db::tags_to_db(&tags)?;
+        let slug = crate::slug::unique_slug(pool, "proposals", &title, None).await?;
 
         let sql = if crate::db::is_sqlite() {
             r#"
-            insert into proposals (author_user_id, title, summary, body_markdown, tags)
-            values ($1, $2, $3, $4, $5)
+            insert into proposals (author_user_id, title, summary, body_markdown, tags, slug)
+            values ($1, $2, $3, $4, $5, $6)
             returning
                 CAST(id as TEXT) as id,
                 CAST(author_user_id as TEXT) as author_user_id,
                 title,
+                slug,
                 summary,
                 body_markdown,
                 tags,
@@ -54,12 +119,13 @@ pub async fn create_proposal(
             "#
         } else {
             r#"
-            insert into proposals (author_user_id, title, summary, body_markdown, tags)
-            values ($1, $2, $3, $4, ARRAY(SELECT jsonb_array_elements_text($5::jsonb)))
+            insert into proposals (author_user_id, title, summary, body_markdown, tags, slug)
+            values ($1, $2, $3, $4, ARRAY(SELECT jsonb_array_elements_text($5::jsonb)), $6)
             returning
                 CAST(id as TEXT) as id,
                 CAST(author_user_id as TEXT) as author_user_id,
                 title,
+                slug,
                 summary,
                 body_markdown,
                 to_json(tags)::text as tags,
@@ -74,6 +140,7 @@ pub async fn create_proposal(
             .bind(&summary)
             .bind(&body_markdown)
             .bind(&tags_json)
+            .bind(&slug)
             .fetch_one(pool)
             .await
             .map_err(|e| ServerFnError::new(e.to_string()))?;
@@ -91,6 +158,17 @@ pub async fn create_proposal(
         .map_err(|e| ServerFnError::new(e.to_string()))?;
 
         let id = crate::db::uuid_from_db(&proposal_id)?;
+        if let Some(reason) = flagged {
+            info!("proposals.create_proposal: flagged proposal_id={}", id);
+            crate::content_filter::report_flagged(
+                pool,
+                author_user_id,
+                crate::types::ContentTargetType::Proposal,
+                id,
+                &reason,
+            )
+            .await?;
+        }
         let author_user_id = crate::db::uuid_from_db(&row.get::<String, _>("author_user_id"))?;
         let created_at = crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?;
         let updated_at = crate::db::datetime_from_db(&row.get::<String, _>("updated_at"))?;
@@ -99,136 +177,522 @@ pub async fn create_proposal(
             id,
             author_user_id,
             title: row.get("title"),
+            slug: row.get("slug"),
             summary: row.get("summary"),
             body_markdown: row.get("body_markdown"),
             tags: crate::db::tags_from_db(&row.get::<String, _>("tags"))?,
             created_at,
             updated_at,
             vote_score: 0,
+            author_display_name: author_display_name(pool, author_user_id).await?,
         })
     }
 }
 
+/// Visible proposals, newest first.
+///
+/// Pass the last proposal of the previous page as `before` to get the next one;
+/// only its `created_at` and `id` are used, so equal timestamps are neither
+/// skipped nor repeated across pages.
 #[dioxus::prelude::post("/api/proposals/list")]
-pub async fn list_proposals(limit: i64) -> Result<Vec<Proposal>, ServerFnError> {
+pub async fn list_proposals(
+    limit: i64,
+    before: Option<ProposalCursor>,
+    id_token: Option<String>,
+) -> Result<Vec<Proposal>, ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = limit;
+        let _ = (limit, before, id_token);
         Err(ServerFnError::new("list_proposals is server-only"))
     }
 
     #[cfg(feature = "server")]
-    {
-        use sqlx::Row;
-
-        debug!("proposals.list_proposals: limit={}", limit);
-        let state = crate::state::AppState::global();
+    crate::telemetry::traced! { "proposals.list_proposals",
+        debug!("proposals.list_proposals: limit={} before={:?}", limit, before);
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let limit = state.clamp_limit(limit);
         let pool = state.db.pool().await;
-        let sql = if crate::db::is_sqlite() {
+
+        // SQLite timestamps are text with varying fractional digits, so compare them
+        // as julian days rather than as strings.
+        let (tags, created_at, before_filter) = if crate::db::is_sqlite() {
+            (
+                "p.tags",
+                "julianday(p.created_at)",
+                "and (julianday(p.created_at) < julianday($2)
+                    or (julianday(p.created_at) = julianday($2) and p.id < $3))",
+            )
+        } else {
+            (
+                "to_json(p.tags)::text",
+                "p.created_at",
+                "and (p.created_at < $2::timestamptz
+                    or (p.created_at = $2::timestamptz and p.id < $3))",
+            )
+        };
+        let before_filter = if before.is_some() { before_filter } else { "" };
+        let sql = format!(
             r#"
             select
                 CAST(p.id as TEXT) as id,
                 CAST(p.author_user_id as TEXT) as author_user_id,
                 p.title,
+                coalesce(p.slug, CAST(p.id as TEXT)) as slug,
                 p.summary,
                 p.body_markdown,
-                p.tags,
+                {tags} as tags,
                 CAST(p.created_at as TEXT) as created_at,
                 CAST(p.updated_at as TEXT) as updated_at,
-                coalesce(sum(v.value), 0) as vote_score
+                coalesce(sum(v.value), 0) as vote_score,
+                ap.display_name as author_display_name
             from proposals p
             left join votes v
                 on v.target_type = 'proposal' and v.target_id = p.id
-            group by p.id
-            order by p.created_at desc
+            left join profiles ap on ap.user_id = p.author_user_id
+            where p.hidden_at is null {before_filter}
+            group by p.id, ap.display_name
+            order by {created_at} desc, p.id desc
             limit $1
             "#
+        );
+
+        let mut query = sqlx::query(&sql).bind(limit);
+        if let Some(cursor) = before {
+            query = query
+                .bind(crate::db::datetime_to_db(cursor.created_at)?)
+                .bind(crate::db::uuid_to_db(cursor.id));
+        }
+        let rows = query
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let mut proposals = Vec::with_capacity(rows.len());
+        for row in rows {
+            proposals.push(proposal_from_row(&row)?);
+        }
+
+        debug!("proposals.list_proposals: count={}", proposals.len());
+        Ok(proposals)
+    }
+}
+
+/// Visible proposals matching `options`, one keyset page at a time: the backend of
+/// the proposal list, combining search, tag filter and sort.
+#[dioxus::prelude::post("/api/proposals/discover")]
+pub async fn discover_proposals(
+    options: DiscoverOptions,
+    id_token: Option<String>,
+) -> Result<ProposalPage, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (options, id_token);
+        Err(ServerFnError::new("discover_proposals is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "proposals.discover_proposals",
+        use crate::types::SortMode;
+
+        debug!(
+            "proposals.discover_proposals: sort={:?} tag={:?} query_len={} limit={}",
+            options.sort,
+            options.tag,
+            options.query.as_deref().map_or(0, str::len),
+            options.limit
+        );
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let limit = state.clamp_limit(options.limit);
+        let pool = state.db.pool().await;
+        let sqlite = crate::db::is_sqlite();
+
+        // $1 is the limit; filters and the cursor take the following placeholders
+        // in the order they're bound below.
+        let (filters, filter_values) =
+            proposal_filters(options.query.as_deref(), options.tag.as_deref(), 2);
+        let next = 1 + filter_values.len();
+
+        // SQLite timestamps are text with varying fractional digits, so compare and
+        // sort them as julian days rather than as strings; ordering by one form and
+        // paging by the other would skip or repeat rows.
+        let ts = if sqlite {
+            "julianday(d.created_at)"
+        } else {
+            "d.created_at"
+        };
+        let keyset = match options.before {
+            None => String::new(),
+            Some(_) => {
+                let (at, id, score) = (next + 1, next + 2, next + 3);
+                let at = if sqlite {
+                    format!("julianday(${at})")
+                } else {
+                    format!("${at}::timestamptz")
+                };
+                match options.sort {
+                    SortMode::Newest => {
+                        format!("where {ts} < {at} or ({ts} = {at} and d.id < ${id})")
+                    }
+                    SortMode::Oldest => {
+                        format!("where {ts} > {at} or ({ts} = {at} and d.id > ${id})")
+                    }
+                    SortMode::TopVoted => format!(
+                        "where d.vote_score < ${score} or (d.vote_score = ${score} \
+                         and ({ts} < {at} or ({ts} = {at} and d.id < ${id})))"
+                    ),
+                }
+            }
+        };
+        let order = match options.sort {
+            SortMode::Newest => format!("{ts} desc, d.id desc"),
+            SortMode::Oldest => format!("{ts} asc, d.id asc"),
+            SortMode::TopVoted => format!("d.vote_score desc, {ts} desc, d.id desc"),
+        };
+        let tags = if sqlite { "d.tags" } else { "to_json(d.tags)::text" };
+        let sql = format!(
+            r#"
+            select
+                CAST(d.id as TEXT) as id,
+                CAST(d.author_user_id as TEXT) as author_user_id,
+                d.title,
+                coalesce(d.slug, CAST(d.id as TEXT)) as slug,
+                d.summary,
+                d.body_markdown,
+                {tags} as tags,
+                CAST(d.created_at as TEXT) as created_at,
+                CAST(d.updated_at as TEXT) as updated_at,
+                d.vote_score,
+                ap.display_name as author_display_name
+            from (
+                select
+                    p.*,
+                    (select coalesce(sum(v.value), 0) from votes v
+                        where v.target_type = 'proposal' and v.target_id = p.id) as vote_score
+                from proposals p
+                where p.hidden_at is null{filters}
+            ) d
+            left join profiles ap on ap.user_id = d.author_user_id
+            {keyset}
+            order by {order}
+            limit $1
+            "#
+        );
+
+        // One extra row tells whether another page follows.
+        let mut rows_query = sqlx::query(&sql).bind(limit + 1);
+        for value in &filter_values {
+            rows_query = rows_query.bind(value);
+        }
+        if let Some(cursor) = options.before {
+            rows_query = rows_query
+                .bind(crate::db::datetime_to_db(cursor.created_at)?)
+                .bind(crate::db::uuid_to_db(cursor.id));
+            if options.sort == SortMode::TopVoted {
+                rows_query = rows_query.bind(cursor.vote_score);
+            }
+        }
+        let mut rows = rows_query
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit as usize);
+
+        let mut proposals = Vec::with_capacity(rows.len());
+        for row in rows {
+            proposals.push(proposal_from_row(&row)?);
+        }
+
+        debug!(
+            "proposals.discover_proposals: count={} has_more={}",
+            proposals.len(),
+            has_more
+        );
+        Ok(ProposalPage {
+            proposals,
+            has_more,
+        })
+    }
+}
+
+/// The search and tag filters of `discover_proposals` as `and ...` clauses over
+/// proposals `p`, numbered from `$first`, with the values to bind in that order.
+/// `count_proposals` builds its filter here too, so a total always matches the
+/// pages it describes.
+#[cfg(feature = "server")]
+fn proposal_filters(query: Option<&str>, tag: Option<&str>, first: usize) -> (String, Vec<String>) {
+    let normalize = |value: Option<&str>| {
+        value
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_lowercase)
+    };
+    let sqlite = crate::db::is_sqlite();
+    let mut sql = String::new();
+    let mut values = Vec::new();
+    if let Some(query) = normalize(query) {
+        let n = first + values.len();
+        let find = if sqlite { "instr" } else { "strpos" };
+        sql.push_str(&format!(
+            " and ({find}(lower(p.title), ${n}) > 0 or {find}(lower(p.summary), ${n}) > 0)"
+        ));
+        values.push(query);
+    }
+    // Tags match like `list_proposals_by_tag`: case-insensitively, ignoring
+    // surrounding whitespace.
+    if let Some(tag) = normalize(tag) {
+        let n = first + values.len();
+        sql.push_str(&if sqlite {
+            format!(
+                " and exists (select 1 from json_each(p.tags) \
+                 where lower(trim(json_each.value)) = ${n})"
+            )
         } else {
+            format!(" and exists (select 1 from unnest(p.tags) as t where lower(trim(t)) = ${n})")
+        });
+        values.push(tag);
+    }
+    (sql, values)
+}
+
+/// Number of visible proposals matching the same search and tag filters as
+/// `discover_proposals` (both optional), for "page 2 of 7" style pagers.
+#[dioxus::prelude::post("/api/proposals/count")]
+pub async fn count_proposals(
+    query: Option<String>,
+    tag: Option<String>,
+    id_token: Option<String>,
+) -> Result<i64, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (query, tag, id_token);
+        Err(ServerFnError::new("count_proposals is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "proposals.count_proposals",
+        crate::auth::reader_id(id_token).await?;
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+        let (filters, values) = proposal_filters(query.as_deref(), tag.as_deref(), 1);
+        let sql = format!("select count(*) from proposals p where p.hidden_at is null{filters}");
+        let mut count_query = sqlx::query_scalar::<_, i64>(&sql);
+        for value in &values {
+            count_query = count_query.bind(value);
+        }
+        let count = count_query
+            .fetch_one(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        debug!("proposals.count_proposals: count={}", count);
+        Ok(count)
+    }
+}
+
+/// A user's proposals, newest first; hidden ones are skipped.
+#[dioxus::prelude::post("/api/proposals/by_author/:user_id")]
+pub async fn list_proposals_by_author(
+    user_id: String,
+    limit: i64,
+    offset: i64,
+    id_token: Option<String>,
+) -> Result<Vec<Proposal>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (user_id, limit, offset, id_token);
+        Err(ServerFnError::new(
+            "list_proposals_by_author is server-only",
+        ))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "proposals.list_proposals_by_author",
+        use uuid::Uuid;
+
+        debug!(
+            "proposals.list_proposals_by_author: user_id={} limit={} offset={}",
+            user_id, limit, offset
+        );
+        let uid = Uuid::parse_str(&user_id).map_err(|_| ServerFnError::new("invalid user_id"))?;
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let (limit, offset) = state.page(limit, offset)?;
+        let pool = state.db.pool().await;
+        let tags = if crate::db::is_sqlite() {
+            "p.tags"
+        } else {
+            "to_json(p.tags)::text"
+        };
+        let sql = format!(
             r#"
             select
                 CAST(p.id as TEXT) as id,
                 CAST(p.author_user_id as TEXT) as author_user_id,
                 p.title,
+                coalesce(p.slug, CAST(p.id as TEXT)) as slug,
                 p.summary,
                 p.body_markdown,
-                to_json(p.tags)::text as tags,
+                {tags} as tags,
                 CAST(p.created_at as TEXT) as created_at,
                 CAST(p.updated_at as TEXT) as updated_at,
-                coalesce(sum(v.value), 0) as vote_score
+                coalesce(sum(v.value), 0) as vote_score,
+                ap.display_name as author_display_name
             from proposals p
             left join votes v
                 on v.target_type = 'proposal' and v.target_id = p.id
-            group by p.id
-            order by p.created_at desc
-            limit $1
+            left join profiles ap on ap.user_id = p.author_user_id
+            where p.author_user_id = $1 and p.hidden_at is null
+            group by p.id, ap.display_name
+            order by p.created_at desc, p.id desc
+            limit $2 offset $3
             "#
-        };
+        );
 
-        let rows = sqlx::query(sql)
+        let rows = sqlx::query(&sql)
+            .bind(crate::db::uuid_to_db(uid))
             .bind(limit)
+            .bind(offset)
             .fetch_all(pool)
             .await
             .map_err(|e| ServerFnError::new(e.to_string()))?;
 
         let mut proposals = Vec::with_capacity(rows.len());
         for row in rows {
-            let id = crate::db::uuid_from_db(&row.get::<String, _>("id"))?;
-            let author_user_id = crate::db::uuid_from_db(&row.get::<String, _>("author_user_id"))?;
-            let created_at = crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?;
-            let updated_at = crate::db::datetime_from_db(&row.get::<String, _>("updated_at"))?;
-            proposals.push(Proposal {
-                id,
-                author_user_id,
-                title: row.get("title"),
-                summary: row.get("summary"),
-                body_markdown: row.get("body_markdown"),
-                tags: crate::db::tags_from_db(&row.get::<String, _>("tags"))?,
-                created_at,
-                updated_at,
-                vote_score: row.get::<i64, _>("vote_score"),
-            });
+            proposals.push(proposal_from_row(&row)?);
         }
 
-        debug!("proposals.list_proposals: count={}", proposals.len());
+        debug!("proposals.list_proposals_by_author: count={}", proposals.len());
         Ok(proposals)
     }
 }
 
-#[dioxus::prelude::get("/api/proposals/get/:id")]
-pub async fn get_proposal(id: String) -> Result<Proposal, ServerFnError> {
+/// Visible proposals carrying `tag`, newest first. Tags match case-insensitively
+/// and ignoring surrounding whitespace; a blank `tag` matches nothing.
+#[dioxus::prelude::post("/api/proposals/by_tag")]
+pub async fn list_proposals_by_tag(
+    tag: String,
+    limit: i64,
+    id_token: Option<String>,
+) -> Result<Vec<Proposal>, ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = id;
-        Err(ServerFnError::new("get_proposal is server-only"))
+        let _ = (tag, limit, id_token);
+        Err(ServerFnError::new("list_proposals_by_tag is server-only"))
     }
 
     #[cfg(feature = "server")]
+    crate::telemetry::traced! { "proposals.list_proposals_by_tag",
+        debug!("proposals.list_proposals_by_tag: tag={:?} limit={}", tag, limit);
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() {
+            return Ok(Vec::new());
+        }
+        let limit = state.clamp_limit(limit);
+        let pool = state.db.pool().await;
+
+        let (tags, tag_filter) = if crate::db::is_sqlite() {
+            (
+                "p.tags",
+                "exists (select 1 from json_each(p.tags) where lower(trim(json_each.value)) = $1)",
+            )
+        } else {
+            (
+                "to_json(p.tags)::text",
+                "array(select lower(trim(t)) from unnest(p.tags) as t) @> array[$1]::text[]",
+            )
+        };
+        let sql = format!(
+            r#"
+            select
+                CAST(p.id as TEXT) as id,
+                CAST(p.author_user_id as TEXT) as author_user_id,
+                p.title,
+                coalesce(p.slug, CAST(p.id as TEXT)) as slug,
+                p.summary,
+                p.body_markdown,
+                {tags} as tags,
+                CAST(p.created_at as TEXT) as created_at,
+                CAST(p.updated_at as TEXT) as updated_at,
+                coalesce(sum(v.value), 0) as vote_score,
+                ap.display_name as author_display_name
+            from proposals p
+            left join votes v
+                on v.target_type = 'proposal' and v.target_id = p.id
+            left join profiles ap on ap.user_id = p.author_user_id
+            where p.hidden_at is null and {tag_filter}
+            group by p.id, ap.display_name
+            order by p.created_at desc, p.id desc
+            limit $2
+            "#
+        );
+
+        let rows = sqlx::query(&sql)
+            .bind(&tag)
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let mut proposals = Vec::with_capacity(rows.len());
+        for row in rows {
+            proposals.push(proposal_from_row(&row)?);
+        }
+
+        debug!("proposals.list_proposals_by_tag: count={}", proposals.len());
+        Ok(proposals)
+    }
+}
+
+/// Proposals ordered by recency-weighted votes ("what's hot now").
+///
+/// Each vote counts `value / (age_hours + 2)^1.5`, with age taken from its last change.
+/// SQLite builds typically lack `pow`, so there the per-vote ages are summed in Rust.
+#[dioxus::prelude::post("/api/proposals/trending")]
+pub async fn list_trending_proposals(
+    limit: i64,
+    id_token: Option<String>,
+) -> Result<Vec<Proposal>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
     {
+        let _ = (limit, id_token);
+        Err(ServerFnError::new("list_trending_proposals is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "proposals.list_trending_proposals",
         use sqlx::Row;
-        use uuid::Uuid;
 
-        debug!("proposals.get_proposal: id={}", id);
-        let pid = Uuid::parse_str(&id).map_err(|_| ServerFnError::new("invalid id"))?;
-        let state = crate::state::AppState::global();
+        debug!("proposals.list_trending_proposals: limit={}", limit);
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let limit = state.clamp_limit(limit);
         let pool = state.db.pool().await;
-
-        let sql = if crate::db::is_sqlite() {
+        let sqlite = crate::db::is_sqlite();
+        let sql = if sqlite {
             r#"
             select
                 CAST(p.id as TEXT) as id,
                 CAST(p.author_user_id as TEXT) as author_user_id,
                 p.title,
+                coalesce(p.slug, CAST(p.id as TEXT)) as slug,
                 p.summary,
                 p.body_markdown,
                 p.tags,
                 CAST(p.created_at as TEXT) as created_at,
                 CAST(p.updated_at as TEXT) as updated_at,
-                coalesce(sum(v.value), 0) as vote_score
+                coalesce(sum(v.value), 0) as vote_score,
+                ap.display_name as author_display_name,
+                group_concat(v.value || ':' || ((julianday('now') - julianday(v.updated_at)) * 24)) as vote_ages
             from proposals p
             left join votes v
                 on v.target_type = 'proposal' and v.target_id = p.id
-            where p.id = $1
-            group by p.id
+            left join profiles ap on ap.user_id = p.author_user_id
+            where p.hidden_at is null
+            group by p.id, ap.display_name
             "#
         } else {
             r#"
@@ -236,43 +700,207 @@ pub async fn get_proposal(id: String) -> Result<Proposal, ServerFnError> {
                 CAST(p.id as TEXT) as id,
                 CAST(p.author_user_id as TEXT) as author_user_id,
                 p.title,
+                coalesce(p.slug, CAST(p.id as TEXT)) as slug,
                 p.summary,
                 p.body_markdown,
                 to_json(p.tags)::text as tags,
                 CAST(p.created_at as TEXT) as created_at,
                 CAST(p.updated_at as TEXT) as updated_at,
-                coalesce(sum(v.value), 0) as vote_score
+                coalesce(sum(v.value), 0) as vote_score,
+                ap.display_name as author_display_name,
+                coalesce(sum(
+                    v.value / power(extract(epoch from (now() - v.updated_at)) / 3600 + 2, 1.5)
+                ), 0) as trending_score
             from proposals p
             left join votes v
                 on v.target_type = 'proposal' and v.target_id = p.id
-            where p.id = $1
-            group by p.id
+            left join profiles ap on ap.user_id = p.author_user_id
+            where p.hidden_at is null
+            group by p.id, ap.display_name
+            order by trending_score desc, p.created_at desc
+            limit $1
             "#
         };
 
-        let row = sqlx::query(sql)
-            .bind(crate::db::uuid_to_db(pid))
-            .fetch_one(pool)
+        let mut query = sqlx::query(sql);
+        if !sqlite {
+            query = query.bind(limit);
+        }
+        let rows = query
+            .fetch_all(pool)
             .await
             .map_err(|e| ServerFnError::new(e.to_string()))?;
 
-        let id = crate::db::uuid_from_db(&row.get::<String, _>("id"))?;
-        let author_user_id = crate::db::uuid_from_db(&row.get::<String, _>("author_user_id"))?;
-        let created_at = crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?;
-        let updated_at = crate::db::datetime_from_db(&row.get::<String, _>("updated_at"))?;
+        let mut scores = Vec::with_capacity(rows.len());
+        let mut proposals = Vec::with_capacity(rows.len());
+        for row in rows {
+            if sqlite {
+                let ages = row.get::<Option<String>, _>("vote_ages").unwrap_or_default();
+                scores.push(trending_score(&ages));
+            }
+            proposals.push(proposal_from_row(&row)?);
+        }
 
-        Ok(Proposal {
-            id,
-            author_user_id,
-            title: row.get("title"),
-            summary: row.get("summary"),
-            body_markdown: row.get("body_markdown"),
-            tags: crate::db::tags_from_db(&row.get::<String, _>("tags"))?,
-            created_at,
-            updated_at,
-            vote_score: row.get::<i64, _>("vote_score"),
-        })
+        if sqlite {
+            let mut ranked: Vec<(f64, Proposal)> = scores.into_iter().zip(proposals).collect();
+            ranked.sort_by(|(a, pa), (b, pb)| {
+                b.total_cmp(a).then_with(|| pb.created_at.cmp(&pa.created_at))
+            });
+            ranked.truncate(limit.max(0) as usize);
+            proposals = ranked.into_iter().map(|(_, p)| p).collect();
+        }
+
+        debug!("proposals.list_trending_proposals: count={}", proposals.len());
+        Ok(proposals)
+    }
+}
+
+/// A visible proposal; `id` is either the UUID or the slug.
+#[dioxus::prelude::post("/api/proposals/get/:id")]
+pub async fn get_proposal(
+    id: String,
+    id_token: Option<String>,
+) -> Result<ProposalDetail, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, id_token);
+        Err(ServerFnError::new("get_proposal is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "proposals.get_proposal",
+        debug!("proposals.get_proposal: id={}", id);
+        load_detail(&id, id_token).await
+    }
+}
+
+/// `get_proposal`, unless `etag` (the client's `If-None-Match`) still matches.
+#[dioxus::prelude::post("/api/proposals/get/:id/if_changed")]
+pub async fn get_proposal_if_changed(
+    id: String,
+    etag: String,
+    id_token: Option<String>,
+) -> Result<Conditional<ProposalDetail>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, etag, id_token);
+        Err(ServerFnError::new("get_proposal_if_changed is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "proposals.get_proposal_if_changed",
+        debug!("proposals.get_proposal_if_changed: id={} etag={}", id, etag);
+        let detail = load_detail(&id, id_token).await?;
+        if crate::etag::matches(&etag, &detail.etag) {
+            return Ok(Conditional::NotModified);
+        }
+        Ok(Conditional::Modified(detail))
+    }
+}
+
+#[cfg(feature = "server")]
+async fn load_detail(id: &str, id_token: Option<String>) -> Result<ProposalDetail, ServerFnError> {
+    // Check the caller before looking anything up, so private deployments don't
+    // reveal which proposals exist.
+    let caller = crate::auth::reader_id(id_token).await?;
+    let state = crate::state::AppState::current()?;
+    let pool = state.db.pool().await;
+    let pid = crate::slug::resolve_id(pool, "proposals", id).await?;
+
+    let (mut proposal, _) = fetch_proposal(pool, pid, false).await?;
+    let verified_vote_score =
+        crate::votes::verified_score(pool, crate::types::ContentTargetType::Proposal, pid).await?;
+    if state.config.vote_policy == crate::config::VotePolicy::VerifiedOnly {
+        proposal.vote_score = verified_vote_score;
     }
+    let is_author = caller == Some(proposal.author_user_id);
+    let contributors = contributor_ids(pool, pid).await?;
+    let can_edit = caller.is_some_and(|caller| is_author || contributors.contains(&caller));
+    let word_count = crate::reading::word_count(&proposal.body_markdown);
+    let comment_count =
+        crate::counts::comment_count(pool, crate::types::ContentTargetType::Proposal, pid).await?;
+    let etag = crate::etag::weak_etag([
+        &proposal.id as &dyn crate::etag::EtagPart,
+        &proposal.updated_at,
+        &proposal.vote_score,
+        &verified_vote_score,
+        &comment_count,
+        &proposal.author_display_name,
+        &contributors,
+        &is_author,
+        &can_edit,
+    ]);
+    Ok(ProposalDetail {
+        proposal,
+        is_author,
+        can_edit,
+        word_count,
+        estimated_read_minutes: crate::reading::estimated_read_minutes(word_count),
+        comment_count,
+        verified_vote_score,
+        etag,
+    })
+}
+
+/// Load a proposal with its vote score and `hidden_at`.
+///
+/// Hidden proposals are only returned when `include_hidden` is set (moderation views).
+#[cfg(feature = "server")]
+pub(crate) async fn fetch_proposal(
+    pool: &sqlx::Pool<sqlx::Any>,
+    pid: uuid::Uuid,
+    include_hidden: bool,
+) -> Result<(Proposal, Option<time::OffsetDateTime>), ServerFnError> {
+    use sqlx::Row;
+
+    let tags_expr = if crate::db::is_sqlite() {
+        "p.tags"
+    } else {
+        "to_json(p.tags)::text"
+    };
+    let hidden_filter = if include_hidden {
+        ""
+    } else {
+        "and p.hidden_at is null"
+    };
+    let sql = format!(
+        r#"
+        select
+            CAST(p.id as TEXT) as id,
+            CAST(p.author_user_id as TEXT) as author_user_id,
+            p.title,
+            coalesce(p.slug, CAST(p.id as TEXT)) as slug,
+            p.summary,
+            p.body_markdown,
+            {tags_expr} as tags,
+            CAST(p.created_at as TEXT) as created_at,
+            CAST(p.updated_at as TEXT) as updated_at,
+            CAST(p.hidden_at as TEXT) as hidden_at,
+            coalesce(sum(v.value), 0) as vote_score,
+            ap.display_name as author_display_name
+        from proposals p
+        left join votes v
+            on v.target_type = 'proposal' and v.target_id = p.id
+        left join profiles ap on ap.user_id = p.author_user_id
+        where p.id = $1 {hidden_filter}
+        group by p.id, ap.display_name
+        "#
+    );
+
+    let row = sqlx::query(&sql)
+        .bind(crate::db::uuid_to_db(pid))
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .ok_or_else(|| ServerFnError::new(crate::types::ErrorCode::NotFound.to_string()))?;
+
+    let hidden_at = row
+        .get::<Option<String>, _>("hidden_at")
+        .as_deref()
+        .map(crate::db::datetime_from_db)
+        .transpose()?;
+    let proposal = proposal_from_row(&row)?;
+    Ok((proposal, hidden_at))
 }
 
 #[dioxus::prelude::post("/api/proposals/update")]
@@ -283,35 +911,51 @@ pub async fn update_proposal(
     summary: String,
     body_markdown: String,
     tags_csv: String,
+    expected_updated_at: time::OffsetDateTime,
 ) -> Result<Proposal, ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = (id_token, id, title, summary, body_markdown, tags_csv);
+        let _ = (
+            id_token,
+            id,
+            title,
+            summary,
+            body_markdown,
+            tags_csv,
+            expected_updated_at,
+        );
         Err(ServerFnError::new("update_proposal is server-only"))
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "proposals.update_proposal",
         use sqlx::Row;
         use uuid::Uuid;
 
+        crate::state::AppState::current()?.ensure_writable()?;
+
         info!("proposals.update_proposal: id={}", id);
         let user_id = crate::auth::require_user_id(id_token).await?;
         let pid = Uuid::parse_str(&id).map_err(|_| ServerFnError::new("invalid id"))?;
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
         let pool = state.db.pool().await;
 
-        let owner = sqlx::query_scalar::<_, String>(
-            "select CAST(author_user_id as TEXT) from proposals where id = $1",
+        let current = sqlx::query(
+            "select CAST(author_user_id as TEXT) as author_user_id, CAST(updated_at as TEXT) as updated_at from proposals where id = $1",
         )
         .bind(crate::db::uuid_to_db(pid))
         .fetch_one(pool)
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
-        let owner = crate::db::uuid_from_db(&owner)?;
-        if owner != user_id {
+        let owner = crate::db::uuid_from_db(&current.get::<String, _>("author_user_id"))?;
+        if owner != user_id && !is_contributor(pool, pid, user_id).await? {
             info!("proposals.update_proposal: forbidden user_id={}", user_id);
-            return Err(ServerFnError::new("not allowed"));
+            return Err(ServerFnError::new(crate::types::ErrorCode::NotAllowed.to_string()));
+        }
+        let current_updated_at: String = current.get("updated_at");
+        if crate::db::datetime_from_db(&current_updated_at)? != expected_updated_at {
+            info!("proposals.update_proposal: conflict id={}", pid);
+            return Err(ServerFnError::new(crate::types::CONFLICT_ERROR));
         }
 
         let tags: Vec<String> = tags_csv
@@ -321,56 +965,53 @@ pub async fn update_proposal(
             .map(|s| s.to_string())
             .collect();
         let tags_json = crate::db::tags_to_db(&tags)?;
+        let slug = crate::slug::unique_slug(pool, "proposals", &title, Some(pid)).await?;
 
-        let sql = if crate::db::is_sqlite() {
-            r#"
-            update proposals
-            set title = $2,
-                summary = $3,
-                body_markdown = $4,
-                tags = $5,
-                updated_at = now()
-            where id = $1
-            returning
-                CAST(id as TEXT) as id,
-                CAST(author_user_id as TEXT) as author_user_id,
-                title,
-                summary,
-                body_markdown,
-                tags,
-                CAST(created_at as TEXT) as created_at,
-                CAST(updated_at as TEXT) as updated_at
-            "#
+        let (tags_value, tags_column) = if crate::db::is_sqlite() {
+            ("$5", "tags")
         } else {
+            (
+                "ARRAY(SELECT jsonb_array_elements_text($5::jsonb))",
+                "to_json(tags)::text",
+            )
+        };
+        // The `updated_at` guard makes the check above atomic with the write.
+        let sql = format!(
             r#"
             update proposals
             set title = $2,
                 summary = $3,
                 body_markdown = $4,
-                tags = ARRAY(SELECT jsonb_array_elements_text($5::jsonb)),
-                updated_at = now()
-            where id = $1
+                tags = {tags_value},
+                slug = $7,
+                updated_at = {now}
+            where id = $1 and CAST(updated_at as TEXT) = $6
             returning
                 CAST(id as TEXT) as id,
                 CAST(author_user_id as TEXT) as author_user_id,
                 title,
+                slug,
                 summary,
                 body_markdown,
-                to_json(tags)::text as tags,
+                {tags_column} as tags,
                 CAST(created_at as TEXT) as created_at,
                 CAST(updated_at as TEXT) as updated_at
-            "#
-        };
+            "#,
+            now = crate::db::now_sql(),
+        );
 
-        let row = sqlx::query(sql)
+        let row = sqlx::query(&sql)
             .bind(crate::db::uuid_to_db(pid))
             .bind(&title)
             .bind(&summary)
             .bind(&body_markdown)
             .bind(&tags_json)
-            .fetch_one(pool)
+            .bind(&current_updated_at)
+            .bind(&slug)
+            .fetch_optional(pool)
             .await
-            .map_err(|e| ServerFnError::new(e.to_string()))?;
+            .map_err(|e| ServerFnError::new(e.to_string()))?
+            .ok_or_else(|| ServerFnError::new(crate::types::CONFLICT_ERROR))?;
 
         let score = sqlx::query_scalar::<_, i64>(
             "select coalesce(sum(value), 0) from votes where target_type = 'proposal' and target_id = $1",
@@ -389,12 +1030,704 @@ pub async fn update_proposal(
             id,
             author_user_id,
             title: row.get("title"),
+            slug: row.get("slug"),
             summary: row.get("summary"),
             body_markdown: row.get("body_markdown"),
             tags: crate::db::tags_from_db(&row.get::<String, _>("tags"))?,
             created_at,
             updated_at,
             vote_score: score,
+            author_display_name: author_display_name(pool, author_user_id).await?,
         })
     }
 }
+
+/// Delete one of your proposals along with its votes, comments, bookmarks, reports,
+/// notifications, program entries, activity and videos (with their captions and
+/// files), then record a `deleted` activity and leave a tombstone that
+/// `sync_proposals` reports as removed.
+///
+/// Only the author may delete, not contributors. A proposal bundled in a program
+/// is refused unless `force` is set, which also drops it from those programs.
+#[dioxus::prelude::post("/api/proposals/delete")]
+pub async fn delete_proposal(
+    id_token: String,
+    id: String,
+    force: bool,
+) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, id, force);
+        Err(ServerFnError::new("delete_proposal is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "proposals.delete_proposal",
+        use uuid::Uuid;
+
+        crate::state::AppState::current()?.ensure_writable()?;
+
+        info!("proposals.delete_proposal: id={} force={}", id, force);
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let pid = Uuid::parse_str(&id).map_err(|_| ServerFnError::new("invalid id"))?;
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        let owner = crate::db::target_owner(pool, crate::types::ContentTargetType::Proposal, pid)
+            .await?
+            .ok_or_else(|| ServerFnError::new(crate::types::ErrorCode::NotFound.to_string()))?;
+        if owner != user_id {
+            info!("proposals.delete_proposal: forbidden user_id={}", user_id);
+            return Err(ServerFnError::new(crate::types::ErrorCode::NotAllowed.to_string()));
+        }
+
+        // Any early return drops the transaction, which rolls it back.
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let programs: i64 = sqlx::query_scalar(
+            "select count(*) from program_items where proposal_id = $1",
+        )
+        .bind(crate::db::uuid_to_db(pid))
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        if programs > 0 && !force {
+            info!(
+                "proposals.delete_proposal: in use id={} programs={}",
+                pid, programs
+            );
+            return Err(ServerFnError::new("proposal is part of a program"));
+        }
+
+        // Files of the proposal's videos and their captions, removed once the rows are gone.
+        let keys: Vec<String> = sqlx::query_scalar(
+            "select storage_key from videos where target_type = 'proposal' and target_id = $1 \
+             union all \
+             select c.storage_key from captions c join videos v on v.id = c.video_id \
+             where v.target_type = 'proposal' and v.target_id = $1",
+        )
+        .bind(crate::db::uuid_to_db(pid))
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        // Rows are removed leaves first, while their parents still identify them:
+        // rows about the comments (on the proposal or on its videos), the comments,
+        // rows about the videos, the videos, then rows about the proposal itself.
+        let videos = "(select id from videos where target_type = 'proposal' and target_id = $1)";
+        let on_own_comments = format!(
+            "(target_type = 'proposal' and target_id = $1) \
+             or (target_type = 'video' and target_id in {videos})"
+        );
+        let comments = format!("(select id from comments where {on_own_comments})");
+        let about = |on: &str| {
+            ["votes", "bookmarks", "reports", "activity", "notifications"]
+                .map(|table| format!("delete from {table} where {on}"))
+        };
+        let mut statements = Vec::new();
+        statements.extend(about(&format!("target_type = 'comment' and target_id in {comments}")));
+        statements.push(format!("delete from comment_mentions where comment_id in {comments}"));
+        statements.push(format!("delete from comments where {on_own_comments}"));
+        statements.extend(about(&format!("target_type = 'video' and target_id in {videos}")));
+        statements.extend([
+            format!("delete from captions where video_id in {videos}"),
+            format!("delete from video_views where video_id in {videos}"),
+            format!("delete from videos where id in {videos}"),
+        ]);
+        statements.extend(about("target_type = 'proposal' and target_id = $1"));
+        // Programs that bundled it change shape, so their ETags and sync must move on.
+        statements.extend([
+            format!(
+                "update programs set updated_at = {} \
+                 where id in (select program_id from program_items where proposal_id = $1)",
+                crate::db::now_sql()
+            ),
+            "delete from program_items where proposal_id = $1".to_string(),
+            "delete from proposal_contributors where proposal_id = $1".to_string(),
+            "delete from proposals where id = $1".to_string(),
+        ]);
+        for sql in &statements {
+            sqlx::query(sql)
+                .bind(crate::db::uuid_to_db(pid))
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?;
+        }
+
+        sqlx::query(
+            "insert into activity (user_id, action, target_type, target_id) values ($1, 'deleted', 'proposal', $2)",
+        )
+        .bind(crate::db::uuid_to_db(user_id))
+        .bind(crate::db::uuid_to_db(pid))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        // The tombstone lets sync report the removal once the row is gone.
+        sqlx::query(&format!(
+            "insert into deleted_proposals (id, deleted_at) values ($1, {})",
+            crate::db::now_sql()
+        ))
+        .bind(crate::db::uuid_to_db(pid))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        // The rows are gone either way; a leftover file only costs storage.
+        for key in &keys {
+            if let Err(e) = state.storage.delete(key).await {
+                warn!("proposals.delete_proposal: could not delete {}: {}", key, e);
+            }
+        }
+        info!(
+            "proposals.delete_proposal: deleted id={} programs={} files={}",
+            pid,
+            programs,
+            keys.len()
+        );
+        Ok(())
+    }
+}
+
+/// Users added as contributors to proposal `pid`, in a stable order.
+#[cfg(feature = "server")]
+async fn contributor_ids(
+    pool: &sqlx::Pool<sqlx::Any>,
+    pid: uuid::Uuid,
+) -> Result<Vec<uuid::Uuid>, ServerFnError> {
+    let ids: Vec<String> = sqlx::query_scalar(
+        "select CAST(user_id as TEXT) from proposal_contributors where proposal_id = $1 order by user_id",
+    )
+    .bind(crate::db::uuid_to_db(pid))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+    ids.iter().map(|id| crate::db::uuid_from_db(id)).collect()
+}
+
+/// Whether `user_id` was added as a contributor to proposal `pid`.
+#[cfg(feature = "server")]
+pub(crate) async fn is_contributor(
+    pool: &sqlx::Pool<sqlx::Any>,
+    pid: uuid::Uuid,
+    user_id: uuid::Uuid,
+) -> Result<bool, ServerFnError> {
+    let count: i64 = sqlx::query_scalar(
+        "select count(*) from proposal_contributors where proposal_id = $1 and user_id = $2",
+    )
+    .bind(crate::db::uuid_to_db(pid))
+    .bind(crate::db::uuid_to_db(user_id))
+    .fetch_one(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+    Ok(count > 0)
+}
+
+/// Parse both ids and check the caller wrote the proposal (contributors can't manage
+/// contributors).
+#[cfg(feature = "server")]
+async fn authored_proposal(
+    pool: &sqlx::Pool<sqlx::Any>,
+    caller: uuid::Uuid,
+    proposal_id: &str,
+    user_id: &str,
+) -> Result<(uuid::Uuid, uuid::Uuid), ServerFnError> {
+    let pid = uuid::Uuid::parse_str(proposal_id)
+        .map_err(|_| ServerFnError::new("invalid proposal_id"))?;
+    let uid = uuid::Uuid::parse_str(user_id).map_err(|_| ServerFnError::new("invalid user_id"))?;
+    let author = crate::db::target_owner(pool, crate::types::ContentTargetType::Proposal, pid)
+        .await?
+        .ok_or_else(|| ServerFnError::new(crate::types::ErrorCode::NotFound.to_string()))?;
+    if author != caller {
+        return Err(ServerFnError::new(
+            crate::types::ErrorCode::NotAllowed.to_string(),
+        ));
+    }
+    Ok((pid, uid))
+}
+
+/// Let `user_id` edit one of your proposals (author only).
+///
+/// Contributors can update it but not delete it or manage contributors.
+#[dioxus::prelude::post("/api/proposals/add_contributor")]
+pub async fn add_proposal_contributor(
+    id_token: String,
+    proposal_id: String,
+    user_id: String,
+) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, proposal_id, user_id);
+        Err(ServerFnError::new(
+            "add_proposal_contributor is server-only",
+        ))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "proposals.add_proposal_contributor",
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        let caller = crate::auth::require_user_id(id_token).await?;
+        let pool = state.db.pool().await;
+        let (pid, uid) = authored_proposal(pool, caller, &proposal_id, &user_id).await?;
+        if uid == caller {
+            return Err(ServerFnError::new("the author can't be a contributor"));
+        }
+        let exists: Option<String> =
+            sqlx::query_scalar("select CAST(id as TEXT) from users where id = $1")
+                .bind(crate::db::uuid_to_db(uid))
+                .fetch_optional(pool)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?;
+        if exists.is_none() {
+            return Err(ServerFnError::new("user not found"));
+        }
+
+        sqlx::query(
+            r#"
+            insert into proposal_contributors (proposal_id, user_id)
+            values ($1, $2)
+            on conflict (proposal_id, user_id) do nothing
+            "#,
+        )
+        .bind(crate::db::uuid_to_db(pid))
+        .bind(crate::db::uuid_to_db(uid))
+        .execute(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        info!(
+            "proposals.add_proposal_contributor: proposal_id={} user_id={}",
+            pid, uid
+        );
+        Ok(())
+    }
+}
+
+/// Revoke a contributor's edit access to one of your proposals (author only).
+#[dioxus::prelude::post("/api/proposals/remove_contributor")]
+pub async fn remove_proposal_contributor(
+    id_token: String,
+    proposal_id: String,
+    user_id: String,
+) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, proposal_id, user_id);
+        Err(ServerFnError::new(
+            "remove_proposal_contributor is server-only",
+        ))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "proposals.remove_proposal_contributor",
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        let caller = crate::auth::require_user_id(id_token).await?;
+        let pool = state.db.pool().await;
+        let (pid, uid) = authored_proposal(pool, caller, &proposal_id, &user_id).await?;
+
+        sqlx::query("delete from proposal_contributors where proposal_id = $1 and user_id = $2")
+            .bind(crate::db::uuid_to_db(pid))
+            .bind(crate::db::uuid_to_db(uid))
+            .execute(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        info!(
+            "proposals.remove_proposal_contributor: proposal_id={} user_id={}",
+            pid, uid
+        );
+        Ok(())
+    }
+}
+
+/// Position of a proposal in the vote-score ordering ("#3 of 120").
+#[dioxus::prelude::post("/api/proposals/:id/rank")]
+pub async fn get_proposal_rank(
+    id: String,
+    id_token: Option<String>,
+) -> Result<crate::types::VoteRank, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, id_token);
+        Err(ServerFnError::new("get_proposal_rank is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "proposals.get_proposal_rank",
+        use uuid::Uuid;
+
+        debug!("proposals.get_proposal_rank: id={}", id);
+        let target_id = Uuid::parse_str(&id).map_err(|_| ServerFnError::new("invalid id"))?;
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let pool = state.db.pool().await;
+        let rank =
+            crate::votes::vote_rank(pool, crate::types::ContentTargetType::Proposal, target_id)
+                .await?;
+        debug!(
+            "proposals.get_proposal_rank: rank={} total={}",
+            rank.rank, rank.total
+        );
+        Ok(rank)
+    }
+}
+
+/// Other proposals sharing tags with `id`, by overlap count then score.
+#[dioxus::prelude::post("/api/proposals/:id/related")]
+pub async fn list_related_proposals(
+    id: String,
+    limit: i64,
+    id_token: Option<String>,
+) -> Result<Vec<Proposal>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, limit, id_token);
+        Err(ServerFnError::new("list_related_proposals is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "proposals.list_related_proposals",
+        use std::cmp::Reverse;
+
+        debug!("proposals.list_related_proposals: id={} limit={}", id, limit);
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let limit = state.clamp_limit(limit);
+        let pool = state.db.pool().await;
+        let pid = crate::slug::resolve_id(pool, "proposals", &id).await?;
+
+        let (source, _) = fetch_proposal(pool, pid, false).await?;
+        if source.tags.is_empty() || limit <= 0 {
+            return Ok(Vec::new());
+        }
+
+        // Postgres narrows and orders candidates with array overlap; SQLite stores tags
+        // as JSON text, so every visible proposal is scored below.
+        let sql = if crate::db::is_sqlite() {
+            r#"
+            select
+                CAST(p.id as TEXT) as id,
+                CAST(p.author_user_id as TEXT) as author_user_id,
+                p.title,
+                coalesce(p.slug, CAST(p.id as TEXT)) as slug,
+                p.summary,
+                p.body_markdown,
+                p.tags,
+                CAST(p.created_at as TEXT) as created_at,
+                CAST(p.updated_at as TEXT) as updated_at,
+                coalesce(sum(v.value), 0) as vote_score,
+                ap.display_name as author_display_name
+            from proposals p
+            left join votes v
+                on v.target_type = 'proposal' and v.target_id = p.id
+            left join profiles ap on ap.user_id = p.author_user_id
+            where p.id <> $1 and p.hidden_at is null
+            group by p.id, ap.display_name
+            "#
+        } else {
+            r#"
+            select
+                CAST(p.id as TEXT) as id,
+                CAST(p.author_user_id as TEXT) as author_user_id,
+                p.title,
+                coalesce(p.slug, CAST(p.id as TEXT)) as slug,
+                p.summary,
+                p.body_markdown,
+                to_json(p.tags)::text as tags,
+                CAST(p.created_at as TEXT) as created_at,
+                CAST(p.updated_at as TEXT) as updated_at,
+                coalesce(sum(v.value), 0) as vote_score,
+                ap.display_name as author_display_name,
+                cardinality(array(select unnest(p.tags) intersect select unnest(src.tags))) as overlap
+            from proposals p
+            join proposals src on src.id = $1
+            left join votes v
+                on v.target_type = 'proposal' and v.target_id = p.id
+            left join profiles ap on ap.user_id = p.author_user_id
+            where p.id <> $1 and p.hidden_at is null and p.tags && src.tags
+            group by p.id, src.id, ap.display_name
+            order by overlap desc, vote_score desc
+            limit $2
+            "#
+        };
+
+        let rows = sqlx::query(sql)
+            .bind(crate::db::uuid_to_db(pid))
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let mut scored = Vec::with_capacity(rows.len());
+        for row in rows {
+            let proposal = proposal_from_row(&row)?;
+            let overlap = tag_overlap(&source.tags, &proposal.tags);
+            if overlap > 0 {
+                scored.push((overlap, proposal));
+            }
+        }
+
+        scored.sort_by_key(|(overlap, p)| (Reverse(*overlap), Reverse(p.vote_score), Reverse(p.created_at)));
+        scored.truncate(limit as usize);
+        let related: Vec<Proposal> = scored.into_iter().map(|(_, p)| p).collect();
+        debug!(
+            "proposals.list_related_proposals: id={} count={}",
+            pid,
+            related.len()
+        );
+        Ok(related)
+    }
+}
+
+/// Most proposals `find_similar_proposals` returns.
+#[cfg(feature = "server")]
+const SIMILAR_PROPOSALS_MAX: i64 = 5;
+
+/// Titles shorter than this, once slugified, match too much to be worth checking.
+#[cfg(feature = "server")]
+const SIMILAR_TITLE_MIN_LEN: usize = 4;
+
+/// Visible proposals whose title looks like `title`, most similar first, so the
+/// create form can point at them before a near-duplicate is posted.
+///
+/// Postgres ranks by trigram similarity; SQLite matches slugs (the normalized
+/// title) that contain, or are contained in, the slugified `title`.
+#[dioxus::prelude::post("/api/proposals/similar")]
+pub async fn find_similar_proposals(
+    title: String,
+    id_token: Option<String>,
+) -> Result<Vec<Proposal>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (title, id_token);
+        Err(ServerFnError::new("find_similar_proposals is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "proposals.find_similar_proposals",
+        debug!("proposals.find_similar_proposals: title_len={}", title.len());
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let normalized = crate::slug::slugify(&title);
+        if normalized.len() < SIMILAR_TITLE_MIN_LEN {
+            return Ok(Vec::new());
+        }
+        let pool = state.db.pool().await;
+
+        let query = if crate::db::is_sqlite() {
+            sqlx::query(
+                r#"
+                select
+                    CAST(p.id as TEXT) as id,
+                    CAST(p.author_user_id as TEXT) as author_user_id,
+                    p.title,
+                    p.slug,
+                    p.summary,
+                    p.body_markdown,
+                    p.tags,
+                    CAST(p.created_at as TEXT) as created_at,
+                    CAST(p.updated_at as TEXT) as updated_at,
+                    coalesce(sum(v.value), 0) as vote_score,
+                    ap.display_name as author_display_name
+                from proposals p
+                left join votes v
+                    on v.target_type = 'proposal' and v.target_id = p.id
+                left join profiles ap on ap.user_id = p.author_user_id
+                where p.hidden_at is null
+                    and (p.slug like '%' || $1 || '%' or $1 like '%' || p.slug || '%')
+                group by p.id, ap.display_name
+                order by abs(length(p.slug) - length($1)), p.created_at desc
+                limit $2
+                "#,
+            )
+            .bind(normalized)
+        } else {
+            sqlx::query(
+                r#"
+                select
+                    CAST(p.id as TEXT) as id,
+                    CAST(p.author_user_id as TEXT) as author_user_id,
+                    p.title,
+                    coalesce(p.slug, CAST(p.id as TEXT)) as slug,
+                    p.summary,
+                    p.body_markdown,
+                    to_json(p.tags)::text as tags,
+                    CAST(p.created_at as TEXT) as created_at,
+                    CAST(p.updated_at as TEXT) as updated_at,
+                    coalesce(sum(v.value), 0) as vote_score,
+                    ap.display_name as author_display_name
+                from proposals p
+                left join votes v
+                    on v.target_type = 'proposal' and v.target_id = p.id
+                left join profiles ap on ap.user_id = p.author_user_id
+                where p.hidden_at is null and lower(p.title) % lower($1)
+                group by p.id, ap.display_name
+                order by similarity(lower(p.title), lower($1)) desc, p.created_at desc
+                limit $2
+                "#,
+            )
+            .bind(title.trim().to_string())
+        };
+        let rows = query
+            .bind(SIMILAR_PROPOSALS_MAX)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let mut similar = Vec::with_capacity(rows.len());
+        for row in rows {
+            similar.push(proposal_from_row(&row)?);
+        }
+        debug!("proposals.find_similar_proposals: count={}", similar.len());
+        Ok(similar)
+    }
+}
+
+/// Visible proposals whose title, summary or body match `query`, best match first.
+///
+/// Postgres ranks a `simple` full-text match with titles weighted highest (see
+/// migration 0027); SQLite falls back to a substring match, titles first. A blank
+/// `query` matches nothing.
+#[dioxus::prelude::post("/api/proposals/search")]
+pub async fn search_proposals(
+    query: String,
+    limit: i64,
+    id_token: Option<String>,
+) -> Result<Vec<Proposal>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (query, limit, id_token);
+        Err(ServerFnError::new("search_proposals is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "proposals.search_proposals",
+        debug!(
+            "proposals.search_proposals: query_len={} limit={}",
+            query.len(),
+            limit
+        );
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let limit = state.clamp_limit(limit);
+        let pool = state.db.pool().await;
+
+        let sql_query = if crate::db::is_sqlite() {
+            let pattern = format!(
+                "%{}%",
+                query
+                    .replace('\\', "\\\\")
+                    .replace('%', "\\%")
+                    .replace('_', "\\_")
+            );
+            sqlx::query(
+                r#"
+                select
+                    CAST(p.id as TEXT) as id,
+                    CAST(p.author_user_id as TEXT) as author_user_id,
+                    p.title,
+                    coalesce(p.slug, CAST(p.id as TEXT)) as slug,
+                    p.summary,
+                    p.body_markdown,
+                    p.tags,
+                    CAST(p.created_at as TEXT) as created_at,
+                    CAST(p.updated_at as TEXT) as updated_at,
+                    coalesce(sum(v.value), 0) as vote_score,
+                    ap.display_name as author_display_name
+                from proposals p
+                left join votes v
+                    on v.target_type = 'proposal' and v.target_id = p.id
+                left join profiles ap on ap.user_id = p.author_user_id
+                where p.hidden_at is null
+                    and (p.title like $1 escape '\'
+                        or p.summary like $1 escape '\'
+                        or p.body_markdown like $1 escape '\')
+                group by p.id, ap.display_name
+                order by (p.title like $1 escape '\') desc, p.created_at desc
+                limit $2
+                "#,
+            )
+            .bind(pattern)
+        } else {
+            // The document expression must stay the one `proposals_search_idx` indexes.
+            sqlx::query(
+                r#"
+                select
+                    CAST(p.id as TEXT) as id,
+                    CAST(p.author_user_id as TEXT) as author_user_id,
+                    p.title,
+                    coalesce(p.slug, CAST(p.id as TEXT)) as slug,
+                    p.summary,
+                    p.body_markdown,
+                    to_json(p.tags)::text as tags,
+                    CAST(p.created_at as TEXT) as created_at,
+                    CAST(p.updated_at as TEXT) as updated_at,
+                    coalesce(sum(v.value), 0) as vote_score,
+                    ap.display_name as author_display_name
+                from proposals p
+                left join votes v
+                    on v.target_type = 'proposal' and v.target_id = p.id
+                left join profiles ap on ap.user_id = p.author_user_id
+                cross join plainto_tsquery('simple', $1) as q(terms)
+                where p.hidden_at is null
+                    and (setweight(to_tsvector('simple', p.title), 'A')
+                        || to_tsvector('simple', p.summary || ' ' || p.body_markdown)) @@ q.terms
+                group by p.id, q.terms, ap.display_name
+                order by
+                    ts_rank(
+                        setweight(to_tsvector('simple', p.title), 'A')
+                            || to_tsvector('simple', p.summary || ' ' || p.body_markdown),
+                        q.terms
+                    ) desc,
+                    p.created_at desc
+                limit $2
+                "#,
+            )
+            .bind(query.to_string())
+        };
+        let rows = sql_query
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let mut found = Vec::with_capacity(rows.len());
+        for row in rows {
+            found.push(proposal_from_row(&row)?);
+        }
+        debug!("proposals.search_proposals: count={}", found.len());
+        Ok(found)
+    }
+}
+
+/// Sum of `value / (age_hours + 2)^1.5` over `value:age_hours` pairs joined by commas.
+#[cfg(feature = "server")]
+fn trending_score(vote_ages: &str) -> f64 {
+    vote_ages
+        .split(',')
+        .filter_map(|pair| {
+            let (value, age) = pair.split_once(':')?;
+            let value: f64 = value.trim().parse().ok()?;
+            let age: f64 = age.trim().parse().ok()?;
+            Some(value / (age.max(0.0) + 2.0).powf(1.5))
+        })
+        .sum()
+}
+
+/// Number of distinct tags in `candidate` that also appear in `source`.
+#[cfg(feature = "server")]
+fn tag_overlap(source: &[String], candidate: &[String]) -> usize {
+    let source: std::collections::HashSet<&str> = source.iter().map(String::as_str).collect();
+    let candidate: std::collections::HashSet<&str> = candidate.iter().map(String::as_str).collect();
+    source.intersection(&candidate).count()
+}
@@ -0,0 +1,152 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How often `check` drops keys whose attempts have all left their window.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// In-memory sliding-window limiter shared through `AppState`.
+///
+/// Keys are free-form (e.g. `comment:<user_id>`), so one instance serves every
+/// throttled endpoint. Some keys are caller-chosen (signup emails), so idle keys are
+/// swept every [`SWEEP_INTERVAL`] rather than kept forever. State is per-process and
+/// resets on restart.
+#[derive(Debug)]
+pub struct RateLimiter {
+    hits: Mutex<Hits>,
+}
+
+#[derive(Debug)]
+struct Hits {
+    keys: HashMap<String, Bucket>,
+    last_sweep: Instant,
+}
+
+/// Recent attempts for one key, with the window they were checked against.
+#[derive(Debug)]
+struct Bucket {
+    window: Duration,
+    attempts: VecDeque<Instant>,
+}
+
+impl Bucket {
+    fn evict(&mut self, now: Instant) {
+        while let Some(first) = self.attempts.front() {
+            if now.duration_since(*first) >= self.window {
+                self.attempts.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self {
+            hits: Mutex::new(Hits {
+                keys: HashMap::new(),
+                last_sweep: Instant::now(),
+            }),
+        }
+    }
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an attempt for `key`; returns `false` when `max` attempts already
+    /// happened inside `window`.
+    pub fn check(&self, key: &str, max: usize, window: Duration) -> bool {
+        self.check_at(key, max, window, Instant::now())
+    }
+
+    pub fn check_at(&self, key: &str, max: usize, window: Duration, now: Instant) -> bool {
+        let mut hits = self.hits.lock().unwrap_or_else(|e| e.into_inner());
+        if now.duration_since(hits.last_sweep) >= SWEEP_INTERVAL {
+            hits.keys.retain(|_, bucket| {
+                bucket.evict(now);
+                !bucket.attempts.is_empty()
+            });
+            hits.last_sweep = now;
+        }
+
+        let bucket = hits.keys.entry(key.to_string()).or_insert_with(|| Bucket {
+            window,
+            attempts: VecDeque::new(),
+        });
+        bucket.window = window;
+        bucket.evict(now);
+        if bucket.attempts.len() >= max {
+            if bucket.attempts.is_empty() {
+                hits.keys.remove(key);
+            }
+            return false;
+        }
+        bucket.attempts.push_back(now);
+        true
+    }
+
+    /// Forget all attempts for `key`.
+    pub fn reset(&self, key: &str) {
+        let mut hits = self.hits.lock().unwrap_or_else(|e| e.into_inner());
+        hits.keys.remove(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_after_max_within_window() {
+        let limiter = RateLimiter::new();
+        let start = Instant::now();
+        let window = Duration::from_secs(60);
+        for _ in 0..3 {
+            assert!(limiter.check_at("k", 3, window, start));
+        }
+        assert!(!limiter.check_at("k", 3, window, start + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn test_allows_again_after_window() {
+        let limiter = RateLimiter::new();
+        let start = Instant::now();
+        let window = Duration::from_secs(60);
+        assert!(limiter.check_at("k", 1, window, start));
+        assert!(!limiter.check_at("k", 1, window, start + Duration::from_secs(59)));
+        assert!(limiter.check_at("k", 1, window, start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_keys_are_independent() {
+        let limiter = RateLimiter::new();
+        let now = Instant::now();
+        let window = Duration::from_secs(60);
+        assert!(limiter.check_at("a", 1, window, now));
+        assert!(limiter.check_at("b", 1, window, now));
+        assert!(!limiter.check_at("a", 1, window, now));
+        limiter.reset("a");
+        assert!(limiter.check_at("a", 1, window, now));
+    }
+
+    #[test]
+    fn test_sweep_drops_expired_keys() {
+        let limiter = RateLimiter::new();
+        let start = Instant::now();
+        assert!(limiter.check_at("short", 1, Duration::from_secs(10), start));
+        assert!(limiter.check_at("long", 1, Duration::from_secs(3600), start));
+        assert!(!limiter.check_at("closed", 0, Duration::from_secs(10), start));
+        assert!(!limiter.hits.lock().unwrap().keys.contains_key("closed"));
+
+        let later = start + SWEEP_INTERVAL;
+        assert!(limiter.check_at("fresh", 1, Duration::from_secs(10), later));
+        let hits = limiter.hits.lock().unwrap();
+        let mut keys: Vec<&str> = hits.keys.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["fresh", "long"]);
+    }
+}
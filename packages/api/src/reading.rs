@@ -0,0 +1,73 @@
+//! Word count and reading time for markdown bodies, shown on detail pages.
+
+/// Average adult silent-reading speed used for the estimate.
+const WORDS_PER_MINUTE: i64 = 200;
+
+/// Words in `markdown` once its syntax is ignored.
+///
+/// Link and image targets are dropped (their text is kept), and tokens with no
+/// letters or digits — heading marks, bullets, rules, code fences — don't count.
+pub fn word_count(markdown: &str) -> i64 {
+    strip_link_targets(markdown)
+        .split_whitespace()
+        .filter(|token| token.chars().any(char::is_alphanumeric))
+        .count() as i64
+}
+
+/// Minutes to read `words` words, rounded up; zero only for an empty body.
+pub fn estimated_read_minutes(words: i64) -> i64 {
+    if words <= 0 {
+        return 0;
+    }
+    (words + WORDS_PER_MINUTE - 1) / WORDS_PER_MINUTE
+}
+
+/// Replace every `](target)` with a space so URLs aren't counted as words.
+fn strip_link_targets(markdown: &str) -> String {
+    let mut out = String::with_capacity(markdown.len());
+    let mut rest = markdown;
+    while let Some(start) = rest.find("](") {
+        out.push_str(&rest[..start]);
+        out.push(' ');
+        rest = &rest[start + 2..];
+        match rest.find(')') {
+            Some(end) => rest = &rest[end + 1..],
+            None => rest = "",
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_count_plain_text() {
+        assert_eq!(word_count("Free public transport for everyone."), 5);
+        assert_eq!(word_count("  l'école   pour tous \n ensemble "), 4);
+    }
+
+    #[test]
+    fn test_word_count_ignores_markdown_syntax() {
+        let body = "# Heading\n\n- first point\n- **second** point\n\n---\n\n\
+                    > quoted\n\n```\nlet x = 1;\n```\n\nSee [the plan](https://example.com/plan).";
+        // Heading, first, point, second, point, quoted, let, x, 1;, See, the, plan.
+        assert_eq!(word_count(body), 12);
+    }
+
+    #[test]
+    fn test_word_count_empty_body() {
+        assert_eq!(word_count(""), 0);
+        assert_eq!(word_count("  \n# \n---\n"), 0);
+    }
+
+    #[test]
+    fn test_estimated_read_minutes_rounds_up() {
+        assert_eq!(estimated_read_minutes(0), 0);
+        assert_eq!(estimated_read_minutes(1), 1);
+        assert_eq!(estimated_read_minutes(200), 1);
+        assert_eq!(estimated_read_minutes(201), 2);
+    }
+}
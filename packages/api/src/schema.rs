@@ -0,0 +1,718 @@
+//! Hand-maintained registry of the server-function surface, served at `/api/_schema`.
+//!
+//! Keep this in sync when adding or changing a `#[get]`/`#[post]` endpoint.
+
+use crate::types::{ActivityAction, ContentTargetType, ErrorCode};
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Copy)]
+pub struct EndpointDoc {
+    pub method: &'static str,
+    pub path: &'static str,
+    pub inputs: &'static [(&'static str, &'static str)],
+    pub output: &'static str,
+}
+
+const fn get(
+    path: &'static str,
+    inputs: &'static [(&'static str, &'static str)],
+    output: &'static str,
+) -> EndpointDoc {
+    EndpointDoc {
+        method: "GET",
+        path,
+        inputs,
+        output,
+    }
+}
+
+const fn post(
+    path: &'static str,
+    inputs: &'static [(&'static str, &'static str)],
+    output: &'static str,
+) -> EndpointDoc {
+    EndpointDoc {
+        method: "POST",
+        path,
+        inputs,
+        output,
+    }
+}
+
+const TOKEN: (&str, &str) = ("id_token", "String");
+const OPTIONAL_TOKEN: (&str, &str) = ("id_token", "Option<String>");
+const TARGET_TYPE: (&str, &str) = ("target_type", "ContentTargetType");
+const TARGET_ID: (&str, &str) = ("target_id", "String");
+const LIMIT: (&str, &str) = ("limit", "i64");
+const OFFSET: (&str, &str) = ("offset", "i64");
+const LANG: (&str, &str) = ("lang", "Option<String>");
+
+pub const ENDPOINTS: &[EndpointDoc] = &[
+    // health / meta
+    get("/api/health", &[], "String"),
+    get("/api/health/detailed", &[], "Json"),
+    post("/api/health/migrations", &[TOKEN], "MigrationStatus"),
+    get("/metrics", &[("token", "Option<String>")], "String"),
+    get("/api/_schema", &[], "Json"),
+    post("/api/echo", &[("input", "String")], "String"),
+    get("/api/config", &[], "PublicConfig"),
+    // auth
+    post("/api/auth/me", &[TOKEN], "Me"),
+    post(
+        "/api/auth/signup",
+        &[
+            ("email", "String"),
+            ("password", "String"),
+            LANG,
+            ("captcha_token", "Option<String>"),
+        ],
+        "()",
+    ),
+    post("/api/auth/verify-email", &[("token", "String"), LANG], "()"),
+    post(
+        "/api/auth/signin",
+        &[("email", "String"), ("password", "String"), LANG],
+        "String",
+    ),
+    post(
+        "/api/auth/resend-verification",
+        &[("email", "String")],
+        "()",
+    ),
+    post(
+        "/api/auth/request-password-reset",
+        &[
+            ("email", "String"),
+            LANG,
+            ("captcha_token", "Option<String>"),
+        ],
+        "()",
+    ),
+    post(
+        "/api/auth/reset-password",
+        &[("token", "String"), ("new_password", "String"), LANG],
+        "()",
+    ),
+    // profile / activity
+    post(
+        "/api/profile/upsert",
+        &[
+            TOKEN,
+            ("display_name", "String"),
+            ("bio", "String"),
+            ("avatar_url", "Option<String>"),
+            ("location", "Option<String>"),
+            ("expected_updated_at", "Option<OffsetDateTime>"),
+        ],
+        "Profile",
+    ),
+    post(
+        "/api/profile/:user_id",
+        &[("user_id", "String"), OPTIONAL_TOKEN],
+        "PublicProfile",
+    ),
+    post("/api/account/export", &[TOKEN], "AccountExport"),
+    post(
+        "/api/activity/me",
+        &[TOKEN, LIMIT, ("before", "Option<ActivityCursor>")],
+        "Vec<ActivityItem>",
+    ),
+    post(
+        "/api/activity/global",
+        &[LIMIT, OFFSET, OPTIONAL_TOKEN],
+        "Vec<ActivityItem>",
+    ),
+    // blog
+    post(
+        "/api/blog/create",
+        &[TOKEN, ("title", "String"), ("body_markdown", "String")],
+        "Post",
+    ),
+    get("/api/blog/:id", &[("id", "i64")], "Post"),
+    get("/api/blog", &[LIMIT, OFFSET], "Vec<Post>"),
+    // proposals
+    post(
+        "/api/proposals/create",
+        &[
+            TOKEN,
+            ("title", "String"),
+            ("summary", "String"),
+            ("body_markdown", "String"),
+            ("tags_csv", "String"),
+        ],
+        "Proposal",
+    ),
+    post(
+        "/api/proposals/list",
+        &[LIMIT, ("before", "Option<ProposalCursor>"), OPTIONAL_TOKEN],
+        "Vec<Proposal>",
+    ),
+    post(
+        "/api/proposals/discover",
+        &[("options", "DiscoverOptions"), OPTIONAL_TOKEN],
+        "ProposalPage",
+    ),
+    post(
+        "/api/proposals/count",
+        &[
+            ("query", "Option<String>"),
+            ("tag", "Option<String>"),
+            OPTIONAL_TOKEN,
+        ],
+        "i64",
+    ),
+    post(
+        "/api/proposals/trending",
+        &[LIMIT, OPTIONAL_TOKEN],
+        "Vec<Proposal>",
+    ),
+    post(
+        "/api/proposals/by_author/:user_id",
+        &[("user_id", "String"), LIMIT, OFFSET, OPTIONAL_TOKEN],
+        "Vec<Proposal>",
+    ),
+    post(
+        "/api/proposals/by_tag",
+        &[("tag", "String"), LIMIT, OPTIONAL_TOKEN],
+        "Vec<Proposal>",
+    ),
+    post(
+        "/api/proposals/get/:id",
+        &[("id", "String"), OPTIONAL_TOKEN],
+        "ProposalDetail",
+    ),
+    post(
+        "/api/proposals/get/:id/if_changed",
+        &[("id", "String"), ("etag", "String"), OPTIONAL_TOKEN],
+        "Conditional<ProposalDetail>",
+    ),
+    post(
+        "/api/proposals/:id/rank",
+        &[("id", "String"), OPTIONAL_TOKEN],
+        "VoteRank",
+    ),
+    post(
+        "/api/proposals/:id/related",
+        &[("id", "String"), LIMIT, OPTIONAL_TOKEN],
+        "Vec<Proposal>",
+    ),
+    post(
+        "/api/proposals/similar",
+        &[("title", "String"), OPTIONAL_TOKEN],
+        "Vec<Proposal>",
+    ),
+    post(
+        "/api/proposals/search",
+        &[("query", "String"), LIMIT, OPTIONAL_TOKEN],
+        "Vec<Proposal>",
+    ),
+    post(
+        "/api/proposals/:id/programs",
+        &[("id", "String"), OPTIONAL_TOKEN],
+        "Vec<ProgramMembership>",
+    ),
+    post(
+        "/api/proposals/update",
+        &[
+            TOKEN,
+            ("id", "String"),
+            ("title", "String"),
+            ("summary", "String"),
+            ("body_markdown", "String"),
+            ("tags_csv", "String"),
+            ("expected_updated_at", "OffsetDateTime"),
+        ],
+        "Proposal",
+    ),
+    post(
+        "/api/proposals/delete",
+        &[TOKEN, ("id", "String"), ("force", "bool")],
+        "()",
+    ),
+    post(
+        "/api/proposals/add_contributor",
+        &[TOKEN, ("proposal_id", "String"), ("user_id", "String")],
+        "()",
+    ),
+    post(
+        "/api/proposals/remove_contributor",
+        &[TOKEN, ("proposal_id", "String"), ("user_id", "String")],
+        "()",
+    ),
+    // programs
+    post(
+        "/api/programs/create",
+        &[
+            TOKEN,
+            ("title", "String"),
+            ("summary", "String"),
+            ("body_markdown", "String"),
+        ],
+        "Program",
+    ),
+    post(
+        "/api/programs/add_item",
+        &[
+            TOKEN,
+            ("program_id", "String"),
+            ("proposal_id", "String"),
+            ("position", "i32"),
+        ],
+        "()",
+    ),
+    post(
+        "/api/programs/set_items",
+        &[
+            TOKEN,
+            ("program_id", "String"),
+            ("proposal_ids", "Vec<String>"),
+        ],
+        "()",
+    ),
+    post(
+        "/api/programs/remove_item",
+        &[TOKEN, ("program_id", "String"), ("proposal_id", "String")],
+        "()",
+    ),
+    post(
+        "/api/programs/reorder_items",
+        &[
+            TOKEN,
+            ("program_id", "String"),
+            ("ordered_proposal_ids", "Vec<String>"),
+        ],
+        "()",
+    ),
+    post(
+        "/api/programs/list",
+        &[LIMIT, OPTIONAL_TOKEN],
+        "Vec<Program>",
+    ),
+    post("/api/programs/count", &[OPTIONAL_TOKEN], "i64"),
+    post(
+        "/api/programs/by_author/:user_id",
+        &[("user_id", "String"), LIMIT, OFFSET, OPTIONAL_TOKEN],
+        "Vec<Program>",
+    ),
+    post(
+        "/api/programs/get/:id",
+        &[("id", "String"), OPTIONAL_TOKEN],
+        "ProgramDetail",
+    ),
+    post(
+        "/api/programs/get/:id/if_changed",
+        &[("id", "String"), ("etag", "String"), OPTIONAL_TOKEN],
+        "Conditional<ProgramDetail>",
+    ),
+    post(
+        "/api/programs/:id/rank",
+        &[("id", "String"), OPTIONAL_TOKEN],
+        "VoteRank",
+    ),
+    post(
+        "/api/programs/update",
+        &[
+            TOKEN,
+            ("id", "String"),
+            ("title", "String"),
+            ("summary", "String"),
+            ("body_markdown", "String"),
+            ("expected_updated_at", "OffsetDateTime"),
+        ],
+        "Program",
+    ),
+    // votes
+    post(
+        "/api/votes/set",
+        &[TOKEN, TARGET_TYPE, TARGET_ID, ("value", "i16")],
+        "VoteState",
+    ),
+    post(
+        "/api/votes/clear",
+        &[TOKEN, TARGET_TYPE, TARGET_ID],
+        "VoteState",
+    ),
+    post(
+        "/api/votes/state",
+        &[TOKEN, TARGET_TYPE, TARGET_ID],
+        "VoteState",
+    ),
+    post(
+        "/api/votes/:target_type/:target_id/score",
+        &[TARGET_TYPE, TARGET_ID, OPTIONAL_TOKEN],
+        "i64",
+    ),
+    post(
+        "/api/votes/breakdown",
+        &[TOKEN, TARGET_TYPE, TARGET_ID],
+        "VoteBreakdown",
+    ),
+    post(
+        "/api/votes/my",
+        &[
+            TOKEN,
+            ("target_type", "Option<ContentTargetType>"),
+            LIMIT,
+            OFFSET,
+        ],
+        "Vec<VotedItem>",
+    ),
+    // bookmarks
+    post(
+        "/api/bookmarks/toggle",
+        &[TOKEN, TARGET_TYPE, TARGET_ID],
+        "bool",
+    ),
+    post(
+        "/api/bookmarks/list",
+        &[
+            TOKEN,
+            ("target_type", "Option<ContentTargetType>"),
+            LIMIT,
+            OFFSET,
+        ],
+        "Vec<BookmarkedItem>",
+    ),
+    // comments
+    post(
+        "/api/comments/create",
+        &[
+            TOKEN,
+            TARGET_TYPE,
+            TARGET_ID,
+            ("parent_comment_id", "Option<String>"),
+            ("body_markdown", "String"),
+        ],
+        "Comment",
+    ),
+    post(
+        "/api/comments/reply",
+        &[
+            TOKEN,
+            TARGET_TYPE,
+            TARGET_ID,
+            ("parent_comment_id", "String"),
+            ("body_markdown", "String"),
+        ],
+        "Comment",
+    ),
+    post(
+        "/api/comments/list",
+        &[TARGET_TYPE, TARGET_ID, LIMIT, OPTIONAL_TOKEN],
+        "Vec<Comment>",
+    ),
+    post(
+        "/api/comments/top",
+        &[TARGET_TYPE, TARGET_ID, LIMIT, OPTIONAL_TOKEN],
+        "Vec<Comment>",
+    ),
+    post(
+        "/api/comments/by_author/:user_id",
+        &[("user_id", "String"), LIMIT, OFFSET, OPTIONAL_TOKEN],
+        "Vec<AuthoredComment>",
+    ),
+    post(
+        "/api/comments/:id",
+        &[("id", "String"), OPTIONAL_TOKEN],
+        "AuthoredComment",
+    ),
+    post(
+        "/api/comments/pin",
+        &[TOKEN, ("comment_id", "String")],
+        "()",
+    ),
+    post(
+        "/api/comments/unpin",
+        &[TOKEN, ("comment_id", "String")],
+        "()",
+    ),
+    post(
+        "/api/comments/update",
+        &[TOKEN, ("comment_id", "String"), ("body_markdown", "String")],
+        "Comment",
+    ),
+    post(
+        "/api/comments/delete",
+        &[TOKEN, ("comment_id", "String")],
+        "()",
+    ),
+    // moderation (reports from any user, the rest admin only)
+    post(
+        "/api/moderation/report",
+        &[TOKEN, TARGET_TYPE, TARGET_ID, ("reason", "String")],
+        "()",
+    ),
+    post(
+        "/api/moderation/hide",
+        &[TOKEN, TARGET_TYPE, TARGET_ID],
+        "()",
+    ),
+    post(
+        "/api/moderation/unhide",
+        &[TOKEN, TARGET_TYPE, TARGET_ID],
+        "()",
+    ),
+    post(
+        "/api/moderation/proposal",
+        &[TOKEN, ("id", "String")],
+        "ModeratedProposal",
+    ),
+    // admin maintenance
+    post("/api/admin/recompute_counts", &[TOKEN], "CountsRecomputed"),
+    post(
+        "/api/admin/audit",
+        &[TOKEN, LIMIT, OFFSET],
+        "Vec<AuditEntry>",
+    ),
+    // sync (offline-first clients)
+    post(
+        "/api/sync/proposals",
+        &[("after", "Option<SyncCursor>"), LIMIT, OPTIONAL_TOKEN],
+        "ProposalSync",
+    ),
+    // notifications
+    post(
+        "/api/notifications/list",
+        &[TOKEN, LIMIT, ("kind", "Option<NotificationKind>")],
+        "Vec<Notification>",
+    ),
+    post("/api/notifications/unread_count", &[TOKEN], "i64"),
+    post("/api/notifications/mark_all_read", &[TOKEN], "u64"),
+    post(
+        "/api/notifications/mark_read",
+        &[TOKEN, ("notification_id", "String")],
+        "()",
+    ),
+    post(
+        "/api/notifications/email_preference",
+        &[TOKEN, ("enabled", "bool")],
+        "bool",
+    ),
+    post(
+        "/api/notifications/unsubscribe",
+        &[("user_id", "String"), ("token", "String")],
+        "String",
+    ),
+    // uploads / videos
+    post(
+        "/api/uploads/video_intent",
+        &[
+            TOKEN,
+            TARGET_TYPE,
+            TARGET_ID,
+            ("content_type", "String"),
+            ("byte_size", "i64"),
+        ],
+        "UploadIntent",
+    ),
+    post(
+        "/api/uploads/finalize_video",
+        &[
+            TOKEN,
+            TARGET_TYPE,
+            TARGET_ID,
+            ("storage_key", "String"),
+            ("content_type", "String"),
+            ("byte_size", "i64"),
+        ],
+        "Video",
+    ),
+    post(
+        "/api/uploads/multipart/start",
+        &[TOKEN, TARGET_TYPE, TARGET_ID, ("content_type", "String")],
+        "MultipartUpload",
+    ),
+    post(
+        "/api/uploads/multipart/part",
+        &[
+            TOKEN,
+            ("storage_key", "String"),
+            ("upload_id", "String"),
+            ("part_number", "i32"),
+            ("data", "Vec<u8>"),
+        ],
+        "String",
+    ),
+    post(
+        "/api/uploads/multipart/complete",
+        &[
+            TOKEN,
+            ("storage_key", "String"),
+            ("upload_id", "String"),
+            ("parts", "Vec<UploadedPart>"),
+        ],
+        "()",
+    ),
+    post(
+        "/api/videos/:id",
+        &[("id", "String"), OPTIONAL_TOKEN],
+        "VideoDetail",
+    ),
+    post(
+        "/api/videos/:id/playback",
+        &[("id", "String"), OPTIONAL_TOKEN],
+        "String",
+    ),
+    post(
+        "/api/videos/list",
+        &[TARGET_TYPE, TARGET_ID, LIMIT, OPTIONAL_TOKEN],
+        "Vec<Video>",
+    ),
+    post(
+        "/api/videos/by_author/:user_id",
+        &[("user_id", "String"), LIMIT, OFFSET, OPTIONAL_TOKEN],
+        "Vec<Video>",
+    ),
+    // captions
+    post(
+        "/api/captions/intent",
+        &[TOKEN, ("video_id", "String"), ("language", "String")],
+        "UploadIntent",
+    ),
+    post(
+        "/api/captions/finalize",
+        &[
+            TOKEN,
+            ("video_id", "String"),
+            ("language", "String"),
+            ("storage_key", "String"),
+        ],
+        "CaptionTrack",
+    ),
+    // video feed
+    post(
+        "/api/video_feed/mark_viewed",
+        &[TOKEN, ("video_id", "String")],
+        "()",
+    ),
+    post(
+        "/api/video_feed/bookmark",
+        &[TOKEN, ("video_id", "String")],
+        "bool",
+    ),
+    post(
+        "/api/video_feed/list_bookmarks",
+        &[TOKEN, LIMIT, OFFSET],
+        "Vec<Video>",
+    ),
+    post(
+        "/api/video_feed/list_mine",
+        &[TOKEN, LIMIT, OFFSET],
+        "Vec<Video>",
+    ),
+    post(
+        "/api/video_feed/list_feed",
+        &[TOKEN, LIMIT, OFFSET],
+        "Vec<Video>",
+    ),
+    post(
+        "/api/video_feed/list_single_content",
+        &[TARGET_TYPE, TARGET_ID, LIMIT, OFFSET],
+        "Vec<Video>",
+    ),
+];
+
+pub fn find(method: &str, path: &str) -> Option<&'static EndpointDoc> {
+    ENDPOINTS
+        .iter()
+        .find(|e| e.method == method && e.path == path)
+}
+
+/// JSON description of every endpoint plus the wire values of the shared enums.
+pub fn schema_json() -> Value {
+    let endpoints: Vec<Value> = ENDPOINTS
+        .iter()
+        .map(|e| {
+            json!({
+                "method": e.method,
+                "path": e.path,
+                "inputs": e
+                    .inputs
+                    .iter()
+                    .map(|(name, ty)| json!({ "name": name, "type": ty }))
+                    .collect::<Vec<_>>(),
+                "output": e.output,
+            })
+        })
+        .collect();
+
+    let target_types = [
+        ContentTargetType::Proposal,
+        ContentTargetType::Program,
+        ContentTargetType::Video,
+        ContentTargetType::Comment,
+    ];
+    let actions = [
+        ActivityAction::Created,
+        ActivityAction::Voted,
+        ActivityAction::Commented,
+        ActivityAction::Bookmarked,
+        ActivityAction::Bundled,
+        ActivityAction::Deleted,
+    ];
+
+    json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "endpoints": endpoints,
+        "enums": {
+            "ContentTargetType": target_types.iter().map(|t| json!(t)).collect::<Vec<_>>(),
+            "ActivityAction": actions.iter().map(|a| json!(a)).collect::<Vec<_>>(),
+            "ErrorCode": ErrorCode::ALL.iter().map(|c| json!(c)).collect::<Vec<_>>(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_endpoints_are_registered() {
+        let vote = find("POST", "/api/votes/set").expect("votes/set registered");
+        assert_eq!(vote.output, "VoteState");
+        // Reads that take an id_token are POSTs so the token stays out of URLs.
+        assert!(find("POST", "/api/proposals/get/:id").is_some());
+        assert!(find("GET", "/api/config").is_some());
+        assert!(find("GET", "/api/proposals/get/:id").is_none());
+    }
+
+    #[test]
+    fn test_get_endpoints_take_no_id_token() {
+        for e in ENDPOINTS.iter().filter(|e| e.method == "GET") {
+            assert!(
+                e.inputs.iter().all(|(name, _)| *name != "id_token"),
+                "GET {} would put the id_token in its URL",
+                e.path
+            );
+        }
+    }
+
+    #[test]
+    fn test_endpoints_are_unique() {
+        for (i, a) in ENDPOINTS.iter().enumerate() {
+            for b in &ENDPOINTS[i + 1..] {
+                assert!(
+                    !(a.method == b.method && a.path == b.path),
+                    "duplicate endpoint {} {}",
+                    a.method,
+                    a.path
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_schema_json_shape() {
+        let schema = schema_json();
+        let endpoints = schema["endpoints"].as_array().unwrap();
+        assert_eq!(endpoints.len(), ENDPOINTS.len());
+        let signin = endpoints
+            .iter()
+            .find(|e| e["path"] == "/api/auth/signin")
+            .unwrap();
+        assert_eq!(signin["method"], "POST");
+        assert_eq!(signin["inputs"][0]["name"], "email");
+        assert_eq!(schema["enums"]["ContentTargetType"][0], "proposal");
+        assert_eq!(schema["enums"]["ErrorCode"][0], "invalid_email");
+    }
+}
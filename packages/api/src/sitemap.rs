@@ -0,0 +1,156 @@
+//! `/sitemap.xml` for crawlers: every visible proposal and program.
+//!
+//! A plain axum route rather than a server fn, so crawlers get `application/xml`
+//! instead of a JSON string. Past [`PAGE_SIZE`] URLs, `/sitemap.xml` becomes a
+//! sitemap index pointing at `/sitemap.xml?page=N`. Deployments with `public_read`
+//! off have nothing for crawlers, so the route answers `404`.
+
+use axum::extract::Query;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use dioxus::prelude::ServerFnError;
+use std::fmt::Write as _;
+use time::format_description::well_known::Rfc3339;
+
+/// URLs per sitemap file; the protocol allows at most 50,000.
+pub const PAGE_SIZE: i64 = 50_000;
+
+/// Add the `/sitemap.xml` route to `router`.
+pub fn routes(router: Router) -> Router {
+    router.route("/sitemap.xml", get(sitemap))
+}
+
+#[derive(serde::Deserialize)]
+struct SitemapQuery {
+    page: Option<i64>,
+}
+
+async fn sitemap(Query(query): Query<SitemapQuery>) -> Response {
+    match render(query.page).await {
+        Ok(Some(xml)) => (
+            [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+            xml,
+        )
+            .into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            tracing::warn!("sitemap: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// The sitemap XML for `page` (1-based), or the index when there's more than one
+/// page and none was asked for; `None` for a page past the end, or for every page
+/// when `public_read` is off.
+pub async fn render(page: Option<i64>) -> Result<Option<String>, ServerFnError> {
+    use sqlx::Row;
+
+    let state = crate::state::AppState::current()?;
+    if !state.config.public_read {
+        return Ok(None);
+    }
+    let pool = state.db.pool().await;
+    let base_url = state.config.app_base_url.trim_end_matches('/');
+
+    let total: i64 = sqlx::query_scalar(
+        "select (select count(*) from proposals where hidden_at is null) + (select count(*) from programs where hidden_at is null)",
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let pages = ((total + PAGE_SIZE - 1) / PAGE_SIZE).max(1);
+
+    let page = match page {
+        None if pages > 1 => return Ok(Some(index_xml(base_url, pages))),
+        None => 1,
+        Some(page) if (1..=pages).contains(&page) => page,
+        Some(_) => return Ok(None),
+    };
+
+    let rows = sqlx::query(
+        r#"
+        select kind, slug, updated_at from (
+            select 'proposals' as kind, CAST(id as TEXT) as id,
+                coalesce(slug, CAST(id as TEXT)) as slug,
+                CAST(updated_at as TEXT) as updated_at
+            from proposals where hidden_at is null
+            union all
+            select 'programs' as kind, CAST(id as TEXT) as id,
+                coalesce(slug, CAST(id as TEXT)) as slug,
+                CAST(updated_at as TEXT) as updated_at
+            from programs where hidden_at is null
+        ) items
+        order by kind desc, id
+        limit $1 offset $2
+        "#,
+    )
+    .bind(PAGE_SIZE)
+    .bind((page - 1) * PAGE_SIZE)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for row in &rows {
+        let kind: String = row.get("kind");
+        let slug: String = row.get("slug");
+        let lastmod = crate::db::datetime_from_db(&row.get::<String, _>("updated_at"))?
+            .format(&Rfc3339)
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        let _ = writeln!(
+            xml,
+            "  <url><loc>{}</loc><lastmod>{}</lastmod></url>",
+            escape(&format!("{base_url}/{kind}/{slug}")),
+            lastmod
+        );
+    }
+    xml.push_str("</urlset>\n");
+    tracing::debug!("sitemap: page={} urls={}", page, rows.len());
+    Ok(Some(xml))
+}
+
+/// Sitemap index listing pages `1..=pages`.
+fn index_xml(base_url: &str, pages: i64) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for page in 1..=pages {
+        let _ = writeln!(
+            xml,
+            "  <sitemap><loc>{}</loc></sitemap>",
+            escape(&format!("{base_url}/sitemap.xml?page={page}"))
+        );
+    }
+    xml.push_str("</sitemapindex>\n");
+    xml
+}
+
+/// Escape text for an XML element body.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_lists_every_page_with_escaped_urls() {
+        let xml = index_xml("https://example.org", 2);
+        assert!(xml.contains("<loc>https://example.org/sitemap.xml?page=1</loc>"));
+        assert!(xml.contains("<loc>https://example.org/sitemap.xml?page=2</loc>"));
+        assert!(!xml.contains("page=3"));
+        assert_eq!(escape("a&b<c>"), "a&amp;b&lt;c&gt;");
+    }
+}
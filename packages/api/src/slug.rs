@@ -0,0 +1,168 @@
+//! Readable URL slugs for proposals and programs.
+//!
+//! Slugs are derived from the title and stored alongside the row; lookups accept
+//! either the UUID or the slug. Rows created before slugs existed have none until
+//! their next update, so readers fall back to the id.
+
+use dioxus::prelude::ServerFnError;
+use uuid::Uuid;
+
+/// Longest slug we generate, before any collision suffix.
+const MAX_SLUG_LEN: usize = 80;
+
+/// Lowercase, strip accents and join words with single hyphens.
+///
+/// Anything that isn't alphanumeric after de-accenting separates words.
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::with_capacity(title.len());
+    let mut pending_hyphen = false;
+    for c in title.chars().flat_map(char::to_lowercase) {
+        let mut buf = [0; 4];
+        let folded = if c.is_ascii_alphanumeric() {
+            &*c.encode_utf8(&mut buf)
+        } else if let Some(folded) = fold_accent(c) {
+            folded
+        } else {
+            pending_hyphen = !slug.is_empty();
+            continue;
+        };
+        if pending_hyphen {
+            slug.push('-');
+            pending_hyphen = false;
+        }
+        slug.push_str(folded);
+    }
+    if slug.len() > MAX_SLUG_LEN {
+        slug.truncate(MAX_SLUG_LEN);
+        while slug.ends_with('-') {
+            slug.pop();
+        }
+    }
+    slug
+}
+
+/// ASCII spelling of an accented lowercase letter.
+fn fold_accent(c: char) -> Option<&'static str> {
+    let folded = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => "a",
+        'æ' => "ae",
+        'ç' => "c",
+        'è' | 'é' | 'ê' | 'ë' => "e",
+        'ì' | 'í' | 'î' | 'ï' => "i",
+        'ñ' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => "o",
+        'œ' => "oe",
+        'ù' | 'ú' | 'û' | 'ü' => "u",
+        'ý' | 'ÿ' => "y",
+        'ß' => "ss",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+/// `base`, or `base-2`, `base-3`, ... — the first one not in `taken`.
+pub fn first_free(base: &str, taken: &[String]) -> String {
+    if !taken.iter().any(|t| t == base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{base}-{n}"))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("unbounded suffixes")
+}
+
+/// A slug for `title` that no other row of `table` uses.
+///
+/// `own_id` is excluded so re-saving a row under the same title keeps its slug.
+pub(crate) async fn unique_slug(
+    pool: &sqlx::Pool<sqlx::Any>,
+    table: &str,
+    title: &str,
+    own_id: Option<Uuid>,
+) -> Result<String, ServerFnError> {
+    // Punctuation-only titles slug to nothing; use the singular table name instead.
+    let mut base = slugify(title);
+    if base.is_empty() {
+        base = table.trim_end_matches('s').to_string();
+    }
+    let taken: Vec<String> = sqlx::query_scalar(&format!(
+        "select slug from {table} where (slug = $1 or slug like $2) and CAST(id as TEXT) <> $3"
+    ))
+    .bind(&base)
+    .bind(format!("{base}-%"))
+    .bind(own_id.map(crate::db::uuid_to_db).unwrap_or_default())
+    .fetch_all(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+    Ok(first_free(&base, &taken))
+}
+
+/// Resolve a path segment that is either a UUID or a slug of `table`.
+pub(crate) async fn resolve_id(
+    pool: &sqlx::Pool<sqlx::Any>,
+    table: &str,
+    id_or_slug: &str,
+) -> Result<Uuid, ServerFnError> {
+    if let Ok(id) = Uuid::parse_str(id_or_slug) {
+        return Ok(id);
+    }
+    let id: Option<String> = sqlx::query_scalar(&format!(
+        "select CAST(id as TEXT) from {table} where slug = $1"
+    ))
+    .bind(id_or_slug)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+    match id {
+        Some(id) => crate::db::uuid_from_db(&id),
+        None => Err(ServerFnError::new(
+            crate::types::ErrorCode::NotFound.to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify_lowercases_and_hyphenates() {
+        assert_eq!(slugify("Free Public Transport"), "free-public-transport");
+        assert_eq!(slugify("  Hello,   world!  "), "hello-world");
+        assert_eq!(slugify("2030: net zero"), "2030-net-zero");
+    }
+
+    #[test]
+    fn test_slugify_strips_accents() {
+        assert_eq!(slugify("Éducation à l'école"), "education-a-l-ecole");
+        assert_eq!(slugify("Cœur de réforme"), "coeur-de-reforme");
+        assert_eq!(slugify("Ça déçoit"), "ca-decoit");
+    }
+
+    #[test]
+    fn test_slugify_drops_everything_else() {
+        assert_eq!(slugify("!!!"), "");
+        assert_eq!(slugify("日本 policy"), "policy");
+    }
+
+    #[test]
+    fn test_slugify_caps_length() {
+        let slug = slugify(&"word ".repeat(40));
+        assert!(slug.len() <= MAX_SLUG_LEN);
+        assert!(!slug.ends_with('-'));
+    }
+
+    #[test]
+    fn test_first_free_suffixes_collisions() {
+        let taken = vec!["budget".to_string(), "budget-2".to_string()];
+        assert_eq!(first_free("budget", &[]), "budget");
+        assert_eq!(first_free("budget", &taken), "budget-3");
+        assert_eq!(first_free("budget-2", &taken), "budget-2-2");
+    }
+
+    #[test]
+    fn test_first_free_ignores_unrelated_slugs() {
+        let taken = vec!["budget-cuts".to_string()];
+        assert_eq!(first_free("budget", &taken), "budget");
+    }
+}
@@ -1,8 +1,13 @@
+use crate::captcha::{CaptchaVerifier, SiteverifyCaptcha};
 use crate::config::{AppConfig, AppMode, DatabaseConfig, EmailConfig, StorageConfig};
+use crate::content_filter::{BlocklistFilter, ContentFilter};
 use crate::db::{Database, PostgresDatabase, SqliteDatabase};
 use crate::email::{ConsoleEmailService, EmailService, SmtpEmailService};
+use crate::rate_limit::RateLimiter;
 use crate::storage::{filesystem::FilesystemStorageService, s3::S3StorageService, StorageService};
 use anyhow::Result;
+use dioxus::prelude::ServerFnError;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, OnceLock};
 
 /// Global application state containing all service implementations
@@ -11,6 +16,14 @@ pub struct AppState {
     pub email: Arc<dyn EmailService>,
     pub storage: Arc<dyn StorageService>,
     pub config: AppConfig,
+    /// Runtime read-only switch, seeded from `config.maintenance_mode`.
+    pub maintenance: AtomicBool,
+    /// Shared in-memory throttle for abuse-prone endpoints.
+    pub rate_limiter: RateLimiter,
+    /// Screens new comments and proposals; allows everything unless configured.
+    pub content_filter: Arc<dyn ContentFilter>,
+    /// Checks signup and password reset CAPTCHA tokens; `None` skips the check.
+    pub captcha: Option<Arc<dyn CaptchaVerifier>>,
 }
 
 impl std::fmt::Debug for AppState {
@@ -29,6 +42,11 @@ impl AppState {
     pub async fn from_config(config: AppConfig) -> Result<Self> {
         // Required for sqlx::Any pools; without this, AnyPoolOptions panics at runtime.
         sqlx::any::install_default_drivers();
+        // Start the metrics uptime clock at boot rather than on first scrape.
+        crate::metrics::global();
+        if let Err(e) = crate::auth::public_config().await {
+            tracing::warn!("public config not cached at startup: {}", e);
+        }
 
         // Log the mode we're running in
         match config.mode {
@@ -93,6 +111,18 @@ impl AppState {
             }
         };
 
+        match crate::db::migration_status(db.as_ref()).await {
+            Ok(status) if status.up_to_date => {
+                tracing::info!("✓ Schema at migration {:?}", status.applied.last());
+            }
+            Ok(status) => tracing::error!(
+                "✗ Schema version mismatch after migrations: applied={:?} expected={:?}",
+                status.applied,
+                status.expected
+            ),
+            Err(e) => tracing::error!("✗ Could not read migration status: {e:#}"),
+        }
+
         // Initialize email service
         let email: Arc<dyn EmailService> = match &config.email {
             EmailConfig::SMTP { .. } => {
@@ -125,10 +155,37 @@ impl AppState {
             }
         };
 
+        if !config.content_blocklist.is_empty() {
+            tracing::info!(
+                "Content filter: {} blocklisted terms ({:?})",
+                config.content_blocklist.len(),
+                config.content_filter_action
+            );
+        }
+        let content_filter: Arc<dyn ContentFilter> =
+            Arc::new(BlocklistFilter::from_config(&config));
+
+        let captcha: Option<Arc<dyn CaptchaVerifier>> =
+            match SiteverifyCaptcha::from_config(&config) {
+                Some(verifier) => {
+                    tracing::info!("CAPTCHA: {:?}", config.captcha_provider);
+                    Some(Arc::new(verifier))
+                }
+                None => None,
+            };
+
+        if config.maintenance_mode {
+            tracing::warn!("⚠ MAINTENANCE_MODE enabled: writes are rejected");
+        }
+
         let state = Self {
             db,
             email,
             storage,
+            maintenance: AtomicBool::new(config.maintenance_mode),
+            rate_limiter: RateLimiter::new(),
+            content_filter,
+            captcha,
             config,
         };
 
@@ -151,6 +208,38 @@ impl AppState {
         Ok(state)
     }
 
+    pub fn maintenance_mode(&self) -> bool {
+        self.maintenance.load(Ordering::Relaxed)
+    }
+
+    pub fn set_maintenance_mode(&self, enabled: bool) {
+        self.maintenance.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Guard called at the top of every mutating server function.
+    pub fn ensure_writable(&self) -> Result<(), ServerFnError> {
+        if self.maintenance_mode() {
+            return Err(ServerFnError::new("service is in read-only mode"));
+        }
+        Ok(())
+    }
+
+    /// A client-supplied list `limit`, clamped to `0..=config.max_list_limit`.
+    ///
+    /// Oversized limits are clamped silently rather than rejected; negative ones
+    /// become 0 (SQLite would read `LIMIT -1` as "no limit").
+    pub fn clamp_limit(&self, limit: i64) -> i64 {
+        limit.clamp(0, self.config.max_list_limit)
+    }
+
+    /// [`clamp_limit`](Self::clamp_limit) plus a check that `offset` isn't negative.
+    pub fn page(&self, limit: i64, offset: i64) -> Result<(i64, i64), ServerFnError> {
+        if offset < 0 {
+            return Err(ServerFnError::new("offset must not be negative"));
+        }
+        Ok((self.clamp_limit(limit), offset))
+    }
+
     /// Set the global AppState instance
     ///
     /// This should be called once at server startup.
@@ -165,18 +254,27 @@ impl AppState {
     ///
     /// Panics if called before set_global.
     pub fn global() -> Arc<Self> {
+        Self::try_global().expect("AppState::global called before set_global")
+    }
+
+    /// Get the global AppState instance, or `None` before set_global.
+    pub fn try_global() -> Option<Arc<Self>> {
         // In tests, check thread-local state first
         #[cfg(feature = "server")]
         {
             if let Some(test_state) = TEST_STATE.with(|s| s.borrow().clone()) {
-                return test_state;
+                return Some(test_state);
             }
         }
 
-        STATE
-            .get()
-            .expect("AppState::global called before set_global")
-            .clone()
+        STATE.get().cloned()
+    }
+
+    /// Get the global AppState instance for a server function.
+    ///
+    /// Errors with "server not initialized" instead of panicking before set_global.
+    pub fn current() -> Result<Arc<Self>, ServerFnError> {
+        Self::try_global().ok_or_else(|| ServerFnError::new("server not initialized"))
     }
 }
 
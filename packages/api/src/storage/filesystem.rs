@@ -1,7 +1,7 @@
 use super::StorageService;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::path::{Component, PathBuf};
 use tokio::fs;
 
 /// Filesystem storage service implementation (local development)
@@ -17,12 +17,43 @@ impl FilesystemStorageService {
             serve_url: serve_url.into(),
         }
     }
+
+    /// Map a storage key to a path under `base_path`, rejecting keys that escape it.
+    fn path_for(&self, key: &str) -> Result<PathBuf> {
+        let relative = std::path::Path::new(key);
+        if key.is_empty()
+            || relative
+                .components()
+                .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            bail!("invalid storage key: {key}");
+        }
+        Ok(self.base_path.join(relative))
+    }
+
+    /// Staging directory for an in-progress multipart upload.
+    fn multipart_dir(&self, upload_id: &str) -> Result<PathBuf> {
+        if upload_id.is_empty() || !upload_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            bail!("invalid upload id");
+        }
+        Ok(self.base_path.join(MULTIPART_DIR).join(upload_id))
+    }
+}
+
+/// Parts are staged under `<base>/.multipart/<upload_id>/<part_number>` until completion.
+const MULTIPART_DIR: &str = ".multipart";
+
+fn part_etag(part_number: i32, data: &[u8]) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    data.hash(&mut hasher);
+    format!("{part_number}-{:016x}", hasher.finish())
 }
 
 #[async_trait]
 impl StorageService for FilesystemStorageService {
     async fn upload(&self, key: &str, data: Vec<u8>) -> Result<()> {
-        let file_path = self.base_path.join(key);
+        let file_path = self.path_for(key)?;
 
         // Create parent directories if they don't exist
         if let Some(parent) = file_path.parent() {
@@ -43,16 +74,202 @@ impl StorageService for FilesystemStorageService {
     }
 
     async fn delete(&self, key: &str) -> Result<()> {
-        let file_path = self.base_path.join(key);
+        let file_path = self.path_for(key)?;
 
         // Ignore error if file doesn't exist
-        if file_path.exists() {
-            fs::remove_file(&file_path).await?;
-            tracing::debug!("Deleted {}", file_path.display());
-        } else {
-            tracing::debug!("File not found (already deleted): {}", file_path.display());
+        match fs::remove_file(&file_path).await {
+            Ok(()) => tracing::debug!("Deleted {}", file_path.display()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                tracing::debug!("File not found (already deleted): {}", file_path.display())
+            }
+            Err(e) => return Err(e.into()),
         }
 
         Ok(())
     }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        let file_path = self.path_for(key)?;
+        Ok(fs::metadata(&file_path)
+            .await
+            .map(|m| m.is_file())
+            .unwrap_or(false))
+    }
+
+    async fn start_multipart(&self, key: &str) -> Result<String> {
+        self.path_for(key)?;
+        let upload_id = uuid::Uuid::new_v4().simple().to_string();
+        fs::create_dir_all(self.multipart_dir(&upload_id)?).await?;
+        tracing::debug!("Started multipart upload {} for {}", upload_id, key);
+        Ok(upload_id)
+    }
+
+    async fn upload_part(
+        &self,
+        _key: &str,
+        upload_id: &str,
+        part_number: i32,
+        data: Vec<u8>,
+    ) -> Result<String> {
+        if part_number < 1 {
+            bail!("part number must be at least 1");
+        }
+        let dir = self.multipart_dir(upload_id)?;
+        if !fs::try_exists(&dir).await? {
+            bail!("unknown upload id");
+        }
+        let etag = part_etag(part_number, &data);
+        fs::write(dir.join(part_number.to_string()), data).await?;
+        Ok(etag)
+    }
+
+    async fn complete_multipart(
+        &self,
+        key: &str,
+        upload_id: &str,
+        parts: &[(i32, String)],
+    ) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        if parts.is_empty() {
+            bail!("no parts to assemble");
+        }
+        let dir = self.multipart_dir(upload_id)?;
+        let file_path = self.path_for(key)?;
+
+        let mut ordered = parts.to_vec();
+        ordered.sort_by_key(|(number, _)| *number);
+        let mut chunks = Vec::with_capacity(ordered.len());
+        for (number, etag) in &ordered {
+            let data = fs::read(dir.join(number.to_string()))
+                .await
+                .map_err(|_| anyhow::anyhow!("missing part {number}"))?;
+            if &part_etag(*number, &data) != etag {
+                bail!("etag mismatch for part {number}");
+            }
+            chunks.push(data);
+        }
+
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let mut file = fs::File::create(&file_path).await?;
+        for chunk in chunks {
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+        fs::remove_dir_all(&dir).await?;
+
+        tracing::debug!(
+            "Assembled {} parts into {}",
+            ordered.len(),
+            file_path.display()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_storage() -> (FilesystemStorageService, PathBuf) {
+        let base = std::env::temp_dir().join(format!("alelysee-fs-{}", uuid::Uuid::new_v4()));
+        (
+            FilesystemStorageService::new(&base, "http://localhost/uploads"),
+            base,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_exists_reflects_uploads() {
+        let (storage, base) = temp_storage();
+        assert!(!storage.exists("videos/a.mp4").await.unwrap());
+
+        storage
+            .upload("videos/a.mp4", b"data".to_vec())
+            .await
+            .unwrap();
+        assert!(storage.exists("videos/a.mp4").await.unwrap());
+        assert!(!storage.exists("videos").await.unwrap());
+
+        let _ = std::fs::remove_dir_all(base);
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_file_and_tolerates_missing() {
+        let (storage, base) = temp_storage();
+        storage
+            .upload("videos/b.mp4", b"data".to_vec())
+            .await
+            .unwrap();
+
+        storage.delete("videos/b.mp4").await.unwrap();
+        assert!(!base.join("videos/b.mp4").exists());
+        storage.delete("videos/b.mp4").await.unwrap();
+
+        let _ = std::fs::remove_dir_all(base);
+    }
+
+    #[tokio::test]
+    async fn test_multipart_assembles_parts_in_order() {
+        let (storage, base) = temp_storage();
+        let upload_id = storage.start_multipart("videos/big.mp4").await.unwrap();
+
+        let second = storage
+            .upload_part("videos/big.mp4", &upload_id, 2, b"world".to_vec())
+            .await
+            .unwrap();
+        let first = storage
+            .upload_part("videos/big.mp4", &upload_id, 1, b"hello ".to_vec())
+            .await
+            .unwrap();
+        storage
+            .complete_multipart("videos/big.mp4", &upload_id, &[(2, second), (1, first)])
+            .await
+            .unwrap();
+
+        let assembled = std::fs::read(base.join("videos/big.mp4")).unwrap();
+        assert_eq!(assembled, b"hello world");
+        assert!(!base.join(MULTIPART_DIR).join(&upload_id).exists());
+
+        let _ = std::fs::remove_dir_all(base);
+    }
+
+    #[tokio::test]
+    async fn test_multipart_rejects_missing_or_mismatched_parts() {
+        let (storage, base) = temp_storage();
+        let upload_id = storage.start_multipart("videos/c.mp4").await.unwrap();
+        let etag = storage
+            .upload_part("videos/c.mp4", &upload_id, 1, b"abc".to_vec())
+            .await
+            .unwrap();
+
+        let missing = storage
+            .complete_multipart(
+                "videos/c.mp4",
+                &upload_id,
+                &[(1, etag.clone()), (2, "2-x".into())],
+            )
+            .await;
+        assert!(missing.is_err());
+        let mismatch = storage
+            .complete_multipart("videos/c.mp4", &upload_id, &[(1, "1-bogus".into())])
+            .await;
+        assert!(mismatch.is_err());
+        assert!(storage
+            .upload_part("videos/c.mp4", "../x", 1, vec![])
+            .await
+            .is_err());
+        assert!(!storage.exists("videos/c.mp4").await.unwrap());
+
+        let _ = std::fs::remove_dir_all(base);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_keys_outside_base() {
+        let (storage, _base) = temp_storage();
+        assert!(storage.exists("../escape").await.is_err());
+        assert!(storage.delete("/etc/passwd").await.is_err());
+    }
 }
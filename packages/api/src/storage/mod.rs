@@ -10,4 +10,24 @@ pub trait StorageService: Send + Sync {
     async fn upload(&self, key: &str, data: Vec<u8>) -> Result<()>;
     async fn get_url(&self, key: &str) -> Result<String>;
     async fn delete(&self, key: &str) -> Result<()>;
+    /// Whether an object is stored under `key` (used to confirm uploads landed).
+    async fn exists(&self, key: &str) -> Result<bool>;
+
+    /// Begin a chunked upload; returns the upload id to pass to the part calls.
+    async fn start_multipart(&self, key: &str) -> Result<String>;
+    /// Store one part (1-based `part_number`); returns its etag for completion.
+    async fn upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: i32,
+        data: Vec<u8>,
+    ) -> Result<String>;
+    /// Assemble `parts` (`(part_number, etag)`) in part order into the object at `key`.
+    async fn complete_multipart(
+        &self,
+        key: &str,
+        upload_id: &str,
+        parts: &[(i32, String)],
+    ) -> Result<()>;
 }
@@ -1,5 +1,5 @@
 use super::StorageService;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use async_trait::async_trait;
 
 /// S3-compatible storage service implementation (production)
@@ -42,4 +42,33 @@ impl StorageService for S3StorageService {
         );
         Ok(())
     }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        // Finalize still checks S3 directly with head_object; refuse rather than guess.
+        bail!("S3StorageService::exists not yet implemented (key: {key})")
+    }
+
+    // Multipart uploads against S3 are driven directly by the upload server functions.
+    async fn start_multipart(&self, key: &str) -> Result<String> {
+        bail!("S3StorageService::start_multipart not yet implemented (key: {key})")
+    }
+
+    async fn upload_part(
+        &self,
+        key: &str,
+        _upload_id: &str,
+        _part_number: i32,
+        _data: Vec<u8>,
+    ) -> Result<String> {
+        bail!("S3StorageService::upload_part not yet implemented (key: {key})")
+    }
+
+    async fn complete_multipart(
+        &self,
+        key: &str,
+        _upload_id: &str,
+        _parts: &[(i32, String)],
+    ) -> Result<()> {
+        bail!("S3StorageService::complete_multipart not yet implemented (key: {key})")
+    }
 }
@@ -0,0 +1,130 @@
+//! Incremental sync for offline-first clients.
+//!
+//! Clients keep the last proposal they received as a [`SyncCursor`] and pass it back
+//! as `after` on the next call; the first sync passes `None`.
+
+use crate::types::{ProposalSync, SyncCursor};
+use dioxus::prelude::*;
+#[cfg(feature = "server")]
+use tracing::debug;
+
+/// Visible proposals changed after `after` (oldest change first, at most `limit`),
+/// plus ids of proposals hidden or deleted since the cursor's `updated_at`.
+///
+/// Changes are ordered by `(updated_at, id)`, so proposals updated at the same
+/// instant are neither skipped nor repeated across pages. When a full page comes
+/// back, call again with the last proposal as the cursor. Without a cursor there is
+/// nothing cached to drop, so `removed_ids` is empty.
+#[dioxus::prelude::post("/api/sync/proposals")]
+pub async fn sync_proposals(
+    after: Option<SyncCursor>,
+    limit: i64,
+    id_token: Option<String>,
+) -> Result<ProposalSync, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (after, limit, id_token);
+        Err(ServerFnError::new("sync_proposals is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "sync.sync_proposals",
+        debug!("sync.sync_proposals: after={:?} limit={}", after, limit);
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let limit = state.clamp_limit(limit);
+        let pool = state.db.pool().await;
+
+        // SQLite timestamps are text with varying fractional digits, so compare them
+        // as julian days rather than as strings.
+        let (tags_expr, updated_at, after_filter, removed_filter) = if crate::db::is_sqlite() {
+            (
+                "p.tags",
+                "julianday(p.updated_at)",
+                "and (julianday(p.updated_at) > julianday($2)
+                    or (julianday(p.updated_at) = julianday($2) and p.id > $3))",
+                "julianday(removed_at) >= julianday($1)",
+            )
+        } else {
+            (
+                "to_json(p.tags)::text",
+                "p.updated_at",
+                "and (p.updated_at > $2::timestamptz
+                    or (p.updated_at = $2::timestamptz and p.id > $3))",
+                "removed_at >= $1::timestamptz",
+            )
+        };
+        let after_filter = if after.is_some() { after_filter } else { "" };
+        let sql = format!(
+            r#"
+            select
+                CAST(p.id as TEXT) as id,
+                CAST(p.author_user_id as TEXT) as author_user_id,
+                p.title,
+                coalesce(p.slug, CAST(p.id as TEXT)) as slug,
+                p.summary,
+                p.body_markdown,
+                {tags_expr} as tags,
+                CAST(p.created_at as TEXT) as created_at,
+                CAST(p.updated_at as TEXT) as updated_at,
+                coalesce(sum(v.value), 0) as vote_score,
+                ap.display_name as author_display_name
+            from proposals p
+            left join votes v
+                on v.target_type = 'proposal' and v.target_id = p.id
+            left join profiles ap on ap.user_id = p.author_user_id
+            where p.hidden_at is null {after_filter}
+            group by p.id, ap.display_name
+            order by {updated_at} asc, p.id asc
+            limit $1
+            "#
+        );
+        let mut query = sqlx::query(&sql).bind(limit);
+        if let Some(cursor) = after {
+            query = query
+                .bind(crate::db::datetime_to_db(cursor.updated_at)?)
+                .bind(crate::db::uuid_to_db(cursor.id));
+        }
+        let rows = query
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let mut proposals = Vec::with_capacity(rows.len());
+        for row in rows {
+            proposals.push(crate::proposals::proposal_from_row(&row)?);
+        }
+
+        // Removals are reported from the cursor's instant inclusive: a repeat is
+        // harmless to the client, a removal tied with the cursor is not.
+        let removed: Vec<String> = match after {
+            Some(cursor) => sqlx::query_scalar(&format!(
+                "select id from ( \
+                    select CAST(id as TEXT) as id, hidden_at as removed_at from proposals \
+                    where hidden_at is not null \
+                    union all \
+                    select CAST(id as TEXT) as id, deleted_at as removed_at from deleted_proposals \
+                ) removed where {removed_filter} order by removed_at"
+            ))
+            .bind(crate::db::datetime_to_db(cursor.updated_at)?)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?,
+            None => Vec::new(),
+        };
+        let removed_ids = removed
+            .iter()
+            .map(|id| crate::db::uuid_from_db(id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        debug!(
+            "sync.sync_proposals: changed={} removed={}",
+            proposals.len(),
+            removed_ids.len()
+        );
+        Ok(ProposalSync {
+            proposals,
+            removed_ids,
+        })
+    }
+}
@@ -0,0 +1,122 @@
+//! Per-request tracing spans for server functions.
+//!
+//! Wrap a server-fn body with `crate::telemetry::traced! { "module.fn", ... }` so every
+//! `debug!`/`info!` it emits carries a `request_id` (and `user_id` once authenticated).
+
+use tracing::field;
+use uuid::Uuid;
+
+/// Span for one server-function call; `user_id` is filled in by [`record_user`].
+pub fn request_span(name: &'static str) -> tracing::Span {
+    tracing::info_span!(
+        "request",
+        fn_name = name,
+        request_id = %Uuid::new_v4().simple(),
+        user_id = field::Empty,
+    )
+}
+
+/// Attach the authenticated user to the current request span (no-op outside one).
+pub fn record_user(user_id: Uuid) {
+    tracing::Span::current().record("user_id", field::display(user_id));
+}
+
+/// Run a server-fn body inside a fresh [`request_span`] and count it in the metrics.
+///
+/// The span is attached with `Instrument` rather than entered, so the future stays `Send`.
+macro_rules! traced {
+    ($name:literal, $($body:tt)*) => {{
+        use ::tracing::Instrument as _;
+        let result = async move { $($body)* }
+            .instrument($crate::telemetry::request_span($name))
+            .await;
+        let error = result.as_ref().err().map(ToString::to_string);
+        $crate::metrics::global().record_request($name, error.as_deref());
+        result
+    }};
+}
+pub(crate) use traced;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
+
+    /// Test layer that records every span field as a `(name, value)` pair.
+    #[derive(Default, Clone)]
+    struct Capture(Arc<Mutex<Vec<(String, String)>>>);
+
+    impl Capture {
+        fn values(&self, name: &str) -> Vec<String> {
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(n, _)| n == name)
+                .map(|(_, v)| v.clone())
+                .collect()
+        }
+    }
+
+    impl field::Visit for Capture {
+        fn record_debug(&mut self, field: &field::Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((field.name().to_string(), format!("{value:?}")));
+        }
+    }
+
+    impl<S: tracing::Subscriber> Layer<S> for Capture {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _: &Id, _: Context<'_, S>) {
+            attrs.record(&mut self.clone());
+        }
+
+        fn on_record(&self, _: &Id, values: &Record<'_>, _: Context<'_, S>) {
+            values.record(&mut self.clone());
+        }
+    }
+
+    #[test]
+    fn test_request_span_carries_ids() {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let user_id = Uuid::new_v4();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = request_span("votes.set_vote");
+            let _guard = span.enter();
+            record_user(user_id);
+        });
+
+        assert_eq!(capture.values("fn_name"), vec!["\"votes.set_vote\""]);
+        let request_ids = capture.values("request_id");
+        assert_eq!(request_ids.len(), 1);
+        assert_eq!(request_ids[0].len(), 32);
+        assert_eq!(capture.values("user_id"), vec![user_id.to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_traced_gives_each_call_its_own_request_id() {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        async fn call() -> Result<u8, String> {
+            traced! { "tests.call",
+                record_user(Uuid::nil());
+                Ok(1)
+            }
+        }
+        assert_eq!(call().await, Ok(1));
+        assert_eq!(call().await, Ok(1));
+
+        let ids = capture.values("request_id");
+        assert_eq!(ids.len(), 2);
+        assert_ne!(ids[0], ids[1]);
+        assert_eq!(capture.values("user_id").len(), 2);
+    }
+}
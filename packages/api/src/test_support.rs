@@ -3,6 +3,9 @@
 //! These tests are designed to:
 //! - be **extensive** when a Postgres `DATABASE_URL` is available
 //! - **skip gracefully** when no DB is configured (so CI/dev without DB still passes)
+//!
+//! [`sqlite_state`] needs no configuration and always runs, against a migrated
+//! temp-file SQLite database.
 
 #![cfg(all(test, feature = "server"))]
 
@@ -67,14 +70,20 @@ pub async fn reset_db() -> Option<()> {
     let _ = sqlx::query(
         r#"
         truncate table
+            admin_audit,
             activity,
+            posts,
+            reports,
             votes,
+            comment_mentions,
             comments,
+            captions,
             video_views,
             bookmarks,
             videos,
             program_items,
             programs,
+            proposal_contributors,
             proposals,
             profiles,
             users
@@ -87,3 +96,28 @@ pub async fn reset_db() -> Option<()> {
 
     Some(())
 }
+
+/// Boot a migrated SQLite `AppState` and install it for the current test thread.
+///
+/// Server functions called while the returned context is alive run against it;
+/// the database file is removed on drop.
+pub async fn sqlite_state() -> crate::test_utils::TestContext {
+    let ctx = crate::test_utils::TestContext::new().await;
+    ctx.set_global();
+    ctx
+}
+
+/// Sign up, verify and sign in `email` against the current state; returns the token.
+pub async fn signed_in_user(ctx: &crate::test_utils::TestContext, email: &str) -> String {
+    crate::signup(email.to_string(), "Password123".to_string(), None, None)
+        .await
+        .expect("signup");
+    sqlx::query("update users set email_verified = true where email = $1")
+        .bind(email)
+        .execute(&ctx.pool)
+        .await
+        .expect("verify user");
+    crate::signin(email.to_string(), "Password123".to_string(), None)
+        .await
+        .expect("signin")
+}
@@ -1,7 +1,9 @@
+use crate::captcha::CaptchaVerifier;
 use crate::config::{AppConfig, AppMode};
+use crate::content_filter::BlocklistFilter;
 use crate::db::sqlite::SqliteDatabase;
 use crate::db::Database;
-use crate::email::ConsoleEmailService;
+use crate::email::EmailService;
 use crate::state::AppState;
 use crate::storage::filesystem::FilesystemStorageService;
 use sqlx::{Any, Pool};
@@ -12,9 +14,66 @@ use uuid::Uuid;
 // Global mutex to serialize test execution since AppState::set_global can only be called once
 static TEST_MUTEX: Mutex<()> = Mutex::new(());
 
+/// An email captured by [`RecordingEmailService`].
+#[derive(Debug, Clone)]
+pub struct SentEmail {
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+}
+
+/// Email service that keeps messages in memory instead of sending them.
+#[derive(Default)]
+pub struct RecordingEmailService {
+    sent: Mutex<Vec<SentEmail>>,
+}
+
+impl RecordingEmailService {
+    /// Everything sent so far, oldest first.
+    pub fn sent(&self) -> Vec<SentEmail> {
+        self.sent.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailService for RecordingEmailService {
+    async fn send_email(
+        &self,
+        to: &str,
+        subject: &str,
+        _html: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.sent
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(SentEmail {
+                to: to.to_string(),
+                subject: subject.to_string(),
+                text: text.to_string(),
+            });
+        Ok(())
+    }
+}
+
+/// The only token [`MockCaptchaVerifier`] accepts.
+pub const TEST_CAPTCHA_TOKEN: &str = "test-captcha-pass";
+
+/// Stands in for the provider when a test sets `captcha_secret`.
+pub struct MockCaptchaVerifier;
+
+#[async_trait::async_trait]
+impl CaptchaVerifier for MockCaptchaVerifier {
+    async fn verify(&self, token: &str) -> anyhow::Result<bool> {
+        Ok(token == TEST_CAPTCHA_TOKEN)
+    }
+}
+
 pub struct TestContext {
     pub pool: Pool<Any>,
     pub state: Arc<AppState>,
+    /// Emails the state's email service was asked to send.
+    pub emails: Arc<RecordingEmailService>,
     db_path: PathBuf,
     uploads_path: PathBuf,
     _guard: MutexGuard<'static, ()>,
@@ -22,6 +81,11 @@ pub struct TestContext {
 
 impl TestContext {
     pub async fn new() -> Self {
+        Self::with_config(|_| {}).await
+    }
+
+    /// Like `new`, but lets a test tweak the `AppConfig` before the state is built.
+    pub async fn with_config(configure: impl FnOnce(&mut AppConfig)) -> Self {
         // Install sqlx drivers for Any pool
         sqlx::any::install_default_drivers();
 
@@ -54,7 +118,7 @@ impl TestContext {
         let guard = TEST_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
 
         // Create AppState
-        let config = AppConfig {
+        let mut config = AppConfig {
             mode: AppMode::Local,
             database: crate::config::DatabaseConfig::SQLite {
                 path: db_path.to_string_lossy().to_string(),
@@ -66,21 +130,48 @@ impl TestContext {
             },
             jwt_secret: "test-secret-key-min-32-characters-long".to_string(),
             app_base_url: "http://localhost:8080".to_string(),
+            maintenance_mode: false,
+            comment_max_length: crate::config::DEFAULT_COMMENT_MAX_LENGTH,
+            verify_token_ttl_hours: crate::config::DEFAULT_VERIFY_TOKEN_TTL_HOURS,
+            reset_token_ttl_minutes: crate::config::DEFAULT_RESET_TOKEN_TTL_MINUTES,
+            metrics_token: None,
+            upload_presign_ttl_secs: crate::config::DEFAULT_UPLOAD_PRESIGN_TTL_SECS,
+            upload_intent_ttl_hours: crate::config::DEFAULT_UPLOAD_INTENT_TTL_HOURS,
+            report_auto_hide_threshold: crate::config::DEFAULT_REPORT_AUTO_HIDE_THRESHOLD,
+            max_list_limit: crate::config::DEFAULT_MAX_LIST_LIMIT,
+            content_blocklist: Vec::new(),
+            content_filter_action: crate::config::ContentFilterAction::Reject,
+            max_request_body_bytes: crate::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            captcha_secret: None,
+            captcha_provider: crate::config::CaptchaProvider::Turnstile,
+            vote_policy: crate::config::VotePolicy::All,
+            public_read: true,
+            trust_proxy: false,
         };
+        configure(&mut config);
 
+        let emails = Arc::new(RecordingEmailService::default());
         let state = Arc::new(AppState {
             db: Arc::new(database),
-            email: Arc::new(ConsoleEmailService),
+            email: emails.clone(),
             storage: Arc::new(FilesystemStorageService::new(
                 uploads_path.to_string_lossy().to_string(),
                 "http://localhost:8080/dev/uploads",
             )),
+            maintenance: std::sync::atomic::AtomicBool::new(config.maintenance_mode),
+            rate_limiter: crate::rate_limit::RateLimiter::new(),
+            content_filter: Arc::new(BlocklistFilter::from_config(&config)),
+            captcha: config
+                .captcha_secret
+                .as_ref()
+                .map(|_| Arc::new(MockCaptchaVerifier) as Arc<dyn CaptchaVerifier>),
             config: config.clone(),
         });
 
         Self {
             pool,
             state,
+            emails,
             db_path,
             uploads_path,
             _guard: guard,
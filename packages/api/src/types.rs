@@ -2,6 +2,59 @@ use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+/// Serde for timestamps sent to the client: strict RFC 3339 in UTC, e.g.
+/// `2025-01-31T09:30:00.123Z`, whatever offset the value was read with.
+///
+/// Use as `#[serde(with = "rfc3339_utc")]`, or `rfc3339_utc::option` for `Option` fields.
+pub mod rfc3339_utc {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use time::{format_description::well_known::Rfc3339, OffsetDateTime, UtcOffset};
+
+    pub fn serialize<S: Serializer>(
+        value: &OffsetDateTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let text = value
+            .to_offset(UtcOffset::UTC)
+            .format(&Rfc3339)
+            .map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&text)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<OffsetDateTime, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        OffsetDateTime::parse(&text, &Rfc3339)
+            .map(|value| value.to_offset(UtcOffset::UTC))
+            .map_err(serde::de::Error::custom)
+    }
+
+    pub mod option {
+        use serde::{Deserialize, Deserializer, Serializer};
+        use time::OffsetDateTime;
+
+        pub fn serialize<S: Serializer>(
+            value: &Option<OffsetDateTime>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(value) => super::serialize(value, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<OffsetDateTime>, D::Error> {
+            #[derive(Deserialize)]
+            struct Wrapped(#[serde(with = "super")] OffsetDateTime);
+
+            Ok(Option::<Wrapped>::deserialize(deserializer)?.map(|Wrapped(value)| value))
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ContentTargetType {
@@ -20,24 +73,234 @@ impl ContentTargetType {
             ContentTargetType::Comment => "comment",
         }
     }
+
+    pub fn from_db(value: &str) -> Option<Self> {
+        match value {
+            "proposal" => Some(ContentTargetType::Proposal),
+            "program" => Some(ContentTargetType::Program),
+            "video" => Some(ContentTargetType::Video),
+            "comment" => Some(ContentTargetType::Comment),
+            _ => None,
+        }
+    }
+}
+
+/// Error message returned when an update's expected `updated_at` no longer matches.
+///
+/// Equal to [`ErrorCode::Conflict`]'s English wire text.
+pub const CONFLICT_ERROR: &str = "conflict: content changed";
+
+/// Stable code for a user-facing validation failure.
+///
+/// Errors travel as `"{code}: {message}"`: clients map the code to their own
+/// translations and fall back to the server-localized message otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    InvalidEmail,
+    PasswordTooShort,
+    PasswordNoUppercase,
+    PasswordNoLowercase,
+    PasswordNoNumber,
+    EmailTaken,
+    InvalidCredentials,
+    EmailNotVerified,
+    OauthAccount,
+    VerificationInvalid,
+    VerificationExpired,
+    ResetInvalid,
+    ResetExpired,
+    NotAllowed,
+    Conflict,
+    CommentEmpty,
+    CommentTooFast,
+    ContentRejected,
+    PayloadTooLarge,
+    NotFound,
+    CaptchaFailed,
+    Unauthorized,
+    TooManyAttempts,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 23] = [
+        ErrorCode::InvalidEmail,
+        ErrorCode::PasswordTooShort,
+        ErrorCode::PasswordNoUppercase,
+        ErrorCode::PasswordNoLowercase,
+        ErrorCode::PasswordNoNumber,
+        ErrorCode::EmailTaken,
+        ErrorCode::InvalidCredentials,
+        ErrorCode::EmailNotVerified,
+        ErrorCode::OauthAccount,
+        ErrorCode::VerificationInvalid,
+        ErrorCode::VerificationExpired,
+        ErrorCode::ResetInvalid,
+        ErrorCode::ResetExpired,
+        ErrorCode::NotAllowed,
+        ErrorCode::Conflict,
+        ErrorCode::CommentEmpty,
+        ErrorCode::CommentTooFast,
+        ErrorCode::ContentRejected,
+        ErrorCode::PayloadTooLarge,
+        ErrorCode::NotFound,
+        ErrorCode::CaptchaFailed,
+        ErrorCode::Unauthorized,
+        ErrorCode::TooManyAttempts,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::InvalidEmail => "invalid_email",
+            ErrorCode::PasswordTooShort => "password_too_short",
+            ErrorCode::PasswordNoUppercase => "password_no_uppercase",
+            ErrorCode::PasswordNoLowercase => "password_no_lowercase",
+            ErrorCode::PasswordNoNumber => "password_no_number",
+            ErrorCode::EmailTaken => "email_taken",
+            ErrorCode::InvalidCredentials => "invalid_credentials",
+            ErrorCode::EmailNotVerified => "email_not_verified",
+            ErrorCode::OauthAccount => "oauth_account",
+            ErrorCode::VerificationInvalid => "verification_invalid",
+            ErrorCode::VerificationExpired => "verification_expired",
+            ErrorCode::ResetInvalid => "reset_invalid",
+            ErrorCode::ResetExpired => "reset_expired",
+            ErrorCode::NotAllowed => "not_allowed",
+            ErrorCode::Conflict => "conflict",
+            ErrorCode::CommentEmpty => "comment_empty",
+            ErrorCode::CommentTooFast => "comment_too_fast",
+            ErrorCode::ContentRejected => "content_rejected",
+            ErrorCode::PayloadTooLarge => "payload_too_large",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::CaptchaFailed => "captcha_failed",
+            ErrorCode::Unauthorized => "unauthorized",
+            ErrorCode::TooManyAttempts => "too_many_attempts",
+        }
+    }
+
+    /// Message for `lang` (`fr`, `en-GB`, or an `Accept-Language` value); English by default.
+    pub fn message(&self, lang: Option<&str>) -> &'static str {
+        let french = lang
+            .and_then(|l| l.split([',', ';', '-', '_']).next())
+            .is_some_and(|primary| primary.trim().eq_ignore_ascii_case("fr"));
+        match (self, french) {
+            (ErrorCode::InvalidEmail, false) => "Invalid email address",
+            (ErrorCode::InvalidEmail, true) => "Adresse e-mail invalide",
+            (ErrorCode::PasswordTooShort, false) => "Password must be at least 8 characters",
+            (ErrorCode::PasswordTooShort, true) => {
+                "Le mot de passe doit contenir au moins 8 caractères"
+            }
+            (ErrorCode::PasswordNoUppercase, false) => {
+                "Password must contain at least one uppercase letter"
+            }
+            (ErrorCode::PasswordNoUppercase, true) => {
+                "Le mot de passe doit contenir au moins une majuscule"
+            }
+            (ErrorCode::PasswordNoLowercase, false) => {
+                "Password must contain at least one lowercase letter"
+            }
+            (ErrorCode::PasswordNoLowercase, true) => {
+                "Le mot de passe doit contenir au moins une minuscule"
+            }
+            (ErrorCode::PasswordNoNumber, false) => "Password must contain at least one number",
+            (ErrorCode::PasswordNoNumber, true) => {
+                "Le mot de passe doit contenir au moins un chiffre"
+            }
+            (ErrorCode::EmailTaken, false) => "Email already registered",
+            (ErrorCode::EmailTaken, true) => "Cette adresse e-mail est déjà inscrite",
+            (ErrorCode::InvalidCredentials, false) => "Invalid email or password",
+            (ErrorCode::InvalidCredentials, true) => "E-mail ou mot de passe incorrect",
+            (ErrorCode::EmailNotVerified, false) => "Please verify your email before signing in",
+            (ErrorCode::EmailNotVerified, true) => {
+                "Veuillez vérifier votre adresse e-mail avant de vous connecter"
+            }
+            (ErrorCode::OauthAccount, false) => {
+                "This account uses OAuth. Please sign in with your provider."
+            }
+            (ErrorCode::OauthAccount, true) => {
+                "Ce compte utilise OAuth. Connectez-vous avec votre fournisseur."
+            }
+            (ErrorCode::VerificationInvalid, false) => {
+                "Verification link is invalid or has expired"
+            }
+            (ErrorCode::VerificationInvalid, true) => {
+                "Le lien de vérification est invalide ou a expiré"
+            }
+            (ErrorCode::VerificationExpired, false) => "Verification link has expired",
+            (ErrorCode::VerificationExpired, true) => "Le lien de vérification a expiré",
+            (ErrorCode::ResetInvalid, false) => "Reset link is invalid or has expired",
+            (ErrorCode::ResetInvalid, true) => {
+                "Le lien de réinitialisation est invalide ou a expiré"
+            }
+            (ErrorCode::ResetExpired, false) => "Reset link has expired",
+            (ErrorCode::ResetExpired, true) => "Le lien de réinitialisation a expiré",
+            (ErrorCode::NotAllowed, false) => "not allowed",
+            (ErrorCode::NotAllowed, true) => "action non autorisée",
+            (ErrorCode::Conflict, false) => "content changed",
+            (ErrorCode::Conflict, true) => "le contenu a été modifié",
+            (ErrorCode::CommentEmpty, false) => "comment body is empty",
+            (ErrorCode::CommentEmpty, true) => "le commentaire est vide",
+            (ErrorCode::CommentTooFast, false) => "You're commenting too fast",
+            (ErrorCode::CommentTooFast, true) => "Vous commentez trop vite",
+            (ErrorCode::ContentRejected, false) => "This text contains a term that isn't allowed",
+            (ErrorCode::ContentRejected, true) => "Ce texte contient un terme non autorisé",
+            (ErrorCode::PayloadTooLarge, false) => "This request is too large",
+            (ErrorCode::PayloadTooLarge, true) => "Cette requête est trop volumineuse",
+            (ErrorCode::NotFound, false) => "not found",
+            (ErrorCode::NotFound, true) => "introuvable",
+            (ErrorCode::CaptchaFailed, false) => "The anti-robot check failed, please try again",
+            (ErrorCode::CaptchaFailed, true) => {
+                "La vérification anti-robot a échoué, veuillez réessayer"
+            }
+            (ErrorCode::Unauthorized, false) => "sign in to see this content",
+            (ErrorCode::Unauthorized, true) => "connectez-vous pour voir ce contenu",
+            (ErrorCode::TooManyAttempts, false) => "Too many attempts, please try again later",
+            (ErrorCode::TooManyAttempts, true) => {
+                "Trop de tentatives, veuillez réessayer plus tard"
+            }
+        }
+    }
+
+    /// Wire form of the error: `"{code}: {message}"`.
+    pub fn error_text(&self, lang: Option<&str>) -> String {
+        format!("{}: {}", self.as_str(), self.message(lang))
+    }
+
+    /// Recover the code from an error's display text, whatever language it was sent in.
+    pub fn from_error(text: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|code| {
+            let needle = format!("{}: ", code.as_str());
+            text.starts_with(&needle) || text.contains(&format!(" {needle}"))
+        })
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.error_text(None))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ActivityAction {
     Created,
-    VotedUp,
-    VotedDown,
+    /// The user has a vote on the target; one row per target, whichever way it went.
+    Voted,
     Commented,
+    Bookmarked,
+    Bundled,
+    Deleted,
 }
 
 impl ActivityAction {
     pub fn as_db(&self) -> &'static str {
         match self {
             ActivityAction::Created => "created",
-            ActivityAction::VotedUp => "voted_up",
-            ActivityAction::VotedDown => "voted_down",
+            ActivityAction::Voted => "voted",
             ActivityAction::Commented => "commented",
+            ActivityAction::Bookmarked => "bookmarked",
+            ActivityAction::Bundled => "bundled",
+            ActivityAction::Deleted => "deleted",
         }
     }
 }
@@ -45,6 +308,7 @@ impl ActivityAction {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct User {
     pub id: Uuid,
+    #[serde(with = "rfc3339_utc")]
     pub created_at: OffsetDateTime,
 }
 
@@ -55,20 +319,187 @@ pub struct Profile {
     pub bio: String,
     pub avatar_url: Option<String>,
     pub location: Option<String>,
+    /// Whether comments on your content and replies to you are also emailed.
+    pub notify_email: bool,
+    #[serde(with = "rfc3339_utc")]
     pub updated_at: OffsetDateTime,
 }
 
+/// What anyone can see of a user: their profile, without private settings, and
+/// when they were last active.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublicProfile {
+    pub user_id: Uuid,
+    /// `None` until the user has saved a profile.
+    pub display_name: Option<String>,
+    pub bio: String,
+    pub avatar_url: Option<String>,
+    pub location: Option<String>,
+    /// Last authenticated call, refreshed at most hourly; `None` if never recorded.
+    #[serde(with = "rfc3339_utc::option")]
+    pub last_seen_at: Option<OffsetDateTime>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Proposal {
     pub id: Uuid,
     pub author_user_id: Uuid,
     pub title: String,
+    /// URL slug; the id for rows saved before slugs existed.
+    pub slug: String,
     pub summary: String,
     pub body_markdown: String,
     pub tags: Vec<String>,
+    #[serde(with = "rfc3339_utc")]
     pub created_at: OffsetDateTime,
+    #[serde(with = "rfc3339_utc")]
+    pub updated_at: OffsetDateTime,
+    pub vote_score: i64,
+    /// Author's profile display name, when they have set one.
+    pub author_display_name: Option<String>,
+}
+
+/// Outcome of `recompute_counts`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CountsRecomputed {
+    /// Rows whose cached counts were checked.
+    pub scanned: i64,
+    /// Rows whose cached counts were wrong and rewritten.
+    pub corrected: i64,
+}
+
+/// Answer to a conditional fetch: `NotModified` when the client's ETag still matches.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Conditional<T> {
+    NotModified,
+    Modified(T),
+}
+
+/// Proposals changed since a sync cursor, for clients that cache offline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposalSync {
+    /// Created or updated after the cursor, oldest change first.
+    pub proposals: Vec<Proposal>,
+    /// Hidden (taken down) or deleted at or after the cursor; drop them from the cache.
+    pub removed_ids: Vec<Uuid>,
+}
+
+/// Keyset position in a proposal sync: the last changed proposal already received.
+///
+/// The `id` breaks ties between proposals updated at the same instant.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SyncCursor {
+    #[serde(with = "rfc3339_utc")]
     pub updated_at: OffsetDateTime,
+    pub id: Uuid,
+}
+
+impl From<&Proposal> for SyncCursor {
+    fn from(proposal: &Proposal) -> Self {
+        SyncCursor {
+            updated_at: proposal.updated_at,
+            id: proposal.id,
+        }
+    }
+}
+
+/// Order of `discover_proposals` results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortMode {
+    #[default]
+    Newest,
+    Oldest,
+    /// Highest net vote score first, newest first among ties.
+    TopVoted,
+}
+
+/// Keyset position in a `discover_proposals` listing: the last proposal already shown.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ProposalCursor {
+    #[serde(with = "rfc3339_utc")]
+    pub created_at: OffsetDateTime,
     pub vote_score: i64,
+    pub id: Uuid,
+}
+
+impl From<&Proposal> for ProposalCursor {
+    fn from(proposal: &Proposal) -> Self {
+        ProposalCursor {
+            created_at: proposal.created_at,
+            vote_score: proposal.vote_score,
+            id: proposal.id,
+        }
+    }
+}
+
+/// Search, filter and sort for `discover_proposals`; every filter is optional.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiscoverOptions {
+    /// Case-insensitive text to find in the title or summary.
+    pub query: Option<String>,
+    /// Only proposals carrying this tag, matched case-insensitively.
+    pub tag: Option<String>,
+    pub sort: SortMode,
+    /// The last proposal of the previous page, to fetch the next one.
+    pub before: Option<ProposalCursor>,
+    pub limit: i64,
+}
+
+/// One page of `discover_proposals`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposalPage {
+    pub proposals: Vec<Proposal>,
+    /// Whether another page follows; pass the last proposal as `before` to get it.
+    pub has_more: bool,
+}
+
+/// Admin view of a proposal, including hidden ones.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModeratedProposal {
+    pub proposal: Proposal,
+    #[serde(with = "rfc3339_utc::option")]
+    pub hidden_at: Option<OffsetDateTime>,
+}
+
+/// What an admin did, as recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminAction {
+    Hide,
+    Unhide,
+    RecomputeCounts,
+}
+
+impl AdminAction {
+    pub fn as_db(&self) -> &'static str {
+        match self {
+            AdminAction::Hide => "hide",
+            AdminAction::Unhide => "unhide",
+            AdminAction::RecomputeCounts => "recompute_counts",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Option<Self> {
+        match value {
+            "hide" => Some(AdminAction::Hide),
+            "unhide" => Some(AdminAction::Unhide),
+            "recompute_counts" => Some(AdminAction::RecomputeCounts),
+            _ => None,
+        }
+    }
+}
+
+/// One admin audit log row; `actor_user_id` is `None` once that admin is deleted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub actor_user_id: Option<Uuid>,
+    pub action: AdminAction,
+    pub target_type: Option<ContentTargetType>,
+    pub target_id: Option<Uuid>,
+    #[serde(with = "rfc3339_utc")]
+    pub created_at: OffsetDateTime,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -76,11 +507,18 @@ pub struct Program {
     pub id: Uuid,
     pub author_user_id: Uuid,
     pub title: String,
+    /// URL slug; the id for rows saved before slugs existed.
+    pub slug: String,
     pub summary: String,
     pub body_markdown: String,
+    #[serde(with = "rfc3339_utc")]
     pub created_at: OffsetDateTime,
+    #[serde(with = "rfc3339_utc")]
     pub updated_at: OffsetDateTime,
     pub vote_score: i64,
+    /// Author's profile display name, when they have set one. Filled by the list and
+    /// detail reads; `None` in write responses.
+    pub author_display_name: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -100,8 +538,21 @@ pub struct Video {
     pub storage_key: String,
     pub content_type: String,
     pub duration_seconds: Option<i32>,
+    /// Uploaded file size in bytes; `None` for videos finalized before it was recorded.
+    pub byte_size: Option<i64>,
+    #[serde(with = "rfc3339_utc")]
     pub created_at: OffsetDateTime,
     pub vote_score: i64,
+    /// WebVTT caption tracks, ordered by language.
+    pub captions: Vec<CaptionTrack>,
+}
+
+/// A WebVTT caption file attached to a video with `finalize_caption`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptionTrack {
+    /// BCP 47 language tag, e.g. `fr` or `en-GB`.
+    pub language: String,
+    pub storage_key: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -110,6 +561,54 @@ pub struct VoteState {
     pub target_id: Uuid,
     pub score: i64,
     pub my_vote: Option<i16>,
+    /// How much this call changed `score` (new vote minus the caller's previous
+    /// one); `0` from reads. Other users' votes account for any remaining gap.
+    pub delta: i64,
+    /// Position of a proposal/program by score after a vote change; `None` for
+    /// other targets and from reads.
+    pub rank: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoteBreakdown {
+    pub target_type: ContentTargetType,
+    pub target_id: Uuid,
+    pub upvotes: i64,
+    pub downvotes: i64,
+    pub score: i64,
+    pub my_vote: Option<i16>,
+}
+
+/// One entry of the caller's vote history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VotedItem {
+    pub target_type: ContentTargetType,
+    pub target_id: Uuid,
+    pub value: i16,
+    #[serde(with = "rfc3339_utc")]
+    pub voted_at: OffsetDateTime,
+    // Best-effort display info, same as the activity feed
+    pub title: Option<String>,
+}
+
+/// One of the caller's bookmarks, most recent first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BookmarkedItem {
+    pub target_type: ContentTargetType,
+    pub target_id: Uuid,
+    #[serde(with = "rfc3339_utc")]
+    pub bookmarked_at: OffsetDateTime,
+    // Best-effort display info, same as the activity feed; None once hidden
+    pub title: Option<String>,
+}
+
+/// 1-based position of a target when its kind is ordered by vote score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoteRank {
+    pub target_type: ContentTargetType,
+    pub target_id: Uuid,
+    pub rank: i64,
+    pub total: i64,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -117,6 +616,7 @@ pub struct Bookmark {
     pub id: Uuid,
     pub user_id: Uuid,
     pub video_id: Uuid,
+    #[serde(with = "rfc3339_utc")]
     pub created_at: OffsetDateTime,
 }
 
@@ -128,8 +628,31 @@ pub struct Comment {
     pub target_id: Uuid,
     pub parent_comment_id: Option<Uuid>,
     pub body_markdown: String,
+    #[serde(with = "rfc3339_utc")]
     pub created_at: OffsetDateTime,
     pub vote_score: i64,
+    /// Author's profile display name, when they have set one; `None` once deleted.
+    pub author_display_name: Option<String>,
+    /// Users `@`-mentioned in the body, resolved when the comment was posted.
+    pub mentioned_user_ids: Vec<Uuid>,
+    /// Nesting level in a `list_comments` thread (0 = top level), capped server-side;
+    /// always 0 from other endpoints.
+    pub depth: u32,
+    /// Pinned by the target's author; at most one top-level comment per target.
+    pub pinned: bool,
+    /// When the author last edited the body; `None` if it was never edited.
+    #[serde(with = "rfc3339_utc::option")]
+    pub updated_at: Option<OffsetDateTime>,
+    /// Deleted by its author: the body is blank, but the comment stays in its thread
+    /// so replies keep their parent.
+    pub deleted: bool,
+}
+
+/// A comment listed on its author's profile, with what it was posted on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuthoredComment {
+    pub comment: Comment,
+    pub target_title: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -139,9 +662,48 @@ pub struct ActivityItem {
     pub action: ActivityAction,
     pub target_type: ContentTargetType,
     pub target_id: Uuid,
+    #[serde(with = "rfc3339_utc")]
     pub created_at: OffsetDateTime,
     // Best-effort display info for the feed
     pub title: Option<String>,
+    pub actor_display_name: Option<String>,
+}
+
+/// Everything stored about a user, as returned by `export_account`.
+///
+/// Includes the user's hidden content; lists are in id order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountExport {
+    pub user: User,
+    /// `None` until the user has saved a profile.
+    pub profile: Option<Profile>,
+    pub proposals: Vec<Proposal>,
+    pub programs: Vec<Program>,
+    pub comments: Vec<Comment>,
+    pub votes: Vec<VotedItem>,
+    pub bookmarks: Vec<BookmarkedItem>,
+    pub activity: Vec<ActivityItem>,
+    #[serde(with = "rfc3339_utc")]
+    pub exported_at: OffsetDateTime,
+}
+
+/// Keyset position in a newest-first activity list: the last item already shown.
+///
+/// Unlike an offset, it stays put when newer activity is recorded between pages.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ActivityCursor {
+    #[serde(with = "rfc3339_utc")]
+    pub created_at: OffsetDateTime,
+    pub id: Uuid,
+}
+
+impl From<&ActivityItem> for ActivityCursor {
+    fn from(item: &ActivityItem) -> Self {
+        ActivityCursor {
+            created_at: item.created_at,
+            id: item.id,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -149,4 +711,89 @@ pub struct UploadIntent {
     pub presigned_put_url: String,
     pub storage_key: String,
     pub bucket: String,
+    /// Signed headers (lowercase names) the client must send verbatim with its PUT.
+    pub headers: std::collections::BTreeMap<String, String>,
+}
+
+/// Handle for a chunked upload started with `start_multipart_upload`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultipartUpload {
+    pub upload_id: String,
+    pub storage_key: String,
+}
+
+/// A stored part, echoed back to `complete_multipart_upload`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UploadedPart {
+    pub part_number: i32,
+    pub etag: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationStatus {
+    pub applied: Vec<i64>,
+    pub expected: Vec<i64>,
+    pub up_to_date: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// Someone commented on content you authored.
+    Comment,
+    /// Someone replied to one of your comments.
+    Reply,
+    /// (Admins) content was auto-hidden after reaching the report threshold.
+    AutoHidden,
+    /// Someone mentioned you (`@display_name`) in a comment.
+    Mention,
+}
+
+impl NotificationKind {
+    pub fn as_db(&self) -> &'static str {
+        match self {
+            NotificationKind::Comment => "comment",
+            NotificationKind::Reply => "reply",
+            NotificationKind::AutoHidden => "auto_hidden",
+            NotificationKind::Mention => "mention",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Option<Self> {
+        match value {
+            "comment" => Some(NotificationKind::Comment),
+            "reply" => Some(NotificationKind::Reply),
+            "auto_hidden" => Some(NotificationKind::AutoHidden),
+            "mention" => Some(NotificationKind::Mention),
+            _ => None,
+        }
+    }
+}
+
+/// Admin-authored blog post; the integer id backs the `/blog/:id` route.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Post {
+    pub id: i64,
+    pub author_user_id: Uuid,
+    pub title: String,
+    pub body_markdown: String,
+    #[serde(with = "rfc3339_utc")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "rfc3339_utc")]
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: NotificationKind,
+    pub actor_user_id: Option<Uuid>,
+    pub target_type: ContentTargetType,
+    pub target_id: Uuid,
+    pub comment_id: Option<Uuid>,
+    #[serde(with = "rfc3339_utc")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "rfc3339_utc::option")]
+    pub read_at: Option<OffsetDateTime>,
 }
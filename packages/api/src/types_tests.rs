@@ -1,4 +1,9 @@
-use crate::types::{ActivityAction, ContentTargetType};
+use crate::types::{
+    ActivityAction, AdminAction, ContentTargetType, ErrorCode, ModeratedProposal, NotificationKind,
+    Proposal, Video, CONFLICT_ERROR,
+};
+use time::macros::datetime;
+use uuid::Uuid;
 
 #[test]
 fn content_target_type_as_db() {
@@ -11,7 +16,151 @@ fn content_target_type_as_db() {
 #[test]
 fn activity_action_as_db() {
     assert_eq!(ActivityAction::Created.as_db(), "created");
-    assert_eq!(ActivityAction::VotedUp.as_db(), "voted_up");
-    assert_eq!(ActivityAction::VotedDown.as_db(), "voted_down");
+    assert_eq!(ActivityAction::Voted.as_db(), "voted");
     assert_eq!(ActivityAction::Commented.as_db(), "commented");
+    assert_eq!(ActivityAction::Bookmarked.as_db(), "bookmarked");
+    assert_eq!(ActivityAction::Bundled.as_db(), "bundled");
+    assert_eq!(ActivityAction::Deleted.as_db(), "deleted");
+}
+
+#[test]
+fn content_target_type_from_db_roundtrip() {
+    for t in [
+        ContentTargetType::Proposal,
+        ContentTargetType::Program,
+        ContentTargetType::Video,
+        ContentTargetType::Comment,
+    ] {
+        assert_eq!(ContentTargetType::from_db(t.as_db()), Some(t));
+    }
+    assert_eq!(ContentTargetType::from_db("unknown"), None);
+}
+
+#[test]
+fn notification_kind_from_db_roundtrip() {
+    for k in [
+        NotificationKind::Comment,
+        NotificationKind::Reply,
+        NotificationKind::AutoHidden,
+        NotificationKind::Mention,
+    ] {
+        assert_eq!(NotificationKind::from_db(k.as_db()), Some(k));
+    }
+    assert_eq!(NotificationKind::from_db("unknown"), None);
+}
+
+#[test]
+fn admin_action_from_db_roundtrip() {
+    for a in [
+        AdminAction::Hide,
+        AdminAction::Unhide,
+        AdminAction::RecomputeCounts,
+    ] {
+        assert_eq!(AdminAction::from_db(a.as_db()), Some(a));
+    }
+    assert_eq!(AdminAction::from_db("unknown"), None);
+}
+
+#[test]
+fn error_code_survives_every_language() {
+    for code in ErrorCode::ALL {
+        for lang in [None, Some("en"), Some("fr"), Some("fr-CA"), Some("de")] {
+            let text = code.error_text(lang);
+            assert!(text.starts_with(code.as_str()));
+            assert_eq!(ErrorCode::from_error(&text), Some(code));
+            let wrapped = format!("error running server function: {text}");
+            assert_eq!(ErrorCode::from_error(&wrapped), Some(code));
+        }
+        assert_ne!(code.message(None), code.message(Some("fr")));
+        assert_eq!(code.message(Some("de")), code.message(None));
+    }
+    assert_eq!(ErrorCode::from_error("database is locked"), None);
+}
+
+#[test]
+fn error_code_serializes_as_its_code() {
+    for code in ErrorCode::ALL {
+        assert_eq!(
+            serde_json::to_value(code).unwrap(),
+            serde_json::json!(code.as_str())
+        );
+    }
+    assert_eq!(ErrorCode::Conflict.to_string(), CONFLICT_ERROR);
+}
+
+fn sample_proposal() -> Proposal {
+    Proposal {
+        id: Uuid::nil(),
+        author_user_id: Uuid::nil(),
+        title: "T".into(),
+        slug: "t".into(),
+        summary: String::new(),
+        body_markdown: String::new(),
+        tags: vec![],
+        created_at: datetime!(2025-01-31 09:30:00.123 UTC),
+        updated_at: datetime!(2025-01-31 11:30:00 +02:00),
+        vote_score: 0,
+        author_display_name: None,
+    }
+}
+
+#[test]
+fn timestamps_serialize_as_rfc3339_utc() {
+    let value = serde_json::to_value(sample_proposal()).unwrap();
+    assert_eq!(value["created_at"], "2025-01-31T09:30:00.123Z");
+    assert_eq!(value["updated_at"], "2025-01-31T09:30:00Z");
+}
+
+#[test]
+fn timestamps_round_trip_through_json() {
+    let proposal = sample_proposal();
+    let back: Proposal = serde_json::from_str(&serde_json::to_string(&proposal).unwrap()).unwrap();
+    assert_eq!(back, proposal);
+    assert!(back.updated_at.offset().is_utc());
+
+    let video = Video {
+        id: Uuid::nil(),
+        owner_user_id: Uuid::nil(),
+        target_type: ContentTargetType::Proposal,
+        target_id: Uuid::nil(),
+        storage_bucket: "local".into(),
+        storage_key: "videos/clip.mp4".into(),
+        content_type: "video/mp4".into(),
+        duration_seconds: None,
+        byte_size: None,
+        created_at: datetime!(1999-12-31 23:59:59.999999 -05:00),
+        vote_score: 0,
+        captions: Vec::new(),
+    };
+    let back: Video = serde_json::from_str(&serde_json::to_string(&video).unwrap()).unwrap();
+    assert_eq!(back, video);
+}
+
+#[test]
+fn optional_timestamps_round_trip_through_json() {
+    for hidden_at in [None, Some(datetime!(2025-06-01 12:00:00 UTC))] {
+        let moderated = ModeratedProposal {
+            proposal: sample_proposal(),
+            hidden_at,
+        };
+        let json = serde_json::to_value(&moderated).unwrap();
+        match hidden_at {
+            Some(_) => assert_eq!(json["hidden_at"], "2025-06-01T12:00:00Z"),
+            None => assert!(json["hidden_at"].is_null()),
+        }
+        let back: ModeratedProposal = serde_json::from_value(json).unwrap();
+        assert_eq!(back, moderated);
+    }
+}
+
+#[test]
+fn timestamps_accept_any_offset_and_reject_other_formats() {
+    let mut value = serde_json::to_value(sample_proposal()).unwrap();
+    value["created_at"] = "2025-01-31T10:30:00.123+01:00".into();
+    let parsed: Proposal = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(parsed.created_at, datetime!(2025-01-31 09:30:00.123 UTC));
+    assert!(parsed.created_at.offset().is_utc());
+
+    value["created_at"] = "2025-01-31 09:30:00".into();
+    assert!(serde_json::from_value::<Proposal>(value).is_err());
 }
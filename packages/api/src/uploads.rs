@@ -3,6 +3,257 @@ use dioxus::prelude::*;
 #[cfg(feature = "server")]
 use tracing::{debug, info};
 
+/// One video with who posted it, what it's attached to and how it's doing, for
+/// the `/videos/:id` page.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct VideoDetail {
+    pub video: Video,
+    /// Owner's profile display name, when they have set one.
+    pub owner_display_name: Option<String>,
+    pub owner_avatar_url: Option<String>,
+    /// Title of the proposal or program the video is on; `None` when that's hidden.
+    pub target_title: Option<String>,
+    /// URL slug of the target, alongside `target_title`.
+    pub target_slug: Option<String>,
+    /// Signed-in users who watched it.
+    pub view_count: i64,
+    /// Visible comments on the video.
+    pub comment_count: i64,
+}
+
+/// Video MIME types accepted by the upload flow (also advertised via `PublicConfig`).
+pub const ALLOWED_VIDEO_TYPES: &[&str] = &["video/mp4", "video/webm", "video/quicktime"];
+
+/// Whether the presigned upload flow works: the active storage config is S3.
+pub(crate) fn uploads_configured() -> bool {
+    #[cfg(feature = "server")]
+    {
+        S3Settings::current().is_ok()
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        false
+    }
+}
+
+/// Connection settings for the S3-compatible bucket.
+#[cfg(feature = "server")]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct S3Settings {
+    bucket: String,
+    endpoint: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+    path_style: bool,
+}
+
+#[cfg(feature = "server")]
+impl S3Settings {
+    /// Settings from an `S3` storage config; `None` for filesystem storage.
+    fn from_storage(storage: &crate::config::StorageConfig) -> Option<Self> {
+        match storage {
+            crate::config::StorageConfig::S3 {
+                bucket,
+                endpoint,
+                region,
+                access_key,
+                secret_key,
+                path_style,
+                ..
+            } => Some(Self {
+                bucket: bucket.clone(),
+                endpoint: endpoint.clone(),
+                region: region.clone(),
+                access_key: access_key.clone(),
+                secret_key: secret_key.clone(),
+                path_style: *path_style,
+            }),
+            crate::config::StorageConfig::Filesystem { .. } => None,
+        }
+    }
+
+    /// The active `S3` storage config. Filesystem deployments have no bucket, even
+    /// with stray `STORAGE_*` env vars, so presigning and finalizing use one backend.
+    fn current() -> Result<Self, ServerFnError> {
+        crate::state::AppState::try_global()
+            .and_then(|state| Self::from_storage(&state.config.storage))
+            .ok_or_else(|| {
+                ServerFnError::new("storage is not S3; presigned uploads are unavailable")
+            })
+    }
+
+    fn credentials(&self) -> aws_credential_types::Credentials {
+        aws_credential_types::Credentials::new(
+            self.access_key.clone(),
+            self.secret_key.clone(),
+            None,
+            None,
+            "railway",
+        )
+    }
+
+    /// S3 client config layered over `sdk_config`, with this region, endpoint and
+    /// addressing style.
+    fn client_config(&self, sdk_config: &aws_config::SdkConfig) -> aws_sdk_s3::Config {
+        use aws_sdk_s3::{config::Builder as S3ConfigBuilder, config::Region};
+
+        S3ConfigBuilder::from(sdk_config)
+            .region(Region::new(self.region.clone()))
+            .credentials_provider(self.credentials())
+            .endpoint_url(self.endpoint.clone())
+            .force_path_style(self.path_style)
+            .build()
+    }
+}
+
+/// S3 client and bucket for the configured storage (see [`S3Settings::current`]).
+#[cfg(feature = "server")]
+pub(crate) async fn s3_client() -> Result<(aws_sdk_s3::Client, String), ServerFnError> {
+    let settings = S3Settings::current()?;
+    let sdk_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .region(aws_sdk_s3::config::Region::new(settings.region.clone()))
+        .credentials_provider(settings.credentials())
+        .load()
+        .await;
+    let s3_config = settings.client_config(&sdk_config);
+    Ok((aws_sdk_s3::Client::from_conf(s3_config), settings.bucket))
+}
+
+/// Presign settings using the configured `upload_presign_ttl_secs`.
+#[cfg(feature = "server")]
+pub(crate) fn presigning_config() -> Result<aws_sdk_s3::presigning::PresigningConfig, ServerFnError>
+{
+    let ttl = crate::state::AppState::current()?
+        .config
+        .upload_presign_ttl_secs;
+    aws_sdk_s3::presigning::PresigningConfig::expires_in(std::time::Duration::from_secs(ttl))
+        .map_err(|_| ServerFnError::new("presign config error"))
+}
+
+#[cfg(feature = "server")]
+pub(crate) fn storage_key_prefix(target_type: ContentTargetType, target_id: uuid::Uuid) -> String {
+    format!("videos/{}/{}/", target_type.as_db(), target_id)
+}
+
+#[cfg(feature = "server")]
+fn new_storage_key(target_type: ContentTargetType, target_id: uuid::Uuid) -> String {
+    format!(
+        "{}{}",
+        storage_key_prefix(target_type, target_id),
+        uuid::Uuid::new_v4()
+    )
+}
+
+/// Remember that `storage_key` was issued to `user_id`, so only they can upload to
+/// or finalize it. The user's expired, never-finalized intents are dropped here.
+#[cfg(feature = "server")]
+pub(crate) async fn record_upload_intent(
+    pool: &sqlx::Pool<sqlx::Any>,
+    user_id: uuid::Uuid,
+    storage_key: &str,
+) -> Result<(), ServerFnError> {
+    let (_, cutoff) = fresh_intent_filter()?;
+    let expired = if crate::db::is_sqlite() {
+        "delete from upload_intents where user_id = $1 and julianday(created_at) <= julianday($2)"
+    } else {
+        "delete from upload_intents where user_id = $1 and created_at <= $2::timestamptz"
+    };
+    sqlx::query(expired)
+        .bind(crate::db::uuid_to_db(user_id))
+        .bind(cutoff)
+        .execute(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    sqlx::query("insert into upload_intents (user_id, storage_key) values ($1, $2)")
+        .bind(crate::db::uuid_to_db(user_id))
+        .bind(storage_key)
+        .execute(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    Ok(())
+}
+
+/// `where` clause matching the intent for `storage_key` (`$2`) issued to `user_id` (`$1`)
+/// since `$3`, the oldest instant still inside `upload_intent_ttl_hours`.
+#[cfg(feature = "server")]
+fn fresh_intent_filter() -> Result<(&'static str, String), ServerFnError> {
+    let ttl = crate::state::AppState::current()?
+        .config
+        .upload_intent_ttl_hours;
+    let cutoff = crate::db::datetime_to_db(
+        time::OffsetDateTime::now_utc() - time::Duration::hours(ttl.into()),
+    )?;
+    let filter = if crate::db::is_sqlite() {
+        "user_id = $1 and storage_key = $2 and julianday(created_at) > julianday($3)"
+    } else {
+        "user_id = $1 and storage_key = $2 and created_at > $3::timestamptz"
+    };
+    Ok((filter, cutoff))
+}
+
+/// Reject keys that were not issued to `user_id` by an upload intent, or whose
+/// intent has expired.
+#[cfg(feature = "server")]
+pub(crate) async fn ensure_upload_intent(
+    pool: &sqlx::Pool<sqlx::Any>,
+    user_id: uuid::Uuid,
+    storage_key: &str,
+) -> Result<(), ServerFnError> {
+    let (filter, cutoff) = fresh_intent_filter()?;
+    let issued: i64 = sqlx::query_scalar(&format!(
+        "select count(*) from upload_intents where {filter}"
+    ))
+    .bind(crate::db::uuid_to_db(user_id))
+    .bind(storage_key)
+    .bind(cutoff)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+    if issued == 0 {
+        return Err(ServerFnError::new("invalid storage key"));
+    }
+    Ok(())
+}
+
+/// Delete the unexpired intent for `storage_key`, failing if there was none.
+///
+/// Finalizers run this in the same transaction as their insert, so a key can only
+/// be finalized once even when two requests race.
+#[cfg(feature = "server")]
+pub(crate) async fn consume_upload_intent<'e>(
+    executor: impl sqlx::Executor<'e, Database = sqlx::Any>,
+    user_id: uuid::Uuid,
+    storage_key: &str,
+) -> Result<(), ServerFnError> {
+    let (filter, cutoff) = fresh_intent_filter()?;
+    let consumed = sqlx::query(&format!("delete from upload_intents where {filter}"))
+        .bind(crate::db::uuid_to_db(user_id))
+        .bind(storage_key)
+        .bind(cutoff)
+        .execute(executor)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    if consumed.rows_affected() == 0 {
+        return Err(ServerFnError::new("invalid storage key"));
+    }
+    Ok(())
+}
+
+/// Whether `storage_key` is served by the local filesystem backend (vs S3).
+#[cfg(feature = "server")]
+pub(crate) fn filesystem_storage() -> bool {
+    crate::state::AppState::try_global().is_some_and(|state| {
+        matches!(
+            state.config.storage,
+            crate::config::StorageConfig::Filesystem { .. }
+        )
+    })
+}
+
+/// Largest accepted video, in bytes (200MB MVP limit).
+const MAX_VIDEO_BYTES: i64 = 200 * 1024 * 1024;
+
 #[dioxus::prelude::post("/api/uploads/video_intent")]
 pub async fn create_video_upload_intent(
     id_token: String,
@@ -20,75 +271,64 @@ pub async fn create_video_upload_intent(
     }
 
     #[cfg(feature = "server")]
-    {
-        use aws_credential_types::Credentials;
-        use aws_sdk_s3::presigning::PresigningConfig;
+    crate::telemetry::traced! { "uploads.create_video_upload_intent",
         use aws_sdk_s3::types::ObjectCannedAcl;
-        use aws_sdk_s3::{config::Builder as S3ConfigBuilder, config::Region};
-        use std::time::Duration;
         use uuid::Uuid;
 
-        const MAX_BYTES: i64 = 200 * 1024 * 1024; // 200MB MVP limit
-        if byte_size <= 0 || byte_size > MAX_BYTES {
+        crate::state::AppState::current()?.ensure_writable()?;
+
+        if byte_size <= 0 || byte_size > MAX_VIDEO_BYTES {
             return Err(ServerFnError::new("invalid file size"));
         }
+        if !ALLOWED_VIDEO_TYPES.contains(&content_type.as_str()) {
+            return Err(ServerFnError::new("unsupported content type"));
+        }
 
         info!(
             "uploads.create_video_upload_intent: target_type={:?} target_id={} size={}",
             target_type, target_id, byte_size
         );
-        // Ensure authenticated user exists (and we record ownership at finalize time).
-        let _user_id = crate::auth::require_user_id(id_token).await?;
-
-        let bucket = std::env::var("STORAGE_BUCKET")
-            .map_err(|_| ServerFnError::new("STORAGE_BUCKET not set"))?;
-        let endpoint = std::env::var("STORAGE_ENDPOINT")
-            .map_err(|_| ServerFnError::new("STORAGE_ENDPOINT not set"))?;
-        let access_key = std::env::var("STORAGE_ACCESS_KEY")
-            .map_err(|_| ServerFnError::new("STORAGE_ACCESS_KEY not set"))?;
-        let secret_key = std::env::var("STORAGE_SECRET_KEY")
-            .map_err(|_| ServerFnError::new("STORAGE_SECRET_KEY not set"))?;
-        let region = std::env::var("STORAGE_REGION").unwrap_or_else(|_| "auto".to_string());
-
-        let key = format!(
-            "videos/{}/{}/{}",
-            target_type.as_db(),
-            target_id,
-            Uuid::new_v4()
-        );
-        debug!("uploads.create_video_upload_intent: storage_key={}", key);
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let tid =
+            Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
 
-        let creds = Credentials::new(access_key, secret_key, None, None, "railway");
-        let sdk_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
-            .region(Region::new(region))
-            .credentials_provider(creds)
-            .load()
-            .await;
+        let (client, bucket) = s3_client().await?;
 
-        let s3_config = S3ConfigBuilder::from(&sdk_config)
-            .endpoint_url(endpoint)
-            .force_path_style(true)
-            .build();
-        let client = aws_sdk_s3::Client::from_conf(s3_config);
+        let key = new_storage_key(target_type, tid);
+        debug!("uploads.create_video_upload_intent: storage_key={}", key);
 
         let presigned = client
             .put_object()
             .bucket(&bucket)
             .key(&key)
-            .content_type(content_type)
+            .content_type(content_type.clone())
             .acl(ObjectCannedAcl::Private)
-            .presigned(
-                PresigningConfig::expires_in(Duration::from_secs(60 * 10))
-                    .map_err(|_| ServerFnError::new("presign config error"))?,
-            )
+            .presigned(presigning_config()?)
             .await
             .map_err(|e| ServerFnError::new(format!("presign error: {e}")))?;
 
-        info!("uploads.create_video_upload_intent: presigned ok");
+        // Providers reject the PUT unless signed headers (content-type, x-amz-acl, ...)
+        // match exactly, so hand them to the client rather than letting it guess.
+        let mut headers: std::collections::BTreeMap<String, String> = presigned
+            .headers()
+            .map(|(name, value)| (name.to_ascii_lowercase(), value.to_string()))
+            .collect();
+        headers
+            .entry("content-type".to_string())
+            .or_insert(content_type);
+
+        let state = crate::state::AppState::current()?;
+        record_upload_intent(state.db.pool().await, user_id, &key).await?;
+
+        info!(
+            "uploads.create_video_upload_intent: presigned ok headers={}",
+            headers.len()
+        );
         Ok(UploadIntent {
             presigned_put_url: presigned.uri().to_string(),
             storage_key: key,
             bucket,
+            headers,
         })
     }
 }
@@ -100,66 +340,92 @@ pub async fn finalize_video_upload(
     target_id: String,
     storage_key: String,
     content_type: String,
+    byte_size: i64,
 ) -> Result<Video, ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = (id_token, target_type, target_id, storage_key, content_type);
+        let _ = (
+            id_token,
+            target_type,
+            target_id,
+            storage_key,
+            content_type,
+            byte_size,
+        );
         Err(ServerFnError::new("finalize_video_upload is server-only"))
     }
 
     #[cfg(feature = "server")]
-    {
-        use aws_credential_types::Credentials;
-        use aws_sdk_s3::{config::Builder as S3ConfigBuilder, config::Region};
+    crate::telemetry::traced! { "uploads.finalize_video_upload",
         use sqlx::Row;
         use uuid::Uuid;
 
+        crate::state::AppState::current()?.ensure_writable()?;
+
         let owner_user_id = crate::auth::require_user_id(id_token).await?;
         let tid =
             Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
+        if byte_size <= 0 || byte_size > MAX_VIDEO_BYTES {
+            return Err(ServerFnError::new("invalid file size"));
+        }
 
         info!(
-            "uploads.finalize_video_upload: target_type={:?} target_id={} storage_key={}",
-            target_type, target_id, storage_key
+            "uploads.finalize_video_upload: target_type={:?} target_id={} storage_key={} size={}",
+            target_type, target_id, storage_key, byte_size
         );
-        let bucket = std::env::var("STORAGE_BUCKET")
-            .map_err(|_| ServerFnError::new("STORAGE_BUCKET not set"))?;
-        let endpoint = std::env::var("STORAGE_ENDPOINT")
-            .map_err(|_| ServerFnError::new("STORAGE_ENDPOINT not set"))?;
-        let access_key = std::env::var("STORAGE_ACCESS_KEY")
-            .map_err(|_| ServerFnError::new("STORAGE_ACCESS_KEY not set"))?;
-        let secret_key = std::env::var("STORAGE_SECRET_KEY")
-            .map_err(|_| ServerFnError::new("STORAGE_SECRET_KEY not set"))?;
-        let region = std::env::var("STORAGE_REGION").unwrap_or_else(|_| "auto".to_string());
-
-        let creds = Credentials::new(access_key, secret_key, None, None, "railway");
-        let sdk_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
-            .region(Region::new(region))
-            .credentials_provider(creds)
-            .load()
-            .await;
-
-        let s3_config = S3ConfigBuilder::from(&sdk_config)
-            .endpoint_url(endpoint)
-            .force_path_style(true)
-            .build();
-        let client = aws_sdk_s3::Client::from_conf(s3_config);
-
-        client
-            .head_object()
-            .bucket(&bucket)
-            .key(&storage_key)
-            .send()
-            .await
-            .map_err(|e| ServerFnError::new(format!("head_object failed: {e}")))?;
-
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
         let pool = state.db.pool().await;
+        if !storage_key.starts_with(&storage_key_prefix(target_type, tid)) {
+            return Err(ServerFnError::new("invalid storage key"));
+        }
+        ensure_upload_intent(pool, owner_user_id, &storage_key).await?;
+
+        let bucket = if filesystem_storage() {
+            let found = state
+                .storage
+                .exists(&storage_key)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?;
+            if !found {
+                return Err(ServerFnError::new("upload not found"));
+            }
+            "local".to_string()
+        } else {
+            let (client, bucket) = s3_client().await?;
+            let head = client
+                .head_object()
+                .bucket(&bucket)
+                .key(&storage_key)
+                .send()
+                .await
+                .map_err(|e| {
+                    if e.as_service_error().is_some_and(|se| se.is_not_found()) {
+                        ServerFnError::new("upload not found")
+                    } else {
+                        ServerFnError::new(format!("head_object failed: {e}"))
+                    }
+                })?;
+            // Trust the stored object over the client when the provider reports a length.
+            if head.content_length().is_some_and(|len| len != byte_size) {
+                info!(
+                    "uploads.finalize_video_upload: size mismatch claimed={} stored={:?}",
+                    byte_size,
+                    head.content_length()
+                );
+                return Err(ServerFnError::new("upload size mismatch"));
+            }
+            bucket
+        };
 
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        consume_upload_intent(&mut *tx, owner_user_id, &storage_key).await?;
         let row = sqlx::query(
             r#"
-            insert into videos (owner_user_id, target_type, target_id, storage_bucket, storage_key, content_type)
-            values ($1, $2, $3, $4, $5, $6)
+            insert into videos (owner_user_id, target_type, target_id, storage_bucket, storage_key, content_type, byte_size)
+            values ($1, $2, $3, $4, $5, $6, $7)
             returning
                 CAST(id as TEXT) as id,
                 CAST(owner_user_id as TEXT) as owner_user_id,
@@ -169,6 +435,7 @@ pub async fn finalize_video_upload(
                 storage_key,
                 content_type,
                 duration_seconds,
+                byte_size,
                 CAST(created_at as TEXT) as created_at
             "#,
         )
@@ -178,12 +445,17 @@ pub async fn finalize_video_upload(
         .bind(&bucket)
         .bind(&storage_key)
         .bind(&content_type)
-        .fetch_one(pool)
+        .bind(byte_size)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
+        tx.commit()
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
 
         let vid = crate::db::uuid_from_db(&row.get::<String, _>("id"))?;
         info!("uploads.finalize_video_upload: video_id={}", vid);
+        crate::metrics::global().record_upload();
         let _ = sqlx::query(
             "insert into activity (user_id, action, target_type, target_id) values ($1, 'created', 'video', $2)",
         )
@@ -205,26 +477,222 @@ pub async fn finalize_video_upload(
             storage_key: row.get("storage_key"),
             content_type: row.get("content_type"),
             duration_seconds: row.get("duration_seconds"),
+            byte_size: row.get("byte_size"),
             created_at,
             vote_score: 0,
+            captions: Vec::new(),
         })
     }
 }
 
+/// Largest chunk accepted by `upload_multipart_part` (S3 parts must be >= 5MB except the last).
+pub(crate) const MAX_PART_BYTES: usize = 16 * 1024 * 1024;
+
+/// Begin a chunked upload for large clips; follow with `upload_multipart_part`
+/// calls, then `complete_multipart_upload` and `finalize_video_upload`.
+#[dioxus::prelude::post("/api/uploads/multipart/start")]
+pub async fn start_multipart_upload(
+    id_token: String,
+    target_type: ContentTargetType,
+    target_id: String,
+    content_type: String,
+) -> Result<crate::types::MultipartUpload, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, target_type, target_id, content_type);
+        Err(ServerFnError::new("start_multipart_upload is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "uploads.start_multipart_upload",
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        if !ALLOWED_VIDEO_TYPES.contains(&content_type.as_str()) {
+            return Err(ServerFnError::new("unsupported content type"));
+        }
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let tid = uuid::Uuid::parse_str(&target_id)
+            .map_err(|_| ServerFnError::new("invalid target_id"))?;
+
+        let storage_key = new_storage_key(target_type, tid);
+        let upload_id = if filesystem_storage() {
+            state
+                .storage
+                .start_multipart(&storage_key)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?
+        } else {
+            let (client, bucket) = s3_client().await?;
+            let out = client
+                .create_multipart_upload()
+                .bucket(&bucket)
+                .key(&storage_key)
+                .content_type(content_type)
+                .send()
+                .await
+                .map_err(|e| ServerFnError::new(format!("create_multipart_upload failed: {e}")))?;
+            out.upload_id()
+                .ok_or_else(|| ServerFnError::new("missing upload id"))?
+                .to_string()
+        };
+
+        record_upload_intent(state.db.pool().await, user_id, &storage_key).await?;
+
+        info!(
+            "uploads.start_multipart_upload: storage_key={} upload_id={}",
+            storage_key, upload_id
+        );
+        Ok(crate::types::MultipartUpload {
+            upload_id,
+            storage_key,
+        })
+    }
+}
+
+/// Store one chunk (1-based `part_number`) and return its etag.
+#[dioxus::prelude::post("/api/uploads/multipart/part")]
+pub async fn upload_multipart_part(
+    id_token: String,
+    storage_key: String,
+    upload_id: String,
+    part_number: i32,
+    data: Vec<u8>,
+) -> Result<String, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, storage_key, upload_id, part_number, data);
+        Err(ServerFnError::new("upload_multipart_part is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "uploads.upload_multipart_part",
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        ensure_upload_intent(state.db.pool().await, user_id, &storage_key).await?;
+        if !(1..=10_000).contains(&part_number) {
+            return Err(ServerFnError::new("invalid part number"));
+        }
+        if data.is_empty() || data.len() > MAX_PART_BYTES {
+            return Err(ServerFnError::new("invalid part size"));
+        }
+        debug!(
+            "uploads.upload_multipart_part: upload_id={} part={} size={}",
+            upload_id,
+            part_number,
+            data.len()
+        );
+
+        if filesystem_storage() {
+            state
+                .storage
+                .upload_part(&storage_key, &upload_id, part_number, data)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))
+        } else {
+            let (client, bucket) = s3_client().await?;
+            let out = client
+                .upload_part()
+                .bucket(&bucket)
+                .key(&storage_key)
+                .upload_id(&upload_id)
+                .part_number(part_number)
+                .body(aws_sdk_s3::primitives::ByteStream::from(data))
+                .send()
+                .await
+                .map_err(|e| ServerFnError::new(format!("upload_part failed: {e}")))?;
+            Ok(out.e_tag().unwrap_or_default().to_string())
+        }
+    }
+}
+
+/// Assemble the uploaded parts into the object at `storage_key`.
+#[dioxus::prelude::post("/api/uploads/multipart/complete")]
+pub async fn complete_multipart_upload(
+    id_token: String,
+    storage_key: String,
+    upload_id: String,
+    parts: Vec<crate::types::UploadedPart>,
+) -> Result<(), ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, storage_key, upload_id, parts);
+        Err(ServerFnError::new(
+            "complete_multipart_upload is server-only",
+        ))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "uploads.complete_multipart_upload",
+        use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+
+        let state = crate::state::AppState::current()?;
+        state.ensure_writable()?;
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        ensure_upload_intent(state.db.pool().await, user_id, &storage_key).await?;
+        if parts.is_empty() {
+            return Err(ServerFnError::new("no parts uploaded"));
+        }
+        info!(
+            "uploads.complete_multipart_upload: upload_id={} parts={}",
+            upload_id,
+            parts.len()
+        );
+
+        if filesystem_storage() {
+            let parts: Vec<(i32, String)> =
+                parts.into_iter().map(|p| (p.part_number, p.etag)).collect();
+            state
+                .storage
+                .complete_multipart(&storage_key, &upload_id, &parts)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))
+        } else {
+            let mut parts = parts;
+            parts.sort_by_key(|p| p.part_number);
+            let completed = CompletedMultipartUpload::builder()
+                .set_parts(Some(
+                    parts
+                        .into_iter()
+                        .map(|p| {
+                            CompletedPart::builder()
+                                .part_number(p.part_number)
+                                .e_tag(p.etag)
+                                .build()
+                        })
+                        .collect(),
+                ))
+                .build();
+            let (client, bucket) = s3_client().await?;
+            client
+                .complete_multipart_upload()
+                .bucket(&bucket)
+                .key(&storage_key)
+                .upload_id(&upload_id)
+                .multipart_upload(completed)
+                .send()
+                .await
+                .map_err(|e| ServerFnError::new(format!("complete_multipart_upload failed: {e}")))?;
+            Ok(())
+        }
+    }
+}
+
 #[dioxus::prelude::post("/api/videos/list")]
 pub async fn list_videos(
     target_type: ContentTargetType,
     target_id: String,
     limit: i64,
+    id_token: Option<String>,
 ) -> Result<Vec<Video>, ServerFnError> {
     #[cfg(not(feature = "server"))]
     {
-        let _ = (target_type, target_id, limit);
+        let _ = (target_type, target_id, limit, id_token);
         Err(ServerFnError::new("list_videos is server-only"))
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "uploads.list_videos",
         use sqlx::Row;
         use uuid::Uuid;
 
@@ -234,7 +702,9 @@ pub async fn list_videos(
         );
         let tid =
             Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let limit = state.clamp_limit(limit);
         let pool = state.db.pool().await;
 
         let rows = sqlx::query(
@@ -247,12 +717,14 @@ pub async fn list_videos(
                 v.storage_key,
                 v.content_type,
                 v.duration_seconds,
+                v.byte_size,
                 CAST(v.created_at as TEXT) as created_at,
                 coalesce(sum(vo.value), 0) as vote_score
             from videos v
             left join votes vo
                 on vo.target_type = 'video' and vo.target_id = v.id
-            where v.target_type = $1 and v.target_id = $2
+            where v.hidden_at is null
+                and v.target_type = $1 and v.target_id = $2
             group by v.id
             order by v.created_at desc
             limit $3
@@ -279,12 +751,317 @@ pub async fn list_videos(
                 storage_key: row.get("storage_key"),
                 content_type: row.get("content_type"),
                 duration_seconds: row.get("duration_seconds"),
+                byte_size: row.get("byte_size"),
                 created_at,
                 vote_score: row.get::<i64, _>("vote_score"),
+                captions: Vec::new(),
             });
         }
 
+        crate::captions::attach(pool, &mut videos).await?;
         debug!("uploads.list_videos: count={}", videos.len());
         Ok(videos)
     }
 }
+
+/// Videos uploaded by a user, newest first; hidden ones are skipped.
+#[dioxus::prelude::post("/api/videos/by_author/:user_id")]
+pub async fn list_videos_by_author(
+    user_id: String,
+    limit: i64,
+    offset: i64,
+    id_token: Option<String>,
+) -> Result<Vec<Video>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (user_id, limit, offset, id_token);
+        Err(ServerFnError::new("list_videos_by_author is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "uploads.list_videos_by_author",
+        use sqlx::Row;
+        use uuid::Uuid;
+
+        debug!(
+            "uploads.list_videos_by_author: user_id={} limit={} offset={}",
+            user_id, limit, offset
+        );
+        let uid = Uuid::parse_str(&user_id).map_err(|_| ServerFnError::new("invalid user_id"))?;
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let (limit, offset) = state.page(limit, offset)?;
+        let pool = state.db.pool().await;
+
+        let rows = sqlx::query(
+            r#"
+            select
+                CAST(v.id as TEXT) as id,
+                CAST(v.owner_user_id as TEXT) as owner_user_id,
+                v.target_type,
+                CAST(v.target_id as TEXT) as target_id,
+                v.storage_bucket,
+                v.storage_key,
+                v.content_type,
+                v.duration_seconds,
+                v.byte_size,
+                CAST(v.created_at as TEXT) as created_at,
+                coalesce(sum(vo.value), 0) as vote_score
+            from videos v
+            left join votes vo
+                on vo.target_type = 'video' and vo.target_id = v.id
+            where v.owner_user_id = $1 and v.hidden_at is null
+            group by v.id
+            order by v.created_at desc, v.id desc
+            limit $2 offset $3
+            "#,
+        )
+        .bind(crate::db::uuid_to_db(uid))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let mut videos = Vec::with_capacity(rows.len());
+        for row in rows {
+            let id = crate::db::uuid_from_db(&row.get::<String, _>("id"))?;
+            let owner_user_id = crate::db::uuid_from_db(&row.get::<String, _>("owner_user_id"))?;
+            let target_type = ContentTargetType::from_db(&row.get::<String, _>("target_type"))
+                .ok_or_else(|| ServerFnError::new("invalid target_type"))?;
+            let target_id = crate::db::uuid_from_db(&row.get::<String, _>("target_id"))?;
+            let created_at = crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?;
+            videos.push(Video {
+                id,
+                owner_user_id,
+                target_type,
+                target_id,
+                storage_bucket: row.get("storage_bucket"),
+                storage_key: row.get("storage_key"),
+                content_type: row.get("content_type"),
+                duration_seconds: row.get("duration_seconds"),
+                byte_size: row.get("byte_size"),
+                created_at,
+                vote_score: row.get::<i64, _>("vote_score"),
+                captions: Vec::new(),
+            });
+        }
+
+        crate::captions::attach(pool, &mut videos).await?;
+        debug!("uploads.list_videos_by_author: count={}", videos.len());
+        Ok(videos)
+    }
+}
+
+/// A visible video by id, with its owner's profile, target and counts.
+#[dioxus::prelude::post("/api/videos/:id")]
+pub async fn get_video(id: String, id_token: Option<String>) -> Result<VideoDetail, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, id_token);
+        Err(ServerFnError::new("get_video is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "uploads.get_video",
+        use sqlx::Row;
+        use uuid::Uuid;
+
+        debug!("uploads.get_video: id={}", id);
+        let vid = Uuid::parse_str(&id).map_err(|_| ServerFnError::new("invalid id"))?;
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let pool = state.db.pool().await;
+
+        let row = sqlx::query(
+            r#"
+            select
+                CAST(v.id as TEXT) as id,
+                CAST(v.owner_user_id as TEXT) as owner_user_id,
+                v.target_type,
+                CAST(v.target_id as TEXT) as target_id,
+                v.storage_bucket,
+                v.storage_key,
+                v.content_type,
+                v.duration_seconds,
+                v.byte_size,
+                CAST(v.created_at as TEXT) as created_at,
+                coalesce((select sum(vo.value) from votes vo where vo.target_type = 'video' and vo.target_id = v.id), 0) as vote_score,
+                pr.display_name as owner_display_name,
+                pr.avatar_url as owner_avatar_url,
+                case
+                    when v.target_type = 'proposal' then (select title from proposals where id = v.target_id and hidden_at is null)
+                    when v.target_type = 'program' then (select title from programs where id = v.target_id and hidden_at is null)
+                    else null
+                end as target_title,
+                case
+                    when v.target_type = 'proposal' then (select coalesce(slug, CAST(id as TEXT)) from proposals where id = v.target_id and hidden_at is null)
+                    when v.target_type = 'program' then (select coalesce(slug, CAST(id as TEXT)) from programs where id = v.target_id and hidden_at is null)
+                    else null
+                end as target_slug,
+                (select count(*) from video_views vv where vv.video_id = v.id) as view_count,
+                (select count(*) from comments c where c.target_type = 'video' and c.target_id = v.id and c.hidden_at is null and c.deleted_at is null) as comment_count
+            from videos v
+            left join profiles pr on pr.user_id = v.owner_user_id
+            where v.id = $1 and v.hidden_at is null
+            "#,
+        )
+        .bind(crate::db::uuid_to_db(vid))
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .ok_or_else(|| ServerFnError::new(crate::types::ErrorCode::NotFound.to_string()))?;
+
+        let target_type = ContentTargetType::from_db(&row.get::<String, _>("target_type"))
+            .ok_or_else(|| ServerFnError::new("invalid target_type"))?;
+        let mut video = Video {
+            id: vid,
+            owner_user_id: crate::db::uuid_from_db(&row.get::<String, _>("owner_user_id"))?,
+            target_type,
+            target_id: crate::db::uuid_from_db(&row.get::<String, _>("target_id"))?,
+            storage_bucket: row.get("storage_bucket"),
+            storage_key: row.get("storage_key"),
+            content_type: row.get("content_type"),
+            duration_seconds: row.get("duration_seconds"),
+            byte_size: row.get("byte_size"),
+            created_at: crate::db::datetime_from_db(&row.get::<String, _>("created_at"))?,
+            vote_score: row.get::<i64, _>("vote_score"),
+            captions: Vec::new(),
+        };
+        crate::captions::attach(pool, std::iter::once(&mut video)).await?;
+
+        Ok(VideoDetail {
+            video,
+            owner_display_name: row.get("owner_display_name"),
+            owner_avatar_url: row.get("owner_avatar_url"),
+            target_title: row.get("target_title"),
+            target_slug: row.get("target_slug"),
+            view_count: row.get::<i64, _>("view_count"),
+            comment_count: row.get::<i64, _>("comment_count"),
+        })
+    }
+}
+
+/// URL the player should load for `id`.
+///
+/// Uses `MEDIA_BASE_URL` when it points at a public CDN; otherwise returns a
+/// short-lived signed GET (or the local file URL in filesystem mode).
+#[dioxus::prelude::post("/api/videos/:id/playback")]
+pub async fn get_video_playback_url(
+    id: String,
+    id_token: Option<String>,
+) -> Result<String, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, id_token);
+        Err(ServerFnError::new("get_video_playback_url is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "uploads.get_video_playback_url",
+        use uuid::Uuid;
+
+        let vid = Uuid::parse_str(&id).map_err(|_| ServerFnError::new("invalid id"))?;
+        let state = crate::state::AppState::current()?;
+        crate::auth::reader_id(id_token).await?;
+        let pool = state.db.pool().await;
+        let storage_key: String = sqlx::query_scalar(
+            "select storage_key from videos where id = $1 and hidden_at is null",
+        )
+        .bind(crate::db::uuid_to_db(vid))
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .ok_or_else(|| ServerFnError::new(crate::types::ErrorCode::NotFound.to_string()))?;
+
+        if let Some(base) = std::env::var("MEDIA_BASE_URL")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+        {
+            return Ok(format!("{}/{}", base.trim_end_matches('/'), storage_key));
+        }
+        if filesystem_storage() {
+            return state
+                .storage
+                .get_url(&storage_key)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()));
+        }
+
+        let (client, bucket) = s3_client().await?;
+        let presigned = client
+            .get_object()
+            .bucket(&bucket)
+            .key(&storage_key)
+            .presigned(presigning_config()?)
+            .await
+            .map_err(|e| ServerFnError::new(format!("presign error: {e}")))?;
+        debug!("uploads.get_video_playback_url: video_id={} signed", vid);
+        Ok(presigned.uri().to_string())
+    }
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    use crate::config::StorageConfig;
+    use aws_sdk_s3::config::Region;
+    use aws_sdk_s3::presigning::PresigningConfig;
+    use std::time::Duration;
+
+    fn storage(path_style: bool) -> StorageConfig {
+        StorageConfig::S3 {
+            bucket: "media".to_string(),
+            endpoint: "https://storage.example.com".to_string(),
+            region: "eu-west-3".to_string(),
+            access_key: "access".to_string(),
+            secret_key: "secret".to_string(),
+            media_base_url: None,
+            path_style,
+        }
+    }
+
+    async fn presigned_get_url(path_style: bool) -> String {
+        let settings = S3Settings::from_storage(&storage(path_style)).unwrap();
+        let sdk_config = aws_config::SdkConfig::builder()
+            .behavior_version(aws_config::BehaviorVersion::latest())
+            .build();
+        let config = settings.client_config(&sdk_config);
+        assert_eq!(config.region(), Some(&Region::new("eu-west-3")));
+
+        aws_sdk_s3::Client::from_conf(config)
+            .get_object()
+            .bucket(&settings.bucket)
+            .key("videos/clip.mp4")
+            .presigned(PresigningConfig::expires_in(Duration::from_secs(60)).unwrap())
+            .await
+            .unwrap()
+            .uri()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_s3_config_applies_path_style_and_region() {
+        let url = presigned_get_url(true).await;
+        assert!(
+            url.starts_with("https://storage.example.com/media/videos/clip.mp4?"),
+            "{url}"
+        );
+        assert!(url.contains("eu-west-3"), "{url}");
+
+        let url = presigned_get_url(false).await;
+        assert!(
+            url.starts_with("https://media.storage.example.com/videos/clip.mp4?"),
+            "{url}"
+        );
+    }
+
+    #[test]
+    fn test_filesystem_storage_has_no_s3_settings() {
+        let storage = StorageConfig::Filesystem {
+            base_path: "/tmp".to_string(),
+            serve_url: "/uploads".to_string(),
+        };
+        assert_eq!(S3Settings::from_storage(&storage), None);
+    }
+}
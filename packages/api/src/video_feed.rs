@@ -14,14 +14,16 @@ pub async fn mark_video_viewed(id_token: String, video_id: String) -> Result<(),
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "video_feed.mark_video_viewed",
         use uuid::Uuid;
 
+        crate::state::AppState::current()?.ensure_writable()?;
+
         debug!("video_feed.mark_video_viewed: video_id={}", video_id);
         let user_id = crate::auth::require_user_id(id_token).await?;
         let vid = Uuid::parse_str(&video_id).map_err(|_| ServerFnError::new("invalid video_id"))?;
 
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
         let pool = state.db.pool().await;
 
         // Insert view record (ignore if duplicate due to unique constraint)
@@ -62,52 +64,25 @@ pub async fn bookmark_video(id_token: String, video_id: String) -> Result<bool,
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "video_feed.bookmark_video",
         use uuid::Uuid;
 
+        crate::state::AppState::current()?.ensure_writable()?;
+
         debug!("video_feed.bookmark_video: video_id={}", video_id);
         let user_id = crate::auth::require_user_id(id_token).await?;
         let vid = Uuid::parse_str(&video_id).map_err(|_| ServerFnError::new("invalid video_id"))?;
 
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
         let pool = state.db.pool().await;
 
-        // Check if bookmark exists
-        let exists = sqlx::query("select 1 from bookmarks where user_id = $1 and video_id = $2")
-            .bind(crate::db::uuid_to_db(user_id))
-            .bind(crate::db::uuid_to_db(vid))
-            .fetch_optional(pool)
-            .await
-            .map_err(|e| ServerFnError::new(e.to_string()))?
-            .is_some();
-
-        if exists {
-            // Remove bookmark
-            sqlx::query("delete from bookmarks where user_id = $1 and video_id = $2")
-                .bind(crate::db::uuid_to_db(user_id))
-                .bind(crate::db::uuid_to_db(vid))
-                .execute(pool)
-                .await
-                .map_err(|e| ServerFnError::new(e.to_string()))?;
-            info!(
-                "video_feed.bookmark_video: removed bookmark user_id={} video_id={}",
-                user_id, vid
-            );
-            Ok(false)
-        } else {
-            // Add bookmark
-            sqlx::query("insert into bookmarks (user_id, video_id) values ($1, $2)")
-                .bind(crate::db::uuid_to_db(user_id))
-                .bind(crate::db::uuid_to_db(vid))
-                .execute(pool)
-                .await
-                .map_err(|e| ServerFnError::new(e.to_string()))?;
-            info!(
-                "video_feed.bookmark_video: added bookmark user_id={} video_id={}",
-                user_id, vid
-            );
-            Ok(true)
-        }
+        let bookmarked =
+            crate::bookmarks::toggle(pool, user_id, ContentTargetType::Video, vid).await?;
+        info!(
+            "video_feed.bookmark_video: user_id={} video_id={} bookmarked={}",
+            user_id, vid, bookmarked
+        );
+        Ok(bookmarked)
     }
 }
 
@@ -124,14 +99,15 @@ pub async fn list_bookmarked_videos(
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "video_feed.list_bookmarked_videos",
         debug!(
             "video_feed.list_bookmarked_videos: limit={} offset={}",
             limit, offset
         );
         let user_id = crate::auth::require_user_id(id_token).await?;
 
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
+        let (limit, offset) = state.page(limit, offset)?;
         let pool = state.db.pool().await;
 
         let rows = sqlx::query(
@@ -145,12 +121,14 @@ pub async fn list_bookmarked_videos(
                 v.storage_key,
                 v.content_type,
                 v.duration_seconds,
+                v.byte_size,
                 CAST(v.created_at as TEXT) as created_at,
                 coalesce(sum(vo.value), 0) as vote_score
             from videos v
-            join bookmarks b on b.video_id = v.id
+            join bookmarks b on b.target_type = 'video' and b.target_id = v.id
             left join votes vo on vo.target_type = 'video' and vo.target_id = v.id
-            where b.user_id = $1
+            where v.hidden_at is null
+                and b.user_id = $1
             group by v.id
             order by b.created_at desc
             limit $2 offset $3
@@ -186,16 +164,80 @@ pub async fn list_bookmarked_videos(
                 storage_key: row.get("storage_key"),
                 content_type: row.get("content_type"),
                 duration_seconds: row.get("duration_seconds"),
+                byte_size: row.get("byte_size"),
                 created_at,
                 vote_score: row.get::<i64, _>("vote_score"),
+                captions: Vec::new(),
             });
         }
 
+        crate::captions::attach(pool, &mut videos).await?;
         debug!("video_feed.list_bookmarked_videos: count={}", videos.len());
         Ok(videos)
     }
 }
 
+/// The signed-in user's own uploads, newest first; hidden ones are skipped.
+#[dioxus::prelude::post("/api/video_feed/list_mine")]
+pub async fn list_my_videos(
+    id_token: String,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<Video>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, limit, offset);
+        Err(ServerFnError::new("list_my_videos is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "video_feed.list_my_videos",
+        debug!(
+            "video_feed.list_my_videos: limit={} offset={}",
+            limit, offset
+        );
+        let user_id = crate::auth::require_user_id(id_token).await?;
+
+        let state = crate::state::AppState::current()?;
+        let (limit, offset) = state.page(limit, offset)?;
+        let pool = state.db.pool().await;
+
+        let rows = sqlx::query(
+            r#"
+            select
+                CAST(v.id as TEXT) as id,
+                CAST(v.owner_user_id as TEXT) as owner_user_id,
+                v.target_type,
+                CAST(v.target_id as TEXT) as target_id,
+                v.storage_bucket,
+                v.storage_key,
+                v.content_type,
+                v.duration_seconds,
+                v.byte_size,
+                CAST(v.created_at as TEXT) as created_at,
+                coalesce(sum(vo.value), 0) as vote_score
+            from videos v
+            left join votes vo on vo.target_type = 'video' and vo.target_id = v.id
+            where v.owner_user_id = $1 and v.hidden_at is null
+            group by v.id
+            order by v.created_at desc, v.id desc
+            limit $2 offset $3
+            "#,
+        )
+        .bind(crate::db::uuid_to_db(user_id))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let mut videos = parse_video_rows(rows)?;
+        crate::captions::attach(pool, &mut videos).await?;
+        debug!("video_feed.list_my_videos: count={}", videos.len());
+        Ok(videos)
+    }
+}
+
 #[dioxus::prelude::post("/api/video_feed/list_feed")]
 pub async fn list_feed_videos(
     id_token: String,
@@ -209,14 +251,15 @@ pub async fn list_feed_videos(
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "video_feed.list_feed_videos",
         debug!(
             "video_feed.list_feed_videos: limit={} offset={}",
             limit, offset
         );
         let user_id = crate::auth::require_user_id(id_token).await?;
 
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
+        let (limit, offset) = state.page(limit, offset)?;
         let pool = state.db.pool().await;
 
         // Phase 1: Get collaborative filtering videos (40% weight)
@@ -247,7 +290,8 @@ pub async fn list_feed_videos(
         let total = feed.len();
         let start = offset.min(total as i64) as usize;
         let end = (offset + limit).min(total as i64) as usize;
-        let paginated_feed = feed[start..end].to_vec();
+        let mut paginated_feed = feed[start..end].to_vec();
+        crate::captions::attach(pool, &mut paginated_feed).await?;
 
         debug!(
             "video_feed.list_feed_videos: total={} returning={}",
@@ -275,6 +319,7 @@ async fn get_collaborative_videos(
             v.storage_key,
             v.content_type,
             v.duration_seconds,
+            v.byte_size,
             CAST(v.created_at as TEXT) as created_at,
             coalesce(sum(vo.value), 0) as vote_score
         from videos v
@@ -291,6 +336,7 @@ async fn get_collaborative_videos(
         and v.id not in (
             select video_id from video_views where user_id = $1
         )
+        and v.hidden_at is null
         group by v.id
         limit 20
         "#,
@@ -320,11 +366,13 @@ async fn get_popular_videos(
             v.storage_key,
             v.content_type,
             v.duration_seconds,
+            v.byte_size,
             CAST(v.created_at as TEXT) as created_at,
             coalesce(sum(vo.value), 0) as vote_score
         from videos v
         left join votes vo on vo.target_type = 'video' and vo.target_id = v.id
-        where v.created_at > datetime('now', '-7 days')
+        where v.hidden_at is null
+            and v.created_at > datetime('now', '-7 days')
             and v.id not in (
                 select video_id from video_views where user_id = $1
             )
@@ -343,11 +391,13 @@ async fn get_popular_videos(
             v.storage_key,
             v.content_type,
             v.duration_seconds,
+            v.byte_size,
             CAST(v.created_at as TEXT) as created_at,
             coalesce(sum(vo.value), 0) as vote_score
         from videos v
         left join votes vo on vo.target_type = 'video' and vo.target_id = v.id
-        where v.created_at > now() - interval '7 days'
+        where v.hidden_at is null
+            and v.created_at > now() - interval '7 days'
             and v.id not in (
                 select video_id from video_views where user_id = $1
             )
@@ -383,13 +433,15 @@ async fn get_interactive_videos(
             v.storage_key,
             v.content_type,
             v.duration_seconds,
+            v.byte_size,
             CAST(v.created_at as TEXT) as created_at,
             coalesce(sum(vo.value), 0) as vote_score,
             (count(distinct vo.id) + count(distinct c.id) * 2) as interaction_score
         from videos v
         left join votes vo on vo.target_type = 'video' and vo.target_id = v.id
         left join comments c on c.target_type = 'video' and c.target_id = v.id
-        where v.created_at > datetime('now', '-7 days')
+        where v.hidden_at is null
+            and v.created_at > datetime('now', '-7 days')
             and v.id not in (
                 select video_id from video_views where user_id = $1
             )
@@ -408,13 +460,15 @@ async fn get_interactive_videos(
             v.storage_key,
             v.content_type,
             v.duration_seconds,
+            v.byte_size,
             CAST(v.created_at as TEXT) as created_at,
             coalesce(sum(vo.value), 0) as vote_score,
             (count(distinct vo.id) + count(distinct c.id) * 2) as interaction_score
         from videos v
         left join votes vo on vo.target_type = 'video' and vo.target_id = v.id
         left join comments c on c.target_type = 'video' and c.target_id = v.id
-        where v.created_at > now() - interval '7 days'
+        where v.hidden_at is null
+            and v.created_at > now() - interval '7 days'
             and v.id not in (
                 select video_id from video_views where user_id = $1
             )
@@ -550,8 +604,10 @@ fn parse_video_rows(rows: Vec<sqlx::any::AnyRow>) -> Result<Vec<Video>, ServerFn
             storage_key: row.get("storage_key"),
             content_type: row.get("content_type"),
             duration_seconds: row.get("duration_seconds"),
+            byte_size: row.get("byte_size"),
             created_at,
             vote_score: row.get::<i64, _>("vote_score"),
+            captions: Vec::new(),
         });
     }
 
@@ -574,7 +630,7 @@ pub async fn list_single_content_videos(
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "video_feed.list_single_content_videos",
         use uuid::Uuid;
 
         debug!(
@@ -585,7 +641,8 @@ pub async fn list_single_content_videos(
         let tid =
             Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
 
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
+        let (limit, offset) = state.page(limit, offset)?;
         let pool = state.db.pool().await;
 
         let rows = sqlx::query(
@@ -599,11 +656,13 @@ pub async fn list_single_content_videos(
                 v.storage_key,
                 v.content_type,
                 v.duration_seconds,
+                v.byte_size,
                 CAST(v.created_at as TEXT) as created_at,
                 coalesce(sum(vo.value), 0) as vote_score
             from videos v
             left join votes vo on vo.target_type = 'video' and vo.target_id = v.id
-            where v.target_type = $1 and v.target_id = $2
+            where v.hidden_at is null
+                and v.target_type = $1 and v.target_id = $2
             group by v.id
             order by v.created_at desc
             limit $3 offset $4
@@ -617,7 +676,8 @@ pub async fn list_single_content_videos(
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
 
-        let videos = parse_video_rows(rows)?;
+        let mut videos = parse_video_rows(rows)?;
+        crate::captions::attach(pool, &mut videos).await?;
         debug!(
             "video_feed.list_single_content_videos: count={}",
             videos.len()
@@ -729,7 +789,7 @@ mod tests {
 
         // Check if bookmark exists (should be none)
         let exists: Option<Uuid> =
-            sqlx::query_scalar("SELECT id FROM bookmarks WHERE user_id = $1 AND video_id = $2")
+            sqlx::query_scalar("SELECT id FROM bookmarks WHERE user_id = $1 AND target_type = 'video' AND target_id = $2")
                 .bind(user_id)
                 .bind(video_id)
                 .fetch_optional(pool)
@@ -740,8 +800,8 @@ mod tests {
 
         // Add bookmark
         sqlx::query(
-            "INSERT INTO bookmarks (user_id, video_id) VALUES ($1, $2)
-             ON CONFLICT (user_id, video_id) DO NOTHING",
+            "INSERT INTO bookmarks (user_id, target_type, target_id) VALUES ($1, 'video', $2)
+             ON CONFLICT (user_id, target_type, target_id) DO NOTHING",
         )
         .bind(user_id)
         .bind(video_id)
@@ -751,7 +811,7 @@ mod tests {
 
         // Verify bookmark exists
         let exists: Option<Uuid> =
-            sqlx::query_scalar("SELECT id FROM bookmarks WHERE user_id = $1 AND video_id = $2")
+            sqlx::query_scalar("SELECT id FROM bookmarks WHERE user_id = $1 AND target_type = 'video' AND target_id = $2")
                 .bind(user_id)
                 .bind(video_id)
                 .fetch_optional(pool)
@@ -761,16 +821,18 @@ mod tests {
         assert!(exists.is_some());
 
         // Remove bookmark
-        sqlx::query("DELETE FROM bookmarks WHERE user_id = $1 AND video_id = $2")
-            .bind(user_id)
-            .bind(video_id)
-            .execute(pool)
-            .await
-            .unwrap();
+        sqlx::query(
+            "DELETE FROM bookmarks WHERE user_id = $1 AND target_type = 'video' AND target_id = $2",
+        )
+        .bind(user_id)
+        .bind(video_id)
+        .execute(pool)
+        .await
+        .unwrap();
 
         // Verify bookmark removed
         let exists: Option<Uuid> =
-            sqlx::query_scalar("SELECT id FROM bookmarks WHERE user_id = $1 AND video_id = $2")
+            sqlx::query_scalar("SELECT id FROM bookmarks WHERE user_id = $1 AND target_type = 'video' AND target_id = $2")
                 .bind(user_id)
                 .bind(video_id)
                 .fetch_optional(pool)
@@ -797,24 +859,28 @@ mod tests {
         let video3 = create_test_video(pool, user_id, proposal_id).await;
 
         // Bookmark video 1 and 3
-        sqlx::query("INSERT INTO bookmarks (user_id, video_id) VALUES ($1, $2)")
-            .bind(user_id)
-            .bind(video1)
-            .execute(pool)
-            .await
-            .unwrap();
+        sqlx::query(
+            "INSERT INTO bookmarks (user_id, target_type, target_id) VALUES ($1, 'video', $2)",
+        )
+        .bind(user_id)
+        .bind(video1)
+        .execute(pool)
+        .await
+        .unwrap();
 
-        sqlx::query("INSERT INTO bookmarks (user_id, video_id) VALUES ($1, $2)")
-            .bind(user_id)
-            .bind(video3)
-            .execute(pool)
-            .await
-            .unwrap();
+        sqlx::query(
+            "INSERT INTO bookmarks (user_id, target_type, target_id) VALUES ($1, 'video', $2)",
+        )
+        .bind(user_id)
+        .bind(video3)
+        .execute(pool)
+        .await
+        .unwrap();
 
         // Query bookmarked videos
         let rows = sqlx::query(
             "SELECT v.* FROM videos v
-             JOIN bookmarks b ON v.id = b.video_id
+             JOIN bookmarks b ON b.target_type = 'video' AND v.id = b.target_id
              WHERE b.user_id = $1
              ORDER BY b.created_at DESC",
         )
@@ -1,4 +1,8 @@
-use crate::types::{ContentTargetType, VoteState};
+#[cfg(feature = "server")]
+use crate::types::ActivityAction;
+#[cfg(feature = "server")]
+use crate::types::VoteRank;
+use crate::types::{ContentTargetType, VoteBreakdown, VoteState, VotedItem};
 use dioxus::prelude::*;
 #[cfg(feature = "server")]
 use tracing::{debug, info};
@@ -22,9 +26,11 @@ pub async fn set_vote(
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "votes.set_vote",
         use uuid::Uuid;
 
+        crate::state::AppState::current()?.ensure_writable()?;
+
         debug!(
             "votes.set_vote: target_type={:?} target_id={} value={}",
             target_type, target_id, value
@@ -32,57 +38,16 @@ pub async fn set_vote(
         let user_id = crate::auth::require_user_id(id_token).await?;
         let tid =
             Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
         let pool = state.db.pool().await;
+        let previous = my_vote(pool, user_id, target_type, tid).await?;
 
         if value == 0 {
             info!("votes.set_vote: clear user_id={}", user_id);
-            sqlx::query(
-                "delete from votes where user_id = $1 and target_type = $2 and target_id = $3",
-            )
-            .bind(crate::db::uuid_to_db(user_id))
-            .bind(target_type.as_db())
-            .bind(crate::db::uuid_to_db(tid))
-            .execute(pool)
-            .await
-            .map_err(|e| ServerFnError::new(e.to_string()))?;
+            delete_vote(pool, user_id, target_type, tid).await?;
         } else if value == 1 || value == -1 {
             info!("votes.set_vote: set user_id={} value={}", user_id, value);
-            let sql = if crate::db::is_sqlite() {
-                r#"
-                insert into votes (user_id, target_type, target_id, value)
-                values ($1, $2, $3, $4)
-                on conflict (user_id, target_type, target_id)
-                do update set value = excluded.value, updated_at = CURRENT_TIMESTAMP
-                "#
-            } else {
-                r#"
-                insert into votes (user_id, target_type, target_id, value)
-                values ($1, $2, $3, $4)
-                on conflict (user_id, target_type, target_id)
-                do update set value = excluded.value, updated_at = now()
-                "#
-            };
-            sqlx::query(sql)
-                .bind(crate::db::uuid_to_db(user_id))
-                .bind(target_type.as_db())
-                .bind(crate::db::uuid_to_db(tid))
-                .bind(value)
-                .execute(pool)
-                .await
-                .map_err(|e| ServerFnError::new(e.to_string()))?;
-
-            // Activity log (best-effort)
-            let action = if value == 1 { "voted_up" } else { "voted_down" };
-            let _ = sqlx::query(
-                "insert into activity (user_id, action, target_type, target_id) values ($1, $2, $3, $4)",
-            )
-            .bind(crate::db::uuid_to_db(user_id))
-            .bind(action)
-            .bind(target_type.as_db())
-            .bind(crate::db::uuid_to_db(tid))
-            .execute(pool)
-            .await;
+            upsert_vote(pool, user_id, target_type, tid, value).await?;
         } else {
             return Err(ServerFnError::new("value must be -1, 0, or 1"));
         }
@@ -96,26 +61,217 @@ pub async fn set_vote(
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
 
-        let my_vote: Option<i16> = sqlx::query_scalar(
-            "select value from votes where user_id = $1 and target_type = $2 and target_id = $3",
+        let my_vote = my_vote(pool, user_id, target_type, tid).await?;
+        let delta = i64::from(my_vote.unwrap_or(0)) - i64::from(previous.unwrap_or(0));
+        let rank = ranked_position(pool, target_type, tid).await?;
+
+        debug!(
+            "votes.set_vote: score={} delta={} my_vote={:?} rank={:?}",
+            score, delta, my_vote, rank
+        );
+        Ok(VoteState {
+            target_type,
+            target_id: tid,
+            score,
+            my_vote,
+            delta,
+            rank,
+        })
+    }
+}
+
+/// Clear the caller's vote on a target (no-op when there is none).
+#[dioxus::prelude::post("/api/votes/clear")]
+pub async fn clear_vote(
+    id_token: String,
+    target_type: ContentTargetType,
+    target_id: String,
+) -> Result<VoteState, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, target_type, target_id);
+        Err(ServerFnError::new("clear_vote is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "votes.clear_vote",
+        use uuid::Uuid;
+
+        crate::state::AppState::current()?.ensure_writable()?;
+
+        debug!(
+            "votes.clear_vote: target_type={:?} target_id={}",
+            target_type, target_id
+        );
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let tid =
+            Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        info!("votes.clear_vote: user_id={}", user_id);
+        let previous = my_vote(pool, user_id, target_type, tid).await?;
+        delete_vote(pool, user_id, target_type, tid).await?;
+
+        let score: i64 = sqlx::query_scalar(
+            "select coalesce(sum(value), 0) from votes where target_type = $1 and target_id = $2",
         )
-        .bind(crate::db::uuid_to_db(user_id))
         .bind(target_type.as_db())
         .bind(crate::db::uuid_to_db(tid))
-        .fetch_optional(pool)
+        .fetch_one(pool)
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
 
-        debug!("votes.set_vote: score={} my_vote={:?}", score, my_vote);
+        let delta = -i64::from(previous.unwrap_or(0));
+        let rank = ranked_position(pool, target_type, tid).await?;
+
+        debug!("votes.clear_vote: score={} delta={}", score, delta);
         Ok(VoteState {
             target_type,
             target_id: tid,
             score,
-            my_vote,
+            my_vote: None,
+            delta,
+            rank,
         })
     }
 }
 
+/// The caller's current vote on a target, if any.
+#[cfg(feature = "server")]
+async fn my_vote(
+    pool: &sqlx::Pool<sqlx::Any>,
+    user_id: uuid::Uuid,
+    target_type: ContentTargetType,
+    target_id: uuid::Uuid,
+) -> Result<Option<i16>, ServerFnError> {
+    sqlx::query_scalar(
+        "select value from votes where user_id = $1 and target_type = $2 and target_id = $3",
+    )
+    .bind(crate::db::uuid_to_db(user_id))
+    .bind(target_type.as_db())
+    .bind(crate::db::uuid_to_db(target_id))
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// `vote_rank(..).rank` for proposals and programs; `None` for other targets or
+/// when the target can't be ranked (e.g. it is hidden).
+#[cfg(feature = "server")]
+async fn ranked_position(
+    pool: &sqlx::Pool<sqlx::Any>,
+    target_type: ContentTargetType,
+    target_id: uuid::Uuid,
+) -> Result<Option<i64>, ServerFnError> {
+    if !matches!(
+        target_type,
+        ContentTargetType::Proposal | ContentTargetType::Program
+    ) {
+        return Ok(None);
+    }
+    Ok(vote_rank(pool, target_type, target_id)
+        .await
+        .ok()
+        .map(|rank| rank.rank))
+}
+
+/// Set the caller's vote on a target and its `voted` activity row in one transaction.
+///
+/// The activity row upserts on `activity_vote_target_idx`, so flipping or re-casting
+/// a vote moves the one row per target to the top of the feed instead of adding more.
+#[cfg(feature = "server")]
+async fn upsert_vote(
+    pool: &sqlx::Pool<sqlx::Any>,
+    user_id: uuid::Uuid,
+    target_type: ContentTargetType,
+    target_id: uuid::Uuid,
+    value: i16,
+) -> Result<(), ServerFnError> {
+    let now = crate::db::now_sql();
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    sqlx::query(&format!(
+        r#"
+        insert into votes (user_id, target_type, target_id, value)
+        values ($1, $2, $3, $4)
+        on conflict (user_id, target_type, target_id)
+        do update set value = excluded.value, updated_at = {now}
+        "#
+    ))
+    .bind(crate::db::uuid_to_db(user_id))
+    .bind(target_type.as_db())
+    .bind(crate::db::uuid_to_db(target_id))
+    .bind(value)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    sqlx::query(&format!(
+        r#"
+        insert into activity (user_id, action, target_type, target_id)
+        values ($1, $2, $3, $4)
+        on conflict (user_id, target_type, target_id) where action = 'voted'
+        do update set created_at = {now}
+        "#
+    ))
+    .bind(crate::db::uuid_to_db(user_id))
+    .bind(ActivityAction::Voted.as_db())
+    .bind(target_type.as_db())
+    .bind(crate::db::uuid_to_db(target_id))
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Remove the caller's vote on a target and its `voted` activity row in one
+/// transaction; a cleared vote leaves nothing in the feed.
+#[cfg(feature = "server")]
+async fn delete_vote(
+    pool: &sqlx::Pool<sqlx::Any>,
+    user_id: uuid::Uuid,
+    target_type: ContentTargetType,
+    target_id: uuid::Uuid,
+) -> Result<(), ServerFnError> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    sqlx::query("delete from votes where user_id = $1 and target_type = $2 and target_id = $3")
+        .bind(crate::db::uuid_to_db(user_id))
+        .bind(target_type.as_db())
+        .bind(crate::db::uuid_to_db(target_id))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    sqlx::query(
+        r#"
+        delete from activity
+        where user_id = $1 and target_type = $2 and target_id = $3 and action = $4
+        "#,
+    )
+    .bind(crate::db::uuid_to_db(user_id))
+    .bind(target_type.as_db())
+    .bind(crate::db::uuid_to_db(target_id))
+    .bind(ActivityAction::Voted.as_db())
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
 /// Get the current vote state for a user + target.
 #[dioxus::prelude::post("/api/votes/state")]
 pub async fn get_vote_state(
@@ -130,7 +286,7 @@ pub async fn get_vote_state(
     }
 
     #[cfg(feature = "server")]
-    {
+    crate::telemetry::traced! { "votes.get_vote_state",
         use uuid::Uuid;
 
         debug!(
@@ -140,7 +296,7 @@ pub async fn get_vote_state(
         let user_id = crate::auth::require_user_id(id_token).await?;
         let tid =
             Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
-        let state = crate::state::AppState::global();
+        let state = crate::state::AppState::current()?;
         let pool = state.db.pool().await;
 
         let score: i64 = sqlx::query_scalar(
@@ -152,6 +308,131 @@ pub async fn get_vote_state(
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
 
+        let my_vote = my_vote(pool, user_id, target_type, tid).await?;
+
+        debug!(
+            "votes.get_vote_state: user_id={} score={} my_vote={:?}",
+            user_id, score, my_vote
+        );
+        Ok(VoteState {
+            target_type,
+            target_id: tid,
+            score,
+            my_vote,
+            delta: 0,
+            rank: None,
+        })
+    }
+}
+
+/// Net score of a target; open to signed-out readers (unless `public_read` is off)
+/// so they see live numbers.
+#[dioxus::prelude::post("/api/votes/:target_type/:target_id/score")]
+pub async fn get_vote_score(
+    target_type: ContentTargetType,
+    target_id: String,
+    id_token: Option<String>,
+) -> Result<i64, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (target_type, target_id, id_token);
+        Err(ServerFnError::new("get_vote_score is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "votes.get_vote_score",
+        use uuid::Uuid;
+
+        debug!(
+            "votes.get_vote_score: target_type={:?} target_id={}",
+            target_type, target_id
+        );
+        crate::auth::reader_id(id_token).await?;
+        let tid =
+            Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        let score: i64 = sqlx::query_scalar(
+            "select coalesce(sum(value), 0) from votes where target_type = $1 and target_id = $2",
+        )
+        .bind(target_type.as_db())
+        .bind(crate::db::uuid_to_db(tid))
+        .fetch_one(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        debug!("votes.get_vote_score: score={}", score);
+        Ok(score)
+    }
+}
+
+/// Net score of a target counting only voters with a verified email.
+#[cfg(feature = "server")]
+pub(crate) async fn verified_score(
+    pool: &sqlx::Pool<sqlx::Any>,
+    target_type: ContentTargetType,
+    target_id: uuid::Uuid,
+) -> Result<i64, ServerFnError> {
+    sqlx::query_scalar(
+        r#"
+        select coalesce(sum(v.value), 0)
+        from votes v
+        join users u on u.id = v.user_id
+        where v.target_type = $1 and v.target_id = $2 and u.email_verified = true
+        "#,
+    )
+    .bind(target_type.as_db())
+    .bind(crate::db::uuid_to_db(target_id))
+    .fetch_one(pool)
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Get the up/down split for a target along with the caller's vote.
+#[dioxus::prelude::post("/api/votes/breakdown")]
+pub async fn get_vote_breakdown(
+    id_token: String,
+    target_type: ContentTargetType,
+    target_id: String,
+) -> Result<VoteBreakdown, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, target_type, target_id);
+        Err(ServerFnError::new("get_vote_breakdown is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "votes.get_vote_breakdown",
+        use sqlx::Row;
+        use uuid::Uuid;
+
+        debug!(
+            "votes.get_vote_breakdown: target_type={:?} target_id={}",
+            target_type, target_id
+        );
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let tid =
+            Uuid::parse_str(&target_id).map_err(|_| ServerFnError::new("invalid target_id"))?;
+        let state = crate::state::AppState::current()?;
+        let pool = state.db.pool().await;
+
+        let row = sqlx::query(
+            r#"
+            select
+                coalesce(sum(case when value = 1 then 1 else 0 end), 0) as upvotes,
+                coalesce(sum(case when value = -1 then 1 else 0 end), 0) as downvotes,
+                coalesce(sum(value), 0) as score
+            from votes
+            where target_type = $1 and target_id = $2
+            "#,
+        )
+        .bind(target_type.as_db())
+        .bind(crate::db::uuid_to_db(tid))
+        .fetch_one(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
         let my_vote: Option<i16> = sqlx::query_scalar(
             "select value from votes where user_id = $1 and target_type = $2 and target_id = $3",
         )
@@ -162,15 +443,169 @@ pub async fn get_vote_state(
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))?;
 
-        debug!(
-            "votes.get_vote_state: user_id={} score={} my_vote={:?}",
-            user_id, score, my_vote
-        );
-        Ok(VoteState {
+        let breakdown = VoteBreakdown {
             target_type,
             target_id: tid,
-            score,
+            upvotes: row.get::<i64, _>("upvotes"),
+            downvotes: row.get::<i64, _>("downvotes"),
+            score: row.get::<i64, _>("score"),
             my_vote,
-        })
+        };
+        debug!(
+            "votes.get_vote_breakdown: up={} down={} score={}",
+            breakdown.upvotes, breakdown.downvotes, breakdown.score
+        );
+        Ok(breakdown)
+    }
+}
+
+/// Everything the caller currently has a vote on, most recent first.
+#[dioxus::prelude::post("/api/votes/my")]
+pub async fn list_my_votes(
+    id_token: String,
+    target_type: Option<ContentTargetType>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<VotedItem>, ServerFnError> {
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id_token, target_type, limit, offset);
+        Err(ServerFnError::new("list_my_votes is server-only"))
+    }
+
+    #[cfg(feature = "server")]
+    crate::telemetry::traced! { "votes.list_my_votes",
+        use sqlx::Row;
+
+        debug!(
+            "votes.list_my_votes: target_type={:?} limit={} offset={}",
+            target_type, limit, offset
+        );
+        let user_id = crate::auth::require_user_id(id_token).await?;
+        let state = crate::state::AppState::current()?;
+        let (limit, offset) = state.page(limit, offset)?;
+        let pool = state.db.pool().await;
+
+        let title_expr = if crate::db::is_sqlite() {
+            "substr(body_markdown, 1, 80)"
+        } else {
+            "left(body_markdown, 80)"
+        };
+        let type_filter = if target_type.is_some() {
+            "and v.target_type = $4"
+        } else {
+            ""
+        };
+        let sql = format!(
+            r#"
+            select
+                CAST(v.target_type as TEXT) as target_type,
+                CAST(v.target_id as TEXT) as target_id,
+                v.value,
+                CAST(v.updated_at as TEXT) as voted_at,
+                case
+                    when v.target_type = 'proposal' then (select title from proposals where id = v.target_id)
+                    when v.target_type = 'program' then (select title from programs where id = v.target_id)
+                    when v.target_type = 'comment' then (select {title_expr} from comments where id = v.target_id)
+                    when v.target_type = 'video' then (select storage_key from videos where id = v.target_id)
+                    else null
+                end as title
+            from votes v
+            where v.user_id = $1 {type_filter}
+            order by v.updated_at desc
+            limit $2 offset $3
+            "#
+        );
+        let mut query = sqlx::query(&sql)
+            .bind(crate::db::uuid_to_db(user_id))
+            .bind(limit)
+            .bind(offset);
+        if let Some(t) = target_type {
+            query = query.bind(t.as_db());
+        }
+        let rows = query
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        let mut items = Vec::with_capacity(rows.len());
+        for row in rows {
+            items.push(VotedItem {
+                target_type: ContentTargetType::from_db(&row.get::<String, _>("target_type"))
+                    .ok_or_else(|| ServerFnError::new("invalid target_type"))?,
+                target_id: crate::db::uuid_from_db(&row.get::<String, _>("target_id"))?,
+                value: row.get::<i16, _>("value"),
+                voted_at: crate::db::datetime_from_db(&row.get::<String, _>("voted_at"))?,
+                title: row.get("title"),
+            });
+        }
+
+        debug!("votes.list_my_votes: count={}", items.len());
+        Ok(items)
     }
 }
+
+/// Rank a proposal/program among all rows of its kind by net vote score.
+///
+/// Ties share a rank (1 + number of rows with a strictly higher score).
+#[cfg(feature = "server")]
+pub(crate) async fn vote_rank(
+    pool: &sqlx::Pool<sqlx::Any>,
+    target_type: ContentTargetType,
+    target_id: uuid::Uuid,
+) -> Result<VoteRank, ServerFnError> {
+    use sqlx::Row;
+
+    let table = crate::db::target_table(target_type);
+    let kind = target_type.as_db();
+    let sql = if crate::db::is_sqlite() {
+        format!(
+            r#"
+            with scores as (
+                select t.id as id, coalesce(sum(v.value), 0) as score
+                from {table} t
+                left join votes v
+                    on v.target_type = '{kind}' and v.target_id = t.id
+                where t.hidden_at is null
+                group by t.id
+            )
+            select
+                (select count(*) from scores s2 where s2.score > s.score) + 1 as rank,
+                (select count(*) from scores) as total
+            from scores s
+            where s.id = $1
+            "#
+        )
+    } else {
+        format!(
+            r#"
+            select rank, total from (
+                select
+                    t.id as id,
+                    rank() over (order by coalesce(sum(v.value), 0) desc) as rank,
+                    count(*) over () as total
+                from {table} t
+                left join votes v
+                    on v.target_type = '{kind}' and v.target_id = t.id
+                where t.hidden_at is null
+                group by t.id
+            ) ranked
+            where ranked.id = $1::uuid
+            "#
+        )
+    };
+
+    let row = sqlx::query(&sql)
+        .bind(crate::db::uuid_to_db(target_id))
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .ok_or_else(|| ServerFnError::new(crate::types::ErrorCode::NotFound.to_string()))?;
+
+    Ok(VoteRank {
+        target_type,
+        target_id,
+        rank: row.get::<i64, _>("rank"),
+        total: row.get::<i64, _>("total"),
+    })
+}
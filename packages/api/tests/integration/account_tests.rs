@@ -0,0 +1,148 @@
+use api::test_utils::TestContext;
+use api::types::ContentTargetType;
+
+async fn create_user_with_token(ctx: &TestContext, email: &str) -> String {
+    api::signup(email.to_string(), "Password123".to_string(), None, None)
+        .await
+        .expect("Signup should succeed");
+
+    sqlx::query("UPDATE users SET email_verified = true WHERE email = $1")
+        .bind(email)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should verify user");
+
+    api::signin(email.to_string(), "Password123".to_string(), None)
+        .await
+        .expect("Signin should succeed")
+}
+
+#[tokio::test]
+async fn export_includes_every_category_of_the_users_data() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "exporter@test.com").await;
+    let other = create_user_with_token(&ctx, "other@test.com").await;
+
+    api::upsert_profile(
+        token.clone(),
+        "Exporter".into(),
+        "Bio".into(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Should save profile");
+    let mut proposal_ids = Vec::new();
+    for title in ["First idea", "Second idea"] {
+        let proposal = api::create_proposal(
+            token.clone(),
+            title.into(),
+            String::new(),
+            String::new(),
+            "tag".into(),
+        )
+        .await
+        .expect("Should create proposal");
+        proposal_ids.push(proposal.id.to_string());
+    }
+    api::create_program(token.clone(), "Plan".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    let theirs = api::create_proposal(
+        other.clone(),
+        "Their idea".into(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Should create proposal");
+    for body in ["One", "Two", "Three"] {
+        api::create_comment(
+            token.clone(),
+            ContentTargetType::Proposal,
+            theirs.id.to_string(),
+            None,
+            body.into(),
+        )
+        .await
+        .expect("Should comment");
+    }
+    api::create_comment(
+        other.clone(),
+        ContentTargetType::Proposal,
+        proposal_ids[0].clone(),
+        None,
+        "Not mine".into(),
+    )
+    .await
+    .expect("Should comment");
+    api::set_vote(
+        token.clone(),
+        ContentTargetType::Proposal,
+        theirs.id.to_string(),
+        1,
+    )
+    .await
+    .expect("Should vote");
+    api::toggle_bookmark(
+        token.clone(),
+        ContentTargetType::Proposal,
+        theirs.id.to_string(),
+    )
+    .await
+    .expect("Should bookmark");
+    api::set_vote(
+        other.clone(),
+        ContentTargetType::Proposal,
+        proposal_ids[1].clone(),
+        1,
+    )
+    .await
+    .expect("Should vote");
+
+    let export = api::export_account(token.clone())
+        .await
+        .expect("Should export account");
+    let me = api::auth_me(token).await.expect("Should fetch me");
+    assert_eq!(export.user.id, me.user.id);
+    assert_eq!(
+        export.profile.map(|p| p.display_name),
+        Some("Exporter".to_string())
+    );
+    assert_eq!(export.proposals.len(), 2);
+    assert!(export
+        .proposals
+        .iter()
+        .any(|p| p.title == "Second idea" && p.vote_score == 1));
+    assert_eq!(export.programs.len(), 1);
+    assert_eq!(export.comments.len(), 3);
+    assert!(export
+        .comments
+        .iter()
+        .all(|c| c.author_user_id == me.user.id));
+    assert_eq!(export.votes.len(), 1);
+    assert_eq!(export.votes[0].target_id, theirs.id);
+    assert_eq!(export.bookmarks.len(), 1);
+    assert_eq!(export.bookmarks[0].target_id, theirs.id);
+
+    let activity: i64 = sqlx::query_scalar("select count(*) from activity where user_id = $1")
+        .bind(me.user.id.to_string())
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count activity");
+    assert!(activity > 0);
+    assert_eq!(export.activity.len() as i64, activity);
+    assert!(export.activity.iter().all(|a| a.user_id == me.user.id));
+}
+
+#[tokio::test]
+async fn export_requires_sign_in() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    assert!(api::export_account("not-a-token".into()).await.is_err());
+}
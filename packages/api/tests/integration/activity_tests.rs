@@ -0,0 +1,291 @@
+use api::test_utils::TestContext;
+use api::types::{ActivityAction, ContentTargetType};
+
+async fn create_user_with_token(ctx: &TestContext, email: &str) -> (String, String) {
+    api::signup(email.to_string(), "Password123".to_string(), None, None)
+        .await
+        .expect("Signup should succeed");
+
+    sqlx::query("UPDATE users SET email_verified = true WHERE email = $1")
+        .bind(email)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should verify user");
+
+    let token = api::signin(email.to_string(), "Password123".to_string(), None)
+        .await
+        .expect("Signin should succeed");
+    let user_id: String = sqlx::query_scalar("select CAST(id as TEXT) from users where email = $1")
+        .bind(email)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    (token, user_id)
+}
+
+async fn backdate_activity(ctx: &TestContext, target_id: &str, action: &str, at: &str) {
+    sqlx::query("UPDATE activity SET created_at = $1 WHERE target_id = $2 AND action = $3")
+        .bind(at)
+        .bind(target_id)
+        .bind(action)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should backdate activity");
+}
+
+#[tokio::test]
+async fn global_activity_aggregates_users_newest_first() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (alice, alice_id) = create_user_with_token(&ctx, "alice@test.com").await;
+    let (bob, _) = create_user_with_token(&ctx, "bob@test.com").await;
+    sqlx::query("insert into profiles (user_id, display_name) values ($1, 'Alice')")
+        .bind(&alice_id)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should create profile");
+
+    let proposal = api::create_proposal(
+        alice.clone(),
+        "Alice's idea".into(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Should create proposal")
+    .id
+    .to_string();
+    let program = api::create_program(
+        bob.clone(),
+        "Bob's plan".into(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Should create program")
+    .id
+    .to_string();
+    api::create_comment(
+        bob.clone(),
+        ContentTargetType::Proposal,
+        proposal.clone(),
+        None,
+        "Nice".into(),
+    )
+    .await
+    .expect("Should comment");
+    api::set_vote(bob, ContentTargetType::Proposal, proposal.clone(), 1)
+        .await
+        .expect("Should vote");
+
+    backdate_activity(&ctx, &proposal, "created", "2024-01-01 10:00:00").await;
+    backdate_activity(&ctx, &program, "created", "2024-01-01 11:00:00").await;
+    backdate_activity(&ctx, &proposal, "commented", "2024-01-01 12:00:00").await;
+
+    let feed = api::list_global_activity(10, 0, None)
+        .await
+        .expect("Should list global activity");
+    let summary: Vec<(ActivityAction, Option<String>, Option<String>)> = feed
+        .into_iter()
+        .map(|a| (a.action, a.title, a.actor_display_name))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                ActivityAction::Commented,
+                Some("Alice's idea".to_string()),
+                None
+            ),
+            (
+                ActivityAction::Created,
+                Some("Bob's plan".to_string()),
+                None
+            ),
+            (
+                ActivityAction::Created,
+                Some("Alice's idea".to_string()),
+                Some("Alice".to_string())
+            ),
+        ],
+        "Votes stay private and items are ordered by time"
+    );
+
+    let page = api::list_global_activity(1, 1, None)
+        .await
+        .expect("Should page global activity");
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].title.as_deref(), Some("Bob's plan"));
+
+    sqlx::query("UPDATE proposals SET hidden_at = CURRENT_TIMESTAMP WHERE id = $1")
+        .bind(&proposal)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should hide proposal");
+    let feed = api::list_global_activity(10, 0, None)
+        .await
+        .expect("Should list global activity");
+    assert_eq!(feed.len(), 1, "Activity on hidden content is dropped");
+}
+
+async fn activity_count(ctx: &TestContext, user_id: &str, action: &str) -> i64 {
+    sqlx::query_scalar("select count(*) from activity where user_id = $1 and action = $2")
+        .bind(user_id)
+        .bind(action)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count activity")
+}
+
+#[tokio::test]
+async fn bookmark_toggle_records_and_removes_activity() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, user_id) = create_user_with_token(&ctx, "bookmarker@test.com").await;
+    let proposal = api::create_proposal(
+        token.clone(),
+        "Filmed".into(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Should create proposal")
+    .id
+    .to_string();
+    let video: String = sqlx::query_scalar(
+        r#"
+        insert into videos (owner_user_id, target_type, target_id, storage_bucket, storage_key, content_type)
+        values ($1, 'proposal', $2, 'local', 'videos/clip.mp4', 'video/mp4')
+        returning CAST(id as TEXT)
+        "#,
+    )
+    .bind(&user_id)
+    .bind(&proposal)
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Should insert video");
+
+    assert!(api::bookmark_video(token.clone(), video.clone())
+        .await
+        .expect("Should bookmark"));
+    assert_eq!(activity_count(&ctx, &user_id, "bookmarked").await, 1);
+    let mine = api::list_my_activity(token.clone(), 10, None)
+        .await
+        .expect("Should list my activity");
+    assert!(mine.iter().any(|a| a.action == ActivityAction::Bookmarked
+        && a.target_type == ContentTargetType::Video
+        && a.target_id.to_string() == video));
+
+    assert!(!api::bookmark_video(token.clone(), video.clone())
+        .await
+        .expect("Should un-bookmark"));
+    assert_eq!(activity_count(&ctx, &user_id, "bookmarked").await, 0);
+
+    api::bookmark_video(token, video)
+        .await
+        .expect("Should bookmark again");
+    assert_eq!(activity_count(&ctx, &user_id, "bookmarked").await, 1);
+}
+
+#[tokio::test]
+async fn add_program_item_records_bundled_once() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, user_id) = create_user_with_token(&ctx, "bundler@test.com").await;
+    let proposal = api::create_proposal(
+        token.clone(),
+        "Bundled".into(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Should create proposal")
+    .id
+    .to_string();
+    let program = api::create_program(token.clone(), "P".into(), String::new(), String::new())
+        .await
+        .expect("Should create program")
+        .id
+        .to_string();
+
+    api::add_program_item(token.clone(), program.clone(), proposal.clone(), 0)
+        .await
+        .expect("Should add item");
+    api::add_program_item(token.clone(), program, proposal, 3)
+        .await
+        .expect("Should reposition item");
+    assert_eq!(activity_count(&ctx, &user_id, "bundled").await, 1);
+
+    let mine = api::list_my_activity(token, 10, None)
+        .await
+        .expect("Should list my activity");
+    assert!(mine
+        .iter()
+        .any(|a| a.action == ActivityAction::Bundled && a.title.as_deref() == Some("Bundled")));
+}
+
+#[tokio::test]
+async fn my_activity_cursor_pages_without_drift() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, _) = create_user_with_token(&ctx, "pager@test.com").await;
+    let mut proposals = Vec::new();
+    for (title, at) in [
+        ("Oldest", "2025-01-01 10:00:00"),
+        ("Tied A", "2025-01-02 10:00:00"),
+        ("Tied B", "2025-01-02 10:00:00"),
+    ] {
+        let id = api::create_proposal(
+            token.clone(),
+            title.into(),
+            String::new(),
+            String::new(),
+            String::new(),
+        )
+        .await
+        .expect("Should create proposal")
+        .id
+        .to_string();
+        backdate_activity(&ctx, &id, "created", at).await;
+        proposals.push(id);
+    }
+
+    let first = api::list_my_activity(token.clone(), 2, None)
+        .await
+        .expect("Should list first page");
+    assert_eq!(first.len(), 2);
+
+    // New activity lands between page loads; an offset of 2 would now repeat a row.
+    api::create_proposal(
+        token.clone(),
+        "Newest".into(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Should create proposal");
+
+    let cursor = first.last().map(api::types::ActivityCursor::from);
+    let second = api::list_my_activity(token, 2, cursor)
+        .await
+        .expect("Should list second page");
+    assert_eq!(second.len(), 1);
+
+    let mut seen: Vec<String> = first
+        .iter()
+        .chain(&second)
+        .map(|a| a.target_id.to_string())
+        .collect();
+    assert_eq!(seen.last(), Some(&proposals[0]));
+    seen.sort();
+    proposals.sort();
+    assert_eq!(seen, proposals);
+}
@@ -1,4 +1,5 @@
 use api::test_utils::TestContext;
+use api::types::ErrorCode;
 use sqlx::Row;
 
 #[tokio::test]
@@ -7,7 +8,13 @@ async fn test_signup_creates_user() {
     ctx.set_global();
 
     // Call the signup function
-    let result = api::signup("newuser@test.com".to_string(), "Password123".to_string()).await;
+    let result = api::signup(
+        "newuser@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        None,
+    )
+    .await;
 
     assert!(result.is_ok(), "Signup should succeed");
 
@@ -32,7 +39,13 @@ async fn test_signup_rejects_weak_password() {
     let ctx = TestContext::new().await;
     ctx.set_global();
 
-    let result = api::signup("test@example.com".to_string(), "weak".to_string()).await;
+    let result = api::signup(
+        "test@example.com".to_string(),
+        "weak".to_string(),
+        None,
+        None,
+    )
+    .await;
 
     assert!(result.is_err(), "Should reject weak password");
     let error = result.unwrap_err().to_string();
@@ -48,12 +61,23 @@ async fn test_signup_rejects_duplicate_email() {
     ctx.set_global();
 
     // First signup should succeed
-    api::signup("duplicate@test.com".to_string(), "Password123".to_string())
-        .await
-        .expect("First signup should succeed");
+    api::signup(
+        "duplicate@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("First signup should succeed");
 
     // Second signup with same email should fail
-    let result = api::signup("duplicate@test.com".to_string(), "Password456".to_string()).await;
+    let result = api::signup(
+        "duplicate@test.com".to_string(),
+        "Password456".to_string(),
+        None,
+        None,
+    )
+    .await;
 
     assert!(result.is_err(), "Should reject duplicate email");
     let error = result.unwrap_err().to_string();
@@ -70,9 +94,14 @@ async fn test_signin_with_valid_credentials() {
     ctx.set_global();
 
     // Create user
-    api::signup("signin@test.com".to_string(), "Password123".to_string())
-        .await
-        .expect("Signup should succeed");
+    api::signup(
+        "signin@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Signup should succeed");
 
     // Verify email manually (bypass email verification for test)
     sqlx::query("UPDATE users SET email_verified = 1 WHERE email = $1")
@@ -82,9 +111,13 @@ async fn test_signin_with_valid_credentials() {
         .expect("Should update user");
 
     // Signin should succeed
-    let token = api::signin("signin@test.com".to_string(), "Password123".to_string())
-        .await
-        .expect("Signin should succeed");
+    let token = api::signin(
+        "signin@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+    )
+    .await
+    .expect("Signin should succeed");
 
     assert!(!token.is_empty(), "Should return JWT token");
 }
@@ -95,9 +128,14 @@ async fn test_signin_rejects_wrong_password() {
     ctx.set_global();
 
     // Create user
-    api::signup("wrongpass@test.com".to_string(), "Password123".to_string())
-        .await
-        .expect("Signup should succeed");
+    api::signup(
+        "wrongpass@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Signup should succeed");
 
     // Verify email
     sqlx::query("UPDATE users SET email_verified = 1 WHERE email = $1")
@@ -110,6 +148,7 @@ async fn test_signin_rejects_wrong_password() {
     let result = api::signin(
         "wrongpass@test.com".to_string(),
         "WrongPassword".to_string(),
+        None,
     )
     .await;
 
@@ -122,12 +161,22 @@ async fn test_signin_rejects_unverified_email() {
     ctx.set_global();
 
     // Create user (email not verified)
-    api::signup("unverified@test.com".to_string(), "Password123".to_string())
-        .await
-        .expect("Signup should succeed");
+    api::signup(
+        "unverified@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Signup should succeed");
 
     // Signin should fail for unverified email
-    let result = api::signin("unverified@test.com".to_string(), "Password123".to_string()).await;
+    let result = api::signin(
+        "unverified@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+    )
+    .await;
 
     assert!(result.is_err(), "Should reject unverified email");
     let error = result.unwrap_err().to_string();
@@ -136,3 +185,450 @@ async fn test_signin_rejects_unverified_email() {
         "Error should mention email verification"
     );
 }
+
+#[tokio::test]
+async fn test_verify_email_rejects_token_past_configured_ttl() {
+    let ctx = TestContext::with_config(|c| c.verify_token_ttl_hours = 0).await;
+    ctx.set_global();
+
+    api::signup(
+        "ttl-verify@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Signup should succeed");
+
+    // The raw token only goes out by email; swap in one we know.
+    let token = api::email::generate_token();
+    sqlx::query("update email_verifications set token_hash = $1")
+        .bind(api::email::hash_token(&token))
+        .execute(&ctx.pool)
+        .await
+        .expect("Should replace token hash");
+
+    let err = api::verify_email(token, None)
+        .await
+        .expect_err("Token should already be expired");
+    assert!(err.to_string().contains("has expired"));
+    assert_eq!(
+        ErrorCode::from_error(&err.to_string()),
+        Some(ErrorCode::VerificationExpired)
+    );
+}
+
+#[tokio::test]
+async fn test_reset_password_rejects_token_past_configured_ttl() {
+    let ctx = TestContext::with_config(|c| c.reset_token_ttl_minutes = 0).await;
+    ctx.set_global();
+
+    api::signup(
+        "ttl-reset@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Signup should succeed");
+    api::request_password_reset("ttl-reset@test.com".to_string(), None, None)
+        .await
+        .expect("Reset request should succeed");
+
+    let token = api::email::generate_token();
+    sqlx::query("update password_resets set token_hash = $1")
+        .bind(api::email::hash_token(&token))
+        .execute(&ctx.pool)
+        .await
+        .expect("Should replace token hash");
+
+    let err = api::reset_password(token, "NewPassword123".to_string(), None)
+        .await
+        .expect_err("Token should already be expired");
+    assert!(err.to_string().contains("has expired"));
+    assert_eq!(
+        ErrorCode::from_error(&err.to_string()),
+        Some(ErrorCode::ResetExpired)
+    );
+}
+
+#[tokio::test]
+async fn test_verification_expiry_uses_configured_ttl() {
+    let ctx = TestContext::with_config(|c| c.verify_token_ttl_hours = 2).await;
+    ctx.set_global();
+
+    api::signup(
+        "ttl-window@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Signup should succeed");
+
+    let expires_at: String =
+        sqlx::query_scalar("select CAST(expires_at as TEXT) from email_verifications")
+            .fetch_one(&ctx.pool)
+            .await
+            .expect("Should fetch expiry");
+    let expires_at = api::db::datetime_from_db(&expires_at).expect("Should parse expiry");
+    let remaining = expires_at - time::OffsetDateTime::now_utc();
+    assert!(remaining > time::Duration::minutes(119));
+    assert!(remaining <= time::Duration::hours(2));
+}
+
+#[tokio::test]
+async fn test_reset_password_revokes_existing_sessions() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    api::signup(
+        "revoke@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Signup should succeed");
+    sqlx::query("UPDATE users SET email_verified = true WHERE email = $1")
+        .bind("revoke@test.com")
+        .execute(&ctx.pool)
+        .await
+        .expect("Should verify user");
+
+    let old_token = api::signin(
+        "revoke@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+    )
+    .await
+    .expect("Signin should succeed");
+    api::list_my_activity(old_token.clone(), 1, None)
+        .await
+        .expect("Fresh token should authenticate");
+
+    api::request_password_reset("revoke@test.com".to_string(), None, None)
+        .await
+        .expect("Reset request should succeed");
+    let reset_token = api::email::generate_token();
+    sqlx::query("update password_resets set token_hash = $1")
+        .bind(api::email::hash_token(&reset_token))
+        .execute(&ctx.pool)
+        .await
+        .expect("Should replace token hash");
+    api::reset_password(reset_token, "NewPassword123".to_string(), None)
+        .await
+        .expect("Reset should succeed");
+
+    let err = api::list_my_activity(old_token, 1, None)
+        .await
+        .expect_err("Old token should be revoked");
+    assert!(err.to_string().contains("token revoked"));
+
+    let new_token = api::signin(
+        "revoke@test.com".to_string(),
+        "NewPassword123".to_string(),
+        None,
+    )
+    .await
+    .expect("Signin with new password should succeed");
+    api::list_my_activity(new_token, 1, None)
+        .await
+        .expect("New token should authenticate");
+}
+
+#[tokio::test]
+async fn test_resend_verification_respects_cooldown() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    api::signup(
+        "cooldown@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Signup should succeed");
+
+    // Signup just sent one; an immediate resend is a silent no-op.
+    api::resend_verification_email("cooldown@test.com".to_string())
+        .await
+        .expect("Resend should still report success");
+    let count: i64 = sqlx::query_scalar("select count(*) from email_verifications")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count tokens");
+    assert_eq!(count, 1);
+
+    sqlx::query("update email_verifications set created_at = '2000-01-01 00:00:00'")
+        .execute(&ctx.pool)
+        .await
+        .expect("Should backdate token");
+    api::resend_verification_email("cooldown@test.com".to_string())
+        .await
+        .expect("Resend should succeed");
+    let count: i64 = sqlx::query_scalar("select count(*) from email_verifications")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count tokens");
+    assert_eq!(count, 2);
+}
+
+#[tokio::test]
+async fn test_password_reset_respects_cooldown() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    api::signup(
+        "reset-cooldown@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Signup should succeed");
+
+    for _ in 0..2 {
+        api::request_password_reset("reset-cooldown@test.com".to_string(), None, None)
+            .await
+            .expect("Reset request should report success");
+    }
+    let count: i64 = sqlx::query_scalar("select count(*) from password_resets")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count tokens");
+    assert_eq!(count, 1);
+
+    sqlx::query("update password_resets set created_at = '2000-01-01 00:00:00'")
+        .execute(&ctx.pool)
+        .await
+        .expect("Should backdate token");
+    api::request_password_reset("reset-cooldown@test.com".to_string(), None, None)
+        .await
+        .expect("Reset request should succeed");
+    let count: i64 = sqlx::query_scalar("select count(*) from password_resets")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count tokens");
+    assert_eq!(count, 2);
+}
+
+#[tokio::test]
+async fn test_signup_and_password_reset_are_rate_limited() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    for _ in 0..5 {
+        // Only the first succeeds; the rest still count as attempts.
+        let _ = api::signup(
+            "limited@test.com".to_string(),
+            "Password123".to_string(),
+            None,
+            None,
+        )
+        .await;
+    }
+    let err = api::signup(
+        " Limited@Test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect_err("Sixth signup should be throttled")
+    .to_string();
+    assert_eq!(
+        ErrorCode::from_error(&err),
+        Some(ErrorCode::TooManyAttempts)
+    );
+
+    let mut refusals = Vec::new();
+    for email in ["limited@test.com", "unknown@test.com"] {
+        for _ in 0..3 {
+            api::request_password_reset(email.to_string(), None, None)
+                .await
+                .expect("Reset request should report success");
+        }
+        let err = api::request_password_reset(email.to_string(), None, None)
+            .await
+            .expect_err("Fourth reset request should be throttled")
+            .to_string();
+        assert_eq!(
+            ErrorCode::from_error(&err),
+            Some(ErrorCode::TooManyAttempts)
+        );
+        refusals.push(err);
+    }
+    // Throttling must not reveal which addresses are registered.
+    assert_eq!(refusals[0], refusals[1]);
+
+    // The refusal is localized like signup's.
+    let err = api::request_password_reset("limited@test.com".to_string(), Some("fr".into()), None)
+        .await
+        .expect_err("Still throttled")
+        .to_string();
+    assert!(
+        err.contains(ErrorCode::TooManyAttempts.message(Some("fr"))),
+        "{err}"
+    );
+}
+
+/// Every auth validation failure for `lang`, as `(decoded code, raw message)`.
+async fn auth_failures(lang: Option<&str>) -> Vec<(Option<ErrorCode>, String)> {
+    let lang = lang.map(str::to_string);
+    let signup = |email: &str, password: &str| {
+        api::signup(email.to_string(), password.to_string(), lang.clone(), None)
+    };
+    let results = vec![
+        signup("not-an-email", "Password123").await,
+        signup("short@test.com", "Pass1").await,
+        signup("upper@test.com", "password1").await,
+        signup("lower@test.com", "PASSWORD1").await,
+        signup("number@test.com", "Password").await,
+        signup("taken@test.com", "Password123").await,
+        api::signin("nobody@test.com".into(), "Password123".into(), lang.clone())
+            .await
+            .map(|_| ()),
+        api::signin("taken@test.com".into(), "Wrong123".into(), lang.clone())
+            .await
+            .map(|_| ()),
+        api::signin("taken@test.com".into(), "Password123".into(), lang.clone())
+            .await
+            .map(|_| ()),
+        api::verify_email("bogus".into(), lang.clone()).await,
+        api::reset_password("bogus".into(), "Password123".into(), lang.clone()).await,
+        api::reset_password("bogus".into(), "short".into(), lang.clone()).await,
+    ];
+    results
+        .into_iter()
+        .map(|r| {
+            let message = r.expect_err("Should be rejected").to_string();
+            (ErrorCode::from_error(&message), message)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_auth_error_codes_are_stable_across_languages() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    // Registered but unverified.
+    api::signup(
+        "taken@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Signup should succeed");
+
+    let expected = vec![
+        Some(ErrorCode::InvalidEmail),
+        Some(ErrorCode::PasswordTooShort),
+        Some(ErrorCode::PasswordNoUppercase),
+        Some(ErrorCode::PasswordNoLowercase),
+        Some(ErrorCode::PasswordNoNumber),
+        Some(ErrorCode::EmailTaken),
+        Some(ErrorCode::InvalidCredentials),
+        Some(ErrorCode::InvalidCredentials),
+        Some(ErrorCode::EmailNotVerified),
+        Some(ErrorCode::VerificationInvalid),
+        Some(ErrorCode::ResetInvalid),
+        Some(ErrorCode::PasswordTooShort),
+    ];
+
+    let english = auth_failures(None).await;
+    let french = auth_failures(Some("fr")).await;
+    let browser = auth_failures(Some("fr-FR,fr;q=0.9,en;q=0.8")).await;
+    for failures in [&english, &french, &browser] {
+        let codes: Vec<_> = failures.iter().map(|(code, _)| *code).collect();
+        assert_eq!(codes, expected);
+    }
+
+    for ((code, en), (_, fr)) in english.iter().zip(&french) {
+        let code = code.expect("coded");
+        assert!(en.contains(code.message(None)), "{en}");
+        assert!(fr.contains(code.message(Some("fr"))), "{fr}");
+        assert_ne!(en, fr);
+    }
+    assert_eq!(
+        french.iter().map(|(_, m)| m).collect::<Vec<_>>(),
+        browser.iter().map(|(_, m)| m).collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn test_captcha_is_required_when_configured() {
+    let ctx =
+        TestContext::with_config(|c| c.captcha_secret = Some("captcha-secret".to_string())).await;
+    ctx.set_global();
+
+    let signup = |email: &str, token: Option<&str>| {
+        api::signup(
+            email.to_string(),
+            "Password123".to_string(),
+            Some("fr".to_string()),
+            token.map(str::to_string),
+        )
+    };
+    for token in [None, Some(""), Some("forged")] {
+        let err = signup("bot@test.com", token)
+            .await
+            .expect_err("Should reject a missing or refused token")
+            .to_string();
+        assert_eq!(ErrorCode::from_error(&err), Some(ErrorCode::CaptchaFailed));
+        assert!(
+            err.contains(ErrorCode::CaptchaFailed.message(Some("fr"))),
+            "{err}"
+        );
+    }
+    let users: i64 = sqlx::query_scalar("select count(*) from users")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count users");
+    assert_eq!(users, 0);
+
+    signup("human@test.com", Some(api::test_utils::TEST_CAPTCHA_TOKEN))
+        .await
+        .expect("Signup with a valid token should succeed");
+
+    let err =
+        api::request_password_reset("human@test.com".to_string(), None, Some("forged".into()))
+            .await
+            .expect_err("Should reject a refused token")
+            .to_string();
+    assert_eq!(ErrorCode::from_error(&err), Some(ErrorCode::CaptchaFailed));
+    api::request_password_reset(
+        "human@test.com".to_string(),
+        None,
+        Some(api::test_utils::TEST_CAPTCHA_TOKEN.to_string()),
+    )
+    .await
+    .expect("Reset request with a valid token should succeed");
+    let resets: i64 = sqlx::query_scalar("select count(*) from password_resets")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count tokens");
+    assert_eq!(resets, 1);
+}
+
+#[tokio::test]
+async fn test_captcha_is_skipped_when_unconfigured() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+    assert!(ctx.state.captcha.is_none());
+
+    api::signup(
+        "nocaptcha@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        Some("anything".to_string()),
+    )
+    .await
+    .expect("Signup should ignore the token");
+    api::request_password_reset("nocaptcha@test.com".to_string(), None, None)
+        .await
+        .expect("Reset request should not need a token");
+}
@@ -0,0 +1,89 @@
+use api::test_utils::TestContext;
+
+async fn create_user_with_token(ctx: &TestContext, email: &str) -> (String, String) {
+    api::signup(email.to_string(), "Password123".to_string(), None, None)
+        .await
+        .expect("Signup should succeed");
+
+    sqlx::query("UPDATE users SET email_verified = true WHERE email = $1")
+        .bind(email)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should verify user");
+
+    let token = api::signin(email.to_string(), "Password123".to_string(), None)
+        .await
+        .expect("Signin should succeed");
+    let user_id: String = sqlx::query_scalar("select id from users where email = $1")
+        .bind(email)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    (token, user_id)
+}
+
+async fn create_admin_with_token(ctx: &TestContext, email: &str) -> (String, String) {
+    let (token, user_id) = create_user_with_token(ctx, email).await;
+    sqlx::query("UPDATE users SET is_admin = true WHERE id = $1")
+        .bind(&user_id)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should promote user");
+    (token, user_id)
+}
+
+#[tokio::test]
+async fn create_post_then_get_round_trip() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (admin, admin_id) = create_admin_with_token(&ctx, "editor@test.com").await;
+    let created = api::create_post(admin, "  Launch notes ".into(), "# Hello".into())
+        .await
+        .expect("Admin should publish");
+    assert_eq!(created.title, "Launch notes");
+    assert_eq!(created.author_user_id.to_string(), admin_id);
+
+    let fetched = api::get_post(created.id).await.expect("Should get post");
+    assert_eq!(fetched, created);
+
+    let missing = api::get_post(created.id + 1)
+        .await
+        .expect_err("Unknown id should fail");
+    assert!(missing.to_string().contains("not found"));
+}
+
+#[tokio::test]
+async fn list_posts_returns_newest_first_and_pages() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (admin, _) = create_admin_with_token(&ctx, "editor@test.com").await;
+    let mut ids = Vec::new();
+    for title in ["First", "Second", "Third"] {
+        let post = api::create_post(admin.clone(), title.into(), String::new())
+            .await
+            .expect("Admin should publish");
+        ids.push(post.id);
+    }
+
+    let listed = api::list_posts(10, 0).await.expect("Should list posts");
+    let titles: Vec<_> = listed.iter().map(|p| p.title.as_str()).collect();
+    assert_eq!(titles, vec!["Third", "Second", "First"]);
+
+    let page = api::list_posts(1, 1).await.expect("Should list page");
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].id, ids[1]);
+}
+
+#[tokio::test]
+async fn create_post_is_admin_only() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, _) = create_user_with_token(&ctx, "reader@test.com").await;
+    api::create_post(token, "Sneaky".into(), String::new())
+        .await
+        .expect_err("Non-admin should be rejected");
+    assert!(api::list_posts(10, 0).await.unwrap().is_empty());
+}
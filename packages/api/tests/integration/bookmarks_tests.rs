@@ -0,0 +1,135 @@
+use api::test_utils::TestContext;
+use api::types::ContentTargetType;
+
+async fn create_user_with_token(ctx: &TestContext, email: &str) -> (String, String) {
+    api::signup(email.to_string(), "Password123".to_string(), None, None)
+        .await
+        .expect("Signup should succeed");
+
+    sqlx::query("UPDATE users SET email_verified = true WHERE email = $1")
+        .bind(email)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should verify user");
+
+    let token = api::signin(email.to_string(), "Password123".to_string(), None)
+        .await
+        .expect("Signin should succeed");
+    let user_id: String = sqlx::query_scalar("select CAST(id as TEXT) from users where email = $1")
+        .bind(email)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    (token, user_id)
+}
+
+async fn create_proposal(token: &str, title: &str) -> String {
+    api::create_proposal(
+        token.to_string(),
+        title.to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Should create proposal")
+    .id
+    .to_string()
+}
+
+async fn toggle(token: &str, target_type: ContentTargetType, target_id: &str) -> bool {
+    api::toggle_bookmark(token.to_string(), target_type, target_id.to_string())
+        .await
+        .expect("Should toggle bookmark")
+}
+
+#[tokio::test]
+async fn toggle_bookmark_on_a_proposal_round_trips() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, _) = create_user_with_token(&ctx, "saver@test.com").await;
+    let proposal = create_proposal(&token, "Worth keeping").await;
+
+    assert!(toggle(&token, ContentTargetType::Proposal, &proposal).await);
+    let saved = api::list_bookmarks(token.clone(), None, 10, 0)
+        .await
+        .expect("Should list bookmarks");
+    assert_eq!(saved.len(), 1);
+    assert_eq!(saved[0].target_type, ContentTargetType::Proposal);
+    assert_eq!(saved[0].target_id.to_string(), proposal);
+    assert_eq!(saved[0].title.as_deref(), Some("Worth keeping"));
+
+    assert!(!toggle(&token, ContentTargetType::Proposal, &proposal).await);
+    let saved = api::list_bookmarks(token, None, 10, 0)
+        .await
+        .expect("Should list bookmarks");
+    assert!(saved.is_empty());
+}
+
+#[tokio::test]
+async fn list_bookmarks_filters_by_type() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, user_id) = create_user_with_token(&ctx, "collector@test.com").await;
+    let (other, _) = create_user_with_token(&ctx, "bystander@test.com").await;
+    let first = create_proposal(&token, "First").await;
+    let second = create_proposal(&token, "Second").await;
+    let video: String = sqlx::query_scalar(
+        r#"
+        insert into videos (owner_user_id, target_type, target_id, storage_bucket, storage_key, content_type)
+        values ($1, 'proposal', $2, 'local', 'videos/clip.mp4', 'video/mp4')
+        returning CAST(id as TEXT)
+        "#,
+    )
+    .bind(&user_id)
+    .bind(&first)
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Should insert video");
+
+    toggle(&token, ContentTargetType::Proposal, &first).await;
+    assert!(api::bookmark_video(token.clone(), video.clone())
+        .await
+        .expect("Should bookmark video"));
+    toggle(&token, ContentTargetType::Proposal, &second).await;
+    toggle(&other, ContentTargetType::Proposal, &first).await;
+
+    let proposals = api::list_bookmarks(token.clone(), Some(ContentTargetType::Proposal), 10, 0)
+        .await
+        .expect("Should list proposal bookmarks");
+    let ids: Vec<_> = proposals.iter().map(|b| b.target_id.to_string()).collect();
+    assert_eq!(ids, vec![second, first], "Newest first, caller's only");
+
+    let videos = api::list_bookmarks(token.clone(), Some(ContentTargetType::Video), 10, 0)
+        .await
+        .expect("Should list video bookmarks");
+    assert_eq!(videos.len(), 1);
+    assert_eq!(videos[0].target_id.to_string(), video);
+    let listed = api::list_bookmarked_videos(token.clone(), 10, 0)
+        .await
+        .expect("Should list bookmarked videos");
+    assert_eq!(listed.len(), 1, "Video bookmarks share the same table");
+
+    let all = api::list_bookmarks(token, None, 10, 0)
+        .await
+        .expect("Should list bookmarks");
+    assert_eq!(all.len(), 3);
+}
+
+#[tokio::test]
+async fn toggle_bookmark_rejects_missing_target() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, _) = create_user_with_token(&ctx, "ghost@test.com").await;
+    let err = api::toggle_bookmark(
+        token,
+        ContentTargetType::Program,
+        uuid::Uuid::new_v4().to_string(),
+    )
+    .await
+    .expect_err("Unknown target should fail");
+    assert!(err.to_string().contains("target not found"));
+}
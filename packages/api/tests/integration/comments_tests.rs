@@ -0,0 +1,1069 @@
+use api::test_utils::TestContext;
+use api::types::ContentTargetType;
+
+async fn create_user_with_token(ctx: &TestContext, email: &str) -> (String, String) {
+    api::signup(email.to_string(), "Password123".to_string(), None, None)
+        .await
+        .expect("Signup should succeed");
+
+    sqlx::query("UPDATE users SET email_verified = true WHERE email = $1")
+        .bind(email)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should verify user");
+
+    let token = api::signin(email.to_string(), "Password123".to_string(), None)
+        .await
+        .expect("Signin should succeed");
+    let user_id: String = sqlx::query_scalar("select id from users where email = $1")
+        .bind(email)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    (token, user_id)
+}
+
+async fn create_proposal(ctx: &TestContext, author_user_id: &str) -> String {
+    sqlx::query_scalar(
+        "insert into proposals (author_user_id, title, summary, body_markdown, tags) values ($1, 'T', '', '', '[]') returning id",
+    )
+    .bind(author_user_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Should create proposal")
+}
+
+async fn comment(
+    token: &str,
+    proposal_id: &str,
+    body: &str,
+) -> Result<api::types::Comment, String> {
+    api::create_comment(
+        token.to_string(),
+        ContentTargetType::Proposal,
+        proposal_id.to_string(),
+        None,
+        body.to_string(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tokio::test]
+async fn comment_rate_limit_rejects_burst() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, user_id) = create_user_with_token(&ctx, "chatty@test.com").await;
+    let proposal_id = create_proposal(&ctx, &user_id).await;
+
+    for i in 0..5 {
+        comment(&token, &proposal_id, &format!("comment {i}"))
+            .await
+            .expect("Comments within the limit should succeed");
+    }
+
+    let error = comment(&token, &proposal_id, "one too many")
+        .await
+        .expect_err("Comment over the limit should be rejected");
+    assert!(error.contains("commenting too fast"));
+
+    // Simulate the window elapsing.
+    ctx.state.rate_limiter.reset(&format!("comment:{user_id}"));
+    comment(&token, &proposal_id, "after the window")
+        .await
+        .expect("Comment should succeed once the window resets");
+}
+
+#[tokio::test]
+async fn comment_validation_rejects_bad_input() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, user_id) = create_user_with_token(&ctx, "validator@test.com").await;
+    let proposal_id = create_proposal(&ctx, &user_id).await;
+
+    let error = comment(&token, &proposal_id, "   \n  ")
+        .await
+        .expect_err("Whitespace-only comment should be rejected");
+    assert!(error.contains("empty"));
+
+    let too_long = "a".repeat(5001);
+    let error = comment(&token, &proposal_id, &too_long)
+        .await
+        .expect_err("Overlong comment should be rejected");
+    assert!(error.contains("exceeds"));
+
+    let missing = "00000000-0000-0000-0000-000000000000";
+    let error = comment(&token, missing, "hello")
+        .await
+        .expect_err("Comment on a missing target should be rejected");
+    assert!(error.contains("target not found"));
+
+    let created = comment(&token, &proposal_id, "  trimmed  ")
+        .await
+        .expect("Valid comment should succeed");
+    assert_eq!(created.body_markdown, "trimmed");
+}
+
+#[tokio::test]
+async fn comment_notifies_target_author_only() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (author_token, author_id) = create_user_with_token(&ctx, "author-a@test.com").await;
+    let (commenter_token, _) = create_user_with_token(&ctx, "commenter-b@test.com").await;
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+
+    let created = comment(&commenter_token, &proposal_id, "Nice idea")
+        .await
+        .expect("Comment should succeed");
+
+    let author_notes = api::list_notifications(author_token.clone(), 20, None)
+        .await
+        .expect("Should list author notifications");
+    assert_eq!(author_notes.len(), 1);
+    assert_eq!(author_notes[0].kind, api::types::NotificationKind::Comment);
+    assert_eq!(author_notes[0].comment_id, Some(created.id));
+    assert!(author_notes[0].read_at.is_none());
+
+    let commenter_notes = api::list_notifications(commenter_token, 20, None)
+        .await
+        .expect("Should list commenter notifications");
+    assert!(commenter_notes.is_empty());
+
+    // Self-comments don't notify.
+    comment(&author_token, &proposal_id, "Thanks")
+        .await
+        .expect("Author comment should succeed");
+    let author_notes = api::list_notifications(author_token.clone(), 20, None)
+        .await
+        .expect("Should list author notifications");
+    assert_eq!(author_notes.len(), 1);
+
+    api::mark_notification_read(author_token.clone(), author_notes[0].id.to_string())
+        .await
+        .expect("Should mark read");
+    let author_notes = api::list_notifications(author_token, 20, None)
+        .await
+        .expect("Should list author notifications");
+    assert!(author_notes[0].read_at.is_some());
+}
+
+#[tokio::test]
+async fn unread_notification_count_tracks_reads() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (author_token, author_id) = create_user_with_token(&ctx, "badge-a@test.com").await;
+    let (commenter_token, _) = create_user_with_token(&ctx, "badge-b@test.com").await;
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+
+    comment(&commenter_token, &proposal_id, "first")
+        .await
+        .expect("Comment should succeed");
+    comment(&commenter_token, &proposal_id, "second")
+        .await
+        .expect("Comment should succeed");
+
+    let count = api::unread_notification_count(author_token.clone())
+        .await
+        .expect("Should count unread");
+    assert_eq!(count, 2);
+
+    let notes = api::list_notifications(author_token.clone(), 20, None)
+        .await
+        .expect("Should list notifications");
+    api::mark_notification_read(author_token.clone(), notes[0].id.to_string())
+        .await
+        .expect("Should mark read");
+
+    let count = api::unread_notification_count(author_token)
+        .await
+        .expect("Should count unread");
+    assert_eq!(count, 1);
+}
+
+#[tokio::test]
+async fn marking_notifications_read_is_blocked_in_maintenance() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (author_token, author_id) = create_user_with_token(&ctx, "maint-a@test.com").await;
+    let (commenter_token, _) = create_user_with_token(&ctx, "maint-b@test.com").await;
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+    comment(&commenter_token, &proposal_id, "while it lasts")
+        .await
+        .expect("Comment should succeed");
+    let notes = api::list_notifications(author_token.clone(), 20, None)
+        .await
+        .expect("Should list notifications");
+
+    ctx.state.set_maintenance_mode(true);
+    let error = api::mark_notification_read(author_token.clone(), notes[0].id.to_string())
+        .await
+        .expect_err("Marking read should be blocked")
+        .to_string();
+    assert!(error.contains("read-only mode"));
+    let error = api::mark_all_notifications_read(author_token.clone())
+        .await
+        .expect_err("Marking all read should be blocked")
+        .to_string();
+    assert!(error.contains("read-only mode"));
+    let count = api::unread_notification_count(author_token)
+        .await
+        .expect("Reads should keep working");
+    assert_eq!(count, 1);
+}
+
+#[tokio::test]
+async fn notification_kind_filter_narrows_results() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (alice, alice_id) = create_user_with_token(&ctx, "kinds-a@test.com").await;
+    let (bob, bob_id) = create_user_with_token(&ctx, "kinds-b@test.com").await;
+    let alice_proposal = create_proposal(&ctx, &alice_id).await;
+    let bob_proposal = create_proposal(&ctx, &bob_id).await;
+
+    // Bob comments on alice's proposal; bob replies to alice on his own.
+    comment(&bob, &alice_proposal, "comment").await.unwrap();
+    let parent = comment(&alice, &bob_proposal, "question").await.unwrap();
+    api::create_comment(
+        bob.clone(),
+        ContentTargetType::Proposal,
+        bob_proposal,
+        Some(parent.id.to_string()),
+        "answer".to_string(),
+    )
+    .await
+    .expect("Reply should succeed");
+
+    use api::types::NotificationKind;
+    let all = api::list_notifications(alice.clone(), 20, None)
+        .await
+        .expect("Should list notifications");
+    assert_eq!(all.len(), 2);
+
+    let comments = api::list_notifications(alice.clone(), 20, Some(NotificationKind::Comment))
+        .await
+        .expect("Should list comment notifications");
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].kind, NotificationKind::Comment);
+
+    let replies = api::list_notifications(alice.clone(), 20, Some(NotificationKind::Reply))
+        .await
+        .expect("Should list reply notifications");
+    assert_eq!(replies.len(), 1);
+    assert_eq!(replies[0].kind, NotificationKind::Reply);
+
+    let hidden = api::list_notifications(alice, 20, Some(NotificationKind::AutoHidden))
+        .await
+        .expect("Should list auto-hidden notifications");
+    assert!(hidden.is_empty());
+}
+
+#[tokio::test]
+async fn mark_all_notifications_read_only_touches_caller() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (alice, alice_id) = create_user_with_token(&ctx, "markall-a@test.com").await;
+    let (bob, bob_id) = create_user_with_token(&ctx, "markall-b@test.com").await;
+    let alice_proposal = create_proposal(&ctx, &alice_id).await;
+    let bob_proposal = create_proposal(&ctx, &bob_id).await;
+
+    comment(&bob, &alice_proposal, "first").await.unwrap();
+    comment(&bob, &alice_proposal, "second").await.unwrap();
+    comment(&alice, &bob_proposal, "back at you").await.unwrap();
+
+    let marked = api::mark_all_notifications_read(alice.clone())
+        .await
+        .expect("Should mark all read");
+    assert_eq!(marked, 2);
+    assert_eq!(
+        api::unread_notification_count(alice.clone()).await.unwrap(),
+        0
+    );
+    assert_eq!(api::unread_notification_count(bob).await.unwrap(), 1);
+
+    // Nothing left to mark.
+    let marked = api::mark_all_notifications_read(alice)
+        .await
+        .expect("Should mark all read");
+    assert_eq!(marked, 0);
+}
+
+#[tokio::test]
+async fn create_comment_returns_full_comment() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, user_id) = create_user_with_token(&ctx, "returned@test.com").await;
+    sqlx::query("insert into profiles (user_id, display_name) values ($1, 'Marianne')")
+        .bind(&user_id)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should create profile");
+    let proposal_id = create_proposal(&ctx, &user_id).await;
+
+    let created = comment(&token, &proposal_id, "  Bien vu  ")
+        .await
+        .expect("Should create comment");
+    assert_eq!(created.body_markdown, "Bien vu");
+    assert_eq!(created.author_user_id.to_string(), user_id);
+    assert_eq!(created.author_display_name.as_deref(), Some("Marianne"));
+    assert_eq!(created.target_type, ContentTargetType::Proposal);
+    assert_eq!(created.target_id.to_string(), proposal_id);
+    assert_eq!(created.parent_comment_id, None);
+    assert_eq!(created.vote_score, 0);
+
+    let listed = api::list_comments(ContentTargetType::Proposal, proposal_id, 10, None)
+        .await
+        .expect("Should list comments");
+    assert_eq!(listed, vec![created]);
+}
+
+#[tokio::test]
+async fn comments_by_author_filters_and_resolves_titles() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (alice, alice_id) = create_user_with_token(&ctx, "alice-history@test.com").await;
+    let (bob, bob_id) = create_user_with_token(&ctx, "bob-history@test.com").await;
+    let proposal_id = create_proposal(&ctx, &bob_id).await;
+
+    let first = comment(&alice, &proposal_id, "alice first").await.unwrap();
+    comment(&bob, &proposal_id, "bob reply").await.unwrap();
+    let hidden = comment(&alice, &proposal_id, "alice hidden").await.unwrap();
+    sqlx::query("update comments set hidden_at = CURRENT_TIMESTAMP where id = $1")
+        .bind(hidden.id.to_string())
+        .execute(&ctx.pool)
+        .await
+        .expect("Should hide comment");
+
+    let history = api::list_comments_by_author(alice_id, 10, 0, None)
+        .await
+        .expect("Should list author comments");
+    assert_eq!(history.len(), 1, "Only alice's visible comments");
+    assert_eq!(history[0].comment.id, first.id);
+    assert_eq!(history[0].comment.target_type, ContentTargetType::Proposal);
+    assert_eq!(history[0].comment.target_id.to_string(), proposal_id);
+    assert_eq!(history[0].target_title.as_deref(), Some("T"));
+
+    let bob_history = api::list_comments_by_author(bob_id, 10, 0, None)
+        .await
+        .expect("Should list author comments");
+    assert_eq!(bob_history.len(), 1);
+    assert_eq!(bob_history[0].comment.body_markdown, "bob reply");
+}
+
+#[tokio::test]
+async fn get_comment_returns_one_comment_with_context() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (alice, alice_id) = create_user_with_token(&ctx, "alice-get@test.com").await;
+    let (bob, _) = create_user_with_token(&ctx, "bob-get@test.com").await;
+    set_display_name(&ctx, &alice_id, "Alice").await;
+    let proposal_id = create_proposal(&ctx, &alice_id).await;
+    let parent = comment(&bob, &proposal_id, "parent").await.unwrap();
+    let reply = api::create_comment(
+        alice.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        Some(parent.id.to_string()),
+        "a reply".to_string(),
+    )
+    .await
+    .expect("Should reply");
+    api::set_vote(bob, ContentTargetType::Comment, reply.id.to_string(), 1)
+        .await
+        .expect("Should vote");
+
+    let item = api::get_comment(reply.id.to_string(), None)
+        .await
+        .expect("Should fetch comment");
+    assert_eq!(item.comment.id, reply.id);
+    assert_eq!(item.comment.body_markdown, "a reply");
+    assert_eq!(item.comment.author_display_name.as_deref(), Some("Alice"));
+    assert_eq!(item.comment.vote_score, 1);
+    assert_eq!(item.comment.target_type, ContentTargetType::Proposal);
+    assert_eq!(item.comment.target_id.to_string(), proposal_id);
+    assert_eq!(item.comment.parent_comment_id, Some(parent.id));
+    assert_eq!(item.target_title.as_deref(), Some("T"));
+
+    let missing = api::get_comment(uuid::Uuid::new_v4().to_string(), None)
+        .await
+        .expect_err("Unknown comment should be rejected")
+        .to_string();
+    assert_eq!(
+        api::types::ErrorCode::from_error(&missing),
+        Some(api::types::ErrorCode::NotFound)
+    );
+    sqlx::query("update comments set hidden_at = CURRENT_TIMESTAMP where id = $1")
+        .bind(parent.id.to_string())
+        .execute(&ctx.pool)
+        .await
+        .expect("Should hide comment");
+    assert!(api::get_comment(parent.id.to_string(), None).await.is_err());
+}
+
+#[tokio::test]
+async fn top_comments_orders_by_score_and_caps_limit() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (alice, alice_id) = create_user_with_token(&ctx, "alice-top@test.com").await;
+    let (bob, _) = create_user_with_token(&ctx, "bob-top@test.com").await;
+    let proposal_id = create_proposal(&ctx, &alice_id).await;
+
+    let quiet = comment(&alice, &proposal_id, "quiet").await.unwrap();
+    let popular = comment(&alice, &proposal_id, "popular").await.unwrap();
+    let liked = comment(&alice, &proposal_id, "liked").await.unwrap();
+    api::create_comment(
+        bob.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        Some(quiet.id.to_string()),
+        "a reply".to_string(),
+    )
+    .await
+    .expect("Should reply");
+    for (token, target) in [(&alice, &popular), (&bob, &popular), (&bob, &liked)] {
+        api::set_vote(
+            token.to_string(),
+            ContentTargetType::Comment,
+            target.id.to_string(),
+            1,
+        )
+        .await
+        .expect("Should vote");
+    }
+
+    let top = api::list_top_comments(ContentTargetType::Proposal, proposal_id.clone(), 2, None)
+        .await
+        .expect("Should list top comments");
+    let ids: Vec<_> = top.iter().map(|c| c.id).collect();
+    assert_eq!(ids, vec![popular.id, liked.id]);
+    assert_eq!(top[0].vote_score, 2);
+
+    let all = api::list_top_comments(ContentTargetType::Proposal, proposal_id, 100, None)
+        .await
+        .expect("Should list top comments");
+    let ids: Vec<_> = all.iter().map(|c| c.id).collect();
+    assert_eq!(
+        ids,
+        vec![popular.id, liked.id, quiet.id],
+        "Replies are left out of the preview"
+    );
+}
+
+async fn set_display_name(ctx: &TestContext, user_id: &str, name: &str) {
+    sqlx::query("insert into profiles (user_id, display_name) values ($1, $2)")
+        .bind(user_id)
+        .bind(name)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should create profile");
+}
+
+#[tokio::test]
+async fn mention_notifies_the_named_user() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (author_token, author_id) = create_user_with_token(&ctx, "mention-author@test.com").await;
+    let (writer_token, writer_id) = create_user_with_token(&ctx, "mention-writer@test.com").await;
+    let (ada_token, ada_id) = create_user_with_token(&ctx, "mention-ada@test.com").await;
+    set_display_name(&ctx, &writer_id, "Writer").await;
+    set_display_name(&ctx, &ada_id, "Ada L.").await;
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+
+    let created = comment(
+        &writer_token,
+        &proposal_id,
+        "What do you think, @ada_l? cc @Writer",
+    )
+    .await
+    .expect("Comment should succeed");
+    assert_eq!(
+        created
+            .mentioned_user_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>(),
+        vec![ada_id],
+        "Self-mentions are skipped"
+    );
+
+    let ada_notes = api::list_notifications(ada_token, 20, None)
+        .await
+        .expect("Should list notifications");
+    assert_eq!(ada_notes.len(), 1);
+    assert_eq!(ada_notes[0].kind, api::types::NotificationKind::Mention);
+    assert_eq!(ada_notes[0].comment_id, Some(created.id));
+    assert_eq!(
+        ada_notes[0].actor_user_id.map(|id| id.to_string()),
+        Some(writer_id)
+    );
+
+    let writer_notes = api::list_notifications(writer_token, 20, None)
+        .await
+        .expect("Should list notifications");
+    assert!(writer_notes.is_empty());
+
+    let author_notes = api::list_notifications(author_token, 20, None)
+        .await
+        .expect("Should list notifications");
+    assert_eq!(author_notes.len(), 1, "Only the comment notification");
+
+    let listed = api::list_comments(ContentTargetType::Proposal, proposal_id, 10, None)
+        .await
+        .expect("Should list comments");
+    assert_eq!(
+        listed,
+        vec![created],
+        "Listed comments carry their mentions"
+    );
+}
+
+#[tokio::test]
+async fn unknown_mention_is_ignored() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (author_token, author_id) = create_user_with_token(&ctx, "ghost-author@test.com").await;
+    let (writer_token, _) = create_user_with_token(&ctx, "ghost-writer@test.com").await;
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+
+    let created = comment(
+        &writer_token,
+        &proposal_id,
+        "Paging @nobody_here and mail@example.org",
+    )
+    .await
+    .expect("Unknown mentions shouldn't fail the comment");
+    assert!(created.mentioned_user_ids.is_empty());
+
+    let count: i64 =
+        sqlx::query_scalar("select count(*) from notifications where kind = 'mention'")
+            .fetch_one(&ctx.pool)
+            .await
+            .expect("Should count notifications");
+    assert_eq!(count, 0);
+    let author_notes = api::list_notifications(author_token, 20, None)
+        .await
+        .expect("Should list notifications");
+    assert_eq!(author_notes.len(), 1);
+}
+
+#[tokio::test]
+async fn content_filter_rejects_blocklisted_comments() {
+    let ctx = TestContext::with_config(|c| c.content_blocklist = vec!["crétin".to_string()]).await;
+    ctx.set_global();
+
+    let (token, user_id) = create_user_with_token(&ctx, "filtered@test.com").await;
+    let proposal_id = create_proposal(&ctx, &user_id).await;
+
+    comment(&token, &proposal_id, "Une remarque constructive")
+        .await
+        .expect("Clean comment should pass the filter");
+
+    let error = comment(&token, &proposal_id, "Quel Crétin.")
+        .await
+        .expect_err("Blocklisted comment should be rejected");
+    assert_eq!(
+        api::types::ErrorCode::from_error(&error),
+        Some(api::types::ErrorCode::ContentRejected)
+    );
+
+    let count: i64 = sqlx::query_scalar("select count(*) from comments")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count comments");
+    assert_eq!(count, 1);
+}
+
+#[tokio::test]
+async fn content_filter_flags_blocklisted_proposals_for_review() {
+    let ctx = TestContext::with_config(|c| {
+        c.content_blocklist = vec!["crétin".to_string()];
+        c.content_filter_action = api::config::ContentFilterAction::Flag;
+    })
+    .await;
+    ctx.set_global();
+
+    let (token, _) = create_user_with_token(&ctx, "flagged@test.com").await;
+    let clean = api::create_proposal(
+        token.clone(),
+        "Pistes cyclables".to_string(),
+        "Plus de pistes".to_string(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Clean proposal should be created");
+    let flagged = api::create_proposal(
+        token,
+        "Réponse".to_string(),
+        String::new(),
+        "Seul un crétin voterait contre".to_string(),
+        String::new(),
+    )
+    .await
+    .expect("Flagged proposal is still created");
+
+    let reported: Vec<(String, String)> =
+        sqlx::query_as("select CAST(target_id as TEXT), reason from reports")
+            .fetch_all(&ctx.pool)
+            .await
+            .expect("Should list reports");
+    assert_eq!(
+        reported,
+        vec![(flagged.id.to_string(), "content filter: crétin".to_string())]
+    );
+    assert_ne!(clean.id, flagged.id);
+}
+
+#[tokio::test]
+async fn list_comments_returns_a_threaded_list_with_depth() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, user_id) = create_user_with_token(&ctx, "threader@test.com").await;
+    let proposal_id = create_proposal(&ctx, &user_id).await;
+
+    // (name, parent) in posting order.
+    let plan = [
+        ("first", None),
+        ("second", None),
+        ("reply to first", Some("first")),
+        ("reply to reply", Some("reply to first")),
+        ("late reply to first", Some("first")),
+    ];
+    let mut ids = std::collections::HashMap::new();
+    for (i, (body, parent)) in plan.into_iter().enumerate() {
+        ctx.state.rate_limiter.reset(&format!("comment:{user_id}"));
+        let created = api::create_comment(
+            token.clone(),
+            ContentTargetType::Proposal,
+            proposal_id.clone(),
+            parent
+                .map(|p: &str| ids[p])
+                .map(|id: uuid::Uuid| id.to_string()),
+            body.to_string(),
+        )
+        .await
+        .expect("Should create comment");
+        // SQLite timestamps only have second precision; spread them out.
+        sqlx::query(&format!(
+            "update comments set created_at = '2020-01-01 00:00:0{i}' where id = '{}'",
+            created.id
+        ))
+        .execute(&ctx.pool)
+        .await
+        .expect("Should set created_at");
+        ids.insert(body, created.id);
+    }
+
+    // A reply whose parent is later hidden shows up at the top level.
+    ctx.state.rate_limiter.reset(&format!("comment:{user_id}"));
+    api::create_comment(
+        token,
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        Some(ids["second"].to_string()),
+        "reply to hidden".to_string(),
+    )
+    .await
+    .expect("Should reply");
+    sqlx::query(&format!(
+        "update comments set hidden_at = created_at where id = '{}'",
+        ids["second"]
+    ))
+    .execute(&ctx.pool)
+    .await
+    .expect("Should hide comment");
+
+    let listed = api::list_comments(ContentTargetType::Proposal, proposal_id, 10, None)
+        .await
+        .expect("Should list comments");
+    let shape: Vec<(&str, u32)> = listed
+        .iter()
+        .map(|c| (c.body_markdown.as_str(), c.depth))
+        .collect();
+    assert_eq!(
+        shape,
+        vec![
+            ("first", 0),
+            ("reply to first", 1),
+            ("reply to reply", 2),
+            ("late reply to first", 1),
+            ("reply to hidden", 0),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn author_pins_one_comment_to_the_top() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (author_token, author_id) = create_user_with_token(&ctx, "pin-author@test.com").await;
+    let (other_token, other_id) = create_user_with_token(&ctx, "pin-other@test.com").await;
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+
+    let mut ids = Vec::new();
+    for (i, body) in ["first", "second", "third"].into_iter().enumerate() {
+        ctx.state.rate_limiter.reset(&format!("comment:{other_id}"));
+        let created = comment(&other_token, &proposal_id, body)
+            .await
+            .expect("Should create comment");
+        // SQLite timestamps only have second precision; spread them out.
+        sqlx::query(&format!(
+            "update comments set created_at = '2020-01-01 00:00:0{i}' where id = '{}'",
+            created.id
+        ))
+        .execute(&ctx.pool)
+        .await
+        .expect("Should set created_at");
+        ids.push(created.id.to_string());
+    }
+    let listed = || async {
+        api::list_comments(ContentTargetType::Proposal, proposal_id.clone(), 10, None)
+            .await
+            .expect("Should list comments")
+            .into_iter()
+            .map(|c| (c.body_markdown, c.pinned))
+            .collect::<Vec<_>>()
+    };
+
+    let err = api::pin_comment(other_token, ids[2].clone())
+        .await
+        .expect_err("Only the proposal author can pin");
+    assert_eq!(
+        api::types::ErrorCode::from_error(&err.to_string()),
+        Some(api::types::ErrorCode::NotAllowed)
+    );
+
+    api::pin_comment(author_token.clone(), ids[2].clone())
+        .await
+        .expect("Author should pin");
+    assert_eq!(
+        listed().await,
+        vec![
+            ("third".to_string(), true),
+            ("first".to_string(), false),
+            ("second".to_string(), false),
+        ]
+    );
+
+    api::pin_comment(author_token.clone(), ids[1].clone())
+        .await
+        .expect("Author should pin another comment");
+    assert_eq!(
+        listed().await,
+        vec![
+            ("second".to_string(), true),
+            ("first".to_string(), false),
+            ("third".to_string(), false),
+        ]
+    );
+
+    api::unpin_comment(author_token, ids[1].clone())
+        .await
+        .expect("Author should unpin");
+    assert!(listed().await.iter().all(|(_, pinned)| !pinned));
+}
+
+/// Comment and reply emails sent so far, leaving out signup verification mail.
+fn notification_emails(ctx: &TestContext) -> Vec<api::test_utils::SentEmail> {
+    ctx.emails
+        .sent()
+        .into_iter()
+        .filter(|email| email.subject.starts_with("New "))
+        .collect()
+}
+
+#[tokio::test]
+async fn notification_emails_follow_the_preference() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (alice, alice_id) = create_user_with_token(&ctx, "alice-email@test.com").await;
+    let (bob, bob_id) = create_user_with_token(&ctx, "bob-email@test.com").await;
+    for token in [&alice, &bob] {
+        api::upsert_profile(
+            token.clone(),
+            "Name".into(),
+            String::new(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .expect("Should create profile");
+    }
+    let alices = create_proposal(&ctx, &alice_id).await;
+    let bobs = create_proposal(&ctx, &bob_id).await;
+
+    comment(&bob, &alices, "Off by default")
+        .await
+        .expect("Comment should succeed");
+    assert!(notification_emails(&ctx).is_empty());
+
+    let enabled = api::set_email_notifications(alice.clone(), true)
+        .await
+        .expect("Should enable emails");
+    assert!(enabled);
+    let me = api::auth_me(alice.clone()).await.expect("Should load me");
+    assert!(me.profile.expect("Profile should exist").notify_email);
+
+    comment(&bob, &alices, "Now emailed")
+        .await
+        .expect("Comment should succeed");
+    let parent = comment(&alice, &bobs, "On Bob's proposal")
+        .await
+        .expect("Comment should succeed");
+    api::create_comment(
+        bob.clone(),
+        ContentTargetType::Proposal,
+        bobs.clone(),
+        Some(parent.id.to_string()),
+        "A reply".to_string(),
+    )
+    .await
+    .expect("Reply should succeed");
+
+    // Bob never opted in, so only Alice's comment and reply emails go out.
+    let emails = notification_emails(&ctx);
+    let subjects: Vec<_> = emails.iter().map(|e| e.subject.as_str()).collect();
+    assert_eq!(
+        subjects,
+        vec!["New comment on your content", "New reply to your comment"]
+    );
+    assert!(emails.iter().all(|e| e.to == "alice-email@test.com"));
+    assert!(emails[0].text.contains(&format!("/proposals/{alices}")));
+
+    let link = emails[0]
+        .text
+        .lines()
+        .find(|line| line.contains("/notifications/unsubscribe?"))
+        .expect("Email should carry an unsubscribe link");
+    let token = link
+        .split("token=")
+        .nth(1)
+        .expect("Link should carry a token");
+    assert!(api::unsubscribe_email(bob_id.clone(), token.to_string())
+        .await
+        .is_err());
+    assert!(
+        api::unsubscribe_email(alice_id.clone(), format!("{}0", &token[1..]))
+            .await
+            .is_err()
+    );
+    api::unsubscribe_email(alice_id.clone(), token.to_string())
+        .await
+        .expect("Should unsubscribe");
+
+    comment(&bob, &alices, "After unsubscribing")
+        .await
+        .expect("Comment should succeed");
+    assert_eq!(notification_emails(&ctx).len(), 2);
+}
+
+#[tokio::test]
+async fn authors_edit_and_delete_their_comments() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (author, author_id) = create_user_with_token(&ctx, "editor@test.com").await;
+    let (other, other_id) = create_user_with_token(&ctx, "bystander@test.com").await;
+    set_display_name(&ctx, &other_id, "Bystander").await;
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+    let first = comment(&author, &proposal_id, "First")
+        .await
+        .expect("Should create comment");
+    let reply = api::create_comment(
+        other.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        Some(first.id.to_string()),
+        "Reply".to_string(),
+    )
+    .await
+    .expect("Should reply");
+    comment(&author, &proposal_id, "Second")
+        .await
+        .expect("Should create comment");
+
+    let edit = |token: &str, body: &str| {
+        api::update_comment(token.to_string(), first.id.to_string(), body.to_string())
+    };
+    let denied = edit(&other, "Hijacked")
+        .await
+        .expect_err("Only the author may edit");
+    assert!(denied.to_string().contains("not allowed"));
+    assert!(edit(&author, "   ").await.is_err());
+    let too_long = edit(&author, &"a".repeat(5001))
+        .await
+        .expect_err("Edits share the length limit");
+    assert!(too_long.to_string().contains("exceeds"));
+    let edited = edit(&author, " First, edited ")
+        .await
+        .expect("Should edit comment");
+    assert_eq!(edited.body_markdown, "First, edited");
+    assert!(edited.updated_at.is_some());
+    assert_eq!(edited.created_at, first.created_at);
+
+    let bodies = |comments: &[api::types::Comment]| {
+        comments
+            .iter()
+            .map(|c| (c.body_markdown.clone(), c.deleted))
+            .collect::<Vec<_>>()
+    };
+    let listed = api::list_comments(ContentTargetType::Proposal, proposal_id.clone(), 10, None)
+        .await
+        .expect("Should list comments");
+    assert_eq!(
+        bodies(&listed),
+        vec![
+            ("First, edited".to_string(), false),
+            ("Reply".to_string(), false),
+            ("Second".to_string(), false),
+        ]
+    );
+
+    assert!(api::delete_comment(other, first.id.to_string())
+        .await
+        .is_err());
+    api::delete_comment(author.clone(), first.id.to_string())
+        .await
+        .expect("Should delete comment");
+    let listed = api::list_comments(ContentTargetType::Proposal, proposal_id.clone(), 10, None)
+        .await
+        .expect("Should list comments");
+    assert_eq!(
+        bodies(&listed),
+        vec![
+            (String::new(), true),
+            ("Reply".to_string(), false),
+            ("Second".to_string(), false),
+        ]
+    );
+    assert_eq!(listed[1].id, reply.id);
+    assert_eq!(listed[1].depth, 1);
+    let count: i64 = sqlx::query_scalar("select comment_count from proposals where id = $1")
+        .bind(&proposal_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should read comment count");
+    assert_eq!(count, 2);
+
+    assert!(edit(&author, "Back from the dead").await.is_err());
+    assert!(api::delete_comment(author.clone(), first.id.to_string())
+        .await
+        .is_err());
+
+    let notified = |author: String| async move {
+        api::list_notifications(author, 20, None)
+            .await
+            .expect("Should list notifications")
+            .iter()
+            .filter(|n| n.comment_id == Some(reply.id))
+            .count()
+    };
+    assert_eq!(notified(author.clone()).await, 1);
+    assert_eq!(listed[1].author_display_name.as_deref(), Some("Bystander"));
+    api::delete_comment(other, reply.id.to_string())
+        .await
+        .expect("Should delete reply");
+    assert_eq!(
+        notified(author).await,
+        0,
+        "The reply's notification goes with it"
+    );
+    let listed = api::list_comments(ContentTargetType::Proposal, proposal_id, 10, None)
+        .await
+        .expect("Should list comments");
+    assert!(listed[1].deleted);
+    assert_eq!(listed[1].author_display_name, None);
+}
+
+#[tokio::test]
+async fn replies_must_stay_on_their_parents_target() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, user_id) = create_user_with_token(&ctx, "replier@test.com").await;
+    let here = create_proposal(&ctx, &user_id).await;
+    let elsewhere = create_proposal(&ctx, &user_id).await;
+    let parent = comment(&token, &here, "Parent")
+        .await
+        .expect("Should comment");
+    let reply = |target_id: &str, parent_id: String| {
+        api::create_comment_reply(
+            token.clone(),
+            ContentTargetType::Proposal,
+            target_id.to_string(),
+            parent_id,
+            "Reply".to_string(),
+        )
+    };
+
+    let error = reply(&elsewhere, parent.id.to_string())
+        .await
+        .expect_err("Parent on another target should be rejected")
+        .to_string();
+    assert!(error.contains("different target"), "{error}");
+    assert!(reply(&here, uuid::Uuid::new_v4().to_string())
+        .await
+        .is_err());
+    assert!(reply(&here, String::new()).await.is_err());
+
+    let hidden = comment(&token, &here, "Hidden")
+        .await
+        .expect("Should comment");
+    sqlx::query("update comments set hidden_at = CURRENT_TIMESTAMP where id = $1")
+        .bind(hidden.id.to_string())
+        .execute(&ctx.pool)
+        .await
+        .expect("Should hide comment");
+    let deleted = comment(&token, &here, "Deleted")
+        .await
+        .expect("Should comment");
+    api::delete_comment(token.clone(), deleted.id.to_string())
+        .await
+        .expect("Should delete comment");
+    ctx.state.rate_limiter.reset(&format!("comment:{user_id}"));
+    for gone in [hidden.id, deleted.id] {
+        let error = reply(&here, gone.to_string())
+            .await
+            .expect_err("Hidden and deleted comments can't be replied to")
+            .to_string();
+        assert!(error.contains("parent comment not found"), "{error}");
+    }
+    ctx.state.rate_limiter.reset(&format!("comment:{user_id}"));
+
+    let created = reply(&here, parent.id.to_string())
+        .await
+        .expect("Should reply");
+    assert_eq!(created.parent_comment_id, Some(parent.id));
+    let listed = api::list_comments(ContentTargetType::Proposal, here, 10, None)
+        .await
+        .expect("Should list comments");
+    let shape: Vec<_> = listed
+        .iter()
+        .filter(|c| !c.deleted)
+        .map(|c| (c.id, c.parent_comment_id, c.depth))
+        .collect();
+    assert_eq!(
+        shape,
+        vec![(parent.id, None, 0), (created.id, Some(parent.id), 1)]
+    );
+    let elsewhere_comments = api::list_comments(ContentTargetType::Proposal, elsewhere, 10, None)
+        .await
+        .expect("Should list comments");
+    assert!(elsewhere_comments.is_empty());
+}
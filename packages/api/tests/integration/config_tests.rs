@@ -0,0 +1,100 @@
+use api::test_utils::TestContext;
+
+const STORAGE_ENVS: &[(&str, &str)] = &[
+    ("STORAGE_BUCKET", "videos"),
+    ("STORAGE_ENDPOINT", "http://localhost:9000"),
+    ("STORAGE_ACCESS_KEY", "access"),
+    ("STORAGE_SECRET_KEY", "secret"),
+];
+
+fn s3_storage() -> api::config::StorageConfig {
+    api::config::StorageConfig::S3 {
+        bucket: "videos".into(),
+        endpoint: "http://localhost:9000".into(),
+        region: "auto".into(),
+        access_key: "access".into(),
+        secret_key: "secret".into(),
+        media_base_url: None,
+        path_style: true,
+    }
+}
+
+fn set_auth_envs() {
+    std::env::set_var("AUTH_AUTHORIZE_URL", "http://localhost/authorize");
+    std::env::set_var("AUTH_CLIENT_ID", "client");
+    std::env::set_var("AUTH_REDIRECT_URI", "http://localhost/callback");
+}
+
+#[tokio::test]
+async fn public_config_reports_upload_readiness() {
+    // Holding the context serializes env mutation with the other integration tests.
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+    set_auth_envs();
+
+    // Stray bucket envs don't turn on uploads for a filesystem deployment.
+    for (key, value) in STORAGE_ENVS {
+        std::env::set_var(key, value);
+    }
+    let cfg = api::PublicConfig::from_env().expect("Should load config");
+    for (key, _) in STORAGE_ENVS {
+        std::env::remove_var(key);
+    }
+    assert!(
+        !cfg.uploads_enabled,
+        "Uploads should be disabled on filesystem storage"
+    );
+    assert!(cfg.allowed_video_types.contains(&"video/mp4".to_string()));
+    drop(ctx);
+
+    let ctx = TestContext::with_config(|c| c.storage = s3_storage()).await;
+    ctx.set_global();
+    let cfg = api::PublicConfig::from_env().expect("Should load config");
+    assert!(
+        cfg.uploads_enabled,
+        "Uploads should be enabled on S3 storage"
+    );
+}
+
+#[tokio::test]
+async fn cached_public_config_reports_uploads_once_s3_state_is_installed() {
+    let ctx = TestContext::new().await;
+    set_auth_envs();
+    // Warm the cache before any S3 state is global, as `AppState::from_config` does.
+    api::public_config().await.expect("Should load config");
+    drop(ctx);
+
+    let ctx = TestContext::with_config(|c| c.storage = s3_storage()).await;
+    ctx.set_global();
+    let cfg = api::public_config()
+        .await
+        .expect("Should load cached config");
+    assert!(
+        cfg.uploads_enabled,
+        "Uploads should be enabled on S3 storage after startup"
+    );
+    drop(ctx);
+
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+    let cfg = api::public_config()
+        .await
+        .expect("Should load cached config");
+    assert!(!cfg.uploads_enabled, "Filesystem storage has no uploads");
+}
+
+#[tokio::test]
+async fn public_config_is_cached_after_first_read() {
+    let _ctx = TestContext::new().await;
+    set_auth_envs();
+
+    let first = api::public_config().await.expect("Should load config");
+    std::env::set_var("AUTH_CLIENT_ID", "changed-client");
+    let second = api::public_config()
+        .await
+        .expect("Should load cached config");
+    assert_eq!(first, second);
+    assert_ne!(second.auth_client_id, "changed-client");
+
+    set_auth_envs();
+}
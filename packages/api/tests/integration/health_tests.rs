@@ -0,0 +1,137 @@
+use api::test_utils::TestContext;
+
+async fn create_user_with_token(ctx: &TestContext, email: &str) -> String {
+    api::signup(email.to_string(), "Password123".to_string(), None, None)
+        .await
+        .expect("Signup should succeed");
+    sqlx::query("UPDATE users SET email_verified = true WHERE email = $1")
+        .bind(email)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should verify user");
+    api::signin(email.to_string(), "Password123".to_string(), None)
+        .await
+        .expect("Signin should succeed")
+}
+
+#[tokio::test]
+async fn migration_health_reports_applied_versions() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "ops@test.com").await;
+    sqlx::query("UPDATE users SET is_admin = true WHERE email = $1")
+        .bind("ops@test.com")
+        .execute(&ctx.pool)
+        .await
+        .expect("Should promote user");
+
+    let status = api::migration_health(token)
+        .await
+        .expect("Should report migration status");
+    assert!(status.up_to_date);
+    assert_eq!(status.applied, status.expected);
+    assert_eq!(&status.applied[..4], &[1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn migration_health_requires_auth() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let result = api::migration_health("not-a-token".to_string()).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn migration_health_rejects_non_admin() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "citizen@test.com").await;
+    let err = api::migration_health(token)
+        .await
+        .expect_err("Non-admin should be rejected");
+    assert!(err.to_string().contains("forbidden"));
+}
+
+#[tokio::test]
+async fn metrics_count_server_function_calls() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let metrics = api::metrics::global();
+    let calls_before = metrics.requests("notifications.unread_notification_count");
+    let unknown_before = metrics.errors(api::metrics::UNKNOWN_ERROR);
+    let not_found_before = metrics.errors("not_found");
+
+    let token = create_user_with_token(&ctx, "scraped@test.com").await;
+    api::unread_notification_count(token)
+        .await
+        .expect("Should count notifications");
+    assert!(api::unread_notification_count("bad-token".to_string())
+        .await
+        .is_err());
+
+    assert_eq!(
+        metrics.requests("notifications.unread_notification_count"),
+        calls_before + 2
+    );
+    // Error counters are shared by every test running in the process, so only check
+    // that they moved. A bad token carries no error code.
+    assert!(metrics.errors(api::metrics::UNKNOWN_ERROR) > unknown_before);
+
+    assert!(api::get_proposal(uuid::Uuid::new_v4().to_string(), None)
+        .await
+        .is_err());
+    assert!(metrics.errors("not_found") > not_found_before);
+
+    let text = api::metrics_endpoint(None)
+        .await
+        .expect("Metrics should be open without METRICS_TOKEN");
+    assert!(
+        text.contains("alelysee_requests_total{fn=\"notifications.unread_notification_count\"}")
+    );
+    assert!(text.contains("alelysee_request_errors_total{code=\"not_found\"}"));
+    assert!(text.contains("alelysee_db_connections{state=\"idle\"}"));
+}
+
+#[tokio::test]
+async fn metrics_require_configured_token() {
+    let ctx = TestContext::with_config(|c| c.metrics_token = Some("scrape-me".into())).await;
+    ctx.set_global();
+
+    assert!(api::metrics_endpoint(None).await.is_err());
+    assert!(api::metrics_endpoint(Some("wrong".into())).await.is_err());
+    assert!(api::metrics_endpoint(Some("scrape-me-too".into()))
+        .await
+        .is_err());
+    let text = api::metrics_endpoint(Some("scrape-me".into()))
+        .await
+        .expect("Correct token should scrape");
+    assert!(text.contains("alelysee_uptime_seconds"));
+
+    // Scrapers look for the conventional path, outside the /api prefix.
+    assert!(api::schema::find("GET", "/metrics").is_some());
+    assert!(api::schema::find("GET", "/api/metrics").is_none());
+}
+
+#[tokio::test]
+async fn server_functions_error_before_state_init() {
+    // No TestContext: neither the global nor the thread-local state is set.
+    let err = api::list_proposals(10, None, None)
+        .await
+        .expect_err("Reads should fail without state");
+    assert!(err.to_string().contains("server not initialized"));
+
+    let err = api::create_proposal(
+        "token".to_string(),
+        "Title".to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect_err("Writes should fail without state");
+    assert!(err.to_string().contains("server not initialized"));
+}
@@ -1,5 +1,19 @@
 #![cfg(feature = "server")]
 
 // Integration tests for the API package
+mod account_tests;
+mod activity_tests;
 mod auth_tests;
+mod blog_tests;
+mod bookmarks_tests;
+mod comments_tests;
+mod config_tests;
+mod health_tests;
+mod maintenance_tests;
+mod moderation_tests;
+mod profile_tests;
+mod programs_tests;
+mod proposal_tests;
+mod sitemap_tests;
+mod uploads_tests;
 mod votes_tests;
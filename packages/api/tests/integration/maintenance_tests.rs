@@ -0,0 +1,98 @@
+use api::test_utils::TestContext;
+
+#[tokio::test]
+async fn maintenance_mode_blocks_writes_but_not_reads() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    api::signup(
+        "maint@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+        None,
+    )
+    .await
+    .expect("Signup should succeed");
+    sqlx::query("UPDATE users SET email_verified = true WHERE email = $1")
+        .bind("maint@test.com")
+        .execute(&ctx.pool)
+        .await
+        .expect("Should verify user");
+    let token = api::signin(
+        "maint@test.com".to_string(),
+        "Password123".to_string(),
+        None,
+    )
+    .await
+    .expect("Signin should succeed");
+
+    ctx.state.set_maintenance_mode(true);
+
+    let result = api::create_proposal(
+        token.clone(),
+        "Blocked".to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await;
+    let error = result.expect_err("Write should be blocked").to_string();
+    assert!(error.contains("read-only mode"));
+
+    let proposals = api::list_proposals(10, None, None)
+        .await
+        .expect("Reads should keep working");
+    assert!(proposals.is_empty());
+
+    // Account flows write too: users, verification and reset tokens.
+    let blocked = [
+        (
+            "signup",
+            api::signup(
+                "maint-new@test.com".to_string(),
+                "Password123".to_string(),
+                None,
+                None,
+            )
+            .await,
+        ),
+        (
+            "verify_email",
+            api::verify_email("token".to_string(), None).await,
+        ),
+        (
+            "resend_verification_email",
+            api::resend_verification_email("maint@test.com".to_string()).await,
+        ),
+        (
+            "request_password_reset",
+            api::request_password_reset("maint@test.com".to_string(), None, None).await,
+        ),
+        (
+            "reset_password",
+            api::reset_password("token".to_string(), "Password456".to_string(), None).await,
+        ),
+    ];
+    for (name, result) in blocked {
+        let error = result.expect_err(name).to_string();
+        assert!(error.contains("read-only mode"), "{name}: {error}");
+    }
+    let created: i64 = sqlx::query_scalar("select count(*) from users where email = $1")
+        .bind("maint-new@test.com")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count users");
+    assert_eq!(created, 0);
+
+    ctx.state.set_maintenance_mode(false);
+
+    api::create_proposal(
+        token,
+        "Allowed".to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Write should succeed once maintenance ends");
+}
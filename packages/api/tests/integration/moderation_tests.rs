@@ -0,0 +1,427 @@
+use api::test_utils::TestContext;
+use api::types::{AdminAction, ContentTargetType, NotificationKind};
+
+async fn create_user_with_token(ctx: &TestContext, email: &str) -> (String, String) {
+    api::signup(email.to_string(), "Password123".to_string(), None, None)
+        .await
+        .expect("Signup should succeed");
+
+    sqlx::query("UPDATE users SET email_verified = true WHERE email = $1")
+        .bind(email)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should verify user");
+
+    let token = api::signin(email.to_string(), "Password123".to_string(), None)
+        .await
+        .expect("Signin should succeed");
+    let user_id: String = sqlx::query_scalar("select id from users where email = $1")
+        .bind(email)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    (token, user_id)
+}
+
+async fn create_admin_with_token(ctx: &TestContext, email: &str) -> String {
+    let (token, user_id) = create_user_with_token(ctx, email).await;
+    sqlx::query("UPDATE users SET is_admin = true WHERE id = $1")
+        .bind(&user_id)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should promote user");
+    token
+}
+
+async fn create_proposal(ctx: &TestContext, author_user_id: &str) -> String {
+    sqlx::query_scalar(
+        "insert into proposals (author_user_id, title, summary, body_markdown, tags) values ($1, 'T', '', '', '[]') returning id",
+    )
+    .bind(author_user_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Should create proposal")
+}
+
+#[tokio::test]
+async fn hidden_proposal_is_only_visible_to_admins() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (_, author_id) = create_user_with_token(&ctx, "author@test.com").await;
+    let admin = create_admin_with_token(&ctx, "mod@test.com").await;
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+
+    api::hide_content(
+        admin.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+    )
+    .await
+    .expect("Admin should hide proposal");
+
+    let listed = api::list_proposals(50, None, None)
+        .await
+        .expect("Should list proposals");
+    assert!(listed.iter().all(|p| p.id.to_string() != proposal_id));
+    assert!(api::get_proposal(proposal_id.clone(), None).await.is_err());
+
+    let moderated = api::moderation_get_proposal(admin.clone(), proposal_id.clone())
+        .await
+        .expect("Admin should still fetch hidden proposal");
+    assert_eq!(moderated.proposal.id.to_string(), proposal_id);
+    assert!(moderated.hidden_at.is_some());
+
+    api::unhide_content(admin, ContentTargetType::Proposal, proposal_id.clone())
+        .await
+        .expect("Admin should unhide proposal");
+    let listed = api::list_proposals(50, None, None)
+        .await
+        .expect("Should list proposals");
+    assert!(listed.iter().any(|p| p.id.to_string() == proposal_id));
+}
+
+#[tokio::test]
+async fn moderation_requires_admin() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, user_id) = create_user_with_token(&ctx, "citizen@test.com").await;
+    let proposal_id = create_proposal(&ctx, &user_id).await;
+
+    let err = api::hide_content(
+        token.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+    )
+    .await
+    .expect_err("Non-admin should be rejected");
+    assert!(err.to_string().contains("forbidden"));
+
+    let err = api::moderation_get_proposal(token, proposal_id)
+        .await
+        .expect_err("Non-admin should be rejected");
+    assert!(err.to_string().contains("forbidden"));
+}
+
+#[tokio::test]
+async fn hiding_writes_an_admin_audit_entry() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (citizen, author_id) = create_user_with_token(&ctx, "author@test.com").await;
+    let admin = create_admin_with_token(&ctx, "mod@test.com").await;
+    let admin_id: String = sqlx::query_scalar("select id from users where email = $1")
+        .bind("mod@test.com")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch admin id");
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+
+    api::hide_content(
+        admin.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+    )
+    .await
+    .expect("Admin should hide proposal");
+
+    let entries = api::list_admin_audit(admin, 50, 0)
+        .await
+        .expect("Admin should read the audit log");
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.action, AdminAction::Hide);
+    assert_eq!(entry.actor_user_id.map(|id| id.to_string()), Some(admin_id));
+    assert_eq!(entry.target_type, Some(ContentTargetType::Proposal));
+    assert_eq!(entry.target_id.map(|id| id.to_string()), Some(proposal_id));
+
+    let err = api::list_admin_audit(citizen, 50, 0)
+        .await
+        .expect_err("Non-admin should be rejected");
+    assert!(err.to_string().contains("forbidden"));
+}
+
+#[tokio::test]
+async fn hidden_comment_is_left_out_of_thread() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, user_id) = create_user_with_token(&ctx, "talker@test.com").await;
+    let admin = create_admin_with_token(&ctx, "mod2@test.com").await;
+    let proposal_id = create_proposal(&ctx, &user_id).await;
+    let comment = api::create_comment(
+        token,
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        None,
+        "spam".to_string(),
+    )
+    .await
+    .expect("Should create comment");
+
+    api::hide_content(admin, ContentTargetType::Comment, comment.id.to_string())
+        .await
+        .expect("Admin should hide comment");
+
+    let comments = api::list_comments(ContentTargetType::Proposal, proposal_id, 10, None)
+        .await
+        .expect("Should list comments");
+    assert!(comments.is_empty());
+}
+
+async fn hidden_at(ctx: &TestContext, proposal_id: &str) -> Option<String> {
+    sqlx::query_scalar("select hidden_at from proposals where id = $1")
+        .bind(proposal_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should read hidden_at")
+}
+
+#[tokio::test]
+async fn nth_distinct_report_auto_hides_and_notifies_admins() {
+    let ctx = TestContext::with_config(|c| c.report_auto_hide_threshold = 3).await;
+    ctx.set_global();
+
+    let (_, author_id) = create_user_with_token(&ctx, "author@test.com").await;
+    let admin = create_admin_with_token(&ctx, "mod@test.com").await;
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+    let mut reporters = Vec::new();
+    for i in 0..3 {
+        let (token, _) = create_user_with_token(&ctx, &format!("reporter{i}@test.com")).await;
+        reporters.push(token);
+    }
+
+    for token in &reporters[..2] {
+        api::report_content(
+            token.clone(),
+            ContentTargetType::Proposal,
+            proposal_id.clone(),
+            "spam".into(),
+        )
+        .await
+        .expect("Should report");
+    }
+    // Reporting twice doesn't count as a second reporter.
+    api::report_content(
+        reporters[0].clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        "still spam".into(),
+    )
+    .await
+    .expect("Duplicate report should be accepted");
+    assert_eq!(hidden_at(&ctx, &proposal_id).await, None);
+    assert!(api::list_notifications(admin.clone(), 10, None)
+        .await
+        .expect("Should list notifications")
+        .is_empty());
+
+    api::report_content(
+        reporters[2].clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        String::new(),
+    )
+    .await
+    .expect("Should report");
+    assert!(hidden_at(&ctx, &proposal_id).await.is_some());
+    assert!(api::get_proposal(proposal_id.clone(), None).await.is_err());
+
+    let notifications = api::list_notifications(admin, 10, None)
+        .await
+        .expect("Should list notifications");
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].kind, NotificationKind::AutoHidden);
+    assert_eq!(notifications[0].target_id.to_string(), proposal_id);
+}
+
+#[tokio::test]
+async fn reports_past_threshold_do_not_rehide_after_review() {
+    let ctx = TestContext::with_config(|c| c.report_auto_hide_threshold = 1).await;
+    ctx.set_global();
+
+    let (first, author_id) = create_user_with_token(&ctx, "first@test.com").await;
+    let (second, _) = create_user_with_token(&ctx, "second@test.com").await;
+    let admin = create_admin_with_token(&ctx, "mod@test.com").await;
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+
+    api::report_content(
+        first,
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        String::new(),
+    )
+    .await
+    .expect("Should report");
+    assert!(hidden_at(&ctx, &proposal_id).await.is_some());
+
+    api::unhide_content(admin, ContentTargetType::Proposal, proposal_id.clone())
+        .await
+        .expect("Admin should restore after review");
+    api::report_content(
+        second,
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        String::new(),
+    )
+    .await
+    .expect("Should report");
+    assert_eq!(hidden_at(&ctx, &proposal_id).await, None);
+}
+
+#[tokio::test]
+async fn report_rejects_unknown_target() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, _) = create_user_with_token(&ctx, "reporter@test.com").await;
+    let err = api::report_content(
+        token,
+        ContentTargetType::Proposal,
+        uuid::Uuid::new_v4().to_string(),
+        String::new(),
+    )
+    .await
+    .expect_err("Unknown target should fail");
+    assert!(err.to_string().contains("target not found"));
+}
+
+async fn cached_comment_count(ctx: &TestContext, proposal_id: &str) -> i64 {
+    sqlx::query_scalar("select comment_count from proposals where id = $1")
+        .bind(proposal_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should read comment_count")
+}
+
+#[tokio::test]
+async fn comment_count_follows_comments_and_hiding() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, user_id) = create_user_with_token(&ctx, "counted@test.com").await;
+    let admin = create_admin_with_token(&ctx, "mod-count@test.com").await;
+    let proposal_id = create_proposal(&ctx, &user_id).await;
+    let mut comment_ids = Vec::new();
+    for body in ["first", "second"] {
+        let comment = api::create_comment(
+            token.clone(),
+            ContentTargetType::Proposal,
+            proposal_id.clone(),
+            None,
+            body.to_string(),
+        )
+        .await
+        .expect("Should create comment");
+        comment_ids.push(comment.id.to_string());
+    }
+    assert_eq!(cached_comment_count(&ctx, &proposal_id).await, 2);
+
+    api::hide_content(admin, ContentTargetType::Comment, comment_ids[0].clone())
+        .await
+        .expect("Admin should hide comment");
+    assert_eq!(cached_comment_count(&ctx, &proposal_id).await, 1);
+    let detail = api::get_proposal(proposal_id, None)
+        .await
+        .expect("Should get proposal");
+    assert_eq!(detail.comment_count, 1);
+}
+
+#[tokio::test]
+async fn recompute_counts_repairs_drifted_counts() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let (token, user_id) = create_user_with_token(&ctx, "drift@test.com").await;
+    let admin = create_admin_with_token(&ctx, "mod-drift@test.com").await;
+    let drifted = create_proposal(&ctx, &user_id).await;
+    let accurate = create_proposal(&ctx, &user_id).await;
+    api::create_comment(
+        token.clone(),
+        ContentTargetType::Proposal,
+        drifted.clone(),
+        None,
+        "hello".to_string(),
+    )
+    .await
+    .expect("Should create comment");
+
+    sqlx::query("update proposals set comment_count = 7 where id = $1")
+        .bind(&drifted)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should corrupt count");
+
+    let err = api::recompute_counts(token)
+        .await
+        .expect_err("Non-admins should be rejected");
+    assert!(err.to_string().contains("forbidden"));
+
+    let report = api::recompute_counts(admin.clone())
+        .await
+        .expect("Admin should recompute");
+    assert_eq!(report.scanned, 2);
+    assert_eq!(report.corrected, 1);
+    assert_eq!(cached_comment_count(&ctx, &drifted).await, 1);
+    assert_eq!(cached_comment_count(&ctx, &accurate).await, 0);
+
+    let again = api::recompute_counts(admin)
+        .await
+        .expect("Admin should recompute");
+    assert_eq!(again.corrected, 0);
+}
+
+#[tokio::test]
+async fn unhidden_proposals_are_synced_again() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let admin = create_admin_with_token(&ctx, "sync-admin@test.com").await;
+    let (_, author_id) = create_user_with_token(&ctx, "sync-author@test.com").await;
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+
+    let first = api::sync_proposals(None, 10, None)
+        .await
+        .expect("Should sync proposals");
+    let cursor: api::types::SyncCursor =
+        first.proposals.last().expect("Proposal should sync").into();
+
+    api::hide_content(
+        admin.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+    )
+    .await
+    .expect("Should hide");
+    let hidden = api::sync_proposals(Some(cursor), 10, None)
+        .await
+        .expect("Should sync proposals");
+    assert!(hidden.proposals.is_empty());
+    assert_eq!(
+        hidden
+            .removed_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>(),
+        vec![proposal_id.clone()]
+    );
+
+    // The client keeps its cursor: that sync brought no proposals to move it.
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    api::unhide_content(admin, ContentTargetType::Proposal, proposal_id.clone())
+        .await
+        .expect("Should unhide");
+    let restored = api::sync_proposals(Some(cursor), 10, None)
+        .await
+        .expect("Should sync proposals");
+    assert_eq!(
+        restored
+            .proposals
+            .iter()
+            .map(|p| p.id.to_string())
+            .collect::<Vec<_>>(),
+        vec![proposal_id],
+        "An unhidden proposal comes back to clients that dropped it"
+    );
+}
@@ -0,0 +1,193 @@
+use api::test_utils::TestContext;
+
+async fn create_user_with_token(ctx: &TestContext, email: &str) -> String {
+    api::signup(email.to_string(), "Password123".to_string(), None, None)
+        .await
+        .expect("Signup should succeed");
+
+    sqlx::query("UPDATE users SET email_verified = true WHERE email = $1")
+        .bind(email)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should verify user");
+
+    api::signin(email.to_string(), "Password123".to_string(), None)
+        .await
+        .expect("Signin should succeed")
+}
+
+/// SQLite `updated_at` has millisecond precision; keep consecutive writes distinct.
+fn tick() {
+    std::thread::sleep(std::time::Duration::from_millis(5));
+}
+
+async fn save(
+    token: &str,
+    name: &str,
+    expected: Option<time::OffsetDateTime>,
+) -> Result<api::types::Profile, String> {
+    api::upsert_profile(
+        token.to_string(),
+        name.to_string(),
+        String::new(),
+        None,
+        None,
+        expected,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tokio::test]
+async fn upsert_profile_creates_then_updates_with_loaded_updated_at() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "profile@test.com").await;
+    let created = save(&token, "Ada", None)
+        .await
+        .expect("Should create profile");
+    assert_eq!(created.display_name, "Ada");
+    tick();
+
+    let updated = save(&token, "Ada L.", Some(created.updated_at))
+        .await
+        .expect("Should update profile");
+    assert_eq!(updated.display_name, "Ada L.");
+}
+
+#[tokio::test]
+async fn upsert_profile_rejects_stale_or_missing_updated_at() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "profile-conflict@test.com").await;
+    let created = save(&token, "Grace", None)
+        .await
+        .expect("Should create profile");
+    tick();
+    save(&token, "Grace H.", Some(created.updated_at))
+        .await
+        .expect("Should update profile");
+
+    let stale = save(&token, "Stale tab", Some(created.updated_at))
+        .await
+        .expect_err("Stale updated_at should conflict");
+    assert!(stale.contains(api::types::CONFLICT_ERROR));
+
+    let blind = save(&token, "Blind write", None)
+        .await
+        .expect_err("Overwriting an existing profile needs its updated_at");
+    assert!(blind.contains(api::types::CONFLICT_ERROR));
+}
+
+#[tokio::test]
+async fn me_reports_profile_completeness() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "complete@test.com").await;
+    let me = api::auth_me(token.clone()).await.expect("Should load me");
+    assert!(!me.profile_complete);
+    assert_eq!(me.profile_completeness.percent, 0);
+
+    save(&token, "Ada", None)
+        .await
+        .expect("Should create profile");
+    let me = api::auth_me(token.clone()).await.expect("Should load me");
+    assert!(me.profile_complete);
+    let bare = me.profile_completeness;
+    assert!(bare.display_name && !bare.bio && !bare.avatar && !bare.location);
+    assert_eq!(bare.percent, 25);
+    tick();
+
+    api::upsert_profile(
+        token.clone(),
+        "Ada".to_string(),
+        "Mathematician".to_string(),
+        Some("https://example.com/ada.png".to_string()),
+        Some("London".to_string()),
+        me.profile.map(|p| p.updated_at),
+    )
+    .await
+    .expect("Should fill profile");
+    let full = api::auth_me(token)
+        .await
+        .expect("Should load me")
+        .profile_completeness;
+    assert!(full.display_name && full.bio && full.avatar && full.location);
+    assert_eq!(full.percent, 100);
+}
+
+async fn last_seen_at(user_id: &str) -> Option<time::OffsetDateTime> {
+    api::get_public_profile(user_id.to_string(), None)
+        .await
+        .expect("Should get public profile")
+        .last_seen_at
+}
+
+#[tokio::test]
+async fn last_seen_at_advances_at_most_once_per_throttle_window() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "seen@test.com").await;
+    let user_id: String = sqlx::query_scalar("select CAST(id as TEXT) from users where email = $1")
+        .bind("seen@test.com")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    let set_last_seen = |value: String| {
+        let pool = ctx.pool.clone();
+        let user_id = user_id.clone();
+        async move {
+            sqlx::query(&format!(
+                "update users set last_seen_at = '{value}' where id = '{user_id}'"
+            ))
+            .execute(&pool)
+            .await
+            .expect("Should set last_seen_at");
+        }
+    };
+    let stamp = |at: time::OffsetDateTime| {
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            at.year(),
+            u8::from(at.month()),
+            at.day(),
+            at.hour(),
+            at.minute(),
+            at.second()
+        )
+    };
+
+    // A stale value is refreshed by the next authenticated call.
+    set_last_seen("2020-01-01 00:00:00".to_string()).await;
+    let before = time::OffsetDateTime::now_utc() - time::Duration::seconds(5);
+    api::list_my_videos(token.clone(), 10, 0)
+        .await
+        .expect("Should list my videos");
+    let seen = last_seen_at(&user_id)
+        .await
+        .expect("Should record last_seen_at");
+    assert!(seen >= before, "{seen} should be after {before}");
+
+    // Within the throttle window it is left alone.
+    let recent = time::OffsetDateTime::now_utc() - time::Duration::minutes(10);
+    set_last_seen(stamp(recent)).await;
+    api::list_my_videos(token, 10, 0)
+        .await
+        .expect("Should list my videos");
+    let seen = last_seen_at(&user_id)
+        .await
+        .expect("Should keep last_seen_at");
+    assert_eq!(seen.unix_timestamp(), recent.unix_timestamp());
+
+    let err = api::get_public_profile(uuid::Uuid::new_v4().to_string(), None)
+        .await
+        .expect_err("Unknown users are not found");
+    assert_eq!(
+        api::types::ErrorCode::from_error(&err.to_string()),
+        Some(api::types::ErrorCode::NotFound)
+    );
+}
@@ -0,0 +1,603 @@
+use api::test_utils::TestContext;
+
+async fn create_user_with_token(ctx: &TestContext, email: &str) -> String {
+    api::signup(email.to_string(), "Password123".to_string(), None, None)
+        .await
+        .expect("Signup should succeed");
+
+    sqlx::query("UPDATE users SET email_verified = true WHERE email = $1")
+        .bind(email)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should verify user");
+
+    api::signin(email.to_string(), "Password123".to_string(), None)
+        .await
+        .expect("Signin should succeed")
+}
+
+async fn create_proposal(token: &str, title: &str) -> String {
+    api::create_proposal(
+        token.to_string(),
+        title.to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Should create proposal")
+    .id
+    .to_string()
+}
+
+async fn bundle_titles(program_id: &str) -> Vec<String> {
+    api::get_program(program_id.to_string(), None)
+        .await
+        .expect("Should fetch program")
+        .proposals
+        .into_iter()
+        .map(|p| p.title)
+        .collect()
+}
+
+#[tokio::test]
+async fn set_program_items_replaces_bundle_in_order() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "bundler@test.com").await;
+    let a = create_proposal(&token, "A").await;
+    let b = create_proposal(&token, "B").await;
+    let c = create_proposal(&token, "C").await;
+    let program = api::create_program(token.clone(), "P".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    let program_id = program.id.to_string();
+
+    api::set_program_items(
+        token.clone(),
+        program_id.clone(),
+        vec![a.clone(), b.clone()],
+    )
+    .await
+    .expect("Should set items");
+    assert_eq!(bundle_titles(&program_id).await, vec!["A", "B"]);
+
+    api::set_program_items(token, program_id.clone(), vec![c, a])
+        .await
+        .expect("Should replace items");
+    assert_eq!(bundle_titles(&program_id).await, vec!["C", "A"]);
+}
+
+#[tokio::test]
+async fn set_program_items_rolls_back_on_unknown_proposal() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "rollback@test.com").await;
+    let a = create_proposal(&token, "A").await;
+    let program = api::create_program(token.clone(), "P".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    let program_id = program.id.to_string();
+
+    api::set_program_items(token.clone(), program_id.clone(), vec![a.clone()])
+        .await
+        .expect("Should set items");
+
+    let missing = "00000000-0000-0000-0000-000000000000".to_string();
+    let result = api::set_program_items(token, program_id.clone(), vec![missing, a]).await;
+    assert!(result.is_err(), "Unknown proposal should be rejected");
+    assert_eq!(bundle_titles(&program_id).await, vec!["A"]);
+}
+
+#[tokio::test]
+async fn reorder_and_remove_program_items() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "reorder@test.com").await;
+    let other = create_user_with_token(&ctx, "meddler@test.com").await;
+    let a = create_proposal(&token, "A").await;
+    let b = create_proposal(&token, "B").await;
+    let c = create_proposal(&token, "C").await;
+    let outsider = create_proposal(&token, "D").await;
+    let program = api::create_program(token.clone(), "P".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    let program_id = program.id.to_string();
+    api::set_program_items(
+        token.clone(),
+        program_id.clone(),
+        vec![a.clone(), b.clone(), c.clone()],
+    )
+    .await
+    .expect("Should set items");
+
+    api::reorder_program_items(
+        token.clone(),
+        program_id.clone(),
+        vec![c.clone(), a.clone()],
+    )
+    .await
+    .expect("Should reorder items");
+    assert_eq!(bundle_titles(&program_id).await, vec!["C", "A", "B"]);
+
+    let result =
+        api::reorder_program_items(token.clone(), program_id.clone(), vec![b.clone(), outsider])
+            .await;
+    assert!(result.is_err(), "Non-member should be rejected");
+    assert_eq!(bundle_titles(&program_id).await, vec!["C", "A", "B"]);
+    let result =
+        api::reorder_program_items(other.clone(), program_id.clone(), vec![b.clone()]).await;
+    assert!(result
+        .expect_err("Non-author")
+        .to_string()
+        .contains("not allowed"));
+
+    let result = api::remove_program_item(other, program_id.clone(), a.clone()).await;
+    assert!(result
+        .expect_err("Non-author")
+        .to_string()
+        .contains("not allowed"));
+    api::remove_program_item(token.clone(), program_id.clone(), a.clone())
+        .await
+        .expect("Should remove item");
+    assert_eq!(bundle_titles(&program_id).await, vec!["C", "B"]);
+    api::remove_program_item(token, program_id, a)
+        .await
+        .expect_err("Already removed");
+}
+
+#[tokio::test]
+async fn set_program_items_requires_program_author() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let owner = create_user_with_token(&ctx, "owner@test.com").await;
+    let other = create_user_with_token(&ctx, "other@test.com").await;
+    let a = create_proposal(&owner, "A").await;
+    let program = api::create_program(owner, "P".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+
+    let result = api::set_program_items(other, program.id.to_string(), vec![a]).await;
+    let error = result
+        .expect_err("Non-author should be rejected")
+        .to_string();
+    assert!(error.contains("not allowed"));
+}
+
+#[tokio::test]
+async fn editing_a_missing_program_is_not_found() {
+    use api::types::ErrorCode;
+
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "nowhere@test.com").await;
+    let a = create_proposal(&token, "A").await;
+    let missing = uuid::Uuid::new_v4().to_string();
+
+    let results = [
+        api::add_program_item(token.clone(), missing.clone(), a.clone(), 0).await,
+        api::set_program_items(token.clone(), missing.clone(), vec![a.clone()]).await,
+        api::reorder_program_items(token.clone(), missing.clone(), vec![a.clone()]).await,
+        api::remove_program_item(token, missing, a).await,
+    ];
+    for result in results {
+        let error = result.expect_err("Missing program").to_string();
+        assert_eq!(
+            ErrorCode::from_error(&error),
+            Some(ErrorCode::NotFound),
+            "{error}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn list_proposal_programs_returns_every_bundle() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "member@test.com").await;
+    let shared = create_proposal(&token, "Shared").await;
+    let other = create_proposal(&token, "Other").await;
+
+    let first = api::create_program(token.clone(), "First".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    let second = api::create_program(token.clone(), "Second".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    api::set_program_items(token.clone(), first.id.to_string(), vec![shared.clone()])
+        .await
+        .expect("Should set items");
+    api::set_program_items(
+        token.clone(),
+        second.id.to_string(),
+        vec![other.clone(), shared.clone()],
+    )
+    .await
+    .expect("Should set items");
+    api::set_vote(
+        token,
+        api::types::ContentTargetType::Program,
+        second.id.to_string(),
+        1,
+    )
+    .await
+    .expect("Should vote");
+
+    let memberships = api::list_proposal_programs(shared, None)
+        .await
+        .expect("Should list programs");
+    let summary: Vec<(String, i32, i64)> = memberships
+        .into_iter()
+        .map(|m| (m.program.title, m.position, m.program.vote_score))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![("Second".to_string(), 1, 1), ("First".to_string(), 0, 0)]
+    );
+
+    let other_memberships = api::list_proposal_programs(other, None)
+        .await
+        .expect("Should list programs");
+    assert_eq!(other_memberships.len(), 1);
+}
+
+#[tokio::test]
+async fn create_and_get_program_round_trip() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "creator@test.com").await;
+    let program = api::create_program(
+        token,
+        "Green city".into(),
+        "Summary".into(),
+        "# Body".into(),
+    )
+    .await
+    .expect("Should create program");
+    assert_eq!(program.vote_score, 0);
+
+    let detail = api::get_program(program.id.to_string(), None)
+        .await
+        .expect("Should fetch program");
+    assert_eq!(detail.program.id, program.id);
+    assert_eq!(detail.program.author_user_id, program.author_user_id);
+    assert_eq!(detail.program.title, "Green city");
+    assert_eq!(detail.program.summary, "Summary");
+    assert_eq!(detail.program.body_markdown, "# Body");
+    assert!(detail.proposals.is_empty());
+
+    assert_eq!(program.slug, "green-city");
+    let by_slug = api::get_program("green-city".to_string(), None)
+        .await
+        .expect("Should fetch program by slug");
+    assert_eq!(by_slug.program.id, program.id);
+}
+
+#[tokio::test]
+async fn list_programs_returns_newest_with_scores() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "lister@test.com").await;
+    let first = api::create_program(token.clone(), "First".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    api::create_program(token.clone(), "Second".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    api::set_vote(
+        token,
+        api::types::ContentTargetType::Program,
+        first.id.to_string(),
+        1,
+    )
+    .await
+    .expect("Should vote");
+
+    let programs = api::list_programs(10, None)
+        .await
+        .expect("Should list programs");
+    assert_eq!(programs.len(), 2);
+    let first_listed = programs
+        .iter()
+        .find(|p| p.id == first.id)
+        .expect("First program should be listed");
+    assert_eq!(first_listed.vote_score, 1);
+
+    let limited = api::list_programs(1, None)
+        .await
+        .expect("Should list programs");
+    assert_eq!(limited.len(), 1);
+}
+
+#[tokio::test]
+async fn programs_by_author_returns_only_that_authors_programs() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let alice = create_user_with_token(&ctx, "alice-programs@test.com").await;
+    let bob = create_user_with_token(&ctx, "bob-programs@test.com").await;
+    let older = api::create_program(
+        alice.clone(),
+        "Alice one".into(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Should create program");
+    let newer = api::create_program(alice, "Alice two".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    let theirs = api::create_program(bob.clone(), "Bob one".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    sqlx::query("UPDATE programs SET created_at = '2000-01-01 00:00:00' WHERE id = $1")
+        .bind(older.id.to_string())
+        .execute(&ctx.pool)
+        .await
+        .expect("Should backdate program");
+    api::set_vote(
+        bob,
+        api::types::ContentTargetType::Program,
+        older.id.to_string(),
+        1,
+    )
+    .await
+    .expect("Should vote");
+
+    let mine = api::list_programs_by_author(older.author_user_id.to_string(), 10, 0, None)
+        .await
+        .expect("Should list author programs");
+    let ids: Vec<_> = mine.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![newer.id, older.id]);
+    assert_eq!(mine[1].vote_score, 1);
+
+    let bobs = api::list_programs_by_author(theirs.author_user_id.to_string(), 10, 0, None)
+        .await
+        .expect("Should list author programs");
+    assert_eq!(bobs.len(), 1);
+    assert_eq!(bobs[0].id, theirs.id);
+}
+
+#[tokio::test]
+async fn update_program_changes_fields_for_author_only() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let owner = create_user_with_token(&ctx, "editor@test.com").await;
+    let other = create_user_with_token(&ctx, "intruder@test.com").await;
+    let program = api::create_program(owner.clone(), "Old".into(), "Old".into(), "Old".into())
+        .await
+        .expect("Should create program");
+
+    let updated = api::update_program(
+        owner,
+        program.id.to_string(),
+        "New".into(),
+        "New summary".into(),
+        "New body".into(),
+        program.updated_at,
+    )
+    .await
+    .expect("Should update program");
+    assert_eq!(updated.id, program.id);
+    assert_eq!(updated.title, "New");
+    assert_eq!(updated.summary, "New summary");
+    assert_eq!(updated.body_markdown, "New body");
+
+    let result = api::update_program(
+        other,
+        program.id.to_string(),
+        "Hijacked".into(),
+        String::new(),
+        String::new(),
+        updated.updated_at,
+    )
+    .await;
+    assert!(result
+        .expect_err("Non-author should be rejected")
+        .to_string()
+        .contains("not allowed"));
+
+    let detail = api::get_program(program.id.to_string(), None)
+        .await
+        .expect("Should fetch program");
+    assert_eq!(detail.program.title, "New");
+}
+
+#[tokio::test]
+async fn add_program_item_bundles_and_repositions() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "adder@test.com").await;
+    let a = create_proposal(&token, "A").await;
+    let b = create_proposal(&token, "B").await;
+    let program = api::create_program(token.clone(), "P".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    let program_id = program.id.to_string();
+
+    api::add_program_item(token.clone(), program_id.clone(), a.clone(), 0)
+        .await
+        .expect("Should add item");
+    api::add_program_item(token.clone(), program_id.clone(), b, 1)
+        .await
+        .expect("Should add item");
+    assert_eq!(bundle_titles(&program_id).await, vec!["A", "B"]);
+
+    api::add_program_item(token, program_id.clone(), a, 2)
+        .await
+        .expect("Should move item");
+    assert_eq!(bundle_titles(&program_id).await, vec!["B", "A"]);
+}
+
+#[tokio::test]
+async fn get_program_returns_bundled_proposal_tags() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "tagger@test.com").await;
+    let proposal = api::create_proposal(
+        token.clone(),
+        "Tagged".into(),
+        String::new(),
+        String::new(),
+        "housing, rent cap".into(),
+    )
+    .await
+    .expect("Should create proposal");
+    let program = api::create_program(token.clone(), "P".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    api::add_program_item(token, program.id.to_string(), proposal.id.to_string(), 0)
+        .await
+        .expect("Should add item");
+
+    let detail = api::get_program(program.id.to_string(), None)
+        .await
+        .expect("Should fetch program");
+    assert_eq!(detail.proposals.len(), 1);
+    assert_eq!(detail.proposals[0].tags, vec!["housing", "rent cap"]);
+}
+
+#[tokio::test]
+async fn count_programs_matches_listed_rows() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "program-counter@test.com").await;
+    for title in ["One", "Two", "Hidden"] {
+        api::create_program(token.clone(), title.into(), String::new(), String::new())
+            .await
+            .expect("Should create program");
+    }
+    sqlx::query("UPDATE programs SET hidden_at = CURRENT_TIMESTAMP WHERE title = 'Hidden'")
+        .execute(&ctx.pool)
+        .await
+        .expect("Should hide program");
+
+    let total = api::count_programs(None).await.expect("Should count");
+    let listed = api::list_programs(100, None)
+        .await
+        .expect("Should list programs");
+    assert_eq!(total, 2);
+    assert_eq!(total, listed.len() as i64);
+}
+
+#[tokio::test]
+async fn update_program_rejects_stale_updated_at() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "two-tabs@test.com").await;
+    let loaded = api::create_program(token.clone(), "Draft".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    // SQLite `updated_at` has millisecond precision; keep the two writes distinct.
+    std::thread::sleep(std::time::Duration::from_millis(5));
+
+    let first = api::update_program(
+        token.clone(),
+        loaded.id.to_string(),
+        "First tab".into(),
+        String::new(),
+        String::new(),
+        loaded.updated_at,
+    )
+    .await
+    .expect("Fresh updated_at should succeed");
+    assert_ne!(first.updated_at, loaded.updated_at);
+
+    let stale = api::update_program(
+        token.clone(),
+        loaded.id.to_string(),
+        "Second tab".into(),
+        String::new(),
+        String::new(),
+        loaded.updated_at,
+    )
+    .await
+    .expect_err("Stale updated_at should conflict");
+    assert!(stale.to_string().contains(api::types::CONFLICT_ERROR));
+
+    let detail = api::get_program(loaded.id.to_string(), None)
+        .await
+        .expect("Should fetch program");
+    assert_eq!(detail.program.title, "First tab");
+}
+
+#[tokio::test]
+async fn get_program_reports_is_author_for_the_caller() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let author = create_user_with_token(&ctx, "program-owner@test.com").await;
+    let reader = create_user_with_token(&ctx, "program-reader@test.com").await;
+    let program = api::create_program(author.clone(), "Owned".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    let id = program.id.to_string();
+
+    let detail = api::get_program(id.clone(), Some(author))
+        .await
+        .expect("Should fetch program as author");
+    assert!(detail.is_author);
+
+    let detail = api::get_program(id.clone(), Some(reader))
+        .await
+        .expect("Should fetch program as another user");
+    assert!(!detail.is_author);
+
+    let detail = api::get_program(id, None)
+        .await
+        .expect("Should fetch program anonymously");
+    assert!(!detail.is_author);
+}
+
+#[tokio::test]
+async fn get_program_if_changed_tracks_bundled_proposals() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "etag-program@test.com").await;
+    let a = create_proposal(&token, "A").await;
+    let b = create_proposal(&token, "B").await;
+    let program = api::create_program(token.clone(), "P".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    let program_id = program.id.to_string();
+    api::add_program_item(token.clone(), program_id.clone(), a, 0)
+        .await
+        .expect("Should add item");
+
+    let etag = api::get_program(program_id.clone(), None)
+        .await
+        .expect("Should fetch program")
+        .etag;
+    let unchanged = api::get_program_if_changed(program_id.clone(), etag.clone(), None)
+        .await
+        .expect("Conditional get should succeed");
+    assert_eq!(unchanged, api::types::Conditional::NotModified);
+
+    api::add_program_item(token, program_id.clone(), b, 1)
+        .await
+        .expect("Should add item");
+    match api::get_program_if_changed(program_id, etag.clone(), None)
+        .await
+        .expect("Conditional get should succeed")
+    {
+        api::types::Conditional::Modified(detail) => {
+            assert_eq!(detail.proposals.len(), 2);
+            assert_ne!(detail.etag, etag);
+        }
+        api::types::Conditional::NotModified => panic!("new item should change the etag"),
+    }
+}
@@ -0,0 +1,127 @@
+use api::test_utils::TestContext;
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use time::format_description::well_known::Rfc3339;
+use tower::ServiceExt;
+
+async fn create_user_with_token(ctx: &TestContext, email: &str) -> String {
+    api::signup(email.to_string(), "Password123".to_string(), None, None)
+        .await
+        .expect("Signup should succeed");
+    sqlx::query("UPDATE users SET email_verified = true WHERE email = $1")
+        .bind(email)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should verify user");
+    api::signin(email.to_string(), "Password123".to_string(), None)
+        .await
+        .expect("Signin should succeed")
+}
+
+async fn get(uri: &str) -> (StatusCode, Option<String>, String) {
+    let res = api::sitemap::routes(axum::Router::new())
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = res.status();
+    let content_type = res
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|v| v.to_str().unwrap().to_string());
+    let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        content_type,
+        String::from_utf8(body.to_vec()).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn sitemap_lists_visible_proposals_and_programs() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "sitemap@test.com").await;
+    let proposal = api::create_proposal(
+        token.clone(),
+        "Pistes cyclables".to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Should create proposal");
+    let hidden = api::create_proposal(
+        token.clone(),
+        "Cachée".to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Should create proposal");
+    sqlx::query("update proposals set hidden_at = CURRENT_TIMESTAMP where id = $1")
+        .bind(hidden.id.to_string())
+        .execute(&ctx.pool)
+        .await
+        .expect("Should hide proposal");
+    let program = api::create_program(token, "Mobilité".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+
+    let (status, content_type, xml) = get("/sitemap.xml").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.unwrap().starts_with("application/xml"));
+    assert!(xml.contains("<urlset"), "{xml}");
+
+    let loc = format!(
+        "<loc>http://localhost:8080/proposals/{}</loc>",
+        proposal.slug
+    );
+    let entry = xml
+        .lines()
+        .find(|line| line.contains(&loc))
+        .unwrap_or_else(|| panic!("missing {loc} in {xml}"));
+    let lastmod = entry
+        .split("<lastmod>")
+        .nth(1)
+        .and_then(|rest| rest.split("</lastmod>").next())
+        .expect("Entry should have a lastmod");
+    let lastmod = time::OffsetDateTime::parse(lastmod, &Rfc3339).expect("lastmod is RFC 3339");
+    let stored = api::get_proposal(proposal.id.to_string(), None)
+        .await
+        .expect("Should get proposal");
+    assert_eq!(lastmod, stored.proposal.updated_at);
+
+    assert!(xml.contains(&format!(
+        "<loc>http://localhost:8080/programs/{}</loc>",
+        program.slug
+    )));
+    assert!(!xml.contains(&hidden.slug), "Hidden proposals are left out");
+
+    let (status, _, _) = get("/sitemap.xml?page=2").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn sitemap_is_withheld_when_reads_require_sign_in() {
+    let ctx = TestContext::with_config(|c| c.public_read = false).await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "private-sitemap@test.com").await;
+    api::create_proposal(
+        token,
+        "Members only".into(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Should create proposal");
+
+    let (status, _, xml) = get("/sitemap.xml").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(!xml.contains("members-only"));
+}
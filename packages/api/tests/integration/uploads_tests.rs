@@ -0,0 +1,715 @@
+use api::test_utils::TestContext;
+use api::types::ContentTargetType;
+
+async fn create_user_with_token(ctx: &TestContext, email: &str) -> String {
+    api::signup(email.to_string(), "Password123".to_string(), None, None)
+        .await
+        .expect("Signup should succeed");
+
+    sqlx::query("UPDATE users SET email_verified = true WHERE email = $1")
+        .bind(email)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should verify user");
+
+    api::signin(email.to_string(), "Password123".to_string(), None)
+        .await
+        .expect("Signin should succeed")
+}
+
+async fn create_proposal(token: &str) -> String {
+    api::create_proposal(
+        token.to_string(),
+        "With video".to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+    )
+    .await
+    .expect("Should create proposal")
+    .id
+    .to_string()
+}
+
+/// Record an upload intent for `email` the way `create_video_upload_intent` would,
+/// without needing S3 to presign it.
+async fn issue_storage_key(ctx: &TestContext, email: &str, proposal_id: &str) -> String {
+    let key = format!("videos/proposal/{proposal_id}/{}", uuid::Uuid::new_v4());
+    sqlx::query(
+        "insert into upload_intents (user_id, storage_key) select id, $2 from users where email = $1",
+    )
+    .bind(email)
+    .bind(&key)
+    .execute(&ctx.pool)
+    .await
+    .expect("Should record upload intent");
+    key
+}
+
+#[tokio::test]
+async fn finalize_confirms_filesystem_upload() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "uploader@test.com").await;
+    let proposal_id = create_proposal(&token).await;
+    let key = issue_storage_key(&ctx, "uploader@test.com", &proposal_id).await;
+    ctx.state
+        .storage
+        .upload(&key, b"video bytes".to_vec())
+        .await
+        .expect("Should store file");
+
+    let video = api::finalize_video_upload(
+        token,
+        ContentTargetType::Proposal,
+        proposal_id,
+        key.clone(),
+        "video/mp4".to_string(),
+        11,
+    )
+    .await
+    .expect("Should finalize stored upload");
+    assert_eq!(video.storage_key, key);
+    assert_eq!(video.storage_bucket, "local");
+}
+
+#[tokio::test]
+async fn finalize_rejects_missing_filesystem_upload() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "ghost-uploader@test.com").await;
+    let proposal_id = create_proposal(&token).await;
+    let key = issue_storage_key(&ctx, "ghost-uploader@test.com", &proposal_id).await;
+
+    let err = api::finalize_video_upload(
+        token,
+        ContentTargetType::Proposal,
+        proposal_id,
+        key,
+        "video/mp4".to_string(),
+        11,
+    )
+    .await
+    .expect_err("Missing upload should be rejected");
+    assert!(err.to_string().contains("upload not found"));
+
+    let count: i64 = sqlx::query_scalar("select count(*) from videos")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count videos");
+    assert_eq!(count, 0);
+}
+
+#[tokio::test]
+async fn finalize_rejects_storage_key_not_issued_to_caller() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let owner = create_user_with_token(&ctx, "key-owner@test.com").await;
+    let intruder = create_user_with_token(&ctx, "key-thief@test.com").await;
+    let proposal_id = create_proposal(&owner).await;
+    let other_proposal_id = create_proposal(&owner).await;
+    let key = issue_storage_key(&ctx, "key-owner@test.com", &proposal_id).await;
+    ctx.state
+        .storage
+        .upload(&key, b"video bytes".to_vec())
+        .await
+        .expect("Should store file");
+
+    // Someone else's key.
+    let err = api::finalize_video_upload(
+        intruder.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        key.clone(),
+        "video/mp4".to_string(),
+        11,
+    )
+    .await
+    .expect_err("Foreign key should be rejected");
+    assert!(err.to_string().contains("invalid storage key"));
+
+    // Own key, but attached to a different target.
+    let err = api::finalize_video_upload(
+        owner.clone(),
+        ContentTargetType::Proposal,
+        other_proposal_id,
+        key.clone(),
+        "video/mp4".to_string(),
+        11,
+    )
+    .await
+    .expect_err("Key for another target should be rejected");
+    assert!(err.to_string().contains("invalid storage key"));
+
+    // A hand-made key under the right prefix that was never issued.
+    let err = api::finalize_video_upload(
+        owner.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        format!("videos/proposal/{proposal_id}/forged"),
+        "video/mp4".to_string(),
+        11,
+    )
+    .await
+    .expect_err("Unissued key should be rejected");
+    assert!(err.to_string().contains("invalid storage key"));
+
+    let video = api::finalize_video_upload(
+        owner.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        key.clone(),
+        "video/mp4".to_string(),
+        11,
+    )
+    .await
+    .expect("Owner should finalize their key");
+    assert_eq!(video.storage_key, key);
+
+    // Intents are single-use.
+    let err = api::finalize_video_upload(
+        owner,
+        ContentTargetType::Proposal,
+        proposal_id,
+        key,
+        "video/mp4".to_string(),
+        11,
+    )
+    .await
+    .expect_err("Consumed key should be rejected");
+    assert!(err.to_string().contains("invalid storage key"));
+}
+
+#[tokio::test]
+async fn finalize_rejects_expired_upload_intent() {
+    let ctx = TestContext::with_config(|c| c.upload_intent_ttl_hours = 1).await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "slow-uploader@test.com").await;
+    let proposal_id = create_proposal(&token).await;
+    let key = issue_storage_key(&ctx, "slow-uploader@test.com", &proposal_id).await;
+    ctx.state
+        .storage
+        .upload(&key, b"video bytes".to_vec())
+        .await
+        .expect("Should store file");
+    sqlx::query(
+        "update upload_intents set created_at = '2000-01-01 00:00:00' where storage_key = $1",
+    )
+    .bind(&key)
+    .execute(&ctx.pool)
+    .await
+    .expect("Should backdate intent");
+
+    let err = api::finalize_video_upload(
+        token,
+        ContentTargetType::Proposal,
+        proposal_id,
+        key,
+        "video/mp4".to_string(),
+        11,
+    )
+    .await
+    .expect_err("Expired intent should be rejected");
+    assert!(err.to_string().contains("invalid storage key"));
+
+    let count: i64 = sqlx::query_scalar("select count(*) from videos")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count videos");
+    assert_eq!(count, 0);
+}
+
+#[tokio::test]
+async fn multipart_upload_assembles_parts_then_finalizes() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "chunker@test.com").await;
+    let proposal_id = create_proposal(&token).await;
+
+    let upload = api::start_multipart_upload(
+        token.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        "video/mp4".to_string(),
+    )
+    .await
+    .expect("Should start multipart upload");
+
+    let mut parts = Vec::new();
+    for (number, chunk) in [(2, b"second".to_vec()), (1, b"first-".to_vec())] {
+        let etag = api::upload_multipart_part(
+            token.clone(),
+            upload.storage_key.clone(),
+            upload.upload_id.clone(),
+            number,
+            chunk,
+        )
+        .await
+        .expect("Should upload part");
+        parts.push(api::types::UploadedPart {
+            part_number: number,
+            etag,
+        });
+    }
+    api::complete_multipart_upload(
+        token.clone(),
+        upload.storage_key.clone(),
+        upload.upload_id.clone(),
+        parts,
+    )
+    .await
+    .expect("Should complete multipart upload");
+
+    let video = api::finalize_video_upload(
+        token,
+        ContentTargetType::Proposal,
+        proposal_id,
+        upload.storage_key.clone(),
+        "video/mp4".to_string(),
+        11,
+    )
+    .await
+    .expect("Should finalize assembled upload");
+    assert_eq!(video.storage_key, upload.storage_key);
+}
+
+fn s3_storage() -> api::config::StorageConfig {
+    api::config::StorageConfig::S3 {
+        bucket: "videos".into(),
+        endpoint: "http://localhost:9000".into(),
+        region: "auto".into(),
+        access_key: "access".into(),
+        secret_key: "secret".into(),
+        media_base_url: None,
+        path_style: true,
+    }
+}
+
+#[tokio::test]
+async fn upload_intent_uses_configured_presign_ttl() {
+    let ctx = TestContext::with_config(|c| {
+        c.upload_presign_ttl_secs = 123;
+        c.storage = s3_storage();
+    })
+    .await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "presigner@test.com").await;
+    let proposal_id = create_proposal(&token).await;
+    let intent = api::create_video_upload_intent(
+        token,
+        ContentTargetType::Proposal,
+        proposal_id,
+        "video/mp4".to_string(),
+        1024,
+    )
+    .await
+    .expect("Should presign upload");
+    assert!(
+        intent.presigned_put_url.contains("X-Amz-Expires=123"),
+        "unexpected presigned url: {}",
+        intent.presigned_put_url
+    );
+    assert_eq!(
+        intent.headers.get("content-type").map(String::as_str),
+        Some("video/mp4"),
+        "Intent should echo the signed content type"
+    );
+}
+
+const STORAGE_ENVS: &[(&str, &str)] = &[
+    ("STORAGE_BUCKET", "videos"),
+    ("STORAGE_ENDPOINT", "http://localhost:9000"),
+    ("STORAGE_ACCESS_KEY", "access"),
+    ("STORAGE_SECRET_KEY", "secret"),
+];
+
+#[tokio::test]
+async fn filesystem_storage_refuses_presigned_intents() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+    // A bucket in the environment must not pull uploads away from the filesystem,
+    // where finalize would then never find them.
+    for (key, value) in STORAGE_ENVS {
+        std::env::set_var(key, value);
+    }
+
+    let token = create_user_with_token(&ctx, "local@test.com").await;
+    let proposal_id = create_proposal(&token).await;
+    let intent = api::create_video_upload_intent(
+        token,
+        ContentTargetType::Proposal,
+        proposal_id,
+        "video/mp4".to_string(),
+        1024,
+    )
+    .await;
+
+    for (key, _) in STORAGE_ENVS {
+        std::env::remove_var(key);
+    }
+    assert!(intent.is_err(), "Filesystem storage cannot presign uploads");
+    let issued: i64 = sqlx::query_scalar("select count(*) from upload_intents")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count intents");
+    assert_eq!(issued, 0);
+}
+
+#[tokio::test]
+async fn videos_by_author_lists_only_owner_uploads() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "filmmaker@test.com").await;
+    create_user_with_token(&ctx, "bystander@test.com").await;
+    let proposal_id = create_proposal(&token).await;
+    let key = issue_storage_key(&ctx, "filmmaker@test.com", &proposal_id).await;
+    ctx.state
+        .storage
+        .upload(&key, b"video bytes".to_vec())
+        .await
+        .expect("Should store file");
+    let video = api::finalize_video_upload(
+        token,
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        key,
+        "video/mp4".to_string(),
+        11,
+    )
+    .await
+    .expect("Should finalize stored upload");
+
+    let owner_id: String =
+        sqlx::query_scalar("select CAST(id as TEXT) from users where email = $1")
+            .bind("filmmaker@test.com")
+            .fetch_one(&ctx.pool)
+            .await
+            .expect("Should fetch user id");
+    let bystander_id: String =
+        sqlx::query_scalar("select CAST(id as TEXT) from users where email = $1")
+            .bind("bystander@test.com")
+            .fetch_one(&ctx.pool)
+            .await
+            .expect("Should fetch user id");
+
+    let mine = api::list_videos_by_author(owner_id, 10, 0, None)
+        .await
+        .expect("Should list author videos");
+    assert_eq!(mine.len(), 1);
+    assert_eq!(mine[0].id, video.id);
+    assert_eq!(mine[0].target_type, ContentTargetType::Proposal);
+    assert_eq!(mine[0].target_id.to_string(), proposal_id);
+
+    let theirs = api::list_videos_by_author(bystander_id, 10, 0, None)
+        .await
+        .expect("Should list author videos");
+    assert!(theirs.is_empty());
+}
+
+#[tokio::test]
+async fn finalize_records_byte_size() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "sized@test.com").await;
+    let proposal_id = create_proposal(&token).await;
+    let key = issue_storage_key(&ctx, "sized@test.com", &proposal_id).await;
+    ctx.state
+        .storage
+        .upload(&key, b"video bytes".to_vec())
+        .await
+        .expect("Should store file");
+
+    let err = api::finalize_video_upload(
+        token.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        key.clone(),
+        "video/mp4".to_string(),
+        0,
+    )
+    .await
+    .expect_err("Zero size should be rejected");
+    assert!(err.to_string().contains("invalid file size"));
+
+    let video = api::finalize_video_upload(
+        token,
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        key,
+        "video/mp4".to_string(),
+        11,
+    )
+    .await
+    .expect("Should finalize stored upload");
+    assert_eq!(video.byte_size, Some(11));
+
+    let listed = api::list_videos(ContentTargetType::Proposal, proposal_id, 10, None)
+        .await
+        .expect("Should list videos");
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].byte_size, Some(11));
+}
+
+/// Finalize a stored upload for `email` and return the video id.
+async fn upload_video(ctx: &TestContext, email: &str, token: &str, proposal_id: &str) -> String {
+    let key = issue_storage_key(ctx, email, proposal_id).await;
+    ctx.state
+        .storage
+        .upload(&key, b"video bytes".to_vec())
+        .await
+        .expect("Should store file");
+    api::finalize_video_upload(
+        token.to_string(),
+        ContentTargetType::Proposal,
+        proposal_id.to_string(),
+        key,
+        "video/mp4".to_string(),
+        11,
+    )
+    .await
+    .expect("Should finalize stored upload")
+    .id
+    .to_string()
+}
+
+/// Record a caption upload intent the way `create_caption_intent` would.
+async fn issue_caption_key(
+    ctx: &TestContext,
+    email: &str,
+    proposal_id: &str,
+    video_id: &str,
+    language: &str,
+) -> String {
+    let key = format!(
+        "videos/proposal/{proposal_id}/captions/{video_id}/{language}-{}.vtt",
+        uuid::Uuid::new_v4()
+    );
+    sqlx::query(
+        "insert into upload_intents (user_id, storage_key) select id, $2 from users where email = $1",
+    )
+    .bind(email)
+    .bind(&key)
+    .execute(&ctx.pool)
+    .await
+    .expect("Should record upload intent");
+    key
+}
+
+#[tokio::test]
+async fn finalized_captions_are_listed_with_their_video() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "captioner@test.com").await;
+    let proposal_id = create_proposal(&token).await;
+    let video_id = upload_video(&ctx, "captioner@test.com", &token, &proposal_id).await;
+
+    let mut keys = Vec::new();
+    for language in ["fr", "en"] {
+        let key = issue_caption_key(
+            &ctx,
+            "captioner@test.com",
+            &proposal_id,
+            &video_id,
+            language,
+        )
+        .await;
+        ctx.state
+            .storage
+            .upload(&key, b"WEBVTT\n\n".to_vec())
+            .await
+            .expect("Should store caption");
+        let track = api::finalize_caption(
+            token.clone(),
+            video_id.clone(),
+            language.to_string(),
+            key.clone(),
+        )
+        .await
+        .expect("Should attach caption");
+        assert_eq!(track.language, language);
+        assert_eq!(track.storage_key, key);
+        keys.push(key);
+    }
+
+    let listed = api::list_videos(ContentTargetType::Proposal, proposal_id.clone(), 10, None)
+        .await
+        .expect("Should list videos");
+    assert_eq!(listed.len(), 1);
+    let languages: Vec<_> = listed[0]
+        .captions
+        .iter()
+        .map(|c| c.language.as_str())
+        .collect();
+    assert_eq!(languages, vec!["en", "fr"]);
+    assert_eq!(listed[0].captions[1].storage_key, keys[0]);
+
+    // Uploading French again replaces the track rather than adding one.
+    let key = issue_caption_key(&ctx, "captioner@test.com", &proposal_id, &video_id, "fr").await;
+    ctx.state
+        .storage
+        .upload(&key, b"WEBVTT\n\n".to_vec())
+        .await
+        .expect("Should store caption");
+    api::finalize_caption(token, video_id.clone(), "fr".to_string(), key.clone())
+        .await
+        .expect("Should replace caption");
+    let uid: String = sqlx::query_scalar("select CAST(id as TEXT) from users where email = $1")
+        .bind("captioner@test.com")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should find user");
+    let by_author = api::list_videos_by_author(uid, 10, 0, None)
+        .await
+        .expect("Should list author videos");
+    assert_eq!(by_author[0].captions.len(), 2);
+    assert_eq!(by_author[0].captions[1].storage_key, key);
+}
+
+#[tokio::test]
+async fn finalize_caption_requires_the_video_owner() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let owner = create_user_with_token(&ctx, "caption-owner@test.com").await;
+    let intruder = create_user_with_token(&ctx, "caption-thief@test.com").await;
+    let proposal_id = create_proposal(&owner).await;
+    let video_id = upload_video(&ctx, "caption-owner@test.com", &owner, &proposal_id).await;
+    let key = issue_caption_key(
+        &ctx,
+        "caption-thief@test.com",
+        &proposal_id,
+        &video_id,
+        "fr",
+    )
+    .await;
+    ctx.state
+        .storage
+        .upload(&key, b"WEBVTT\n\n".to_vec())
+        .await
+        .expect("Should store caption");
+
+    let err = api::finalize_caption(intruder, video_id.clone(), "fr".to_string(), key.clone())
+        .await
+        .expect_err("Only the owner can caption a video");
+    assert!(err.to_string().contains("forbidden"));
+
+    let err = api::finalize_caption(owner, video_id, "../fr".to_string(), key)
+        .await
+        .expect_err("Language must be a language tag");
+    assert!(err.to_string().contains("invalid language"));
+
+    let count: i64 = sqlx::query_scalar("select count(*) from captions")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count captions");
+    assert_eq!(count, 0);
+}
+
+#[tokio::test]
+async fn my_videos_lists_only_the_callers_uploads() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let me = create_user_with_token(&ctx, "me-filmmaker@test.com").await;
+    let other = create_user_with_token(&ctx, "other-filmmaker@test.com").await;
+    let proposal_id = create_proposal(&me).await;
+    let first = upload_video(&ctx, "me-filmmaker@test.com", &me, &proposal_id).await;
+    let theirs = upload_video(&ctx, "other-filmmaker@test.com", &other, &proposal_id).await;
+    let second = upload_video(&ctx, "me-filmmaker@test.com", &me, &proposal_id).await;
+    // SQLite timestamps only have second precision, so make the order unambiguous.
+    sqlx::query(&format!(
+        "update videos set created_at = '2020-01-01 00:00:00' where id = '{first}'"
+    ))
+    .execute(&ctx.pool)
+    .await
+    .expect("Should backdate first upload");
+    api::set_vote(other.clone(), ContentTargetType::Video, first.clone(), 1)
+        .await
+        .expect("Should vote");
+
+    let mine = api::list_my_videos(me, 10, 0)
+        .await
+        .expect("Should list my videos");
+    let ids: Vec<String> = mine.iter().map(|v| v.id.to_string()).collect();
+    assert_eq!(ids, vec![second, first]);
+    assert_eq!(mine[1].vote_score, 1);
+
+    let theirs_listed = api::list_my_videos(other, 10, 0)
+        .await
+        .expect("Should list their videos");
+    assert_eq!(theirs_listed.len(), 1);
+    assert_eq!(theirs_listed[0].id.to_string(), theirs);
+
+    let err = api::list_my_videos(String::new(), 10, 0)
+        .await
+        .expect_err("Signed-out callers have no videos");
+    assert!(!err.to_string().is_empty());
+}
+
+#[tokio::test]
+async fn get_video_joins_owner_target_and_counts() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let owner = create_user_with_token(&ctx, "detail-owner@test.com").await;
+    let viewer = create_user_with_token(&ctx, "detail-viewer@test.com").await;
+    let proposal_id = create_proposal(&owner).await;
+    let video_id = upload_video(&ctx, "detail-owner@test.com", &owner, &proposal_id).await;
+    sqlx::query(
+        "insert into profiles (user_id, display_name) select id, 'Agnès' from users where email = $1",
+    )
+    .bind("detail-owner@test.com")
+    .execute(&ctx.pool)
+    .await
+    .expect("Should create profile");
+
+    api::mark_video_viewed(viewer.clone(), video_id.clone())
+        .await
+        .expect("Should mark viewed");
+    api::set_vote(
+        viewer.clone(),
+        ContentTargetType::Video,
+        video_id.clone(),
+        1,
+    )
+    .await
+    .expect("Should vote");
+    api::create_comment(
+        viewer,
+        ContentTargetType::Video,
+        video_id.clone(),
+        None,
+        "Nice".to_string(),
+    )
+    .await
+    .expect("Should comment");
+
+    let detail = api::get_video(video_id.clone(), None)
+        .await
+        .expect("Should get video");
+    assert_eq!(detail.video.id.to_string(), video_id);
+    assert_eq!(detail.video.target_id.to_string(), proposal_id);
+    assert_eq!(detail.video.vote_score, 1);
+    assert_eq!(detail.owner_display_name.as_deref(), Some("Agnès"));
+    assert_eq!(detail.target_title.as_deref(), Some("With video"));
+    assert_eq!(detail.target_slug.as_deref(), Some("with-video"));
+    assert_eq!(detail.view_count, 1);
+    assert_eq!(detail.comment_count, 1);
+
+    let err = api::get_video(uuid::Uuid::new_v4().to_string(), None)
+        .await
+        .expect_err("Unknown ids are not found");
+    assert_eq!(
+        api::types::ErrorCode::from_error(&err.to_string()),
+        Some(api::types::ErrorCode::NotFound)
+    );
+}
@@ -2,7 +2,7 @@ use api::test_utils::TestContext;
 use api::types::ContentTargetType;
 
 async fn create_user_with_token(ctx: &TestContext, email: &str) -> String {
-    api::signup(email.to_string(), "Password123".to_string())
+    api::signup(email.to_string(), "Password123".to_string(), None, None)
         .await
         .expect("Signup should succeed");
 
@@ -12,7 +12,7 @@ async fn create_user_with_token(ctx: &TestContext, email: &str) -> String {
         .await
         .expect("Should verify user");
 
-    api::signin(email.to_string(), "Password123".to_string())
+    api::signin(email.to_string(), "Password123".to_string(), None)
         .await
         .expect("Signin should succeed")
 }
@@ -89,3 +89,546 @@ async fn vote_state_roundtrip() {
     assert_eq!(state.score, 0);
     assert_eq!(state.my_vote, None);
 }
+
+#[tokio::test]
+async fn vote_breakdown_counts_up_and_down() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "breakdown@test.com").await;
+    let author_id: String = sqlx::query_scalar("select id from users where email = $1")
+        .bind("breakdown@test.com")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+
+    // Three other voters: two up, one down.
+    for (i, value) in [1i16, 1, -1].iter().enumerate() {
+        let voter_id: String = sqlx::query_scalar(
+            "insert into users (auth_subject, email) values ($1, $2) returning id",
+        )
+        .bind(format!("local:voter{i}"))
+        .bind(format!("voter{i}@test.com"))
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should create voter");
+        sqlx::query(
+            "insert into votes (user_id, target_type, target_id, value) values ($1, 'proposal', $2, $3)",
+        )
+        .bind(voter_id)
+        .bind(&proposal_id)
+        .bind(*value)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should insert vote");
+    }
+
+    api::set_vote(
+        token.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        -1,
+    )
+    .await
+    .expect("Should downvote");
+
+    let breakdown = api::get_vote_breakdown(token, ContentTargetType::Proposal, proposal_id)
+        .await
+        .expect("Should fetch breakdown");
+    assert_eq!(breakdown.upvotes, 2);
+    assert_eq!(breakdown.downvotes, 2);
+    assert_eq!(breakdown.score, 0);
+    assert_eq!(breakdown.my_vote, Some(-1));
+}
+
+#[tokio::test]
+async fn public_vote_score_needs_no_token() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "score@test.com").await;
+    let author_id: String = sqlx::query_scalar("select id from users where email = $1")
+        .bind("score@test.com")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+
+    let score = api::get_vote_score(ContentTargetType::Proposal, proposal_id.clone(), None)
+        .await
+        .expect("Should fetch score without a vote");
+    assert_eq!(score, 0);
+
+    for (i, value) in [1i16, 1, -1].iter().enumerate() {
+        let voter_id: String = sqlx::query_scalar(
+            "insert into users (auth_subject, email) values ($1, $2) returning id",
+        )
+        .bind(format!("local:scorer{i}"))
+        .bind(format!("scorer{i}@test.com"))
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should create voter");
+        sqlx::query(
+            "insert into votes (user_id, target_type, target_id, value) values ($1, 'proposal', $2, $3)",
+        )
+        .bind(voter_id)
+        .bind(&proposal_id)
+        .bind(*value)
+        .execute(&ctx.pool)
+        .await
+        .expect("Should insert vote");
+    }
+    api::set_vote(token, ContentTargetType::Proposal, proposal_id.clone(), 1)
+        .await
+        .expect("Should upvote");
+
+    let score = api::get_vote_score(ContentTargetType::Proposal, proposal_id, None)
+        .await
+        .expect("Should fetch score without a token");
+    assert_eq!(score, 2);
+
+    let error = api::get_vote_score(ContentTargetType::Proposal, "nope".to_string(), None)
+        .await
+        .expect_err("Invalid target id should be rejected");
+    assert!(error.to_string().contains("invalid target_id"));
+}
+
+async fn count_vote_activity(ctx: &TestContext, user_id: &str, proposal_id: &str) -> i64 {
+    sqlx::query_scalar(
+        "select count(*) from activity where user_id = $1 and target_id = $2 and action = 'voted'",
+    )
+    .bind(user_id)
+    .bind(proposal_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Should count activity")
+}
+
+#[tokio::test]
+async fn vote_activity_is_deduplicated_and_cleared() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "activity-voter@test.com").await;
+    let user_id: String = sqlx::query_scalar("select id from users where email = $1")
+        .bind("activity-voter@test.com")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    let proposal_id = create_proposal(&ctx, &user_id).await;
+
+    api::set_vote(
+        token.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        1,
+    )
+    .await
+    .expect("Should upvote");
+    assert_eq!(count_vote_activity(&ctx, &user_id, &proposal_id).await, 1);
+
+    api::set_vote(
+        token.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        -1,
+    )
+    .await
+    .expect("Should flip vote");
+    api::set_vote(
+        token.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        1,
+    )
+    .await
+    .expect("Should flip vote back");
+    assert_eq!(count_vote_activity(&ctx, &user_id, &proposal_id).await, 1);
+
+    // The unique index, not only set_vote, keeps it to one row per target.
+    let duplicate = sqlx::query(
+        "insert into activity (user_id, action, target_type, target_id) values ($1, 'voted', 'proposal', $2)",
+    )
+    .bind(&user_id)
+    .bind(&proposal_id)
+    .execute(&ctx.pool)
+    .await;
+    assert!(duplicate.is_err());
+
+    let feed = api::list_my_activity(token.clone(), 20, None)
+        .await
+        .expect("Should list activity");
+    assert!(feed
+        .iter()
+        .any(|item| item.action == api::types::ActivityAction::Voted));
+
+    api::set_vote(token, ContentTargetType::Proposal, proposal_id.clone(), 0)
+        .await
+        .expect("Should clear vote");
+    assert_eq!(count_vote_activity(&ctx, &user_id, &proposal_id).await, 0);
+}
+
+#[tokio::test]
+async fn proposal_rank_orders_by_score() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let alice = create_user_with_token(&ctx, "rank-a@test.com").await;
+    let bob = create_user_with_token(&ctx, "rank-b@test.com").await;
+    let author_id: String = sqlx::query_scalar("select id from users where email = $1")
+        .bind("rank-a@test.com")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+
+    let top = create_proposal(&ctx, &author_id).await;
+    let middle = create_proposal(&ctx, &author_id).await;
+    let neutral = create_proposal(&ctx, &author_id).await;
+    let bottom = create_proposal(&ctx, &author_id).await;
+
+    for token in [&alice, &bob] {
+        api::set_vote(token.clone(), ContentTargetType::Proposal, top.clone(), 1)
+            .await
+            .expect("Should upvote");
+    }
+    api::set_vote(
+        alice.clone(),
+        ContentTargetType::Proposal,
+        middle.clone(),
+        1,
+    )
+    .await
+    .expect("Should upvote");
+    api::set_vote(bob.clone(), ContentTargetType::Proposal, bottom.clone(), -1)
+        .await
+        .expect("Should downvote");
+
+    for (id, expected) in [(&top, 1), (&middle, 2), (&neutral, 3), (&bottom, 4)] {
+        let rank = api::get_proposal_rank(id.clone(), None)
+            .await
+            .expect("Should compute rank");
+        assert_eq!(rank.rank, expected);
+        assert_eq!(rank.total, 4);
+    }
+
+    let err = api::get_proposal_rank(uuid::Uuid::new_v4().to_string(), None)
+        .await
+        .expect_err("Unknown proposal has no rank");
+    assert!(err.to_string().contains("not found"));
+}
+
+#[tokio::test]
+async fn clear_vote_removes_existing_vote() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "clearer@test.com").await;
+    let author_id: String = sqlx::query_scalar("select id from users where email = $1")
+        .bind("clearer@test.com")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+
+    api::set_vote(
+        token.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        1,
+    )
+    .await
+    .expect("Should upvote");
+
+    let state = api::clear_vote(
+        token.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+    )
+    .await
+    .expect("Should clear vote");
+    assert_eq!(state.score, 0);
+    assert_eq!(state.my_vote, None);
+
+    let rows: i64 = sqlx::query_scalar("select count(*) from votes where target_id = $1")
+        .bind(&proposal_id)
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should count votes");
+    assert_eq!(rows, 0);
+}
+
+#[tokio::test]
+async fn clear_vote_without_vote_is_noop() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "noop@test.com").await;
+    let other = create_user_with_token(&ctx, "noop-other@test.com").await;
+    let author_id: String = sqlx::query_scalar("select id from users where email = $1")
+        .bind("noop@test.com")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+
+    api::set_vote(other, ContentTargetType::Proposal, proposal_id.clone(), 1)
+        .await
+        .expect("Other user should upvote");
+
+    let state = api::clear_vote(token, ContentTargetType::Proposal, proposal_id.clone())
+        .await
+        .expect("Clearing a missing vote should succeed");
+    assert_eq!(state.score, 1);
+    assert_eq!(state.my_vote, None);
+}
+
+#[tokio::test]
+async fn my_votes_lists_mixed_targets() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "history@test.com").await;
+    let author_id: String = sqlx::query_scalar("select id from users where email = $1")
+        .bind("history@test.com")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+    let cleared_id = create_proposal(&ctx, &author_id).await;
+    let program = api::create_program(token.clone(), "Plan".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+    let program_id = program.id.to_string();
+
+    api::set_vote(
+        token.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+        1,
+    )
+    .await
+    .expect("Should upvote proposal");
+    api::set_vote(
+        token.clone(),
+        ContentTargetType::Program,
+        program_id.clone(),
+        -1,
+    )
+    .await
+    .expect("Should downvote program");
+    api::set_vote(
+        token.clone(),
+        ContentTargetType::Proposal,
+        cleared_id.clone(),
+        1,
+    )
+    .await
+    .expect("Should upvote second proposal");
+    api::clear_vote(
+        token.clone(),
+        ContentTargetType::Proposal,
+        cleared_id.clone(),
+    )
+    .await
+    .expect("Should clear vote");
+
+    let all = api::list_my_votes(token.clone(), None, 10, 0)
+        .await
+        .expect("Should list votes");
+    assert_eq!(all.len(), 2);
+    assert!(all.iter().all(|v| v.target_id.to_string() != cleared_id));
+    let on_proposal = all
+        .iter()
+        .find(|v| v.target_id.to_string() == proposal_id)
+        .expect("Proposal vote listed");
+    assert_eq!(on_proposal.value, 1);
+    assert_eq!(on_proposal.title.as_deref(), Some("T"));
+
+    let programs = api::list_my_votes(token.clone(), Some(ContentTargetType::Program), 10, 0)
+        .await
+        .expect("Should list program votes");
+    assert_eq!(programs.len(), 1);
+    assert_eq!(programs[0].target_id.to_string(), program_id);
+    assert_eq!(programs[0].value, -1);
+    assert_eq!(programs[0].title.as_deref(), Some("Plan"));
+
+    let paged = api::list_my_votes(token, None, 1, 1)
+        .await
+        .expect("Should page votes");
+    assert_eq!(paged.len(), 1);
+}
+
+#[tokio::test]
+async fn concurrent_votes_leave_single_row() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let token = create_user_with_token(&ctx, "double-click@test.com").await;
+    let author_id: String = sqlx::query_scalar("select id from users where email = $1")
+        .bind("double-click@test.com")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+
+    let (first, second) = tokio::join!(
+        api::set_vote(
+            token.clone(),
+            ContentTargetType::Proposal,
+            proposal_id.clone(),
+            1,
+        ),
+        api::set_vote(
+            token.clone(),
+            ContentTargetType::Proposal,
+            proposal_id.clone(),
+            -1,
+        ),
+    );
+    first.expect("First vote should succeed");
+    second.expect("Second vote should succeed");
+
+    let values: Vec<i64> = sqlx::query_scalar(
+        "select value from votes where target_type = 'proposal' and target_id = $1",
+    )
+    .bind(&proposal_id)
+    .fetch_all(&ctx.pool)
+    .await
+    .expect("Should fetch votes");
+    assert_eq!(values.len(), 1);
+
+    let state = api::get_vote_state(token, ContentTargetType::Proposal, proposal_id)
+        .await
+        .expect("Should fetch vote state");
+    assert_eq!(state.my_vote.map(i64::from), Some(values[0]));
+    assert_eq!(state.score, values[0]);
+}
+
+#[tokio::test]
+async fn vote_changes_report_delta_and_rank() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+
+    let alice = create_user_with_token(&ctx, "delta-a@test.com").await;
+    let bob = create_user_with_token(&ctx, "delta-b@test.com").await;
+    let author_id: String = sqlx::query_scalar("select id from users where email = $1")
+        .bind("delta-a@test.com")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    let leader = create_proposal(&ctx, &author_id).await;
+    let proposal_id = create_proposal(&ctx, &author_id).await;
+    api::set_vote(bob.clone(), ContentTargetType::Proposal, leader, 1)
+        .await
+        .expect("Should upvote");
+
+    let vote = |token: &String, value: i16| {
+        api::set_vote(
+            token.clone(),
+            ContentTargetType::Proposal,
+            proposal_id.clone(),
+            value,
+        )
+    };
+
+    let state = vote(&alice, 1).await.expect("Should upvote");
+    assert_eq!((state.score, state.delta), (1, 1));
+    assert_eq!(state.rank, Some(1));
+
+    // Someone else's vote moves the score but not the caller's delta.
+    vote(&bob, 1).await.expect("Should upvote");
+    let state = vote(&alice, -1).await.expect("Should flip vote");
+    assert_eq!((state.score, state.delta), (0, -2));
+    assert_eq!(state.rank, Some(2));
+
+    let state = vote(&alice, -1).await.expect("Should repeat vote");
+    assert_eq!((state.score, state.delta), (0, 0));
+
+    let state = api::clear_vote(
+        alice.clone(),
+        ContentTargetType::Proposal,
+        proposal_id.clone(),
+    )
+    .await
+    .expect("Should clear vote");
+    assert_eq!((state.score, state.delta), (1, 1));
+
+    let state = api::get_vote_state(alice, ContentTargetType::Proposal, proposal_id.clone())
+        .await
+        .expect("Should fetch vote state");
+    assert_eq!((state.delta, state.rank), (0, None));
+}
+
+/// Two verified upvotes and one downvote from a voter who is then unverified, on a
+/// new proposal and program; returns their ids.
+async fn mixed_votes(ctx: &TestContext) -> (String, String) {
+    let alice = create_user_with_token(ctx, "verified-a@test.com").await;
+    let bob = create_user_with_token(ctx, "verified-b@test.com").await;
+    let carol = create_user_with_token(ctx, "unverified-c@test.com").await;
+    let author_id: String = sqlx::query_scalar("select id from users where email = $1")
+        .bind("verified-a@test.com")
+        .fetch_one(&ctx.pool)
+        .await
+        .expect("Should fetch user id");
+    let proposal_id = create_proposal(ctx, &author_id).await;
+    let program = api::create_program(alice.clone(), "P".into(), String::new(), String::new())
+        .await
+        .expect("Should create program");
+
+    for (target_type, target_id) in [
+        (ContentTargetType::Proposal, proposal_id.clone()),
+        (ContentTargetType::Program, program.id.to_string()),
+    ] {
+        for (token, value) in [(&alice, 1), (&bob, 1), (&carol, -1)] {
+            api::set_vote(token.clone(), target_type, target_id.clone(), value)
+                .await
+                .expect("Should vote");
+        }
+    }
+    sqlx::query("update users set email_verified = false where email = $1")
+        .bind("unverified-c@test.com")
+        .execute(&ctx.pool)
+        .await
+        .expect("Should unverify user");
+    (proposal_id, program.id.to_string())
+}
+
+#[tokio::test]
+async fn detail_reports_verified_score_alongside_raw_score() {
+    let ctx = TestContext::new().await;
+    ctx.set_global();
+    let (proposal_id, program_id) = mixed_votes(&ctx).await;
+
+    let proposal = api::get_proposal(proposal_id, None)
+        .await
+        .expect("Should get proposal");
+    assert_eq!(proposal.proposal.vote_score, 1);
+    assert_eq!(proposal.verified_vote_score, 2);
+
+    let program = api::get_program(program_id, None)
+        .await
+        .expect("Should get program");
+    assert_eq!(program.program.vote_score, 1);
+    assert_eq!(program.verified_vote_score, 2);
+}
+
+#[tokio::test]
+async fn verified_only_policy_excludes_unverified_votes() {
+    let ctx =
+        TestContext::with_config(|c| c.vote_policy = api::config::VotePolicy::VerifiedOnly).await;
+    ctx.set_global();
+    let (proposal_id, program_id) = mixed_votes(&ctx).await;
+
+    let proposal = api::get_proposal(proposal_id, None)
+        .await
+        .expect("Should get proposal");
+    assert_eq!(proposal.proposal.vote_score, 2);
+    assert_eq!(proposal.verified_vote_score, 2);
+
+    let program = api::get_program(program_id, None)
+        .await
+        .expect("Should get program");
+    assert_eq!(program.program.vote_score, 2);
+    assert_eq!(program.verified_vote_score, 2);
+}
@@ -1,10 +1,9 @@
 use dioxus::prelude::*;
-use std::env;
 
 use views::{
     AuthCallback, AuthResetConfirm, AuthResetPassword, AuthSignIn, AuthSignUp, AuthVerify, Blog,
-    Home, Me, ProfileEdit, ProgramDetail, ProgramNew, Programs, ProposalDetail, ProposalNew,
-    Proposals,
+    Home, Me, ProfileEdit, ProgramDetail, ProgramNew, Programs, ProposalDetail, ProposalEdit,
+    ProposalNew, Proposals,
 };
 
 mod views;
@@ -39,6 +38,8 @@ enum Route {
     ProposalNew {},
     #[route("/proposals/:id")]
     ProposalDetail { id: String },
+    #[route("/proposals/:id/edit")]
+    ProposalEdit { id: String },
     #[route("/programs")]
     Programs {},
     #[route("/programs/new")]
@@ -60,7 +61,7 @@ fn main() {
     #[cfg(feature = "server")]
     init_server_state();
 
-    log_runtime_config();
+    api::config::log_runtime_config();
     dioxus::launch(App);
 }
 
@@ -118,71 +119,6 @@ fn install_panic_hook() {
     }));
 }
 
-fn log_runtime_config() {
-    let ip = env::var("IP").unwrap_or_else(|_| "0.0.0.0".to_string());
-    let port = env::var("PORT").unwrap_or_else(|_| "8080".to_string());
-    let database_url = env::var("DATABASE_URL").unwrap_or_else(|_| "<missing>".to_string());
-
-    eprintln!("startup: IP={ip} PORT={port}");
-    eprintln!("startup: DATABASE_URL={}", redact_db_url(&database_url));
-
-    if database_url.contains("127.0.0.1") || database_url.contains("localhost") {
-        eprintln!("startup: WARNING DATABASE_URL points to localhost; this will fail in Railway");
-    }
-
-    log_missing_envs(
-        "auth",
-        &[
-            "AUTH_AUTHORIZE_URL",
-            "AUTH_CLIENT_ID",
-            "AUTH_REDIRECT_URI",
-            "AUTH_ISSUER",
-            "AUTH_JWKS_URL",
-        ],
-    );
-    log_missing_envs(
-        "storage",
-        &[
-            "STORAGE_BUCKET",
-            "STORAGE_ENDPOINT",
-            "STORAGE_REGION",
-            "STORAGE_ACCESS_KEY",
-            "STORAGE_SECRET_KEY",
-        ],
-    );
-}
-
-fn redact_db_url(value: &str) -> String {
-    if value == "<missing>" {
-        return value.to_string();
-    }
-
-    if let Some((prefix, rest)) = value.split_once("://") {
-        if let Some((creds, host)) = rest.split_once('@') {
-            let user = creds.split(':').next().unwrap_or("user");
-            return format!("{prefix}://{user}:***@{host}");
-        }
-    }
-
-    "<invalid DATABASE_URL>".to_string()
-}
-
-fn log_missing_envs(group: &str, keys: &[&str]) {
-    let missing: Vec<&str> = keys
-        .iter()
-        .copied()
-        .filter(|key| env::var(key).ok().is_none())
-        .collect();
-    if missing.is_empty() {
-        return;
-    }
-
-    eprintln!(
-        "startup: WARNING missing {group} envs: {}",
-        missing.join(", ")
-    );
-}
-
 #[component]
 fn App() -> Element {
     // Build cool things ✌️
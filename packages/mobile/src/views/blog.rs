@@ -11,9 +11,7 @@ pub fn Blog(id: i32) -> Element {
         div {
             id: "blog",
 
-            // Content
-            h1 { "This is blog #{id}!" }
-            p { "In blog #{id}, we show how the Dioxus router works and how URL parameters can be passed as props to our route components." }
+            ui::BlogPostPage { id: i64::from(id) }
 
             // Navigation links
             Link {
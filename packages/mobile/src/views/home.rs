@@ -1,5 +1,5 @@
 use dioxus::prelude::*;
-use ui::Hero;
+use ui::{GlobalActivityFeed, Hero};
 
 #[component]
 pub fn Home() -> Element {
@@ -14,5 +14,6 @@ pub fn Home() -> Element {
                 a { class: "btn", href: "/me", "Your profile" }
             }
         }
+        GlobalActivityFeed {}
     }
 }
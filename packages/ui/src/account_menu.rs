@@ -16,17 +16,24 @@ pub fn AccountMenu() -> Element {
     let lang = lang_sig();
 
     let mut open = use_signal(|| false);
+    let client = crate::use_api_client();
 
     let me = use_resource(move || {
-        let token = id_token().unwrap_or_default();
+        let call = client.call(api::auth_me);
         async move {
-            if token.trim().is_empty() {
-                return Ok(None);
+            match call.await {
+                Ok(me) => Ok(Some(me)),
+                Err(crate::ApiError::NotSignedIn) => Ok(None),
+                Err(crate::ApiError::Server(e)) => Err(e),
             }
-            api::auth_me(token).await.map(Some)
         }
     });
 
+    let unread = use_resource(move || {
+        let call = client.call(api::unread_notification_count);
+        async move { call.await.unwrap_or(0) }
+    });
+
     let on_sign_out = move |_| {
         id_token.set(None);
         open.set(false);
@@ -77,6 +84,13 @@ pub fn AccountMenu() -> Element {
                         Some(Err(_)) => rsx! { span { class: "avatar_fallback", "!" } },
                     }
                 }
+                if let Some(count) = unread().filter(|c| *c > 0) {
+                    span {
+                        class: "notif_badge",
+                        title: crate::t(lang, "nav.unread_notifications"),
+                        if count > 99 { "99+" } else { "{count}" }
+                    }
+                }
 
                 if open() {
                     div { class: "dropdown",
@@ -0,0 +1,139 @@
+//! Calls that need the signed-in user's id token.
+//!
+//! Server fns take the token as their first argument. Rather than each component
+//! reading the token signal and passing `""` when signed out, go through
+//! [`ApiClient`], which skips the request and returns [`ApiError::NotSignedIn`].
+
+use std::future::Future;
+
+use dioxus::prelude::*;
+
+/// Failure of an [`ApiClient`] call.
+#[derive(Debug, Clone)]
+pub enum ApiError {
+    /// No one is signed in, so the request was never sent.
+    NotSignedIn,
+    /// The server fn itself failed.
+    Server(ServerFnError),
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::NotSignedIn => f.write_str("not signed in"),
+            ApiError::Server(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl From<ServerFnError> for ApiError {
+    fn from(e: ServerFnError) -> Self {
+        ApiError::Server(e)
+    }
+}
+
+impl ApiError {
+    /// How a page should show this error; `NotSignedIn` has its own prompts, so
+    /// pages normally handle it before getting here.
+    pub fn load_failure(&self) -> crate::LoadFailure {
+        match self {
+            ApiError::NotSignedIn => crate::LoadFailure::Internal,
+            ApiError::Server(e) => crate::LoadFailure::of(e),
+        }
+    }
+}
+
+/// Handle on the app-wide `Signal<Option<String>>` id token context.
+#[derive(Clone, Copy)]
+pub struct ApiClient {
+    id_token: Signal<Option<String>>,
+}
+
+pub fn use_api_client() -> ApiClient {
+    ApiClient {
+        id_token: use_context::<Signal<Option<String>>>(),
+    }
+}
+
+impl ApiClient {
+    /// The current id token, or `None` when signed out.
+    pub fn token(&self) -> Option<String> {
+        signed_in((self.id_token)())
+    }
+
+    /// Run `f` with the current id token.
+    ///
+    /// The token is read when `call` is invoked, not when the future is polled, so
+    /// calling it inside `use_resource` re-runs the resource on sign-in/out.
+    pub fn call<T, F, Fut>(&self, f: F) -> impl Future<Output = Result<T, ApiError>>
+    where
+        F: FnOnce(String) -> Fut,
+        Fut: Future<Output = Result<T, ServerFnError>>,
+    {
+        with_token(self.token(), f)
+    }
+}
+
+fn signed_in(token: Option<String>) -> Option<String> {
+    token.filter(|t| !t.trim().is_empty())
+}
+
+async fn with_token<T, F, Fut>(token: Option<String>, f: F) -> Result<T, ApiError>
+where
+    F: FnOnce(String) -> Fut,
+    Fut: Future<Output = Result<T, ServerFnError>>,
+{
+    let token = signed_in(token).ok_or(ApiError::NotSignedIn)?;
+    Ok(f(token).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = pin!(fut);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+                return out;
+            }
+        }
+    }
+
+    #[test]
+    fn short_circuits_without_a_token() {
+        for token in [None, Some(String::new()), Some("  ".to_string())] {
+            let mut called = false;
+            let result = block_on(with_token(token, |_| {
+                called = true;
+                async { Ok(()) }
+            }));
+            assert!(matches!(result, Err(ApiError::NotSignedIn)));
+            assert!(!called);
+        }
+    }
+
+    #[test]
+    fn passes_token_and_result_through() {
+        let result = block_on(with_token(Some("tok".to_string()), |token| async move {
+            Ok(format!("{token}!"))
+        }));
+        assert_eq!(result.unwrap(), "tok!");
+    }
+
+    #[test]
+    fn wraps_server_errors() {
+        let result: Result<(), _> = block_on(with_token(Some("tok".to_string()), |_| async {
+            Err(ServerFnError::new("boom"))
+        }));
+        match result {
+            Err(ApiError::Server(e)) => assert!(e.to_string().contains("boom")),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+}
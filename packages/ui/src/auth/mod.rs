@@ -1,3 +1,5 @@
+use api::config::CaptchaProvider;
+use api::types::ErrorCode;
 use dioxus::prelude::*;
 
 const AUTH_CSS: Asset = asset!("/assets/styling/auth.css");
@@ -87,7 +89,7 @@ pub fn SignIn() -> Element {
         let navigator = navigator;
         let toasts = toasts_submit.clone();
         spawn(async move {
-            match api::signin(email(), password()).await {
+            match api::signin(email(), password(), Some(lang.code().to_string())).await {
                 Ok(token) => {
                     // Store in localStorage
                     let _ = document::eval(&format!(
@@ -107,12 +109,12 @@ pub fn SignIn() -> Element {
                 }
                 Err(e) => {
                     let message = e.to_string();
-                    if message.to_lowercase().contains("verify your email") {
+                    if ErrorCode::from_error(&message) == Some(ErrorCode::EmailNotVerified) {
                         show_resend.set(true);
                     }
                     toasts.error(
                         crate::t(lang, "toast.signin_failed_title"),
-                        Some(format!("{} {message}", crate::t(lang, "toast.details"))),
+                        Some(crate::error_details(lang, &message)),
                     );
                 }
             }
@@ -227,6 +229,48 @@ pub fn SignIn() -> Element {
     }
 }
 
+/// Reads the token the CAPTCHA widget stores in its hidden form input.
+const JS_CAPTCHA_TOKEN: &str = r#"(function(){
+    var el = document.querySelector('[name="cf-turnstile-response"],[name="h-captcha-response"]');
+    return el ? el.value : "";
+})()"#;
+
+/// The solved CAPTCHA token, if a widget is shown.
+async fn captcha_token() -> Option<String> {
+    document::eval(JS_CAPTCHA_TOKEN)
+        .await
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .filter(|token| !token.is_empty())
+}
+
+/// The provider's CAPTCHA widget, when the server requires one.
+#[component]
+fn CaptchaField() -> Element {
+    let cfg = use_resource(|| async move { api::public_config().await });
+    let Some(Ok(api::PublicConfig {
+        captcha: Some(widget),
+        ..
+    })) = cfg()
+    else {
+        return rsx! {};
+    };
+    let (script, class) = match widget.provider {
+        CaptchaProvider::Turnstile => (
+            "https://challenges.cloudflare.com/turnstile/v0/api.js",
+            "cf-turnstile",
+        ),
+        CaptchaProvider::HCaptcha => ("https://js.hcaptcha.com/1/api.js", "h-captcha"),
+    };
+
+    rsx! {
+        document::Script { src: script, defer: true }
+        div { class: "form-group",
+            div { class: class, "data-sitekey": widget.site_key }
+        }
+    }
+}
+
 #[component]
 pub fn SignUpForm() -> Element {
     let mut email = use_signal(String::new);
@@ -258,14 +302,15 @@ pub fn SignUpForm() -> Element {
             }
 
             // Call signup
-            match api::signup(email(), password()).await {
+            let captcha = captcha_token().await;
+            match api::signup(email(), password(), Some(lang.code().to_string()), captcha).await {
                 Ok(_) => {
                     success.set(true);
                 }
                 Err(e) => {
                     toasts.error(
                         crate::t(lang, "toast.signup_failed_title"),
-                        Some(format!("{} {e}", crate::t(lang, "toast.details"))),
+                        Some(crate::error_details(lang, &e.to_string())),
                     );
                 }
             }
@@ -324,6 +369,8 @@ pub fn SignUpForm() -> Element {
                         }
                     }
 
+                    CaptchaField {}
+
                     button { class: "btn primary", r#type: "submit",
                         {crate::t(lang, "auth.signup.submit")}
                     }
@@ -362,16 +409,17 @@ pub fn VerifyEmailPage(token: Option<String>) -> Element {
             }
 
             // Call verify_email
-            match api::verify_email(token.to_string()).await {
+            match api::verify_email(token.to_string(), Some(lang.code().to_string())).await {
                 Ok(_) => {
                     status.set("success".to_string());
                 }
                 Err(e) => {
+                    let message = e.to_string();
                     status.set("error".to_string());
-                    error_msg.set(e.to_string());
+                    error_msg.set(crate::error_text(lang, &message).unwrap_or(message.clone()));
                     toasts.error(
                         crate::t(lang, "toast.verify_failed_title"),
-                        Some(format!("{} {e}", crate::t(lang, "toast.details"))),
+                        Some(crate::error_details(lang, &message)),
                     );
                 }
             }
@@ -406,17 +454,88 @@ pub fn VerifyEmailPage(token: Option<String>) -> Element {
     }
 }
 
+/// Landing page for the unsubscribe link in notification emails.
+///
+/// Waits for a click before calling the API, so link scanners that prefetch emails
+/// don't unsubscribe anyone.
+#[component]
+pub fn UnsubscribePage(user_id: Option<String>, token: Option<String>) -> Element {
+    let mut status = use_signal(|| "idle".to_string());
+    let mut error_msg = use_signal(String::new);
+    let lang = crate::use_lang()();
+    let user_id = user_id.unwrap_or_default();
+    let token = token.unwrap_or_default();
+
+    let on_confirm = move |_| {
+        let user_id = user_id.clone();
+        let token = token.clone();
+        status.set("loading".to_string());
+        spawn(async move {
+            match api::unsubscribe_email(user_id, token).await {
+                Ok(_) => status.set("success".to_string()),
+                Err(e) => {
+                    let message = e.to_string();
+                    status.set("error".to_string());
+                    error_msg.set(crate::error_text(lang, &message).unwrap_or(message));
+                }
+            }
+        });
+    };
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: AUTH_CSS }
+
+        div { class: "auth_signin",
+            h1 { {crate::t(lang, "unsubscribe.title")} }
+
+            if status() == "success" {
+                p { class: "success", {crate::t(lang, "unsubscribe.success")} }
+            } else if status() == "error" {
+                p { class: "error", {crate::t(lang, "unsubscribe.error")} }
+                if !error_msg().is_empty() {
+                    p { class: "hint", {error_msg()} }
+                }
+            } else {
+                p { {crate::t(lang, "unsubscribe.body")} }
+                button {
+                    class: "btn primary",
+                    disabled: status() == "loading",
+                    onclick: on_confirm,
+                    {crate::t(lang, "unsubscribe.confirm")}
+                }
+            }
+        }
+    }
+}
+
 #[component]
 pub fn RequestPasswordResetForm() -> Element {
     let mut email = use_signal(String::new);
     let mut submitted = use_signal(|| false);
     let lang = crate::use_lang()();
+    let toasts = crate::use_toasts();
 
     let on_submit = move |evt: Event<FormData>| {
         evt.prevent_default();
+        let toasts = toasts.clone();
         spawn(async move {
-            // Always succeeds (security: don't reveal if email exists)
-            let _ = api::request_password_reset(email()).await;
+            // Succeeds whether or not the email exists (security: don't reveal it);
+            // only a failed CAPTCHA or the rate limit is worth reporting.
+            let captcha = captcha_token().await;
+            let lang_code = Some(lang.code().to_string());
+            if let Err(e) = api::request_password_reset(email(), lang_code, captcha).await {
+                let message = e.to_string();
+                if matches!(
+                    ErrorCode::from_error(&message),
+                    Some(ErrorCode::CaptchaFailed | ErrorCode::TooManyAttempts)
+                ) {
+                    toasts.error(
+                        crate::t(lang, "toast.reset_failed_title"),
+                        Some(crate::error_details(lang, &message)),
+                    );
+                    return;
+                }
+            }
             submitted.set(true);
         });
     };
@@ -447,6 +566,8 @@ pub fn RequestPasswordResetForm() -> Element {
                         }
                     }
 
+                    CaptchaField {}
+
                     button { class: "btn primary", r#type: "submit",
                         {crate::t(lang, "auth.reset.submit")}
                     }
@@ -503,14 +624,14 @@ pub fn ResetPasswordConfirmForm() -> Element {
                 return;
             }
 
-            match api::reset_password(token(), password()).await {
+            match api::reset_password(token(), password(), Some(lang.code().to_string())).await {
                 Ok(_) => {
                     success.set(true);
                 }
                 Err(e) => {
                     toasts.error(
                         crate::t(lang, "toast.reset_failed_title"),
-                        Some(format!("{} {e}", crate::t(lang, "toast.details"))),
+                        Some(crate::error_details(lang, &e.to_string())),
                     );
                 }
             }
@@ -641,7 +762,6 @@ pub fn MePage() -> Element {
     let mut id_token = use_context::<Signal<Option<String>>>();
     let auth_ready = try_use_context::<Signal<bool>>();
     let lang = crate::use_lang()();
-    let toasts = crate::use_toasts();
 
     use_effect(move || {
         if id_token().is_some() {
@@ -671,7 +791,7 @@ pub fn MePage() -> Element {
         });
     });
 
-    let me = use_resource(move || {
+    let mut me_resource = use_resource(move || {
         let token = id_token().unwrap_or_default();
         async move {
             if token.trim().is_empty() {
@@ -680,20 +800,11 @@ pub fn MePage() -> Element {
             api::auth_me(token).await
         }
     });
-    let mut load_error = use_signal(|| None::<String>);
-
-    use_effect(move || {
-        let err = me().and_then(|res| res.err()).map(|e| e.to_string());
-        if err.as_ref() != load_error().as_ref() {
-            if let Some(message) = &err {
-                toasts.error(
-                    crate::t(lang, "toast.me_load_title"),
-                    Some(format!("{} {message}", crate::t(lang, "toast.details"))),
-                );
-            }
-            load_error.set(err);
-        }
-    });
+    let me = crate::use_server_result(me_resource, "toast.me_load_title");
+    let user_id = me
+        .as_ref()
+        .and_then(|res| res.as_ref().ok())
+        .map(|me| me.user.id.to_string());
 
     rsx! {
         document::Link { rel: "stylesheet", href: AUTH_CSS }
@@ -709,12 +820,10 @@ pub fn MePage() -> Element {
                 a { class: "btn primary", href: "/auth/signin", {crate::t(lang, "me.signin")} }
             } else {
                 SignOutButton {}
-                match me() {
-                    None => rsx! {
-                        p { {crate::t(lang, "common.loading")} }
-                    },
-                    Some(Err(_)) => rsx! {
-                        p { class: "hint", {crate::t(lang, "common.error_try_again")} }
+                match me {
+                    None => rsx! { crate::Loading {} },
+                    Some(Err(e)) => rsx! {
+                        crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| me_resource.restart() }
                     },
                     Some(Ok(me)) => rsx! {
                         p {
@@ -732,42 +841,114 @@ pub fn MePage() -> Element {
                             p { class: "hint", {crate::t(lang, "me.profile_incomplete")} }
                             a { class: "btn", href: "/me/edit", {crate::t(lang, "me.complete_profile")} }
                         }
+                        ProfileProgress { completeness: me.profile_completeness }
                     },
                 }
             }
         }
 
         if id_token().is_some() {
-            ProfileTabs {}
+            ProfileTabs { user_id }
         }
     }
 }
 
+/// Progress bar for the optional profile fields, naming the ones still missing.
 #[component]
-fn ProfileTabs() -> Element {
-    let mut active_tab = use_signal(|| "activity");
+fn ProfileProgress(completeness: api::ProfileCompleteness) -> Element {
+    let lang = crate::use_lang()();
+    let missing = [
+        (completeness.display_name, "me.field.display_name"),
+        (completeness.bio, "me.field.bio"),
+        (completeness.avatar, "me.field.avatar"),
+        (completeness.location, "me.field.location"),
+    ]
+    .into_iter()
+    .filter(|(filled, _)| !filled)
+    .map(|(_, key)| crate::t(lang, key))
+    .collect::<Vec<_>>()
+    .join(", ");
 
     rsx! {
-        div { class: "profile-tabs",
-            button {
-                class: if active_tab() == "activity" { "tab active" } else { "tab" },
-                onclick: move |_| active_tab.set("activity"),
-                "Activity"
+        div { class: "profile_progress",
+            progress { max: "100", value: "{completeness.percent}" }
+            span { class: "hint",
+                {format!("{} {}%", crate::t(lang, "me.completeness"), completeness.percent)}
             }
-            button {
-                class: if active_tab() == "bookmarks" { "tab active" } else { "tab" },
-                onclick: move |_| active_tab.set("bookmarks"),
-                "Bookmarks"
+            if !missing.is_empty() {
+                p { class: "hint",
+                    {format!("{} {missing}", crate::t(lang, "me.completeness_missing"))}
+                }
             }
         }
+    }
+}
 
-        match active_tab() {
-            "activity" => rsx! {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProfileTab {
+    Activity,
+    Bookmarks,
+    Comments,
+    Content,
+    Videos,
+}
+
+impl ProfileTab {
+    const ALL: [ProfileTab; 5] = [
+        ProfileTab::Activity,
+        ProfileTab::Bookmarks,
+        ProfileTab::Comments,
+        ProfileTab::Content,
+        ProfileTab::Videos,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            ProfileTab::Activity => "Activity",
+            ProfileTab::Bookmarks => "Bookmarks",
+            ProfileTab::Comments => "Comments",
+            ProfileTab::Content => "My content",
+            ProfileTab::Videos => "My videos",
+        }
+    }
+}
+
+#[component]
+fn ProfileTabs(user_id: Option<String>) -> Element {
+    let mut active_tab = use_signal(|| ProfileTab::Activity);
+
+    rsx! {
+        div { class: "profile-tabs",
+            for tab in ProfileTab::ALL {
+                button {
+                    class: if active_tab() == tab { "tab active" } else { "tab" },
+                    onclick: move |_| active_tab.set(tab),
+                    {tab.label()}
+                }
+            }
+        }
+
+        match (active_tab(), user_id) {
+            (ProfileTab::Activity, _) => rsx! {
                 crate::ActivityFeed {}
             },
-            "bookmarks" => rsx! {
+            (ProfileTab::Bookmarks, _) => rsx! {
                 BookmarksSection {}
             },
+            (ProfileTab::Comments, Some(user_id)) => rsx! {
+                crate::AuthorComments { user_id }
+            },
+            (ProfileTab::Content, Some(user_id)) => rsx! {
+                crate::MyContent { user_id }
+            },
+            (ProfileTab::Videos, _) => rsx! {
+                crate::VideoFeed {
+                    starting_video_id: None,
+                    filter_target_type: None,
+                    filter_target_id: None,
+                    only_mine: true,
+                }
+            },
             _ => rsx! {}
         }
     }
@@ -777,53 +958,44 @@ fn ProfileTabs() -> Element {
 fn BookmarksSection() -> Element {
     let id_token = use_context::<Signal<Option<String>>>();
     let token = id_token().unwrap_or_default();
-
-    let mut bookmarks = use_signal(Vec::<api::types::Video>::new);
-    let mut loading = use_signal(|| true);
-    let mut error_msg = use_signal(|| None::<String>);
     let offset = use_signal(|| 0i64);
 
-    // Load bookmarks
-    use_effect(move || {
+    let mut bookmarks_resource = use_resource(move || {
         let token = token.clone();
-        spawn(async move {
-            loading.set(true);
-            match api::list_bookmarked_videos(token, 20, offset()).await {
-                Ok(vids) => {
-                    bookmarks.set(vids);
-                    loading.set(false);
-                }
-                Err(e) => {
-                    error_msg.set(Some(e.to_string()));
-                    loading.set(false);
-                }
-            }
-        });
+        async move { api::list_bookmarked_videos(token, 20, offset()).await }
     });
+    let bookmarks = crate::use_server_result(bookmarks_resource, "toast.load_videos_title");
 
     rsx! {
         div { class: "bookmarks-section",
-            h2 { "Bookmarked Videos ({bookmarks().len()})" }
-
-            if loading() {
-                p { "Loading bookmarks..." }
-            } else if let Some(err) = error_msg() {
-                p { class: "error", "Error: {err}" }
-            } else if bookmarks().is_empty() {
-                div { class: "empty-state",
-                    p { "You haven't bookmarked any videos yet" }
-                    p { class: "hint", "Discover videos to save your favorites" }
-                    a { href: "/videos", class: "btn primary", "Explore Videos" }
-                }
-            } else {
-                div { class: "bookmarks-grid",
-                    for video in bookmarks() {
-                        BookmarkCard {
-                            key: "{video.id}",
-                            video: video,
+            match bookmarks {
+                None => rsx! {
+                    h2 { "Bookmarked Videos" }
+                    crate::Loading {}
+                },
+                Some(Err(e)) => rsx! {
+                    h2 { "Bookmarked Videos" }
+                    crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| bookmarks_resource.restart() }
+                },
+                Some(Ok(videos)) if videos.is_empty() => rsx! {
+                    h2 { "Bookmarked Videos (0)" }
+                    div { class: "empty-state",
+                        p { "You haven't bookmarked any videos yet" }
+                        p { class: "hint", "Discover videos to save your favorites" }
+                        a { href: "/videos", class: "btn primary", "Explore Videos" }
+                    }
+                },
+                Some(Ok(videos)) => rsx! {
+                    h2 { "Bookmarked Videos ({videos.len()})" }
+                    div { class: "bookmarks-grid",
+                        for video in videos {
+                            BookmarkCard {
+                                key: "{video.id}",
+                                video: video,
+                            }
                         }
                     }
-                }
+                },
             }
         }
     }
@@ -947,6 +1119,21 @@ mod tests {
         assert_eq!(extract_id_token_from_hash(h).as_deref(), Some("a+b=c"));
     }
 
+    #[test]
+    fn profile_tabs_include_my_content() {
+        let labels: Vec<&str> = ProfileTab::ALL.iter().map(|t| t.label()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Activity",
+                "Bookmarks",
+                "Comments",
+                "My content",
+                "My videos"
+            ]
+        );
+    }
+
     #[test]
     fn js_escape_quotes_and_backslashes() {
         let s = r#"a"b\c"#;
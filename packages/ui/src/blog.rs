@@ -0,0 +1,34 @@
+use dioxus::prelude::*;
+
+const FEED_CSS: Asset = asset!("/assets/styling/feed.css");
+
+#[component]
+pub fn BlogPostPage(id: i64) -> Element {
+    let lang = crate::use_lang()();
+    let mut post = use_resource(move || async move { api::get_post(id).await });
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: FEED_CSS }
+
+        div { class: "page",
+            match post() {
+                None => rsx! { crate::Loading {} },
+                Some(Err(e)) if crate::LoadFailure::of(&e) == crate::LoadFailure::NotFound => rsx! {
+                    p { class: "hint", {crate::t(lang, "blog.not_found")} }
+                },
+                Some(Err(_)) => rsx! {
+                    crate::LoadFailed { on_retry: move |_| post.restart() }
+                },
+                Some(Ok(p)) => rsx! {
+                    div { class: "panel",
+                        h1 { "{p.title}" }
+                        div { class: "meta",
+                            span { class: "hint", {crate::format_datetime(lang, p.created_at)} }
+                        }
+                        pre { class: "body", "{p.body_markdown}" }
+                    }
+                },
+            }
+        }
+    }
+}
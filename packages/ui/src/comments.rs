@@ -4,39 +4,27 @@ use api::types::ContentTargetType;
 
 #[component]
 pub fn CommentThread(target_type: ContentTargetType, target_id: String) -> Element {
-    let id_token = use_context::<Signal<Option<String>>>();
-    let token = id_token().unwrap_or_default();
+    let client = crate::use_api_client();
     let lang = crate::use_lang()();
     let toasts = crate::use_toasts();
 
     let mut draft = use_signal(String::new);
+    // Comments posted from this view, appended without refetching the thread.
+    let mut posted = use_signal(Vec::<api::types::Comment>::new);
 
     let target_id_for_list = target_id.clone();
-    let mut comments = use_resource(move || {
+    let mut comments_resource = use_resource(move || {
         let target_id = target_id_for_list.clone();
-        async move { api::list_comments(target_type, target_id, 200).await }
-    });
-    let mut load_error = use_signal(|| None::<String>);
-
-    let toasts_for_load = toasts.clone();
-    use_effect(move || {
-        let err = comments().and_then(|res| res.err()).map(|e| e.to_string());
-        if err.as_ref() != load_error().as_ref() {
-            if let Some(message) = &err {
-                toasts_for_load.error(
-                    crate::t(lang, "toast.load_comments_title"),
-                    Some(format!("{} {message}", crate::t(lang, "toast.details"))),
-                );
-            }
-            load_error.set(err);
-        }
+        let token = client.token();
+        async move { api::list_comments(target_type, target_id, 100, token).await }
     });
+    let comments = crate::use_server_result(comments_resource, "toast.load_comments_title");
 
     rsx! {
         div { class: "panel",
             h2 { {crate::t(lang, "comments.title")} }
 
-            if id_token().is_none() {
+            if client.token().is_none() {
                 p { class: "hint", {crate::t(lang, "common.signin_to_comment")} }
                 a { class: "btn primary", href: "/auth/signin", {crate::t(lang, "common.signin")} }
             } else {
@@ -49,7 +37,6 @@ pub fn CommentThread(target_type: ContentTargetType, target_id: String) -> Eleme
                 button {
                     class: "btn primary",
                     onclick: move |_| {
-                        let token = token.clone();
                         let body = draft();
                         let tid = target_id.clone();
                         let lang = lang;
@@ -62,11 +49,17 @@ pub fn CommentThread(target_type: ContentTargetType, target_id: String) -> Eleme
                                 );
                                 return;
                             }
-                            match api::create_comment(token, target_type, tid, None, body).await {
-                                Ok(_) => {
+                            let create =
+                                |token| api::create_comment(token, target_type, tid, None, body);
+                            match client.call(create).await {
+                                Ok(comment) => {
                                     draft.set(String::new());
-                                    comments.restart();
+                                    posted.write().push(comment);
                                 }
+                                Err(crate::ApiError::NotSignedIn) => toasts.error(
+                                    crate::t(lang, "toast.create_comment_title"),
+                                    Some(crate::t(lang, "common.signin_to_comment")),
+                                ),
                                 Err(e) => toasts.error(
                                     crate::t(lang, "toast.create_comment_title"),
                                     Some(format!("{} {e}", crate::t(lang, "toast.details"))),
@@ -78,22 +71,40 @@ pub fn CommentThread(target_type: ContentTargetType, target_id: String) -> Eleme
                 }
             }
 
-            match comments() {
-                None => rsx! {
-                    p { {crate::t(lang, "common.loading")} }
+            match comments {
+                None => rsx! { crate::Loading {} },
+                Some(Err(e)) => rsx! {
+                    crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| comments_resource.restart() }
                 },
-                Some(Err(_)) => rsx! { p { class: "hint", {crate::t(lang, "common.error_try_again")} } },
                 Some(Ok(items)) => rsx! {
-                    if items.is_empty() {
+                    if items.is_empty() && posted().is_empty() {
                         p { class: "hint", {crate::t(lang, "common.no_comments_yet")} }
                     }
-                    for c in items {
-                        div { class: "comment",
+                    for c in items.into_iter().chain(posted()) {
+                        div { class: "comment", style: "--depth: {c.depth}",
                             div { class: "comment_meta",
-                                span { class: "hint", {format!("{} {}", crate::t(lang, "comments.by"), c.author_user_id)} }
+                                span { class: "hint",
+                                    {
+                                        format!(
+                                            "{} {}",
+                                            crate::t(lang, "comments.by"),
+                                            c.author_display_name.clone().unwrap_or_else(|| c.author_user_id.to_string()),
+                                        )
+                                    }
+                                }
+                                if c.pinned {
+                                    span { class: "tag", {crate::t(lang, "comments.pinned")} }
+                                }
+                                if c.updated_at.is_some() && !c.deleted {
+                                    span { class: "hint", {crate::t(lang, "comments.edited")} }
+                                }
                                 span { class: "score", "{c.vote_score} votes" }
                             }
-                            pre { class: "body", "{c.body_markdown}" }
+                            if c.deleted {
+                                p { class: "hint", {crate::t(lang, "comments.deleted")} }
+                            } else {
+                                pre { class: "body", "{c.body_markdown}" }
+                            }
                         }
                     }
                 },
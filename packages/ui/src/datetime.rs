@@ -0,0 +1,61 @@
+//! Display formatting for the UTC timestamps the API sends.
+
+use crate::Lang;
+use time::{OffsetDateTime, UtcOffset};
+
+const MONTHS_FR: [&str; 12] = [
+    "janv.", "fevr.", "mars", "avr.", "mai", "juin", "juil.", "aout", "sept.", "oct.", "nov.",
+    "dec.",
+];
+const MONTHS_EN: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Date and time of `at` in UTC, spelled the way `lang` readers expect.
+///
+/// `31 janv. 2025, 09:30 UTC` / `Jan 31, 2025, 09:30 UTC`.
+pub fn format_datetime(lang: Lang, at: OffsetDateTime) -> String {
+    let at = at.to_offset(UtcOffset::UTC);
+    let month = usize::from(u8::from(at.month())) - 1;
+    match lang {
+        Lang::Fr => format!(
+            "{} {} {}, {:02}:{:02} UTC",
+            at.day(),
+            MONTHS_FR[month],
+            at.year(),
+            at.hour(),
+            at.minute()
+        ),
+        Lang::En => format!(
+            "{} {}, {}, {:02}:{:02} UTC",
+            MONTHS_EN[month],
+            at.day(),
+            at.year(),
+            at.hour(),
+            at.minute()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(unix: i64) -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(unix).unwrap()
+    }
+
+    #[test]
+    fn formats_per_language() {
+        // 2025-01-31T09:30:00Z
+        let t = at(1_738_315_800);
+        assert_eq!(format_datetime(Lang::Fr, t), "31 janv. 2025, 09:30 UTC");
+        assert_eq!(format_datetime(Lang::En, t), "Jan 31, 2025, 09:30 UTC");
+    }
+
+    #[test]
+    fn converts_other_offsets_to_utc() {
+        let t = at(1_738_315_800).to_offset(UtcOffset::from_hms(2, 0, 0).unwrap());
+        assert_eq!(format_datetime(Lang::En, t), "Jan 31, 2025, 09:30 UTC");
+    }
+}
@@ -1,3 +1,4 @@
+use api::types::ErrorCode;
 use dioxus::prelude::*;
 
 /// Supported languages.
@@ -99,6 +100,8 @@ pub fn t(lang: Lang, key: &str) -> String {
         (Lang::En, "nav.edit_profile") => "Edit profile".to_string(),
         (Lang::Fr, "nav.signout") => "Se déconnecter".to_string(),
         (Lang::En, "nav.signout") => "Sign out".to_string(),
+        (Lang::Fr, "nav.unread_notifications") => "Notifications non lues".to_string(),
+        (Lang::En, "nav.unread_notifications") => "Unread notifications".to_string(),
         (Lang::Fr, "lang.label") => "Langue".to_string(),
 
         // Home / hero
@@ -124,6 +127,12 @@ pub fn t(lang: Lang, key: &str) -> String {
         (Lang::En, "proposals.form.title") => "Title".to_string(),
         (Lang::Fr, "proposals.form.title_ph") => "Titre de la proposition".to_string(),
         (Lang::En, "proposals.form.title_ph") => "Proposal title".to_string(),
+        (Lang::Fr, "proposals.form.similar") => {
+            "Des propositions proches existent déjà, peut-être pouvez-vous les soutenir :".to_string()
+        }
+        (Lang::En, "proposals.form.similar") => {
+            "Similar proposals already exist; consider supporting one of them:".to_string()
+        }
         (Lang::Fr, "proposals.form.summary_opt") => "Résumé (facultatif)".to_string(),
         (Lang::En, "proposals.form.summary_opt") => "Summary (optional)".to_string(),
         (Lang::Fr, "proposals.form.summary_ph") => "Résumé en une phrase".to_string(),
@@ -140,8 +149,36 @@ pub fn t(lang: Lang, key: &str) -> String {
         (Lang::En, "proposals.form.create") => "Create".to_string(),
         (Lang::Fr, "proposals.created_open") => "Créé ! Ouvrir :".to_string(),
         (Lang::En, "proposals.created_open") => "Created! Open:".to_string(),
+        (Lang::Fr, "proposals.edit") => "Modifier la proposition".to_string(),
+        (Lang::En, "proposals.edit") => "Edit proposal".to_string(),
+        (Lang::Fr, "proposals.edit_link") => "Modifier".to_string(),
+        (Lang::En, "proposals.edit_link") => "Edit".to_string(),
+        (Lang::Fr, "proposals.words") => "mots".to_string(),
+        (Lang::En, "proposals.words") => "words".to_string(),
+        (Lang::Fr, "proposals.read_minutes") => "min de lecture".to_string(),
+        (Lang::En, "proposals.read_minutes") => "min read".to_string(),
+        (Lang::Fr, "proposals.comments") => "commentaires".to_string(),
+        (Lang::En, "proposals.comments") => "comments".to_string(),
+        (Lang::Fr, "votes.verified_score") => "venant de comptes vérifiés".to_string(),
+        (Lang::En, "votes.verified_score") => "from verified accounts".to_string(),
+        (Lang::Fr, "proposals.need_signin_edit") => "Vous devez vous connecter pour modifier des propositions.".to_string(),
+        (Lang::En, "proposals.need_signin_edit") => "You need to sign in to edit proposals.".to_string(),
+        (Lang::Fr, "proposals.form.save") => "Enregistrer".to_string(),
+        (Lang::En, "proposals.form.save") => "Save".to_string(),
+        (Lang::Fr, "proposals.saved") => "Enregistré.".to_string(),
+        (Lang::En, "proposals.saved") => "Saved.".to_string(),
+        (Lang::Fr, "proposals.not_author") => "Seul l'auteur peut modifier cette proposition.".to_string(),
+        (Lang::En, "proposals.not_author") => "Only the author can edit this proposal.".to_string(),
+        (Lang::Fr, "proposals.conflict") => "Cette proposition a été modifiée entre-temps. Rechargez la page avant d'enregistrer.".to_string(),
+        (Lang::En, "proposals.conflict") => "This proposal changed since you opened it. Reload before saving.".to_string(),
         (Lang::Fr, "proposals.bundle_into_program") => "Ajouter à un programme".to_string(),
         (Lang::En, "proposals.bundle_into_program") => "Bundle into program".to_string(),
+        (Lang::Fr, "proposals.included_in") => "Inclus dans".to_string(),
+        (Lang::En, "proposals.included_in") => "Included in".to_string(),
+        (Lang::Fr, "proposals.included_in_programs") => "programme(s)".to_string(),
+        (Lang::En, "proposals.included_in_programs") => "program(s)".to_string(),
+        (Lang::Fr, "proposals.related") => "Propositions liées".to_string(),
+        (Lang::En, "proposals.related") => "Related proposals".to_string(),
         (Lang::Fr, "common.vote") => "Vote".to_string(),
         (Lang::En, "common.vote") => "Vote".to_string(),
         (Lang::Fr, "common.id") => "id :".to_string(),
@@ -154,6 +191,12 @@ pub fn t(lang: Lang, key: &str) -> String {
         (Lang::En, "common.error_prefix") => "Error:".to_string(),
         (Lang::Fr, "common.error_try_again") => "Impossible de charger pour le moment. Reessayez.".to_string(),
         (Lang::En, "common.error_try_again") => "Unable to load right now. Please try again.".to_string(),
+        (Lang::Fr, "common.retry") => "Réessayer".to_string(),
+        (Lang::En, "common.retry") => "Retry".to_string(),
+        (Lang::Fr, "common.load_more") => "Charger plus".to_string(),
+        (Lang::En, "common.load_more") => "Load more".to_string(),
+        (Lang::Fr, "common.nothing_here") => "Rien ici pour le moment.".to_string(),
+        (Lang::En, "common.nothing_here") => "Nothing here yet.".to_string(),
         (Lang::Fr, "common.signin") => "Se connecter".to_string(),
         (Lang::En, "common.signin") => "Sign in".to_string(),
         (Lang::Fr, "common.no_proposals_yet") => "Aucune proposition pour le moment.".to_string(),
@@ -174,6 +217,12 @@ pub fn t(lang: Lang, key: &str) -> String {
         (Lang::En, "common.signin_to_upload_video") => "Sign in to upload a video.".to_string(),
         (Lang::Fr, "videos.loading_player") => "Chargement du lecteur…".to_string(),
         (Lang::En, "videos.loading_player") => "Loading player…".to_string(),
+        (Lang::Fr, "videos.votes") => "votes".to_string(),
+        (Lang::En, "videos.votes") => "votes".to_string(),
+        (Lang::Fr, "videos.views") => "vues".to_string(),
+        (Lang::En, "videos.views") => "views".to_string(),
+        (Lang::Fr, "videos.comments") => "commentaires".to_string(),
+        (Lang::En, "videos.comments") => "comments".to_string(),
         (Lang::Fr, "vote.clear") => "Effacer".to_string(),
         (Lang::En, "vote.clear") => "Clear".to_string(),
         (Lang::Fr, "vote.your_vote") => "Votre vote :".to_string(),
@@ -188,6 +237,12 @@ pub fn t(lang: Lang, key: &str) -> String {
         (Lang::En, "comments.empty_error") => "Comment cannot be empty".to_string(),
         (Lang::Fr, "comments.by") => "par".to_string(),
         (Lang::En, "comments.by") => "by".to_string(),
+        (Lang::Fr, "comments.pinned") => "Épinglé".to_string(),
+        (Lang::En, "comments.pinned") => "Pinned".to_string(),
+        (Lang::Fr, "comments.edited") => "(modifié)".to_string(),
+        (Lang::En, "comments.edited") => "(edited)".to_string(),
+        (Lang::Fr, "comments.deleted") => "[supprimé]".to_string(),
+        (Lang::En, "comments.deleted") => "[deleted]".to_string(),
 
         // Toasts
         (Lang::Fr, "toast.details") => "Details :".to_string(),
@@ -200,6 +255,8 @@ pub fn t(lang: Lang, key: &str) -> String {
         (Lang::En, "toast.load_proposal_title") => "Couldn't load the proposal".to_string(),
         (Lang::Fr, "toast.create_proposal_title") => "Impossible de creer la proposition".to_string(),
         (Lang::En, "toast.create_proposal_title") => "Couldn't create the proposal".to_string(),
+        (Lang::Fr, "toast.update_proposal_title") => "Impossible d'enregistrer la proposition".to_string(),
+        (Lang::En, "toast.update_proposal_title") => "Couldn't save the proposal".to_string(),
         (Lang::Fr, "toast.load_programs_title") => "Impossible de charger les programmes".to_string(),
         (Lang::En, "toast.load_programs_title") => "Couldn't load programs".to_string(),
         (Lang::Fr, "toast.load_program_title") => "Impossible de charger le programme".to_string(),
@@ -222,8 +279,24 @@ pub fn t(lang: Lang, key: &str) -> String {
         (Lang::En, "toast.video_missing_file_title") => "Select a video file".to_string(),
         (Lang::Fr, "toast.profile_save_title") => "Impossible d'enregistrer le profil".to_string(),
         (Lang::En, "toast.profile_save_title") => "Couldn't save the profile".to_string(),
+        (Lang::Fr, "profile.conflict") => "Votre profil a été modifié ailleurs. Rechargez la page avant d'enregistrer.".to_string(),
+        (Lang::En, "profile.conflict") => "Your profile changed elsewhere. Reload before saving.".to_string(),
         (Lang::Fr, "toast.load_activity_title") => "Impossible de charger votre activite".to_string(),
         (Lang::En, "toast.load_activity_title") => "Couldn't load your activity".to_string(),
+        (Lang::Fr, "toast.load_global_activity_title") => "Impossible de charger l'activite recente".to_string(),
+        (Lang::En, "toast.load_global_activity_title") => "Couldn't load recent activity".to_string(),
+        (Lang::Fr, "comments.history_title") => "Commentaires".to_string(),
+        (Lang::En, "comments.history_title") => "Comments".to_string(),
+        (Lang::Fr, "comments.history_empty") => "Aucun commentaire pour le moment.".to_string(),
+        (Lang::En, "comments.history_empty") => "No comments yet.".to_string(),
+        (Lang::Fr, "comments.history_untitled") => "Contenu indisponible".to_string(),
+        (Lang::En, "comments.history_untitled") => "Unavailable content".to_string(),
+        (Lang::Fr, "activity.global_title") => "Activite recente".to_string(),
+        (Lang::En, "activity.global_title") => "Recent activity".to_string(),
+        (Lang::Fr, "activity.global_empty") => "Rien pour le moment.".to_string(),
+        (Lang::En, "activity.global_empty") => "Nothing happening yet.".to_string(),
+        (Lang::Fr, "activity.someone") => "Quelqu'un".to_string(),
+        (Lang::En, "activity.someone") => "Someone".to_string(),
         (Lang::Fr, "toast.signin_failed_title") => "Connexion impossible".to_string(),
         (Lang::En, "toast.signin_failed_title") => "Sign in failed".to_string(),
         (Lang::Fr, "toast.signup_failed_title") => "Inscription impossible".to_string(),
@@ -291,6 +364,12 @@ pub fn t(lang: Lang, key: &str) -> String {
         (Lang::En, "auth.callback.body.prefix") => "If this screen doesn't redirect, go to ".to_string(),
         (Lang::Fr, "auth.callback.body.suffix") => ".".to_string(),
         (Lang::En, "auth.callback.body.suffix") => ".".to_string(),
+        (Lang::Fr, "me.content.proposals") => "Mes propositions".to_string(),
+        (Lang::En, "me.content.proposals") => "My proposals".to_string(),
+        (Lang::Fr, "me.content.programs") => "Mes programmes".to_string(),
+        (Lang::En, "me.content.programs") => "My programs".to_string(),
+        (Lang::Fr, "me.content.videos") => "Mes vidéos".to_string(),
+        (Lang::En, "me.content.videos") => "My videos".to_string(),
         (Lang::Fr, "me.title") => "Mon compte".to_string(),
         (Lang::En, "me.title") => "My account".to_string(),
         (Lang::Fr, "me.signed_out") => "Vous n'êtes pas connecté.".to_string(),
@@ -307,6 +386,18 @@ pub fn t(lang: Lang, key: &str) -> String {
         (Lang::En, "me.profile_incomplete") => "Profile incomplete: add a display name.".to_string(),
         (Lang::Fr, "me.complete_profile") => "Compléter le profil".to_string(),
         (Lang::En, "me.complete_profile") => "Complete profile".to_string(),
+        (Lang::Fr, "me.completeness") => "Profil rempli à".to_string(),
+        (Lang::En, "me.completeness") => "Profile filled in:".to_string(),
+        (Lang::Fr, "me.completeness_missing") => "À ajouter :".to_string(),
+        (Lang::En, "me.completeness_missing") => "Still to add:".to_string(),
+        (Lang::Fr, "me.field.display_name") => "nom d'affichage".to_string(),
+        (Lang::En, "me.field.display_name") => "display name".to_string(),
+        (Lang::Fr, "me.field.bio") => "bio".to_string(),
+        (Lang::En, "me.field.bio") => "bio".to_string(),
+        (Lang::Fr, "me.field.avatar") => "photo".to_string(),
+        (Lang::En, "me.field.avatar") => "avatar".to_string(),
+        (Lang::Fr, "me.field.location") => "localisation".to_string(),
+        (Lang::En, "me.field.location") => "location".to_string(),
         (Lang::Fr, "auth.not_signed_in") => "Non connecté".to_string(),
         (Lang::En, "auth.not_signed_in") => "Not signed in".to_string(),
         (Lang::Fr, "auth.config_error_prefix") => "Erreur de configuration :".to_string(),
@@ -343,6 +434,17 @@ pub fn t(lang: Lang, key: &str) -> String {
         (Lang::Fr, "auth.verify.error") => "La vérification a échoué. Ce lien est peut-être expiré.".to_string(),
         (Lang::Fr, "auth.verify.signin_link") => "Aller à la connexion".to_string(),
 
+        (Lang::Fr, "unsubscribe.title") => "Notifications par e-mail".to_string(),
+        (Lang::Fr, "unsubscribe.body") => "Ne plus recevoir d'e-mails quand quelqu'un commente vos contenus ou vous répond ?".to_string(),
+        (Lang::Fr, "unsubscribe.confirm") => "Se désabonner".to_string(),
+        (Lang::Fr, "unsubscribe.success") => "Vous ne recevrez plus d'e-mails de notification.".to_string(),
+        (Lang::Fr, "unsubscribe.error") => "Le désabonnement a échoué. Ce lien est peut-être invalide.".to_string(),
+        (Lang::En, "unsubscribe.title") => "Email notifications".to_string(),
+        (Lang::En, "unsubscribe.body") => "Stop receiving emails when someone comments on your content or replies to you?".to_string(),
+        (Lang::En, "unsubscribe.confirm") => "Unsubscribe".to_string(),
+        (Lang::En, "unsubscribe.success") => "You will no longer receive notification emails.".to_string(),
+        (Lang::En, "unsubscribe.error") => "Unsubscribing failed. This link may be invalid.".to_string(),
+
         (Lang::Fr, "auth.reset.title") => "Réinitialiser le mot de passe".to_string(),
         (Lang::Fr, "auth.reset.body") => "Entrez votre e-mail pour recevoir un lien de réinitialisation".to_string(),
         (Lang::Fr, "auth.reset.email") => "Adresse e-mail".to_string(),
@@ -366,12 +468,73 @@ pub fn t(lang: Lang, key: &str) -> String {
         (Lang::En, "auth.resend.body") => "If this address is pending verification, a new link was sent.".to_string(),
         (Lang::En, "auth.resend.failed_title") => "Failed to send".to_string(),
 
+        (Lang::Fr, "blog.not_found") => "Cet article n'existe pas.".to_string(),
+        (Lang::En, "blog.not_found") => "This post doesn't exist.".to_string(),
+
+        // API error codes (see `api::types::ErrorCode`)
+        (Lang::Fr, "error.invalid_email") => "Adresse e-mail invalide".to_string(),
+        (Lang::Fr, "error.password_too_short") => "Le mot de passe doit contenir au moins 8 caractères".to_string(),
+        (Lang::Fr, "error.password_no_uppercase") => "Le mot de passe doit contenir au moins une majuscule".to_string(),
+        (Lang::Fr, "error.password_no_lowercase") => "Le mot de passe doit contenir au moins une minuscule".to_string(),
+        (Lang::Fr, "error.password_no_number") => "Le mot de passe doit contenir au moins un chiffre".to_string(),
+        (Lang::Fr, "error.email_taken") => "Cette adresse e-mail est déjà inscrite".to_string(),
+        (Lang::Fr, "error.invalid_credentials") => "E-mail ou mot de passe incorrect".to_string(),
+        (Lang::Fr, "error.email_not_verified") => "Veuillez vérifier votre adresse e-mail avant de vous connecter".to_string(),
+        (Lang::Fr, "error.oauth_account") => "Ce compte utilise OAuth. Connectez-vous avec votre fournisseur.".to_string(),
+        (Lang::Fr, "error.verification_invalid") => "Le lien de vérification est invalide ou a expiré".to_string(),
+        (Lang::Fr, "error.verification_expired") => "Le lien de vérification a expiré".to_string(),
+        (Lang::Fr, "error.reset_invalid") => "Le lien de réinitialisation est invalide ou a expiré".to_string(),
+        (Lang::Fr, "error.reset_expired") => "Le lien de réinitialisation a expiré".to_string(),
+        (Lang::Fr, "error.not_allowed") => "Action non autorisée".to_string(),
+        (Lang::Fr, "error.conflict") => "Le contenu a été modifié entre-temps. Rechargez la page.".to_string(),
+        (Lang::Fr, "error.comment_empty") => "Le commentaire est vide".to_string(),
+        (Lang::Fr, "error.comment_too_fast") => "Vous commentez trop vite".to_string(),
+        (Lang::Fr, "error.content_rejected") => "Ce texte contient un terme non autorisé. Reformulez-le.".to_string(),
+        (Lang::Fr, "error.captcha_failed") => "La vérification anti-robot a échoué. Réessayez.".to_string(),
+        (Lang::Fr, "error.not_found") => "Ce contenu est introuvable".to_string(),
+        (Lang::Fr, "error.unauthorized") => "Connectez-vous pour voir ce contenu".to_string(),
+        (Lang::Fr, "error.payload_too_large") => "Ce contenu est trop volumineux. Raccourcissez-le.".to_string(),
+        (Lang::Fr, "error.too_many_attempts") => "Trop de tentatives. Réessayez plus tard.".to_string(),
+        (Lang::En, "error.invalid_email") => "Invalid email address".to_string(),
+        (Lang::En, "error.password_too_short") => "Password must be at least 8 characters".to_string(),
+        (Lang::En, "error.password_no_uppercase") => "Password must contain at least one uppercase letter".to_string(),
+        (Lang::En, "error.password_no_lowercase") => "Password must contain at least one lowercase letter".to_string(),
+        (Lang::En, "error.password_no_number") => "Password must contain at least one number".to_string(),
+        (Lang::En, "error.email_taken") => "This email is already registered".to_string(),
+        (Lang::En, "error.invalid_credentials") => "Invalid email or password".to_string(),
+        (Lang::En, "error.email_not_verified") => "Please verify your email before signing in".to_string(),
+        (Lang::En, "error.oauth_account") => "This account uses OAuth. Sign in with your provider.".to_string(),
+        (Lang::En, "error.verification_invalid") => "This verification link is invalid or has expired".to_string(),
+        (Lang::En, "error.verification_expired") => "This verification link has expired".to_string(),
+        (Lang::En, "error.reset_invalid") => "This reset link is invalid or has expired".to_string(),
+        (Lang::En, "error.reset_expired") => "This reset link has expired".to_string(),
+        (Lang::En, "error.not_allowed") => "You're not allowed to do that".to_string(),
+        (Lang::En, "error.conflict") => "This content changed in the meantime. Reload the page.".to_string(),
+        (Lang::En, "error.comment_empty") => "The comment is empty".to_string(),
+        (Lang::En, "error.comment_too_fast") => "You're commenting too fast".to_string(),
+        (Lang::En, "error.content_rejected") => "This text contains a term that isn't allowed. Please rephrase it.".to_string(),
+        (Lang::En, "error.captcha_failed") => "The anti-robot check failed. Please try again.".to_string(),
+        (Lang::En, "error.not_found") => "This content doesn't exist".to_string(),
+        (Lang::En, "error.unauthorized") => "Sign in to see this content".to_string(),
+        (Lang::En, "error.payload_too_large") => "This content is too large. Please shorten it.".to_string(),
+        (Lang::En, "error.too_many_attempts") => "Too many attempts. Please try again later.".to_string(),
+
         // Fallback: use French string if present, else show key.
         (Lang::En, k) => t(Lang::Fr, k),
         (Lang::Fr, _) => key.to_string(),
     }
 }
 
+/// Localized text for an API error carrying an [`ErrorCode`], if it has one.
+pub fn error_text(lang: Lang, error: &str) -> Option<String> {
+    ErrorCode::from_error(error).map(|code| t(lang, &format!("error.{}", code.as_str())))
+}
+
+/// Toast details for a failed call: the localized code message, else the raw error.
+pub fn error_details(lang: Lang, error: &str) -> String {
+    error_text(lang, error).unwrap_or_else(|| format!("{} {error}", t(lang, "toast.details")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -389,4 +552,36 @@ mod tests {
         // Missing everywhere returns key:
         assert_eq!(t(Lang::En, "missing.key"), "missing.key");
     }
+
+    #[test]
+    fn every_error_code_has_translations() {
+        for code in ErrorCode::ALL {
+            let key = format!("error.{}", code.as_str());
+            assert_ne!(t(Lang::Fr, &key), key);
+            assert_ne!(
+                t(Lang::En, &key),
+                t(Lang::Fr, &key),
+                "{key} missing English"
+            );
+        }
+    }
+
+    #[test]
+    fn coded_errors_map_to_ui_language_whatever_the_server_sent() {
+        let from_fr = format!(
+            "error running server function: {}",
+            ErrorCode::PasswordTooShort.error_text(Some("fr"))
+        );
+        let from_en = ErrorCode::PasswordTooShort.error_text(None);
+        assert_eq!(
+            error_text(Lang::En, &from_fr),
+            error_text(Lang::En, &from_en)
+        );
+        assert_eq!(
+            error_details(Lang::Fr, &from_en),
+            t(Lang::Fr, "error.password_too_short")
+        );
+        assert_eq!(error_text(Lang::Fr, "database is locked"), None);
+        assert!(error_details(Lang::En, "database is locked").ends_with("database is locked"));
+    }
 }
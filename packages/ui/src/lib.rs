@@ -12,11 +12,14 @@ pub use echo::Echo;
 mod auth;
 pub use auth::{
     AuthBootstrap, AuthCallback, AuthGate, MePage, RequestPasswordResetForm,
-    ResetPasswordConfirmForm, SignIn, SignOutButton, SignUpForm, VerifyEmailPage,
+    ResetPasswordConfirmForm, SignIn, SignOutButton, SignUpForm, UnsubscribePage, VerifyEmailPage,
 };
 
+mod blog;
+pub use blog::BlogPostPage;
+
 mod proposals;
-pub use proposals::{ProposalDetailPage, ProposalListPage, ProposalNewPage};
+pub use proposals::{ProposalDetailPage, ProposalEditPage, ProposalListPage, ProposalNewPage};
 
 mod programs;
 pub use programs::{ProgramDetailPage, ProgramListPage, ProgramNewPage};
@@ -28,10 +31,10 @@ mod comments;
 pub use comments::CommentThread;
 
 mod profile;
-pub use profile::{ActivityFeed, ProfileEditPage};
+pub use profile::{ActivityFeed, AuthorComments, GlobalActivityFeed, MyContent, ProfileEditPage};
 
 mod videos;
-pub use videos::VideoSection;
+pub use videos::{VideoDetailHeader, VideoSection};
 
 mod video_feed;
 pub use video_feed::VideoFeed;
@@ -45,5 +48,14 @@ pub use account_menu::AccountMenu;
 mod toast;
 pub use toast::{use_toasts, ToastProvider};
 
+mod server_result;
+pub use server_result::{use_server_result, LoadFailed, LoadFailure, Loading};
+
+mod api_client;
+pub use api_client::{use_api_client, ApiClient, ApiError};
+
+mod datetime;
+pub use datetime::format_datetime;
+
 mod i18n;
-pub use i18n::{set_lang, t, use_lang, I18nProvider, Lang};
+pub use i18n::{error_details, error_text, set_lang, t, use_lang, I18nProvider, Lang};
@@ -1,3 +1,4 @@
+use api::types::ErrorCode;
 use dioxus::prelude::*;
 
 const FEED_CSS: Asset = asset!("/assets/styling/feed.css");
@@ -13,7 +14,37 @@ pub fn ProfileEditPage() -> Element {
     let mut bio = use_signal(String::new);
     let mut avatar_url = use_signal(String::new);
     let mut location = use_signal(String::new);
+    let mut notify_email = use_signal(|| false);
     let mut status = use_signal(String::new);
+    // `None` until a profile exists; the server rejects saves over newer edits.
+    let mut loaded_at = use_signal(|| None::<time::OffsetDateTime>);
+    let mut prefilled = use_signal(|| false);
+
+    let me = use_resource(move || {
+        let token = id_token();
+        async move {
+            match token {
+                Some(token) => api::auth_me(token).await.map(Some),
+                None => Ok(None),
+            }
+        }
+    });
+
+    use_effect(move || {
+        if let Some(Ok(Some(me))) = me() {
+            if !prefilled() {
+                if let Some(p) = me.profile {
+                    display_name.set(p.display_name);
+                    bio.set(p.bio);
+                    avatar_url.set(p.avatar_url.unwrap_or_default());
+                    location.set(p.location.unwrap_or_default());
+                    notify_email.set(p.notify_email);
+                    loaded_at.set(Some(p.updated_at));
+                }
+                prefilled.set(true);
+            }
+        }
+    });
 
     rsx! {
         document::Link { rel: "stylesheet", href: FEED_CSS }
@@ -47,6 +78,7 @@ pub fn ProfileEditPage() -> Element {
                             let b = bio();
                             let av = avatar_url();
                             let loc = location();
+                            let expected = loaded_at();
                             let lang = lang;
                             let toasts = toasts.clone();
                             spawn(async move {
@@ -56,9 +88,17 @@ pub fn ProfileEditPage() -> Element {
                                     b,
                                     if av.trim().is_empty() { None } else { Some(av) },
                                     if loc.trim().is_empty() { None } else { Some(loc) },
+                                    expected,
                                 )
                                 .await {
-                                    Ok(_) => status.set("Saved.".to_string()),
+                                    Ok(saved) => {
+                                        loaded_at.set(Some(saved.updated_at));
+                                        status.set("Saved.".to_string());
+                                    }
+                                    Err(e) if ErrorCode::from_error(&e.to_string()) == Some(ErrorCode::Conflict) => toasts.error(
+                                        crate::t(lang, "toast.profile_save_title"),
+                                        Some(crate::t(lang, "profile.conflict")),
+                                    ),
                                     Err(e) => toasts.error(
                                         crate::t(lang, "toast.profile_save_title"),
                                         Some(format!(
@@ -75,6 +115,27 @@ pub fn ProfileEditPage() -> Element {
                     if !status().is_empty() {
                         p { class: "hint", "{status}" }
                     }
+
+                    // The preference lives on the profile, so it appears once one is saved.
+                    if loaded_at().is_some() {
+                        label {
+                            input {
+                                r#type: "checkbox",
+                                checked: notify_email(),
+                                onchange: move |e| {
+                                    let token = id_token().unwrap_or_default();
+                                    let enabled = e.checked();
+                                    spawn(async move {
+                                        match api::set_email_notifications(token, enabled).await {
+                                            Ok(saved) => notify_email.set(saved),
+                                            Err(e) => status.set(e.to_string()),
+                                        }
+                                    });
+                                },
+                            }
+                            " Email me when someone comments on my content or replies to me"
+                        }
+                    }
                 }
             }
         }
@@ -86,46 +147,34 @@ pub fn ActivityFeed() -> Element {
     let id_token = use_context::<Signal<Option<String>>>();
     let token = id_token().unwrap_or_default();
     let lang = crate::use_lang()();
-    let toasts = crate::use_toasts();
 
-    let feed = use_resource(move || {
+    let mut feed_resource = use_resource(move || {
         let token = token.clone();
         async move {
             if token.trim().is_empty() {
                 return Ok(vec![]);
             }
-            api::list_my_activity(token, 50).await
-        }
-    });
-    let mut load_error = use_signal(|| None::<String>);
-
-    use_effect(move || {
-        let err = feed().and_then(|res| res.err()).map(|e| e.to_string());
-        if err.as_ref() != load_error().as_ref() {
-            if let Some(message) = &err {
-                toasts.error(
-                    crate::t(lang, "toast.load_activity_title"),
-                    Some(format!("{} {message}", crate::t(lang, "toast.details"))),
-                );
-            }
-            load_error.set(err);
+            api::list_my_activity(token, 50, None).await
         }
     });
+    let feed = crate::use_server_result(feed_resource, "toast.load_activity_title");
 
     rsx! {
         document::Link { rel: "stylesheet", href: FEED_CSS }
         div { class: "panel",
             h2 { "Your activity" }
-            match feed() {
-                None => rsx! { p { "Loading…" } },
-                Some(Err(_)) => rsx! { p { class: "hint", {crate::t(lang, "common.error_try_again")} } },
+            match feed {
+                None => rsx! { crate::Loading {} },
+                Some(Err(e)) => rsx! {
+                    crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| feed_resource.restart() }
+                },
                 Some(Ok(items)) => rsx! {
                     if items.is_empty() {
                         p { class: "hint", "No activity yet." }
                     }
                     for a in items {
                         div { class: "activity",
-                            span { class: "hint", "{a.created_at}" }
+                            span { class: "hint", {crate::format_datetime(lang, a.created_at)} }
                             span { " " }
                             span { class: "hint", "{a.action:?}" }
                             span { " " }
@@ -141,3 +190,195 @@ pub fn ActivityFeed() -> Element {
         }
     }
 }
+
+/// Recent public activity across all users, for the home page.
+#[component]
+pub fn GlobalActivityFeed() -> Element {
+    let lang = crate::use_lang()();
+    let id_token = use_context::<Signal<Option<String>>>();
+    let mut feed_resource = use_resource(move || {
+        let token = id_token();
+        async move { api::list_global_activity(20, 0, token).await }
+    });
+    let feed = crate::use_server_result(feed_resource, "toast.load_global_activity_title");
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: FEED_CSS }
+        div { class: "panel",
+            h2 { {crate::t(lang, "activity.global_title")} }
+            match feed {
+                None => rsx! { crate::Loading {} },
+                Some(Err(e)) => rsx! {
+                    crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| feed_resource.restart() }
+                },
+                Some(Ok(items)) => rsx! {
+                    if items.is_empty() {
+                        p { class: "hint", {crate::t(lang, "activity.global_empty")} }
+                    }
+                    for a in items {
+                        div { class: "activity",
+                            span { class: "hint", {crate::format_datetime(lang, a.created_at)} }
+                            span { " " }
+                            span { {a.actor_display_name.clone().unwrap_or_else(|| crate::t(lang, "activity.someone"))} }
+                            span { " " }
+                            span { class: "hint", "{a.action:?}" }
+                            if let Some(title) = a.title {
+                                span { " — " }
+                                span { "{title}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A user's comment history, each linked back to what it was posted on.
+#[component]
+pub fn AuthorComments(user_id: String) -> Element {
+    let lang = crate::use_lang()();
+    let id_token = use_context::<Signal<Option<String>>>();
+    let mut comments_resource = use_resource(move || {
+        let user_id = user_id.clone();
+        let token = id_token();
+        async move { api::list_comments_by_author(user_id, 50, 0, token).await }
+    });
+    let comments = crate::use_server_result(comments_resource, "toast.load_comments_title");
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: FEED_CSS }
+        div { class: "panel",
+            h2 { {crate::t(lang, "comments.history_title")} }
+            match comments {
+                None => rsx! { crate::Loading {} },
+                Some(Err(e)) => rsx! {
+                    crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| comments_resource.restart() }
+                },
+                Some(Ok(items)) => rsx! {
+                    if items.is_empty() {
+                        p { class: "hint", {crate::t(lang, "comments.history_empty")} }
+                    }
+                    for item in items {
+                        div { class: "activity",
+                            span { class: "hint", {crate::format_datetime(lang, item.comment.created_at)} }
+                            span { " " }
+                            a {
+                                href: target_href(&item.comment),
+                                {item.target_title.clone().unwrap_or_else(|| crate::t(lang, "comments.history_untitled"))}
+                            }
+                            p { "{item.comment.body_markdown}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The signed-in user's own proposals, programs and videos, with edit links where available.
+#[component]
+pub fn MyContent(user_id: String) -> Element {
+    let lang = crate::use_lang()();
+    let id_token = use_context::<Signal<Option<String>>>();
+    let proposals_user = user_id.clone();
+    let programs_user = user_id.clone();
+    let mut proposals_resource = use_resource(move || {
+        let user_id = proposals_user.clone();
+        let token = id_token();
+        async move { api::list_proposals_by_author(user_id, 50, 0, token).await }
+    });
+    let mut programs_resource = use_resource(move || {
+        let user_id = programs_user.clone();
+        let token = id_token();
+        async move { api::list_programs_by_author(user_id, 50, 0, token).await }
+    });
+    let mut videos_resource = use_resource(move || {
+        let user_id = user_id.clone();
+        let token = id_token();
+        async move { api::list_videos_by_author(user_id, 50, 0, token).await }
+    });
+    let proposals = crate::use_server_result(proposals_resource, "toast.load_proposals_title");
+    let programs = crate::use_server_result(programs_resource, "toast.load_programs_title");
+    let videos = crate::use_server_result(videos_resource, "toast.load_videos_title");
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: FEED_CSS }
+        div { class: "panel",
+            h2 { {crate::t(lang, "me.content.proposals")} }
+            match proposals {
+                None => rsx! { crate::Loading {} },
+                Some(Err(e)) => rsx! {
+                    crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| proposals_resource.restart() }
+                },
+                Some(Ok(items)) => rsx! {
+                    if items.is_empty() {
+                        p { class: "hint", {crate::t(lang, "common.no_proposals_yet")} }
+                    }
+                    for p in items {
+                        div { class: "card",
+                            div { class: "card_top",
+                                a { href: "/proposals/{p.slug}", h3 { "{p.title}" } }
+                                span { class: "score", "{p.vote_score} votes" }
+                            }
+                            a { class: "btn", href: "/proposals/{p.id}/edit", {crate::t(lang, "proposals.edit_link")} }
+                        }
+                    }
+                }
+            }
+        }
+        div { class: "panel",
+            h2 { {crate::t(lang, "me.content.programs")} }
+            match programs {
+                None => rsx! { crate::Loading {} },
+                Some(Err(e)) => rsx! {
+                    crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| programs_resource.restart() }
+                },
+                Some(Ok(items)) => rsx! {
+                    if items.is_empty() {
+                        p { class: "hint", {crate::t(lang, "common.no_programs_yet")} }
+                    }
+                    for p in items {
+                        a { class: "card", href: "/programs/{p.slug}",
+                            div { class: "card_top",
+                                h3 { "{p.title}" }
+                                span { class: "score", "{p.vote_score} votes" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        div { class: "panel",
+            h2 { {crate::t(lang, "me.content.videos")} }
+            match videos {
+                None => rsx! { crate::Loading {} },
+                Some(Err(e)) => rsx! {
+                    crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| videos_resource.restart() }
+                },
+                Some(Ok(items)) => rsx! {
+                    if items.is_empty() {
+                        p { class: "hint", {crate::t(lang, "common.no_videos_yet")} }
+                    }
+                    for v in items {
+                        a { class: "card", href: "/videos/{v.id}",
+                            div { class: "card_top",
+                                h3 { "{v.storage_key}" }
+                                span { class: "score", "{v.vote_score} votes" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn target_href(comment: &api::types::Comment) -> String {
+    use api::types::ContentTargetType;
+    match comment.target_type {
+        ContentTargetType::Proposal => format!("/proposals/{}", comment.target_id),
+        ContentTargetType::Program => format!("/programs/{}", comment.target_id),
+        ContentTargetType::Video | ContentTargetType::Comment => "/videos".to_string(),
+    }
+}
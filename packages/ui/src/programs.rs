@@ -5,22 +5,12 @@ const FEED_CSS: Asset = asset!("/assets/styling/feed.css");
 #[component]
 pub fn ProgramListPage() -> Element {
     let lang = crate::use_lang()();
-    let toasts = crate::use_toasts();
-    let programs = use_resource(|| async move { api::list_programs(50).await });
-    let mut load_error = use_signal(|| None::<String>);
-
-    use_effect(move || {
-        let err = programs().and_then(|res| res.err()).map(|e| e.to_string());
-        if err.as_ref() != load_error().as_ref() {
-            if let Some(message) = &err {
-                toasts.error(
-                    crate::t(lang, "toast.load_programs_title"),
-                    Some(format!("{} {message}", crate::t(lang, "toast.details"))),
-                );
-            }
-            load_error.set(err);
-        }
+    let id_token = use_context::<Signal<Option<String>>>();
+    let mut programs_resource = use_resource(move || {
+        let token = id_token();
+        async move { api::list_programs(50, token).await }
     });
+    let programs = crate::use_server_result(programs_resource, "toast.load_programs_title");
 
     rsx! {
         document::Link { rel: "stylesheet", href: FEED_CSS }
@@ -30,7 +20,7 @@ pub fn ProgramListPage() -> Element {
                 a { class: "btn primary", href: "/programs/new", {crate::t(lang, "programs.new")} }
             }
 
-            match programs() {
+            match programs {
                 None => rsx! {
                     for _ in 0..5 {
                         div { class: "card skeleton",
@@ -42,13 +32,15 @@ pub fn ProgramListPage() -> Element {
                         }
                     }
                 },
-                Some(Err(_)) => rsx! { p { class: "hint", {crate::t(lang, "common.error_try_again")} } },
+                Some(Err(e)) => rsx! {
+                    crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| programs_resource.restart() }
+                },
                 Some(Ok(items)) => rsx! {
                     if items.is_empty() {
                         p { class: "hint", {crate::t(lang, "common.no_programs_yet")} }
                     }
                     for p in items {
-                        a { class: "card", href: "/programs/{p.id}",
+                        a { class: "card", href: "/programs/{p.slug}",
                             div { class: "card_top",
                                 h3 { "{p.title}" }
                                 span { class: "score", "{p.vote_score} votes" }
@@ -160,25 +152,13 @@ pub fn ProgramNewPage() -> Element {
 #[component]
 pub fn ProgramDetailPage(id: String) -> Element {
     let lang = crate::use_lang()();
-    let toasts = crate::use_toasts();
-    let detail = use_resource(move || {
+    let id_token = use_context::<Signal<Option<String>>>();
+    let mut detail_resource = use_resource(move || {
         let id = id.clone();
-        async move { api::get_program(id).await }
-    });
-    let mut load_error = use_signal(|| None::<String>);
-
-    use_effect(move || {
-        let err = detail().and_then(|res| res.err()).map(|e| e.to_string());
-        if err.as_ref() != load_error().as_ref() {
-            if let Some(message) = &err {
-                toasts.error(
-                    crate::t(lang, "toast.load_program_title"),
-                    Some(format!("{} {message}", crate::t(lang, "toast.details"))),
-                );
-            }
-            load_error.set(err);
-        }
+        let token = id_token();
+        async move { api::get_program(id, token).await }
     });
+    let detail = crate::use_server_result(detail_resource, "toast.load_program_title");
 
     rsx! {
         document::Link { rel: "stylesheet", href: FEED_CSS }
@@ -187,14 +167,19 @@ pub fn ProgramDetailPage(id: String) -> Element {
                 a { class: "btn", href: "/programs", {crate::t(lang, "common.back")} }
                 a { class: "btn", href: "/proposals", {crate::t(lang, "programs.browse_proposals")} }
             }
-            match detail() {
-                None => rsx! { p { {crate::t(lang, "common.loading")} } },
-                Some(Err(_)) => rsx! { p { class: "hint", {crate::t(lang, "common.error_try_again")} } },
+            match detail {
+                None => rsx! { crate::Loading {} },
+                Some(Err(e)) => rsx! {
+                    crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| detail_resource.restart() }
+                },
                 Some(Ok(d)) => rsx! {
                     div { class: "panel",
                         h1 { "{d.program.title}" }
                         div { class: "meta",
                             span { class: "score", "{d.program.vote_score} votes" }
+                            if d.verified_vote_score != d.program.vote_score {
+                                span { class: "hint", {format!("{} {}", d.verified_vote_score, crate::t(lang, "votes.verified_score"))} }
+                            }
                             span { class: "hint", {format!("{} {}", crate::t(lang, "common.id"), d.program.id)} }
                         }
                         if !d.program.summary.trim().is_empty() {
@@ -224,7 +209,7 @@ pub fn ProgramDetailPage(id: String) -> Element {
                             p { class: "hint", {crate::t(lang, "programs.none_bundled")} }
                         }
                         for p in d.proposals {
-                            a { class: "card", href: "/proposals/{p.id}",
+                            a { class: "card", href: "/proposals/{p.slug}",
                                 div { class: "card_top",
                                     h3 { "{p.title}" }
                                     span { class: "score", "{p.vote_score} votes" }
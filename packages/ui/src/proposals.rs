@@ -1,26 +1,34 @@
+use api::types::ErrorCode;
+use api::ProposalDetail;
 use dioxus::prelude::*;
 
 const FEED_CSS: Asset = asset!("/assets/styling/feed.css");
 
+/// Proposals per page of the list, and per "Load more".
+const PAGE_SIZE: i64 = 50;
+
 #[component]
 pub fn ProposalListPage() -> Element {
     let lang = crate::use_lang()();
     let toasts = crate::use_toasts();
-    let proposals = use_resource(|| async move { api::list_proposals(50).await });
-    let mut load_error = use_signal(|| None::<String>);
-
-    use_effect(move || {
-        let err = proposals().and_then(|res| res.err()).map(|e| e.to_string());
-        if err.as_ref() != load_error().as_ref() {
-            if let Some(message) = &err {
-                toasts.error(
-                    crate::t(lang, "toast.load_proposals_title"),
-                    Some(format!("{} {message}", crate::t(lang, "toast.details"))),
-                );
-            }
-            load_error.set(err);
+    let id_token = use_context::<Signal<Option<String>>>();
+    // Pages loaded with "Load more", and whether another follows them (`None` until
+    // the first of them loads).
+    let mut older = use_signal(Vec::<api::types::Proposal>::new);
+    let mut older_has_more = use_signal(|| None::<bool>);
+    let mut proposals_resource = use_resource(move || {
+        let token = id_token();
+        older.set(Vec::new());
+        older_has_more.set(None);
+        async move {
+            let options = api::types::DiscoverOptions {
+                limit: PAGE_SIZE,
+                ..Default::default()
+            };
+            api::discover_proposals(options, token).await
         }
     });
+    let proposals = crate::use_server_result(proposals_resource, "toast.load_proposals_title");
 
     rsx! {
         document::Link { rel: "stylesheet", href: FEED_CSS }
@@ -30,7 +38,7 @@ pub fn ProposalListPage() -> Element {
                 a { class: "btn primary", href: "/proposals/new", {crate::t(lang, "proposals.new")} }
             }
 
-            match proposals() {
+            match proposals {
                 None => rsx! {
                     for _ in 0..5 {
                         div { class: "card skeleton",
@@ -42,30 +50,70 @@ pub fn ProposalListPage() -> Element {
                         }
                     }
                 },
-                Some(Err(_)) => rsx! { p { class: "hint", {crate::t(lang, "common.error_try_again")} } },
-                Some(Ok(items)) => rsx! {
-                    if items.is_empty() {
-                        p { class: "hint", {crate::t(lang, "common.no_proposals_yet")} }
-                    }
-                    for p in items {
-                        a { class: "card", href: "/proposals/{p.id}",
-                            div { class: "card_top",
-                                h3 { "{p.title}" }
-                                span { class: "score", "{p.vote_score} votes" }
-                            }
-                            if !p.summary.trim().is_empty() {
-                                p { class: "summary", "{p.summary}" }
-                            } else {
-                                p { class: "summary", "{truncate(&p.body_markdown, 140)}" }
-                            }
-                            if !p.tags.is_empty() {
-                                div { class: "tags",
-                                    for t in p.tags {
-                                        span { class: "tag", "{t}" }
+                Some(Err(e)) => rsx! {
+                    crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| proposals_resource.restart() }
+                },
+                Some(Ok(page)) => {
+                    let first_page_end =
+                        page.proposals.last().map(api::types::ProposalCursor::from);
+                    rsx! {
+                        if page.proposals.is_empty() {
+                            p { class: "hint", {crate::t(lang, "common.no_proposals_yet")} }
+                        }
+                        for p in page.proposals.iter().cloned().chain(older()) {
+                            a { class: "card", href: "/proposals/{p.slug}",
+                                div { class: "card_top",
+                                    h3 { "{p.title}" }
+                                    span { class: "score", "{p.vote_score} votes" }
+                                }
+                                if !p.summary.trim().is_empty() {
+                                    p { class: "summary", "{p.summary}" }
+                                } else {
+                                    p { class: "summary", "{truncate(&p.body_markdown, 140)}" }
+                                }
+                                if !p.tags.is_empty() {
+                                    div { class: "tags",
+                                        for t in p.tags {
+                                            span { class: "tag", "{t}" }
+                                        }
                                     }
                                 }
                             }
                         }
+                        if older_has_more().unwrap_or(page.has_more) {
+                            button {
+                                class: "btn",
+                                onclick: move |_| {
+                                    let before = older()
+                                        .last()
+                                        .map(api::types::ProposalCursor::from)
+                                        .or(first_page_end);
+                                    let options = api::types::DiscoverOptions {
+                                        before,
+                                        limit: PAGE_SIZE,
+                                        ..Default::default()
+                                    };
+                                    let token = id_token();
+                                    let toasts = toasts.clone();
+                                    spawn(async move {
+                                        match api::discover_proposals(options, token).await {
+                                            Ok(next) => {
+                                                older.with_mut(|o| o.extend(next.proposals));
+                                                older_has_more.set(Some(next.has_more));
+                                            }
+                                            Err(e) => toasts.error(
+                                                crate::t(lang, "toast.load_proposals_title"),
+                                                Some(format!(
+                                                    "{} {e}",
+                                                    crate::t(lang, "toast.details")
+                                                )),
+                                            ),
+                                        }
+                                    });
+                                },
+                                {crate::t(lang, "common.load_more")}
+                            }
+                        }
                     }
                 }
             }
@@ -85,6 +133,8 @@ pub fn ProposalNewPage() -> Element {
     let mut body = use_signal(String::new);
     let mut tags = use_signal(String::new);
     let mut status = use_signal(String::new);
+    // Existing proposals with a similar title, checked when the title field loses focus.
+    let mut similar = use_signal(Vec::<api::types::Proposal>::new);
 
     let title_ph = crate::t(lang, "proposals.form.title_ph");
     let summary_ph = crate::t(lang, "proposals.form.summary_ph");
@@ -111,8 +161,29 @@ pub fn ProposalNewPage() -> Element {
                     input {
                         value: "{title}",
                         oninput: move |e| title.set(e.value()),
+                        onchange: move |e| {
+                            let t = e.value();
+                            let token = id_token();
+                            spawn(async move {
+                                if let Ok(found) = api::find_similar_proposals(t, token).await {
+                                    similar.set(found);
+                                }
+                            });
+                        },
                         placeholder: "{title_ph}",
                     }
+                    if !similar().is_empty() {
+                        div { class: "hint",
+                            p { {crate::t(lang, "proposals.form.similar")} }
+                            ul {
+                                for p in similar() {
+                                    li { key: "{p.id}",
+                                        a { href: "/proposals/{p.slug}", "{p.title}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
                     label { {crate::t(lang, "proposals.form.summary_opt")} }
                     input {
                         value: "{summary}",
@@ -164,28 +235,189 @@ pub fn ProposalNewPage() -> Element {
     }
 }
 
+/// Form values for editing a proposal, pre-filled from the stored proposal.
+#[derive(Debug, Clone, PartialEq)]
+struct ProposalForm {
+    title: String,
+    summary: String,
+    body: String,
+    tags: String,
+}
+
+impl ProposalForm {
+    fn from_proposal(p: &api::types::Proposal) -> Self {
+        Self {
+            title: p.title.clone(),
+            summary: p.summary.clone(),
+            body: p.body_markdown.clone(),
+            tags: p.tags.join(", "),
+        }
+    }
+}
+
 #[component]
-pub fn ProposalDetailPage(id: String) -> Element {
+pub fn ProposalEditPage(id: String) -> Element {
     let lang = crate::use_lang()();
     let toasts = crate::use_toasts();
-    let proposal = use_resource(move || {
+    let id_token = use_context::<Signal<Option<String>>>();
+    let token = id_token().unwrap_or_default();
+    let proposal_id = id.clone();
+    let mut proposal = use_resource(move || {
         let id = id.clone();
-        async move { api::get_proposal(id).await }
+        let token = id_token();
+        async move { api::get_proposal(id, token).await.map(|d| d.proposal) }
     });
-    let mut load_error = use_signal(|| None::<String>);
+    let loaded = crate::use_server_result(proposal, "toast.load_proposal_title");
+
+    let mut title = use_signal(String::new);
+    let mut summary = use_signal(String::new);
+    let mut body = use_signal(String::new);
+    let mut tags = use_signal(String::new);
+    let mut status = use_signal(String::new);
+    let mut prefilled = use_signal(|| false);
+    // The version the form was loaded from; the server rejects saves over newer edits.
+    let mut loaded_at = use_signal(|| None::<time::OffsetDateTime>);
 
     use_effect(move || {
-        let err = proposal().and_then(|res| res.err()).map(|e| e.to_string());
-        if err.as_ref() != load_error().as_ref() {
-            if let Some(message) = &err {
-                toasts.error(
-                    crate::t(lang, "toast.load_proposal_title"),
-                    Some(format!("{} {message}", crate::t(lang, "toast.details"))),
-                );
+        if let Some(Ok(p)) = proposal() {
+            if !prefilled() {
+                let form = ProposalForm::from_proposal(&p);
+                title.set(form.title);
+                summary.set(form.summary);
+                body.set(form.body);
+                tags.set(form.tags);
+                loaded_at.set(Some(p.updated_at));
+                prefilled.set(true);
+            }
+        }
+    });
+
+    let title_ph = crate::t(lang, "proposals.form.title_ph");
+    let summary_ph = crate::t(lang, "proposals.form.summary_ph");
+    let body_ph = crate::t(lang, "proposals.form.body_ph");
+    let tags_ph = crate::t(lang, "proposals.form.tags_ph");
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: FEED_CSS }
+
+        div { class: "page",
+            div { class: "page_header",
+                h1 { {crate::t(lang, "proposals.edit")} }
+                a { class: "btn", href: "/proposals/{proposal_id}", {crate::t(lang, "common.back")} }
+            }
+
+            if id_token().is_none() {
+                div { class: "panel",
+                    p { {crate::t(lang, "proposals.need_signin_edit")} }
+                    a { class: "btn primary", href: "/auth/signin", {crate::t(lang, "common.signin")} }
+                }
+            } else {
+                match loaded {
+                    None => rsx! { crate::Loading {} },
+                    Some(Err(e)) => rsx! {
+                        crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| proposal.restart() }
+                    },
+                    Some(Ok(_)) => rsx! {
+                        div { class: "panel",
+                            label { {crate::t(lang, "proposals.form.title")} }
+                            input {
+                                value: "{title}",
+                                oninput: move |e| title.set(e.value()),
+                                placeholder: "{title_ph}",
+                            }
+                            label { {crate::t(lang, "proposals.form.summary_opt")} }
+                            input {
+                                value: "{summary}",
+                                oninput: move |e| summary.set(e.value()),
+                                placeholder: "{summary_ph}",
+                            }
+                            label { {crate::t(lang, "proposals.form.body")} }
+                            textarea {
+                                value: "{body}",
+                                oninput: move |e| body.set(e.value()),
+                                placeholder: "{body_ph}",
+                                rows: 10,
+                            }
+                            label { {crate::t(lang, "proposals.form.tags")} }
+                            input {
+                                value: "{tags}",
+                                oninput: move |e| tags.set(e.value()),
+                                placeholder: "{tags_ph}",
+                            }
+                            button {
+                                class: "btn primary",
+                                onclick: move |_| {
+                                    let token = token.clone();
+                                    let id = proposal_id.clone();
+                                    let t = title();
+                                    let s = summary();
+                                    let b = body();
+                                    let tg = tags();
+                                    let Some(expected) = loaded_at() else {
+                                        return;
+                                    };
+                                    let lang = lang;
+                                    let toasts = toasts.clone();
+                                    spawn(async move {
+                                        match api::update_proposal(token, id, t, s, b, tg, expected).await {
+                                            Ok(saved) => {
+                                                loaded_at.set(Some(saved.updated_at));
+                                                status.set(crate::t(lang, "proposals.saved"));
+                                            }
+                                            Err(e) if ErrorCode::from_error(&e.to_string()) == Some(ErrorCode::Conflict) => toasts.error(
+                                                crate::t(lang, "toast.update_proposal_title"),
+                                                Some(crate::t(lang, "proposals.conflict")),
+                                            ),
+                                            Err(e) if ErrorCode::from_error(&e.to_string()) == Some(ErrorCode::NotAllowed) => toasts.error(
+                                                crate::t(lang, "toast.update_proposal_title"),
+                                                Some(crate::t(lang, "proposals.not_author")),
+                                            ),
+                                            Err(e) => toasts.error(
+                                                crate::t(lang, "toast.update_proposal_title"),
+                                                Some(format!(
+                                                    "{} {e}",
+                                                    crate::t(lang, "toast.details")
+                                                )),
+                                            ),
+                                        }
+                                    });
+                                },
+                                {crate::t(lang, "proposals.form.save")}
+                            }
+
+                            if !status().is_empty() {
+                                p { class: "hint", "{status}" }
+                            }
+                        }
+                    },
+                }
             }
-            load_error.set(err);
         }
+    }
+}
+
+#[component]
+pub fn ProposalDetailPage(id: String) -> Element {
+    let lang = crate::use_lang()();
+    let id_token = use_context::<Signal<Option<String>>>();
+    let programs_id = id.clone();
+    let related_id = id.clone();
+    let mut proposal_resource = use_resource(move || {
+        let id = id.clone();
+        let token = id_token();
+        async move { api::get_proposal(id, token).await }
+    });
+    let programs = use_resource(move || {
+        let id = programs_id.clone();
+        let token = id_token();
+        async move { api::list_proposal_programs(id, token).await }
+    });
+    let related = use_resource(move || {
+        let id = related_id.clone();
+        let token = id_token();
+        async move { api::list_related_proposals(id, 5, token).await }
     });
+    let proposal = crate::use_server_result(proposal_resource, "toast.load_proposal_title");
 
     rsx! {
         document::Link { rel: "stylesheet", href: FEED_CSS }
@@ -194,21 +426,41 @@ pub fn ProposalDetailPage(id: String) -> Element {
                 a { class: "btn", href: "/proposals", {crate::t(lang, "common.back")} }
                 a { class: "btn", href: "/programs/new", {crate::t(lang, "proposals.bundle_into_program")} }
             }
-            match proposal() {
-                None => rsx! { p { {crate::t(lang, "common.loading")} } },
-                Some(Err(_)) => rsx! { p { class: "hint", {crate::t(lang, "common.error_try_again")} } },
-                Some(Ok(p)) => rsx! {
+            match proposal {
+                None => rsx! { crate::Loading {} },
+                Some(Err(e)) => rsx! {
+                    crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| proposal_resource.restart() }
+                },
+                Some(Ok(ProposalDetail { proposal: p, can_edit, word_count, estimated_read_minutes, comment_count, verified_vote_score, .. })) => rsx! {
                     div { class: "panel",
                         h1 { "{p.title}" }
                         div { class: "meta",
                             span { class: "score", "{p.vote_score} votes" }
+                            if verified_vote_score != p.vote_score {
+                                span { class: "hint", {format!("{} {}", verified_vote_score, crate::t(lang, "votes.verified_score"))} }
+                            }
                             span { class: "hint", {format!("{} {}", crate::t(lang, "common.id"), p.id)} }
+                            span { class: "hint", {format!("{} {} · {} {}", word_count, crate::t(lang, "proposals.words"), estimated_read_minutes, crate::t(lang, "proposals.read_minutes"))} }
+                            span { class: "hint", {format!("{} {}", comment_count, crate::t(lang, "proposals.comments"))} }
+                            if can_edit {
+                                a { class: "btn", href: "/proposals/{p.id}/edit", {crate::t(lang, "proposals.edit_link")} }
+                            }
                         }
                         if !p.summary.trim().is_empty() {
                             p { class: "summary", "{p.summary}" }
                         }
                         pre { class: "body", "{p.body_markdown}" }
                     }
+                    if let Some(Ok(memberships)) = programs() {
+                        if !memberships.is_empty() {
+                            div { class: "panel",
+                                h2 { {format!("{} {} {}", crate::t(lang, "proposals.included_in"), memberships.len(), crate::t(lang, "proposals.included_in_programs"))} }
+                                for m in memberships {
+                                    a { class: "btn", href: "/programs/{m.program.slug}", "{m.program.title}" }
+                                }
+                            }
+                        }
+                    }
                     div { class: "panel",
                         h2 { {crate::t(lang, "common.vote")} }
                         crate::VoteWidget {
@@ -217,6 +469,16 @@ pub fn ProposalDetailPage(id: String) -> Element {
                             initial_score: p.vote_score,
                         }
                     }
+                    if let Some(Ok(items)) = related() {
+                        if !items.is_empty() {
+                            div { class: "panel",
+                                h2 { {crate::t(lang, "proposals.related")} }
+                                for r in items {
+                                    a { class: "btn", href: "/proposals/{r.slug}", "{r.title}" }
+                                }
+                            }
+                        }
+                    }
                     crate::CommentThread {
                         target_type: api::types::ContentTargetType::Proposal,
                         target_id: p.id.to_string(),
@@ -237,3 +499,48 @@ fn truncate(s: &str, max: usize) -> String {
     }
     s.chars().take(max).collect::<String>() + "…"
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::types::Proposal;
+
+    fn proposal(author: uuid::Uuid) -> Proposal {
+        Proposal {
+            id: uuid::Uuid::nil(),
+            author_user_id: author,
+            title: "Bike lanes".to_string(),
+            slug: "bike-lanes".to_string(),
+            summary: "Safer streets".to_string(),
+            body_markdown: "# Plan".to_string(),
+            tags: vec!["mobility".to_string(), "city".to_string()],
+            created_at: time::OffsetDateTime::UNIX_EPOCH,
+            updated_at: time::OffsetDateTime::UNIX_EPOCH,
+            vote_score: 3,
+            author_display_name: None,
+        }
+    }
+
+    #[test]
+    fn edit_form_prefills_from_proposal() {
+        let form = ProposalForm::from_proposal(&proposal(uuid::Uuid::nil()));
+        assert_eq!(
+            form,
+            ProposalForm {
+                title: "Bike lanes".to_string(),
+                summary: "Safer streets".to_string(),
+                body: "# Plan".to_string(),
+                tags: "mobility, city".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn edit_form_tags_submit_as_the_same_csv() {
+        // The pre-filled tag field is sent back as `tags_csv`; it must split to the same tags.
+        let p = proposal(uuid::Uuid::nil());
+        let form = ProposalForm::from_proposal(&p);
+        let resubmitted: Vec<&str> = form.tags.split(',').map(str::trim).collect();
+        assert_eq!(resubmitted, p.tags);
+    }
+}
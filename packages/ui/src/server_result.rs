@@ -0,0 +1,187 @@
+//! Shared handling for server calls loaded with `use_resource`: one error toast per
+//! failure plus the standard loading / failed placeholders.
+
+use api::types::ErrorCode;
+use dioxus::prelude::*;
+
+/// How a failed load should be shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LoadFailure {
+    /// What was asked for doesn't exist (or was hidden): nothing to show or retry.
+    NotFound,
+    /// The deployment only shows this to signed-in users.
+    SignInRequired,
+    /// Anything else; a retry may help.
+    #[default]
+    Internal,
+}
+
+impl LoadFailure {
+    pub fn of(error: &ServerFnError) -> Self {
+        Self::from_error_text(&error.to_string())
+    }
+
+    fn from_error_text(text: &str) -> Self {
+        match ErrorCode::from_error(text) {
+            Some(ErrorCode::NotFound) => LoadFailure::NotFound,
+            Some(ErrorCode::Unauthorized) => LoadFailure::SignInRequired,
+            _ => LoadFailure::Internal,
+        }
+    }
+}
+
+/// Remembers the last load error so a failure is toasted once, not on every rerun.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ErrorDedupe {
+    last: Option<String>,
+}
+
+impl ErrorDedupe {
+    /// Record the current error; returns it when it should be toasted.
+    ///
+    /// A repeated message is reported once. A success (or reload) in between
+    /// re-arms it, so the same failure toasts again after a retry.
+    pub fn observe(&mut self, error: Option<String>) -> Option<String> {
+        if error == self.last {
+            return None;
+        }
+        self.last = error.clone();
+        error
+    }
+}
+
+/// Toast `resource`'s error once under the `title_key` heading; returns its current value.
+///
+/// Render `None` with [`Loading`] (or a page skeleton) and `Some(Err(_))` with [`LoadFailed`].
+/// Not-found errors aren't toasted: the placeholder says all there is to say.
+pub fn use_server_result<T: Clone + 'static>(
+    resource: Resource<Result<T, ServerFnError>>,
+    title_key: &'static str,
+) -> Option<Result<T, ServerFnError>> {
+    let lang = crate::use_lang()();
+    let toasts = crate::use_toasts();
+    let mut dedupe = use_signal(ErrorDedupe::default);
+
+    use_effect(move || {
+        let error = resource()
+            .and_then(|res| res.err())
+            .filter(|e| LoadFailure::of(e) == LoadFailure::Internal)
+            .map(|e| e.to_string());
+        if let Some(message) = dedupe.with_mut(|d| d.observe(error)) {
+            toasts.error(
+                crate::t(lang, title_key),
+                Some(crate::error_details(lang, &message)),
+            );
+        }
+    });
+
+    resource()
+}
+
+/// Placeholder while a resource loads.
+#[component]
+pub fn Loading() -> Element {
+    let lang = crate::use_lang()();
+    rsx! {
+        p { {crate::t(lang, "common.loading")} }
+    }
+}
+
+/// Placeholder for a failed load: "nothing here" when it doesn't exist, a sign-in
+/// link when it's members-only, otherwise a retry prompt (the details went to a toast).
+#[component]
+pub fn LoadFailed(
+    #[props(default)] failure: LoadFailure,
+    /// Shows a retry button, typically `move |_| resource.restart()`.
+    on_retry: Option<EventHandler>,
+) -> Element {
+    let lang = crate::use_lang()();
+    match failure {
+        LoadFailure::NotFound => rsx! {
+            p { class: "hint", {crate::t(lang, "common.nothing_here")} }
+        },
+        LoadFailure::SignInRequired => rsx! {
+            p { class: "hint", {crate::t(lang, "error.unauthorized")} }
+            a { class: "btn primary", href: "/auth/signin", {crate::t(lang, "common.signin")} }
+        },
+        LoadFailure::Internal => rsx! {
+            p { class: "hint", {crate::t(lang, "common.error_try_again")} }
+            if let Some(on_retry) = on_retry {
+                button { class: "btn", onclick: move |_| on_retry.call(()), {crate::t(lang, "common.retry")} }
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_error_toasts_once() {
+        let mut dedupe = ErrorDedupe::default();
+        assert_eq!(dedupe.observe(None), None);
+        assert_eq!(dedupe.observe(Some("boom".into())), Some("boom".into()));
+        assert_eq!(dedupe.observe(Some("boom".into())), None);
+        assert_eq!(dedupe.observe(Some("boom".into())), None);
+    }
+
+    #[test]
+    fn new_error_toasts_again() {
+        let mut dedupe = ErrorDedupe::default();
+        assert_eq!(dedupe.observe(Some("boom".into())), Some("boom".into()));
+        assert_eq!(dedupe.observe(Some("bang".into())), Some("bang".into()));
+    }
+
+    #[test]
+    fn not_found_code_maps_to_not_found() {
+        for lang in [None, Some("fr")] {
+            let text = format!(
+                "error running server function: {}",
+                ErrorCode::NotFound.error_text(lang)
+            );
+            assert_eq!(LoadFailure::from_error_text(&text), LoadFailure::NotFound);
+        }
+        let err = ServerFnError::new(ErrorCode::NotFound.to_string());
+        assert_eq!(LoadFailure::of(&err), LoadFailure::NotFound);
+        assert_eq!(
+            crate::ApiError::Server(err).load_failure(),
+            LoadFailure::NotFound
+        );
+    }
+
+    #[test]
+    fn unauthorized_code_asks_to_sign_in() {
+        let err = ServerFnError::new(ErrorCode::Unauthorized.error_text(Some("fr")));
+        assert_eq!(LoadFailure::of(&err), LoadFailure::SignInRequired);
+    }
+
+    #[test]
+    fn other_errors_map_to_internal() {
+        for text in [
+            "",
+            "connection refused",
+            "target not found",
+            &ErrorCode::NotAllowed.to_string(),
+            &ErrorCode::Conflict.to_string(),
+        ] {
+            assert_eq!(
+                LoadFailure::from_error_text(text),
+                LoadFailure::Internal,
+                "{text}"
+            );
+        }
+        assert_eq!(
+            crate::ApiError::NotSignedIn.load_failure(),
+            LoadFailure::Internal
+        );
+    }
+
+    #[test]
+    fn success_rearms_the_same_error() {
+        let mut dedupe = ErrorDedupe::default();
+        assert_eq!(dedupe.observe(Some("boom".into())), Some("boom".into()));
+        assert_eq!(dedupe.observe(None), None);
+        assert_eq!(dedupe.observe(Some("boom".into())), Some("boom".into()));
+    }
+}
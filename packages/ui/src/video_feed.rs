@@ -3,6 +3,9 @@ use dioxus::prelude::*;
 
 const VIDEO_FEED_CSS: Asset = asset!("/assets/styling/video_feed.css");
 
+/// Comments previewed under the metadata before opening the full thread.
+const TOP_COMMENT_PREVIEW: i64 = 2;
+
 #[component]
 fn VideoOverlay(
     video_id: String,
@@ -120,27 +123,48 @@ fn VideoOverlay(
 
 #[component]
 fn VideoMetadata(video: Video) -> Element {
+    let id_token = use_context::<Signal<Option<String>>>();
     // Load proposal/program info
     let mut content_title = use_signal(|| String::from("Loading..."));
-    let author_name = use_signal(|| String::from(""));
+    let mut author_name = use_signal(|| String::from(""));
+
+    let video_id = video.id.to_string();
+    let top_comments = use_resource(move || {
+        let vid = video_id.clone();
+        let token = id_token();
+        async move {
+            api::list_top_comments(ContentTargetType::Video, vid, TOP_COMMENT_PREVIEW, token).await
+        }
+    });
 
     let target_id = video.target_id.to_string();
     use_effect(move || {
         let target_type = video.target_type;
         let tid = target_id.clone();
+        let token = id_token();
 
         spawn(async move {
             match target_type {
                 ContentTargetType::Proposal => {
-                    if let Ok(proposal) = api::get_proposal(tid).await {
-                        content_title.set(proposal.title);
-                        // TODO: Load author name from proposal.author_user_id
+                    if let Ok(detail) = api::get_proposal(tid, token).await {
+                        content_title.set(detail.proposal.title);
+                        author_name.set(
+                            detail
+                                .proposal
+                                .author_display_name
+                                .unwrap_or_else(|| "Anonymous".to_string()),
+                        );
                     }
                 }
                 ContentTargetType::Program => {
-                    if let Ok(program_detail) = api::get_program(tid).await {
+                    if let Ok(program_detail) = api::get_program(tid, token).await {
                         content_title.set(program_detail.program.title);
-                        // TODO: Load author name from program_detail.program.author_user_id
+                        author_name.set(
+                            program_detail
+                                .program
+                                .author_display_name
+                                .unwrap_or_else(|| "Anonymous".to_string()),
+                        );
                     }
                 }
                 _ => {}
@@ -165,6 +189,16 @@ fn VideoMetadata(video: Video) -> Element {
                     _ => "View content",
                 }}
             }
+            if let Some(Ok(comments)) = top_comments() {
+                ul { class: "metadata-comments",
+                    for c in comments {
+                        li { key: "{c.id}",
+                            strong { {c.author_display_name.clone().unwrap_or_else(|| "Anonymous".to_string())} }
+                            " {c.body_markdown}"
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -231,19 +265,25 @@ fn VideoFeedItem(video: Video, is_active: bool) -> Element {
                 None => rsx! { p { class: "hint", "Loading player..." } },
                 Some(Err(_)) => rsx! { p { class: "hint", "Player not configured." } },
                 Some(Ok(cfg)) => {
-                    let src = cfg.media_base_url.as_ref().map(|base| {
-                        format!("{}/{}", base.trim_end_matches('/'), video.storage_key)
-                    });
+                    let base = cfg.media_base_url.as_deref().map(|base| base.trim_end_matches('/'));
 
                     rsx! {
-                        if let Some(src) = src {
+                        if let Some(base) = base {
                             video {
                                 class: "video-feed-player",
-                                src: "{src}",
+                                src: "{base}/{video.storage_key}",
                                 muted: false,
                                 autoplay: is_active,
                                 playsinline: true,
                                 preload: "auto",
+                                for caption in video.captions.iter() {
+                                    track {
+                                        kind: "captions",
+                                        srclang: "{caption.language}",
+                                        label: "{caption.language}",
+                                        src: "{base}/{caption.storage_key}",
+                                    }
+                                }
                             }
                         } else {
                             p { class: "hint", "Set MEDIA_BASE_URL to enable playback." }
@@ -275,65 +315,56 @@ pub fn VideoFeed(
     starting_video_id: Option<String>,
     filter_target_type: Option<ContentTargetType>,
     filter_target_id: Option<String>,
+    /// Show only the signed-in user's own uploads instead of discovery.
+    only_mine: bool,
 ) -> Element {
     let id_token = use_context::<Signal<Option<String>>>();
     let token = id_token().unwrap_or_default();
 
-    // State management
     let current_index = use_signal(|| 0usize);
-    let mut videos = use_signal(Vec::<Video>::new);
-    let mut loading = use_signal(|| true);
-    let mut error_msg = use_signal(|| None::<String>);
 
-    // Load initial videos
     let filter_context = (filter_target_type, filter_target_id.clone());
-    use_effect(move || {
+    let mut videos_resource = use_resource(move || {
         let token = token.clone();
         let filter = filter_context.clone();
-        spawn(async move {
-            loading.set(true);
-
-            let result = if let (Some(target_type), Some(target_id)) = filter {
+        async move {
+            if let (Some(target_type), Some(target_id)) = filter {
                 // Single content mode
                 api::list_single_content_videos(target_type, target_id, 5, 0).await
+            } else if only_mine {
+                // Own uploads mode
+                api::list_my_videos(token, 5, 0).await
             } else {
                 // Discovery mode
                 api::list_feed_videos(token, 5, 0).await
-            };
-
-            match result {
-                Ok(vids) => {
-                    videos.set(vids);
-                    loading.set(false);
-                }
-                Err(e) => {
-                    error_msg.set(Some(e.to_string()));
-                    loading.set(false);
-                }
             }
-        });
+        }
     });
+    let videos = crate::use_server_result(videos_resource, "toast.load_videos_title");
 
     rsx! {
         document::Link { rel: "stylesheet", href: VIDEO_FEED_CSS }
 
         div { class: "video-feed-container",
-            if loading() {
-                p { "Loading videos..." }
-            } else if let Some(err) = error_msg() {
-                p { class: "error", "Error: {err}" }
-            } else if videos().is_empty() {
-                p { "No videos available" }
-            } else {
-                div { class: "video-feed-scroll",
-                    for (idx, video) in videos().iter().enumerate() {
-                        VideoFeedItem {
-                            key: "{video.id}",
-                            video: video.clone(),
-                            is_active: idx == current_index(),
+            match videos {
+                None => rsx! { crate::Loading {} },
+                Some(Err(e)) => rsx! {
+                    crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| videos_resource.restart() }
+                },
+                Some(Ok(videos)) if videos.is_empty() => rsx! {
+                    p { "No videos available" }
+                },
+                Some(Ok(videos)) => rsx! {
+                    div { class: "video-feed-scroll",
+                        for (idx, video) in videos.into_iter().enumerate() {
+                            VideoFeedItem {
+                                key: "{video.id}",
+                                video: video,
+                                is_active: idx == current_index(),
+                            }
                         }
                     }
-                }
+                },
             }
         }
     }
@@ -13,33 +13,26 @@ pub fn VideoSection(target_type: ContentTargetType, target_id: String) -> Elemen
     let target_id_for_list = target_id.clone();
     let mut videos = use_resource(move || {
         let target_id = target_id_for_list.clone();
-        async move { api::list_videos(target_type, target_id, 20).await }
+        let token = id_token();
+        async move { api::list_videos(target_type, target_id, 20, token).await }
     });
-    let mut load_error = use_signal(|| None::<String>);
 
     let mut status = use_signal(String::new);
-
-    let toasts_for_load = toasts.clone();
-    use_effect(move || {
-        let err = videos().and_then(|res| res.err()).map(|e| e.to_string());
-        if err.as_ref() != load_error().as_ref() {
-            if let Some(message) = &err {
-                toasts_for_load.error(
-                    crate::t(lang, "toast.load_videos_title"),
-                    Some(format!("{} {message}", crate::t(lang, "toast.details"))),
-                );
-            }
-            load_error.set(err);
-        }
-    });
+    let (uploads_enabled, accept) = match cfg() {
+        Some(Ok(cfg)) => (cfg.uploads_enabled, cfg.allowed_video_types.join(",")),
+        _ => (false, "video/*".to_string()),
+    };
+    let listed = crate::use_server_result(videos, "toast.load_videos_title");
 
     rsx! {
         div { class: "panel",
             h2 { "Videos" }
 
-            match videos() {
-                None => rsx! { p { "Loading…" } },
-                Some(Err(_)) => rsx! { p { class: "hint", {crate::t(lang, "common.error_try_again")} } },
+            match listed {
+                None => rsx! { crate::Loading {} },
+                Some(Err(e)) => rsx! {
+                    crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| videos.restart() }
+                },
                 Some(Ok(items)) => rsx! {
                     if items.is_empty() {
                         p { class: "hint", "No videos yet." }
@@ -55,18 +48,27 @@ pub fn VideoSection(target_type: ContentTargetType, target_id: String) -> Elemen
                                 None => rsx! { p { class: "hint", "Loading player…" } },
                                 Some(Err(_)) => rsx! { p { class: "hint", "Player not configured." } },
                                 Some(Ok(cfg)) => {
-                                    let src = cfg.media_base_url.as_ref().map(|base| {
-                                        format!("{}/{}", base.trim_end_matches('/'), v.storage_key)
-                                    });
+                                    let base = cfg
+                                        .media_base_url
+                                        .as_deref()
+                                        .map(|base| base.trim_end_matches('/'));
                                     rsx! {
-                                        if let Some(src) = src {
+                                        if let Some(base) = base {
                                             video {
                                                 class: "video_player",
                                                 controls: true,
-                                                src: "{src}",
+                                                src: "{base}/{v.storage_key}",
+                                                for caption in v.captions.iter() {
+                                                    track {
+                                                        kind: "captions",
+                                                        srclang: "{caption.language}",
+                                                        label: "{caption.language}",
+                                                        src: "{base}/{caption.storage_key}",
+                                                    }
+                                                }
                                             }
                                         } else {
-                                            p { class: "hint", "Set MEDIA_BASE_URL to enable playback." }
+                                            SignedVideo { video_id: v.id.to_string() }
                                         }
                                     }
                                 }
@@ -88,10 +90,12 @@ pub fn VideoSection(target_type: ContentTargetType, target_id: String) -> Elemen
 
             if id_token().is_none() {
                 p { class: "hint", "Sign in to upload a video." }
+            } else if !uploads_enabled {
+                p { class: "hint", "Video uploads are not configured." }
             } else {
                 div { class: "panel",
                     label { "Upload a video" }
-                    input { id: "alelysee_video_file", r#type: "file", accept: "video/*" }
+                    input { id: "alelysee_video_file", r#type: "file", accept: "{accept}" }
                     button {
                         class: "btn primary",
                         onclick: move |_| {
@@ -148,7 +152,14 @@ pub fn VideoSection(target_type: ContentTargetType, target_id: String) -> Elemen
 
                                 status.set("Uploading to storage…".to_string());
 
-                                // Upload file using fetch(PUT presigned_url, body=file)
+                                // Upload file using fetch(PUT presigned_url, body=file), sending
+                                // exactly the headers the server signed.
+                                let headers = intent
+                                    .headers
+                                    .iter()
+                                    .map(|(k, v)| format!("\"{}\": \"{}\"", js_escape(k), js_escape(v)))
+                                    .collect::<Vec<_>>()
+                                    .join(", ");
                                 let js = format!(
                                     r#"(async function(){{
                                         const el = document.getElementById("alelysee_video_file");
@@ -156,14 +167,14 @@ pub fn VideoSection(target_type: ContentTargetType, target_id: String) -> Elemen
                                         const f = el.files[0];
                                         const resp = await fetch("{}", {{
                                             method: "PUT",
-                                            headers: {{ "Content-Type": "{}" }},
+                                            headers: {{ {} }},
                                             body: f
                                         }});
                                         if(!resp.ok) return "upload_failed:" + resp.status;
                                         return "ok";
                                     }})()"#,
                                     js_escape(&intent.presigned_put_url),
-                                    js_escape(&ctype),
+                                    headers,
                                 );
 
                                 let upload_res = document::eval(&js)
@@ -188,6 +199,7 @@ pub fn VideoSection(target_type: ContentTargetType, target_id: String) -> Elemen
                                     tid,
                                     intent.storage_key,
                                     ctype,
+                                    size,
                                 )
                                 .await
                                 {
@@ -213,6 +225,80 @@ pub fn VideoSection(target_type: ContentTargetType, target_id: String) -> Elemen
     }
 }
 
+/// Header for the `/videos/:id` page: what the video is on, who posted it, and its counts.
+#[component]
+pub fn VideoDetailHeader(id: String) -> Element {
+    let lang = crate::use_lang()();
+    let id_token = use_context::<Signal<Option<String>>>();
+    let mut detail_resource = use_resource(move || {
+        let id = id.clone();
+        let token = id_token();
+        async move { api::get_video(id, token).await }
+    });
+    let detail = crate::use_server_result(detail_resource, "toast.load_videos_title");
+
+    match detail {
+        None => rsx! { crate::Loading {} },
+        Some(Err(e)) => rsx! {
+            crate::LoadFailed { failure: crate::LoadFailure::of(&e), on_retry: move |_| detail_resource.restart() }
+        },
+        Some(Ok(detail)) => {
+            let video = &detail.video;
+            let owner = detail
+                .owner_display_name
+                .clone()
+                .unwrap_or_else(|| video.owner_user_id.to_string());
+            let target_href = match (video.target_type, &detail.target_slug) {
+                (ContentTargetType::Proposal, Some(slug)) => Some(format!("/proposals/{slug}")),
+                (ContentTargetType::Program, Some(slug)) => Some(format!("/programs/{slug}")),
+                _ => None,
+            };
+            rsx! {
+                div { class: "panel",
+                    if let (Some(href), Some(title)) = (target_href, detail.target_title.clone()) {
+                        h2 { a { href: "{href}", "{title}" } }
+                    }
+                    p { class: "meta",
+                        {format!("{} {owner}", crate::t(lang, "comments.by"))}
+                    }
+                    p { class: "hint",
+                        {
+                            format!(
+                                "{} {} · {} {} · {} {}",
+                                video.vote_score,
+                                crate::t(lang, "videos.votes"),
+                                detail.view_count,
+                                crate::t(lang, "videos.views"),
+                                detail.comment_count,
+                                crate::t(lang, "videos.comments"),
+                            )
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 fn js_escape(s: &str) -> String {
     s.replace('\\', "\\\\").replace('"', "\\\"")
 }
+
+/// Player for private storage: asks the server for a short-lived playback URL.
+#[component]
+fn SignedVideo(video_id: String) -> Element {
+    let id_token = use_context::<Signal<Option<String>>>();
+    let url = use_resource(move || {
+        let id = video_id.clone();
+        let token = id_token();
+        async move { api::get_video_playback_url(id, token).await }
+    });
+
+    match url() {
+        None => rsx! { p { class: "hint", "Loading player…" } },
+        Some(Err(_)) => rsx! { p { class: "hint", "Playback unavailable." } },
+        Some(Ok(src)) => rsx! {
+            video { class: "video_player", controls: true, src: "{src}" }
+        },
+    }
+}
@@ -8,7 +8,7 @@ pub fn VoteWidget(
     target_id: String,
     initial_score: i64,
 ) -> Element {
-    let id_token = use_context::<Signal<Option<String>>>();
+    let client = crate::use_api_client();
     let lang = crate::use_lang()();
     let toasts = crate::use_toasts();
 
@@ -30,7 +30,7 @@ pub fn VoteWidget(
     let toasts_for_effect = toasts.clone();
     use_effect(move || {
         let toasts = toasts_for_effect.clone();
-        let token = id_token();
+        let token = client.token();
         let tid = target_key();
         let initial_score = initial_score;
         spawn(async move {
@@ -46,8 +46,12 @@ pub fn VoteWidget(
                     ),
                 }
             } else {
-                score.set(initial_score);
                 my_vote.set(None);
+                // Fall back to the (possibly stale) initial score if this fails.
+                match api::get_vote_score(target_type, tid, None).await {
+                    Ok(fresh) => score.set(fresh),
+                    Err(_) => score.set(initial_score),
+                }
             }
         });
     });
@@ -62,8 +66,7 @@ pub fn VoteWidget(
                 button {
                     class: "btn",
                     onclick: move |_| {
-                        let token = id_token().unwrap_or_default();
-                        if token.trim().is_empty() {
+                        if client.token().is_none() {
                             toasts_up
                                 .error(
                                     crate::t(lang, "toast.vote_required_title"),
@@ -85,7 +88,8 @@ pub fn VoteWidget(
                         let tid = target_id_up.clone();
                         let toasts = toasts_up.clone();
                         spawn(async move {
-                            match api::set_vote(token, target_type, tid, desired).await {
+                            let save = |token| api::set_vote(token, target_type, tid, desired);
+                            match client.call(save).await {
                                 Ok(state) => {
                                     score.set(state.score);
                                     my_vote.set(state.my_vote);
@@ -106,8 +110,7 @@ pub fn VoteWidget(
                 button {
                     class: "btn",
                     onclick: move |_| {
-                        let token = id_token().unwrap_or_default();
-                        if token.trim().is_empty() {
+                        if client.token().is_none() {
                             toasts_down
                                 .error(
                                     crate::t(lang, "toast.vote_required_title"),
@@ -129,7 +132,8 @@ pub fn VoteWidget(
                         let tid = target_id_down.clone();
                         let toasts = toasts_down.clone();
                         spawn(async move {
-                            match api::set_vote(token, target_type, tid, desired).await {
+                            let save = |token| api::set_vote(token, target_type, tid, desired);
+                            match client.call(save).await {
                                 Ok(state) => {
                                     score.set(state.score);
                                     my_vote.set(state.my_vote);
@@ -149,8 +153,7 @@ pub fn VoteWidget(
                 button {
                     class: "btn",
                     onclick: move |_| {
-                        let token = id_token().unwrap_or_default();
-                        if token.trim().is_empty() {
+                        if client.token().is_none() {
                             toasts_clear
                                 .error(
                                     crate::t(lang, "toast.vote_required_title"),
@@ -168,7 +171,8 @@ pub fn VoteWidget(
                         let tid = target_id_clear.clone();
                         let toasts = toasts_clear.clone();
                         spawn(async move {
-                            match api::set_vote(token, target_type, tid, 0).await {
+                            let clear = |token| api::clear_vote(token, target_type, tid);
+                            match client.call(clear).await {
                                 Ok(state) => {
                                     score.set(state.score);
                                     my_vote.set(state.my_vote);
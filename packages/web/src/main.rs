@@ -1,10 +1,9 @@
 use dioxus::prelude::*;
-use std::env;
 
 use views::{
     AuthCallback, AuthResetConfirm, AuthResetPassword, AuthSignIn, AuthSignUp, AuthVerify, Blog,
-    Home, Me, ProfileEdit, ProgramDetail, ProgramNew, Programs, ProposalDetail, ProposalNew,
-    Proposals, VideoDetail, Videos,
+    Home, Me, ProfileEdit, ProgramDetail, ProgramNew, Programs, ProposalDetail, ProposalEdit,
+    ProposalNew, Proposals, Unsubscribe, VideoDetail, Videos,
 };
 
 mod views;
@@ -33,12 +32,16 @@ enum Route {
     Me {},
     #[route("/me/edit")]
     ProfileEdit {},
+    #[route("/notifications/unsubscribe?:user_id&:token")]
+    Unsubscribe { user_id: Option<String>, token: Option<String> },
     #[route("/proposals")]
     Proposals {},
     #[route("/proposals/new")]
     ProposalNew {},
     #[route("/proposals/:id")]
     ProposalDetail { id: String },
+    #[route("/proposals/:id/edit")]
+    ProposalEdit { id: String },
     #[route("/programs")]
     Programs {},
     #[route("/programs/new")]
@@ -65,10 +68,33 @@ fn main() {
     #[cfg(feature = "server")]
     init_server_state();
 
-    log_runtime_config();
+    api::config::log_runtime_config();
+
+    #[cfg(feature = "server")]
+    serve();
+
+    #[cfg(not(feature = "server"))]
     dioxus::launch(App);
 }
 
+/// Serve the app with each connection's peer address exposed, which per-IP
+/// throttling needs and `dioxus::serve` doesn't provide.
+#[cfg(feature = "server")]
+fn serve() {
+    let config = api::state::AppState::global().config.clone();
+    tokio::runtime::Runtime::new()
+        .expect("Failed to create tokio runtime")
+        .block_on(async move {
+            let router = api::sitemap::routes(dioxus::server::router(App));
+            let router = api::body_limit::apply(router, config.max_request_body_bytes);
+            let router = api::client_ip::apply(router, config.trust_proxy);
+            if let Err(e) = api::client_ip::serve(router).await {
+                eprintln!("Server error: {}", e);
+                std::process::exit(1);
+            }
+        });
+}
+
 #[cfg(feature = "server")]
 fn init_tracing() {
     use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -128,71 +154,6 @@ fn install_panic_hook() {
     }));
 }
 
-fn log_runtime_config() {
-    let ip = env::var("IP").unwrap_or_else(|_| "0.0.0.0".to_string());
-    let port = env::var("PORT").unwrap_or_else(|_| "8080".to_string());
-    let database_url = env::var("DATABASE_URL").unwrap_or_else(|_| "<missing>".to_string());
-
-    eprintln!("startup: IP={ip} PORT={port}");
-    eprintln!("startup: DATABASE_URL={}", redact_db_url(&database_url));
-
-    if database_url.contains("127.0.0.1") || database_url.contains("localhost") {
-        eprintln!("startup: WARNING DATABASE_URL points to localhost; this will fail in Railway");
-    }
-
-    log_missing_envs(
-        "auth",
-        &[
-            "AUTH_AUTHORIZE_URL",
-            "AUTH_CLIENT_ID",
-            "AUTH_REDIRECT_URI",
-            "AUTH_ISSUER",
-            "AUTH_JWKS_URL",
-        ],
-    );
-    log_missing_envs(
-        "storage",
-        &[
-            "STORAGE_BUCKET",
-            "STORAGE_ENDPOINT",
-            "STORAGE_REGION",
-            "STORAGE_ACCESS_KEY",
-            "STORAGE_SECRET_KEY",
-        ],
-    );
-}
-
-fn redact_db_url(value: &str) -> String {
-    if value == "<missing>" {
-        return value.to_string();
-    }
-
-    if let Some((prefix, rest)) = value.split_once("://") {
-        if let Some((creds, host)) = rest.split_once('@') {
-            let user = creds.split(':').next().unwrap_or("user");
-            return format!("{prefix}://{user}:***@{host}");
-        }
-    }
-
-    "<invalid DATABASE_URL>".to_string()
-}
-
-fn log_missing_envs(group: &str, keys: &[&str]) {
-    let missing: Vec<&str> = keys
-        .iter()
-        .copied()
-        .filter(|key| env::var(key).ok().is_none())
-        .collect();
-    if missing.is_empty() {
-        return;
-    }
-
-    eprintln!(
-        "startup: WARNING missing {group} envs: {}",
-        missing.join(", ")
-    );
-}
-
 #[component]
 fn App() -> Element {
     // Build cool things ✌️
@@ -22,6 +22,9 @@ pub use proposals_new::ProposalNew;
 mod proposals_detail;
 pub use proposals_detail::ProposalDetail;
 
+mod proposals_edit;
+pub use proposals_edit::ProposalEdit;
+
 mod programs_list;
 pub use programs_list::Programs;
 
@@ -46,5 +49,8 @@ pub use auth_reset_password::*;
 mod auth_reset_confirm;
 pub use auth_reset_confirm::*;
 
+mod unsubscribe;
+pub use unsubscribe::Unsubscribe;
+
 mod videos;
 pub use videos::*;
@@ -0,0 +1,6 @@
+use dioxus::prelude::*;
+
+#[component]
+pub fn ProposalEdit(id: String) -> Element {
+    rsx! { ui::ProposalEditPage { id } }
+}
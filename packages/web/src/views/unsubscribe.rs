@@ -0,0 +1,6 @@
+use dioxus::prelude::*;
+
+#[component]
+pub fn Unsubscribe(user_id: Option<String>, token: Option<String>) -> Element {
+    rsx! { ui::UnsubscribePage { user_id, token } }
+}
@@ -7,6 +7,7 @@ pub fn Videos() -> Element {
             starting_video_id: None,
             filter_target_type: None,
             filter_target_id: None,
+            only_mine: false,
         }
     }
 }
@@ -14,10 +15,12 @@ pub fn Videos() -> Element {
 #[component]
 pub fn VideoDetail(id: String) -> Element {
     rsx! {
+        ui::VideoDetailHeader { id: id.clone() }
         ui::VideoFeed {
             starting_video_id: Some(id),
             filter_target_type: None,
             filter_target_id: None,
+            only_mine: false,
         }
     }
 }